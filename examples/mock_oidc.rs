@@ -0,0 +1,198 @@
+//! 本地模擬的 OpenID Connect 身分提供者，用於開發與測試社群登入
+//!
+//! 執行方式：`cargo run --example mock_oidc`
+//!
+//! 授權端點不顯示登入頁面，直接以 `login_hint`（預設 fan@example.com）作為登入的電子郵件並導回用戶端；
+//! 令牌端點會檢查授權碼、回呼網址與 PKCE 驗證碼，再以啟動時產生的 Ed25519 金鑰簽發 ID Token。
+//!
+//! 環境變數：
+//! - `MOCK_OIDC_PORT`：監聽埠號，預設 9000
+//! - `MOCK_OIDC_EMAIL_VERIFIED`：ID Token 的 email_verified，預設 true
+
+use std::collections::HashMap;
+use std::net::SocketAddr;
+use std::sync::{Arc, Mutex};
+
+use axum::extract::{Query, State};
+use axum::http::StatusCode;
+use axum::response::{IntoResponse, Redirect, Response};
+use axum::routing::{get, post};
+use axum::{Form, Json, Router};
+use base64::engine::general_purpose::URL_SAFE_NO_PAD;
+use base64::Engine;
+use jsonwebtoken::{encode, Algorithm, EncodingKey, Header};
+use reqwest::Url;
+use ring::rand::SystemRandom;
+use ring::signature::{Ed25519KeyPair, KeyPair};
+use serde::Deserialize;
+use serde_json::{json, Value};
+use sha2::{Digest, Sha256};
+
+/// 已核發、尚未兌換的授權碼
+struct PendingCode {
+    client_id: String,
+    redirect_uri: String,
+    code_challenge: String,
+    nonce: Option<String>,
+    email: String,
+}
+
+struct MockProvider {
+    issuer: String,
+    email_verified: bool,
+    /// 每次啟動都產生新金鑰，kid 取自公鑰雜湊，重新啟動後用戶端會重新下載 JWKS
+    key_id: String,
+    signing_key: EncodingKey,
+    public_key: Vec<u8>,
+    codes: Mutex<HashMap<String, PendingCode>>,
+}
+
+#[derive(Deserialize)]
+struct AuthorizeQuery {
+    client_id: String,
+    redirect_uri: String,
+    state: Option<String>,
+    nonce: Option<String>,
+    code_challenge: Option<String>,
+    code_challenge_method: Option<String>,
+    login_hint: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct TokenForm {
+    grant_type: String,
+    code: String,
+    redirect_uri: String,
+    client_id: String,
+    code_verifier: String,
+}
+
+#[tokio::main]
+async fn main() {
+    let port: u16 = std::env::var("MOCK_OIDC_PORT")
+        .unwrap_or_else(|_| "9000".to_string())
+        .parse()
+        .expect("MOCK_OIDC_PORT 必須是有效的數字");
+    let email_verified = std::env::var("MOCK_OIDC_EMAIL_VERIFIED")
+        .unwrap_or_else(|_| "true".to_string())
+        .parse()
+        .expect("MOCK_OIDC_EMAIL_VERIFIED 必須是 true 或 false");
+
+    let pkcs8 = Ed25519KeyPair::generate_pkcs8(&SystemRandom::new()).expect("產生金鑰失敗");
+    let key_pair = Ed25519KeyPair::from_pkcs8(pkcs8.as_ref()).expect("無效的金鑰");
+
+    let provider = Arc::new(MockProvider {
+        issuer: format!("http://localhost:{}", port),
+        email_verified,
+        key_id: hex::encode(&Sha256::digest(key_pair.public_key().as_ref())[..8]),
+        signing_key: EncodingKey::from_ed_der(pkcs8.as_ref()),
+        public_key: key_pair.public_key().as_ref().to_vec(),
+        codes: Mutex::new(HashMap::new()),
+    });
+
+    let app = Router::new()
+        .route("/.well-known/openid-configuration", get(discovery))
+        .route("/authorize", get(authorize))
+        .route("/token", post(token))
+        .route("/jwks", get(jwks))
+        .with_state(provider.clone());
+
+    let addr = SocketAddr::from(([127, 0, 0, 1], port));
+    println!("模擬 OIDC 身分提供者: {}", provider.issuer);
+    let listener = tokio::net::TcpListener::bind(addr).await.expect("無法綁定埠號");
+    axum::serve(listener, app).await.expect("伺服器錯誤");
+}
+
+async fn discovery(State(provider): State<Arc<MockProvider>>) -> Json<Value> {
+    Json(json!({
+        "issuer": provider.issuer,
+        "authorization_endpoint": format!("{}/authorize", provider.issuer),
+        "token_endpoint": format!("{}/token", provider.issuer),
+        "jwks_uri": format!("{}/jwks", provider.issuer),
+        "response_types_supported": ["code"],
+        "subject_types_supported": ["public"],
+        "id_token_signing_alg_values_supported": ["EdDSA"],
+        "code_challenge_methods_supported": ["S256"],
+    }))
+}
+
+/// 直接核發授權碼並導回用戶端
+async fn authorize(State(provider): State<Arc<MockProvider>>, Query(query): Query<AuthorizeQuery>) -> Response {
+    let code_challenge = match (query.code_challenge, query.code_challenge_method.as_deref()) {
+        (Some(challenge), Some("S256")) => challenge,
+        _ => return (StatusCode::BAD_REQUEST, "需要 S256 PKCE").into_response(),
+    };
+    let Ok(mut redirect) = Url::parse(&query.redirect_uri) else {
+        return (StatusCode::BAD_REQUEST, "無效的 redirect_uri").into_response();
+    };
+
+    let code = uuid::Uuid::new_v4().simple().to_string();
+    provider.codes.lock().unwrap().insert(code.clone(), PendingCode {
+        client_id: query.client_id,
+        redirect_uri: query.redirect_uri,
+        code_challenge,
+        nonce: query.nonce,
+        email: query.login_hint.unwrap_or_else(|| "fan@example.com".to_string()),
+    });
+
+    redirect.query_pairs_mut().append_pair("code", &code);
+    if let Some(state) = &query.state {
+        redirect.query_pairs_mut().append_pair("state", state);
+    }
+    Redirect::to(redirect.as_str()).into_response()
+}
+
+/// 以授權碼換取 ID Token，授權碼只能使用一次
+async fn token(State(provider): State<Arc<MockProvider>>, Form(form): Form<TokenForm>) -> Response {
+    let invalid_grant = || (StatusCode::BAD_REQUEST, Json(json!({ "error": "invalid_grant" }))).into_response();
+
+    if form.grant_type != "authorization_code" {
+        return (StatusCode::BAD_REQUEST, Json(json!({ "error": "unsupported_grant_type" }))).into_response();
+    }
+    let Some(pending) = provider.codes.lock().unwrap().remove(&form.code) else {
+        return invalid_grant();
+    };
+    let challenge = URL_SAFE_NO_PAD.encode(Sha256::digest(form.code_verifier.as_bytes()));
+    if pending.client_id != form.client_id
+        || pending.redirect_uri != form.redirect_uri
+        || pending.code_challenge != challenge
+    {
+        return invalid_grant();
+    }
+
+    let now = chrono::Utc::now().timestamp();
+    let claims = json!({
+        "iss": provider.issuer,
+        "aud": pending.client_id,
+        "sub": format!("mock|{}", pending.email),
+        "email": pending.email,
+        "email_verified": provider.email_verified,
+        "nonce": pending.nonce,
+        "iat": now,
+        "exp": now + 300,
+    });
+    let mut header = Header::new(Algorithm::EdDSA);
+    header.kid = Some(provider.key_id.clone());
+    let id_token = encode(&header, &claims, &provider.signing_key).expect("簽發 ID Token 失敗");
+
+    Json(json!({
+        "access_token": uuid::Uuid::new_v4().simple().to_string(),
+        "token_type": "Bearer",
+        "expires_in": 300,
+        "id_token": id_token,
+    }))
+    .into_response()
+}
+
+async fn jwks(State(provider): State<Arc<MockProvider>>) -> Json<Value> {
+    Json(json!({
+        "keys": [{
+            "kty": "OKP",
+            "crv": "Ed25519",
+            "alg": "EdDSA",
+            "use": "sig",
+            "kid": provider.key_id,
+            "x": URL_SAFE_NO_PAD.encode(&provider.public_key),
+        }]
+    }))
+}
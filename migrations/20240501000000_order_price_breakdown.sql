@@ -0,0 +1,58 @@
+-- === 演唱會稅率設定 ===
+ALTER TABLE concerts
+    ADD COLUMN tax_rate NUMERIC NOT NULL DEFAULT 0 CHECK (tax_rate >= 0),
+    ADD COLUMN tax_inclusive BOOLEAN NOT NULL DEFAULT false;
+
+-- === 演唱會服務費表 ===
+-- per_ticket：每張票收取；per_order：每筆訂單收取一次
+CREATE TABLE concert_fees (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    concert_id UUID NOT NULL REFERENCES concerts(id) ON DELETE CASCADE,
+    name TEXT NOT NULL,
+    fee_type TEXT NOT NULL CHECK (fee_type IN ('per_ticket', 'per_order')),
+    amount NUMERIC NOT NULL CHECK (amount >= 0),
+    created_at TIMESTAMP DEFAULT CURRENT_TIMESTAMP
+);
+
+CREATE INDEX idx_concert_fees_concert_id ON concert_fees(concert_id);
+
+-- === 訂單價格明細 ===
+-- 下單時寫入，之後票價或費用調整都不會影響既有訂單
+ALTER TABLE orders
+    ADD COLUMN unit_price NUMERIC NOT NULL DEFAULT 0,
+    ADD COLUMN subtotal NUMERIC NOT NULL DEFAULT 0,
+    ADD COLUMN fee_total NUMERIC NOT NULL DEFAULT 0,
+    ADD COLUMN discount_total NUMERIC NOT NULL DEFAULT 0,
+    ADD COLUMN tax_rate NUMERIC NOT NULL DEFAULT 0,
+    ADD COLUMN tax_inclusive BOOLEAN NOT NULL DEFAULT false,
+    ADD COLUMN tax_total NUMERIC NOT NULL DEFAULT 0,
+    ADD COLUMN total NUMERIC NOT NULL DEFAULT 0;
+
+-- 既有訂單以目前票價回填
+UPDATE orders o
+SET unit_price = t.price,
+    subtotal = t.price * o.quantity,
+    total = t.price * o.quantity
+FROM tickets t
+WHERE o.ticket_id = t.id;
+
+-- 價格明細寫入後不可修改
+CREATE FUNCTION prevent_order_price_change() RETURNS TRIGGER AS $$
+BEGIN
+    IF NEW.unit_price IS DISTINCT FROM OLD.unit_price
+        OR NEW.subtotal IS DISTINCT FROM OLD.subtotal
+        OR NEW.fee_total IS DISTINCT FROM OLD.fee_total
+        OR NEW.discount_total IS DISTINCT FROM OLD.discount_total
+        OR NEW.tax_rate IS DISTINCT FROM OLD.tax_rate
+        OR NEW.tax_inclusive IS DISTINCT FROM OLD.tax_inclusive
+        OR NEW.tax_total IS DISTINCT FROM OLD.tax_total
+        OR NEW.total IS DISTINCT FROM OLD.total THEN
+        RAISE EXCEPTION '訂單價格明細不可修改';
+    END IF;
+    RETURN NEW;
+END;
+$$ LANGUAGE plpgsql;
+
+CREATE TRIGGER orders_price_immutable
+    BEFORE UPDATE ON orders
+    FOR EACH ROW EXECUTE FUNCTION prevent_order_price_change();
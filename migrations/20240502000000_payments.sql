@@ -0,0 +1,32 @@
+-- === 訂單狀態 ===
+-- 既有訂單視為已付款，新訂單預設為待付款
+ALTER TABLE orders
+    ADD COLUMN status TEXT NOT NULL DEFAULT 'paid'
+        CHECK (status IN ('pending', 'paid', 'failed', 'refunded'));
+
+ALTER TABLE orders ALTER COLUMN status SET DEFAULT 'pending';
+
+-- === 付款表 ===
+CREATE TABLE payments (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    order_id UUID NOT NULL REFERENCES orders(id) ON DELETE CASCADE,
+    provider TEXT NOT NULL,
+    intent_id TEXT NOT NULL UNIQUE,
+    amount NUMERIC NOT NULL,
+    currency TEXT NOT NULL,
+    status TEXT NOT NULL DEFAULT 'pending'
+        CHECK (status IN ('pending', 'succeeded', 'failed', 'refunded')),
+    created_at TIMESTAMP DEFAULT CURRENT_TIMESTAMP,
+    updated_at TIMESTAMP DEFAULT CURRENT_TIMESTAMP
+);
+
+CREATE INDEX idx_payments_order_id ON payments(order_id);
+
+-- === 已處理的付款 Webhook 事件 ===
+-- 用於確保同一事件重送時只處理一次
+CREATE TABLE payment_webhook_events (
+    provider TEXT NOT NULL,
+    event_id TEXT NOT NULL,
+    received_at TIMESTAMP DEFAULT CURRENT_TIMESTAMP,
+    PRIMARY KEY (provider, event_id)
+);
@@ -0,0 +1,80 @@
+-- === 複式記帳分錄表 ===
+-- 每筆分錄屬於一個傳票（journal_id），同一傳票的借貸總額必須相等
+CREATE TABLE ledger_entries (
+    id BIGSERIAL PRIMARY KEY,
+    journal_id UUID NOT NULL,
+    order_id UUID REFERENCES orders(id),
+    account TEXT NOT NULL
+        CHECK (account IN ('customer', 'platform_revenue', 'organizer_payable', 'tax_payable', 'cash')),
+    entry_type TEXT NOT NULL
+        CHECK (entry_type IN ('payment', 'fee', 'tax', 'discount', 'refund', 'payout')),
+    debit NUMERIC NOT NULL DEFAULT 0 CHECK (debit >= 0),
+    credit NUMERIC NOT NULL DEFAULT 0 CHECK (credit >= 0),
+    memo TEXT,
+    created_at TIMESTAMP DEFAULT CURRENT_TIMESTAMP,
+    -- 每筆分錄只能記在借方或貸方其中一邊
+    CHECK ((debit = 0) <> (credit = 0))
+);
+
+CREATE INDEX idx_ledger_entries_journal_id ON ledger_entries(journal_id);
+CREATE INDEX idx_ledger_entries_order_id ON ledger_entries(order_id);
+
+-- 分錄只能新增，不可修改或刪除；更正請以沖銷分錄處理
+CREATE FUNCTION prevent_ledger_mutation() RETURNS TRIGGER AS $$
+BEGIN
+    RAISE EXCEPTION '分錄不可修改或刪除';
+END;
+$$ LANGUAGE plpgsql;
+
+CREATE TRIGGER ledger_entries_append_only
+    BEFORE UPDATE OR DELETE ON ledger_entries
+    FOR EACH ROW EXECUTE FUNCTION prevent_ledger_mutation();
+
+-- 交易提交時檢查傳票借貸平衡
+CREATE FUNCTION check_journal_balance() RETURNS TRIGGER AS $$
+DECLARE
+    difference NUMERIC;
+BEGIN
+    SELECT SUM(debit) - SUM(credit) INTO difference
+    FROM ledger_entries
+    WHERE journal_id = NEW.journal_id;
+
+    IF difference <> 0 THEN
+        RAISE EXCEPTION '傳票 % 借貸不平衡，差額 %', NEW.journal_id, difference;
+    END IF;
+    RETURN NULL;
+END;
+$$ LANGUAGE plpgsql;
+
+CREATE CONSTRAINT TRIGGER ledger_entries_balanced
+    AFTER INSERT ON ledger_entries
+    DEFERRABLE INITIALLY DEFERRED
+    FOR EACH ROW EXECUTE FUNCTION check_journal_balance();
+
+-- === 回填既有訂單的分錄 ===
+-- 已付款與已退款的訂單補記收款分錄，已退款的訂單再補記退款分錄
+CREATE TEMPORARY TABLE backfill_journals AS
+SELECT id AS order_id, status, gen_random_uuid() AS payment_journal, gen_random_uuid() AS refund_journal,
+       total, fee_total, tax_total, discount_total, subtotal
+FROM orders
+WHERE status IN ('paid', 'refunded');
+
+INSERT INTO ledger_entries (journal_id, order_id, account, entry_type, debit, credit, memo)
+SELECT payment_journal, order_id, account, entry_type, debit, credit, '既有訂單回填'
+FROM backfill_journals b,
+LATERAL (VALUES
+    ('customer', 'payment', b.total, 0::NUMERIC),
+    ('organizer_payable', 'discount', b.discount_total, 0),
+    ('organizer_payable', 'payment', 0, b.total - b.fee_total - b.tax_total + b.discount_total),
+    ('platform_revenue', 'fee', 0, b.fee_total),
+    ('tax_payable', 'tax', 0, b.tax_total)
+) AS lines(account, entry_type, debit, credit)
+WHERE debit > 0 OR credit > 0;
+
+INSERT INTO ledger_entries (journal_id, order_id, account, entry_type, debit, credit, memo)
+SELECT b.refund_journal, e.order_id, e.account, 'refund', e.credit, e.debit, '既有訂單回填'
+FROM backfill_journals b
+JOIN ledger_entries e ON e.journal_id = b.payment_journal
+WHERE b.status = 'refunded';
+
+DROP TABLE backfill_journals;
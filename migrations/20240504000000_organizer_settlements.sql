@@ -0,0 +1,52 @@
+-- === 主辦單位表 ===
+-- commission_rate 為平台抽成比例，例如 0.1 代表 10%
+CREATE TABLE organizers (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    name TEXT NOT NULL,
+    commission_rate NUMERIC NOT NULL DEFAULT 0 CHECK (commission_rate >= 0 AND commission_rate <= 1),
+    created_at TIMESTAMP DEFAULT CURRENT_TIMESTAMP
+);
+
+ALTER TABLE concerts
+    ADD COLUMN organizer_id UUID REFERENCES organizers(id);
+
+-- === 結算表 ===
+-- 未鎖定的結算不落地，每次查詢即時計算；鎖定時寫入當下的金額快照
+CREATE TABLE settlements (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    concert_id UUID NOT NULL UNIQUE REFERENCES concerts(id),
+    organizer_id UUID NOT NULL REFERENCES organizers(id),
+    status TEXT NOT NULL DEFAULT 'locked' CHECK (status IN ('locked', 'paid')),
+    commission_rate NUMERIC NOT NULL,
+    gross_sales NUMERIC NOT NULL,
+    discounts NUMERIC NOT NULL,
+    refunds NUMERIC NOT NULL,
+    tax_withheld NUMERIC NOT NULL,
+    net_sales NUMERIC NOT NULL,
+    platform_commission NUMERIC NOT NULL,
+    net_payable NUMERIC NOT NULL,
+    locked_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    paid_at TIMESTAMP
+);
+
+-- 結算明細（依票種）
+CREATE TABLE settlement_lines (
+    settlement_id UUID NOT NULL REFERENCES settlements(id) ON DELETE CASCADE,
+    ticket_type TEXT NOT NULL,
+    tickets_sold INTEGER NOT NULL,
+    tickets_refunded INTEGER NOT NULL,
+    gross_sales NUMERIC NOT NULL,
+    discounts NUMERIC NOT NULL,
+    refunds NUMERIC NOT NULL,
+    tax_withheld NUMERIC NOT NULL,
+    net_sales NUMERIC NOT NULL,
+    PRIMARY KEY (settlement_id, ticket_type)
+);
+
+-- === 分錄：撥款與抽成 ===
+ALTER TABLE ledger_entries
+    ADD COLUMN concert_id UUID REFERENCES concerts(id);
+
+ALTER TABLE ledger_entries DROP CONSTRAINT ledger_entries_entry_type_check;
+ALTER TABLE ledger_entries ADD CONSTRAINT ledger_entries_entry_type_check
+    CHECK (entry_type IN ('payment', 'fee', 'tax', 'discount', 'refund', 'payout', 'commission'));
@@ -0,0 +1,61 @@
+-- === 票券原始庫存 ===
+-- stock 會隨訂單扣減，售出率以開賣時的庫存為分母
+ALTER TABLE tickets ADD COLUMN initial_stock INTEGER;
+
+-- 既有票券以目前庫存加上仍佔用庫存的訂單回填（失敗與退款的訂單已釋放庫存）
+UPDATE tickets t
+SET initial_stock = t.stock + COALESCE((
+    SELECT SUM(o.quantity)
+    FROM orders o
+    WHERE o.ticket_id = t.id AND o.status IN ('pending', 'paid')
+), 0);
+
+ALTER TABLE tickets ALTER COLUMN initial_stock SET NOT NULL;
+
+-- 新增票券時未指定原始庫存則以開賣庫存為準
+CREATE FUNCTION default_initial_stock() RETURNS TRIGGER AS $$
+BEGIN
+    NEW.initial_stock := COALESCE(NEW.initial_stock, NEW.stock);
+    RETURN NEW;
+END;
+$$ LANGUAGE plpgsql;
+
+CREATE TRIGGER tickets_default_initial_stock
+    BEFORE INSERT ON tickets
+    FOR EACH ROW EXECUTE FUNCTION default_initial_stock();
+
+CREATE INDEX idx_orders_created_at ON orders(created_at);
+
+-- === 報表物化視圖 ===
+-- 由背景任務定期以 REFRESH MATERIALIZED VIEW CONCURRENTLY 重新整理，報表查詢不會掃描訂單表
+
+-- 每小時銷售彙總（依票券與訂單狀態）
+CREATE MATERIALIZED VIEW report_sales_hourly AS
+SELECT t.concert_id,
+       o.ticket_id,
+       date_trunc('hour', o.created_at) AS bucket,
+       o.status,
+       COUNT(*)::bigint AS orders,
+       SUM(o.quantity)::bigint AS tickets,
+       SUM(o.total) AS revenue
+FROM orders o
+JOIN tickets t ON o.ticket_id = t.id
+GROUP BY t.concert_id, o.ticket_id, date_trunc('hour', o.created_at), o.status;
+
+CREATE UNIQUE INDEX idx_report_sales_hourly_key ON report_sales_hourly(ticket_id, bucket, status);
+CREATE INDEX idx_report_sales_hourly_bucket ON report_sales_hourly(bucket);
+
+-- 每日購買者彙總（只計入已付款訂單）
+CREATE MATERIALIZED VIEW report_buyer_daily AS
+SELECT o.user_id,
+       t.concert_id,
+       o.created_at::date AS day,
+       COUNT(*)::bigint AS orders,
+       SUM(o.quantity)::bigint AS tickets,
+       SUM(o.total) AS spent
+FROM orders o
+JOIN tickets t ON o.ticket_id = t.id
+WHERE o.status = 'paid'
+GROUP BY o.user_id, t.concert_id, o.created_at::date;
+
+CREATE UNIQUE INDEX idx_report_buyer_daily_key ON report_buyer_daily(user_id, concert_id, day);
@@ -0,0 +1,62 @@
+-- === 座位圖 ===
+-- 場館座位圖由區、排、座位組成，座標供前端繪製座位圖
+CREATE TABLE seat_maps (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    name TEXT NOT NULL,
+    venue TEXT NOT NULL,
+    created_at TIMESTAMP DEFAULT CURRENT_TIMESTAMP
+);
+
+CREATE TABLE seat_sections (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    seat_map_id UUID NOT NULL REFERENCES seat_maps(id) ON DELETE CASCADE,
+    name TEXT NOT NULL,
+    position INTEGER NOT NULL,
+    UNIQUE (seat_map_id, name)
+);
+
+CREATE TABLE seat_rows (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    section_id UUID NOT NULL REFERENCES seat_sections(id) ON DELETE CASCADE,
+    label TEXT NOT NULL,
+    position INTEGER NOT NULL,
+    UNIQUE (section_id, label)
+);
+
+CREATE TABLE seats (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    row_id UUID NOT NULL REFERENCES seat_rows(id) ON DELETE CASCADE,
+    number TEXT NOT NULL,
+    position INTEGER NOT NULL,
+    x DOUBLE PRECISION NOT NULL,
+    y DOUBLE PRECISION NOT NULL,
+    UNIQUE (row_id, number)
+);
+
+-- === 演唱會座位庫存 ===
+ALTER TABLE concerts
+    ADD COLUMN seat_map_id UUID REFERENCES seat_maps(id);
+
+-- 價區：一組座位對應一個票種（票價）
+CREATE TABLE price_zones (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    concert_id UUID NOT NULL REFERENCES concerts(id) ON DELETE CASCADE,
+    name TEXT NOT NULL,
+    ticket_id UUID NOT NULL REFERENCES tickets(id) ON DELETE CASCADE,
+    UNIQUE (concert_id, name)
+);
+
+-- 每場演唱會每個座位一筆；held 為待付款訂單保留，sold 為已付款
+-- 以 (concert_id, seat_id) 為主鍵，狀態只能透過條件式更新從 available 轉出，同一座位不會被賣兩次
+CREATE TABLE concert_seats (
+    concert_id UUID NOT NULL REFERENCES concerts(id) ON DELETE CASCADE,
+    seat_id UUID NOT NULL REFERENCES seats(id),
+    price_zone_id UUID NOT NULL REFERENCES price_zones(id) ON DELETE CASCADE,
+    status TEXT NOT NULL DEFAULT 'available' CHECK (status IN ('available', 'held', 'sold')),
+    order_id UUID REFERENCES orders(id),
+    PRIMARY KEY (concert_id, seat_id),
+    CHECK ((status = 'available') = (order_id IS NULL))
+);
+
+CREATE INDEX idx_concert_seats_order_id ON concert_seats(order_id);
+CREATE INDEX idx_concert_seats_price_zone_id ON concert_seats(price_zone_id);
@@ -0,0 +1,20 @@
+-- === 共用庫存池 ===
+-- 多個票種共用同一塊實體容量（例如「站區」與「站區 + 周邊」共用搖滾區）
+-- remaining 為池中尚未售出的數量，購買時與票種庫存在同一交易中扣減
+CREATE TABLE inventory_pools (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    concert_id UUID NOT NULL REFERENCES concerts(id) ON DELETE CASCADE,
+    name TEXT NOT NULL,
+    capacity INTEGER NOT NULL CHECK (capacity >= 0),
+    remaining INTEGER NOT NULL CHECK (remaining >= 0),
+    created_at TIMESTAMP DEFAULT CURRENT_TIMESTAMP,
+    UNIQUE (concert_id, name)
+);
+
+-- 票種可選擇加入一個庫存池，未加入時只受自身庫存限制
+ALTER TABLE tickets ADD COLUMN pool_id UUID REFERENCES inventory_pools(id) ON DELETE SET NULL;
+
+CREATE INDEX idx_tickets_pool_id ON tickets(pool_id);
+
+-- 庫存改為條件式扣減後不應再出現負數；既有資料不回溯檢查
+ALTER TABLE tickets ADD CONSTRAINT tickets_stock_non_negative CHECK (stock >= 0) NOT VALID;
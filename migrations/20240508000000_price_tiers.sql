@@ -0,0 +1,22 @@
+-- === 價格階梯 ===
+-- 依 position 順序比對，第一個時間區間與售出數量都符合的階梯即為目前價格；都不符合時使用 tickets.price
+CREATE TABLE price_tiers (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    ticket_id UUID NOT NULL REFERENCES tickets(id) ON DELETE CASCADE,
+    position INTEGER NOT NULL,
+    name TEXT NOT NULL,
+    price NUMERIC NOT NULL CHECK (price >= 0),
+    starts_at TIMESTAMP,
+    ends_at TIMESTAMP,
+    max_sold INTEGER CHECK (max_sold > 0),
+    CHECK (starts_at IS NULL OR ends_at IS NULL OR starts_at < ends_at),
+    UNIQUE (ticket_id, position)
+);
+
+-- === 需求加價規則 ===
+-- 售出率超過門檻後線性加價，售完時達到加價上限
+CREATE TABLE ticket_demand_pricing (
+    ticket_id UUID PRIMARY KEY REFERENCES tickets(id) ON DELETE CASCADE,
+    start_sell_through NUMERIC NOT NULL CHECK (start_sell_through >= 0 AND start_sell_through < 1),
+    max_increase NUMERIC NOT NULL CHECK (max_increase >= 0 AND max_increase <= 1)
+);
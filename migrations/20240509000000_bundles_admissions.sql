@@ -0,0 +1,43 @@
+-- === 組合商品 ===
+-- 例如多日音樂節通行證或含停車的 VIP 組合，有自己的售價、庫存與稅率設定
+CREATE TABLE bundles (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    name TEXT NOT NULL,
+    description TEXT,
+    price NUMERIC NOT NULL CHECK (price >= 0),
+    stock INTEGER NOT NULL CHECK (stock >= 0),
+    initial_stock INTEGER NOT NULL,
+    tax_rate NUMERIC NOT NULL DEFAULT 0 CHECK (tax_rate >= 0 AND tax_rate <= 1),
+    tax_inclusive BOOLEAN NOT NULL DEFAULT false,
+    created_at TIMESTAMP DEFAULT CURRENT_TIMESTAMP
+);
+
+-- 組合內容：每售出一組，各票種扣減 quantity 張
+CREATE TABLE bundle_components (
+    bundle_id UUID NOT NULL REFERENCES bundles(id) ON DELETE CASCADE,
+    ticket_id UUID NOT NULL REFERENCES tickets(id),
+    quantity INTEGER NOT NULL DEFAULT 1 CHECK (quantity > 0),
+    PRIMARY KEY (bundle_id, ticket_id)
+);
+
+-- 訂單購買的是單一票種或一個組合商品
+ALTER TABLE orders ALTER COLUMN ticket_id DROP NOT NULL;
+ALTER TABLE orders ADD COLUMN bundle_id UUID REFERENCES bundles(id);
+ALTER TABLE orders ADD CONSTRAINT orders_product_check CHECK ((ticket_id IS NULL) <> (bundle_id IS NULL));
+
+CREATE INDEX idx_orders_bundle_id ON orders(bundle_id);
+
+-- === 入場憑證 ===
+-- 訂單付款後發出，可在入口掃描；票種訂單每張一份，組合商品每組每場演唱會一份
+CREATE TABLE admissions (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    order_id UUID NOT NULL REFERENCES orders(id) ON DELETE CASCADE,
+    concert_id UUID NOT NULL REFERENCES concerts(id),
+    label TEXT NOT NULL,
+    code TEXT NOT NULL UNIQUE,
+    status TEXT NOT NULL DEFAULT 'valid' CHECK (status IN ('valid', 'used', 'void')),
+    issued_at TIMESTAMP DEFAULT CURRENT_TIMESTAMP,
+    used_at TIMESTAMP
+);
+
+CREATE INDEX idx_admissions_order_id ON admissions(order_id);
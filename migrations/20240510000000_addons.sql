@@ -0,0 +1,64 @@
+-- === 加購商品 ===
+-- 周邊、停車、置物櫃等，隨票種訂單一併購買；可限定只有特定票種的購買者能加購
+CREATE TABLE addons (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    concert_id UUID NOT NULL REFERENCES concerts(id) ON DELETE CASCADE,
+    name TEXT NOT NULL,
+    description TEXT,
+    price NUMERIC NOT NULL CHECK (price >= 0),
+    created_at TIMESTAMP DEFAULT CURRENT_TIMESTAMP
+);
+
+-- 加購商品規格（例如 T 恤尺寸），各自有庫存；沒有規格的商品只有一個規格
+CREATE TABLE addon_variants (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    addon_id UUID NOT NULL REFERENCES addons(id) ON DELETE CASCADE,
+    name TEXT NOT NULL,
+    position INTEGER NOT NULL,
+    stock INTEGER NOT NULL CHECK (stock >= 0),
+    initial_stock INTEGER NOT NULL,
+    UNIQUE (addon_id, name)
+);
+
+-- 限定可加購的票種；沒有任何資料列代表不限票種
+CREATE TABLE addon_ticket_types (
+    addon_id UUID NOT NULL REFERENCES addons(id) ON DELETE CASCADE,
+    ticket_id UUID NOT NULL REFERENCES tickets(id) ON DELETE CASCADE,
+    PRIMARY KEY (addon_id, ticket_id)
+);
+
+-- === 訂單明細 ===
+-- 加購商品以明細列記錄下單當時的單價；付款後憑 code 於現場領取
+CREATE TABLE order_items (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    order_id UUID NOT NULL REFERENCES orders(id) ON DELETE CASCADE,
+    variant_id UUID NOT NULL REFERENCES addon_variants(id),
+    quantity INTEGER NOT NULL CHECK (quantity > 0),
+    unit_price NUMERIC NOT NULL,
+    total NUMERIC NOT NULL,
+    code TEXT NOT NULL UNIQUE,
+    status TEXT NOT NULL DEFAULT 'pending' CHECK (status IN ('pending', 'valid', 'redeemed', 'void')),
+    redeemed_at TIMESTAMP
+);
+
+CREATE INDEX idx_order_items_order_id ON order_items(order_id);
+
+-- 加購商品小計獨立於票價小計，一併計入課稅基礎與應付主辦單位
+ALTER TABLE orders ADD COLUMN addon_total NUMERIC NOT NULL DEFAULT 0;
+
+CREATE OR REPLACE FUNCTION prevent_order_price_change() RETURNS TRIGGER AS $$
+BEGIN
+    IF NEW.unit_price IS DISTINCT FROM OLD.unit_price
+        OR NEW.subtotal IS DISTINCT FROM OLD.subtotal
+        OR NEW.addon_total IS DISTINCT FROM OLD.addon_total
+        OR NEW.fee_total IS DISTINCT FROM OLD.fee_total
+        OR NEW.discount_total IS DISTINCT FROM OLD.discount_total
+        OR NEW.tax_rate IS DISTINCT FROM OLD.tax_rate
+        OR NEW.tax_inclusive IS DISTINCT FROM OLD.tax_inclusive
+        OR NEW.tax_total IS DISTINCT FROM OLD.tax_total
+        OR NEW.total IS DISTINCT FROM OLD.total THEN
+        RAISE EXCEPTION '訂單價格明細不可修改';
+    END IF;
+    RETURN NEW;
+END;
+$$ LANGUAGE plpgsql;
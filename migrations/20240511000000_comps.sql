@@ -0,0 +1,25 @@
+-- === 招待票與公關名單 ===
+-- 主辦單位發給媒體、來賓的免費票，不經過訂單與金流；
+-- 庫存從指定票種扣減（可建立專用的招待票種或加入庫存池作為招待配額）
+CREATE TABLE comps (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    concert_id UUID NOT NULL REFERENCES concerts(id) ON DELETE CASCADE,
+    ticket_id UUID NOT NULL REFERENCES tickets(id),
+    email TEXT NOT NULL,
+    name TEXT,
+    quantity INTEGER NOT NULL CHECK (quantity > 0),
+    note TEXT,
+    issued_by UUID REFERENCES users(id) ON DELETE SET NULL,
+    issued_at TIMESTAMP DEFAULT CURRENT_TIMESTAMP
+);
+
+CREATE INDEX idx_comps_concert_id ON comps(concert_id);
+CREATE INDEX idx_comps_ticket_id ON comps(ticket_id);
+
+-- 招待票同樣發出入場憑證，憑證屬於訂單或招待票其中之一
+ALTER TABLE admissions ALTER COLUMN order_id DROP NOT NULL;
+ALTER TABLE admissions ADD COLUMN comp_id UUID REFERENCES comps(id) ON DELETE CASCADE;
+ALTER TABLE admissions ADD CONSTRAINT admissions_source_check
+    CHECK ((order_id IS NULL) <> (comp_id IS NULL));
+
+CREATE INDEX idx_admissions_comp_id ON admissions(comp_id);
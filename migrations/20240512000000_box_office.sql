@@ -0,0 +1,52 @@
+-- === 售票處 ===
+-- 演出當天現場售票的工作人員，可使用 POS 端點代客下單
+ALTER TABLE users ADD COLUMN is_box_office BOOLEAN NOT NULL DEFAULT false;
+
+-- 現場購票的顧客可以不註冊帳號
+ALTER TABLE orders ALTER COLUMN user_id DROP NOT NULL;
+
+-- 錢櫃班次：工作人員開班時登記零用金，交班時點算現金
+-- 每位工作人員同時只能有一個未結束的班次
+CREATE TABLE cash_drawer_sessions (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    staff_id UUID NOT NULL REFERENCES users(id),
+    opening_float NUMERIC NOT NULL CHECK (opening_float >= 0),
+    counted_cash NUMERIC CHECK (counted_cash >= 0),
+    note TEXT,
+    opened_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    closed_at TIMESTAMP
+);
+
+CREATE UNIQUE INDEX idx_cash_drawer_sessions_open ON cash_drawer_sessions(staff_id) WHERE closed_at IS NULL;
+
+-- 現場售票記錄：付款方式、收取現金與找零，以及所屬的錢櫃班次
+CREATE TABLE box_office_sales (
+    order_id UUID PRIMARY KEY REFERENCES orders(id) ON DELETE CASCADE,
+    session_id UUID NOT NULL REFERENCES cash_drawer_sessions(id),
+    staff_id UUID NOT NULL REFERENCES users(id),
+    payment_method TEXT NOT NULL CHECK (payment_method IN ('cash', 'card')),
+    amount NUMERIC NOT NULL,
+    cash_tendered NUMERIC,
+    change_due NUMERIC,
+    card_reference TEXT,
+    created_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP
+);
+
+CREATE INDEX idx_box_office_sales_session_id ON box_office_sales(session_id);
+
+-- 匿名訂單沒有購買者，不計入購買者排行
+DROP MATERIALIZED VIEW report_buyer_daily;
+
+CREATE MATERIALIZED VIEW report_buyer_daily AS
+SELECT o.user_id,
+       t.concert_id,
+       o.created_at::date AS day,
+       COUNT(*)::bigint AS orders,
+       SUM(o.quantity)::bigint AS tickets,
+       SUM(o.total) AS spent
+FROM orders o
+JOIN tickets t ON o.ticket_id = t.id
+WHERE o.status = 'paid' AND o.user_id IS NOT NULL
+GROUP BY o.user_id, t.concert_id, o.created_at::date;
+
+CREATE UNIQUE INDEX idx_report_buyer_daily_key ON report_buyer_daily(user_id, concert_id, day);
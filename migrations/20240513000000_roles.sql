@@ -0,0 +1,19 @@
+-- === 角色 ===
+-- 取代 users.is_admin 與 users.is_box_office，一位使用者可以擁有多個角色
+CREATE TABLE user_roles (
+    user_id UUID NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    role TEXT NOT NULL CHECK (role IN ('admin', 'organizer', 'box_office', 'scanner', 'support', 'customer')),
+    granted_by UUID REFERENCES users(id) ON DELETE SET NULL,
+    granted_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    PRIMARY KEY (user_id, role)
+);
+
+CREATE INDEX idx_user_roles_role ON user_roles(role);
+
+-- 既有使用者都是顧客；管理員與售票處工作人員轉換為對應角色
+INSERT INTO user_roles (user_id, role) SELECT id, 'customer' FROM users;
+INSERT INTO user_roles (user_id, role) SELECT id, 'admin' FROM users WHERE is_admin;
+INSERT INTO user_roles (user_id, role) SELECT id, 'box_office' FROM users WHERE is_box_office;
+
+ALTER TABLE users DROP COLUMN is_admin;
+ALTER TABLE users DROP COLUMN is_box_office;
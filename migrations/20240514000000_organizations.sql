@@ -0,0 +1,14 @@
+-- === 主辦單位（多租戶） ===
+-- 主辦單位成為租戶：演唱會、票種、報表與工作人員都歸屬於一個主辦單位
+ALTER TABLE organizers RENAME TO organizations;
+ALTER TABLE concerts RENAME COLUMN organizer_id TO organization_id;
+ALTER TABLE settlements RENAME COLUMN organizer_id TO organization_id;
+
+CREATE INDEX idx_concerts_organization_id ON concerts(organization_id);
+
+-- 工作人員所屬的主辦單位；NULL 為平台工作人員
+-- 不使用 ON DELETE SET NULL，避免刪除主辦單位後其工作人員變成可存取所有資料的平台工作人員
+ALTER TABLE users
+    ADD COLUMN organization_id UUID REFERENCES organizations(id);
+
+CREATE INDEX idx_users_organization_id ON users(organization_id);
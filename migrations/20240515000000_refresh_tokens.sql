@@ -0,0 +1,28 @@
+-- === 刷新令牌 ===
+-- 存取令牌改為短效期，登入時另發可輪替的刷新令牌；資料庫只保存刷新令牌的 SHA-256 雜湊
+-- 同一次登入輪替出的刷新令牌屬於同一個 family，已使用的令牌再次出現時撤銷整個 family
+CREATE TABLE refresh_tokens (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    user_id UUID NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    family_id UUID NOT NULL,
+    token_hash TEXT NOT NULL UNIQUE,
+    -- 與此刷新令牌一起簽發的存取令牌，撤銷 family 時一併撤銷
+    access_jti UUID NOT NULL,
+    access_expires_at TIMESTAMP NOT NULL,
+    expires_at TIMESTAMP NOT NULL,
+    used_at TIMESTAMP,
+    revoked_at TIMESTAMP,
+    created_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP
+);
+
+CREATE INDEX idx_refresh_tokens_user_id ON refresh_tokens(user_id);
+CREATE INDEX idx_refresh_tokens_family_id ON refresh_tokens(family_id);
+
+-- === 已撤銷的存取令牌 ===
+-- 以 JWT 的 jti 記錄，存取令牌過期後即可刪除
+CREATE TABLE revoked_tokens (
+    jti UUID PRIMARY KEY,
+    expires_at TIMESTAMP NOT NULL
+);
+
+CREATE INDEX idx_revoked_tokens_expires_at ON revoked_tokens(expires_at);
@@ -0,0 +1,17 @@
+-- === 電子郵件驗證 ===
+ALTER TABLE users ADD COLUMN email_verified_at TIMESTAMP;
+
+-- === 單次使用的用戶令牌 ===
+-- 電子郵件驗證與密碼重設令牌，資料庫只保存 SHA-256 雜湊；
+-- 同一用途發出新令牌時，先前尚未使用的令牌一併失效
+CREATE TABLE user_tokens (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    user_id UUID NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    purpose TEXT NOT NULL CHECK (purpose IN ('verify_email', 'reset_password')),
+    token_hash TEXT NOT NULL UNIQUE,
+    expires_at TIMESTAMP NOT NULL,
+    used_at TIMESTAMP,
+    created_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP
+);
+
+CREATE INDEX idx_user_tokens_user_id ON user_tokens(user_id, purpose);
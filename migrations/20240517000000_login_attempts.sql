@@ -0,0 +1,11 @@
+-- === 登入失敗次數 ===
+-- key 為 'account:<電子郵件>' 或 'ip:<位址>'，帳號與來源 IP 分別計算
+-- 超過門檻後依失敗次數指數延長鎖定時間，登入成功或管理員解除鎖定時刪除
+CREATE TABLE login_attempts (
+    key TEXT PRIMARY KEY,
+    failures INTEGER NOT NULL,
+    last_failure_at TIMESTAMP NOT NULL,
+    locked_until TIMESTAMP
+);
+
+CREATE INDEX idx_login_attempts_last_failure_at ON login_attempts(last_failure_at);
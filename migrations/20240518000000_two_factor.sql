@@ -0,0 +1,29 @@
+-- === 兩步驟驗證（TOTP） ===
+-- enabled_at 為 NULL 表示設定中，尚未以驗證碼確認
+-- last_used_step 記錄最後一次使用的時間步，同一組驗證碼不可重複使用
+CREATE TABLE user_totp (
+    user_id UUID PRIMARY KEY REFERENCES users(id) ON DELETE CASCADE,
+    secret TEXT NOT NULL,
+    enabled_at TIMESTAMP,
+    last_used_step BIGINT,
+    created_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP
+);
+
+-- === 救援碼 ===
+-- 遺失驗證器時使用，每組只能使用一次，資料庫只保存 SHA-256 雜湊
+CREATE TABLE totp_recovery_codes (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    user_id UUID NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    code_hash TEXT NOT NULL,
+    used_at TIMESTAMP,
+    created_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    UNIQUE (user_id, code_hash)
+);
+
+-- 密碼驗證通過後發出的兩步驟驗證挑戰令牌
+ALTER TABLE user_tokens DROP CONSTRAINT user_tokens_purpose_check;
+ALTER TABLE user_tokens ADD CONSTRAINT user_tokens_purpose_check
+    CHECK (purpose IN ('verify_email', 'reset_password', 'login_challenge'));
+
+-- 刷新令牌記錄該次登入是否通過兩步驟驗證，換發時沿用
+ALTER TABLE refresh_tokens ADD COLUMN two_factor BOOLEAN NOT NULL DEFAULT FALSE;
@@ -0,0 +1,29 @@
+-- === 社群登入（OpenID Connect） ===
+-- 只以社群帳號登入的用戶沒有密碼
+ALTER TABLE users ALTER COLUMN password_hash DROP NOT NULL;
+
+-- 用戶連結的外部身分，同一個身分提供者的 subject 只能連結一個用戶
+CREATE TABLE user_identities (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    user_id UUID NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    provider TEXT NOT NULL,
+    subject TEXT NOT NULL,
+    -- 連結時身分提供者回傳的電子郵件
+    email TEXT,
+    created_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    last_login_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    UNIQUE (provider, subject)
+);
+
+CREATE INDEX idx_user_identities_user_id ON user_identities(user_id);
+
+-- 授權碼流程進行中的登入狀態
+-- state 只保存 SHA-256 雜湊；回呼時取出並刪除，每個 state 只能使用一次
+CREATE TABLE oidc_login_states (
+    state_hash TEXT PRIMARY KEY,
+    provider TEXT NOT NULL,
+    code_verifier TEXT NOT NULL,
+    nonce TEXT NOT NULL,
+    expires_at TIMESTAMP NOT NULL,
+    created_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP
+);
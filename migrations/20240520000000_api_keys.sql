@@ -0,0 +1,19 @@
+-- === 合作夥伴 API 金鑰 ===
+-- 金鑰只保存 SHA-256 雜湊；prefix 為金鑰開頭的可見部分，方便在列表中辨識
+CREATE TABLE api_keys (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    name TEXT NOT NULL,
+    prefix TEXT NOT NULL UNIQUE,
+    key_hash TEXT NOT NULL UNIQUE,
+    scopes TEXT[] NOT NULL,
+    -- 有值時只能存取該主辦單位的資料
+    organization_id UUID REFERENCES organizations(id) ON DELETE CASCADE,
+    -- 每分鐘可呼叫次數
+    rate_limit_per_minute INT NOT NULL CHECK (rate_limit_per_minute > 0),
+    expires_at TIMESTAMP NOT NULL,
+    created_by UUID REFERENCES users(id) ON DELETE SET NULL,
+    created_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    last_used_at TIMESTAMP,
+    revoked_at TIMESTAMP,
+    CHECK (scopes <@ ARRAY['concerts:read', 'orders:read', 'reports:read'])
+);
@@ -0,0 +1,18 @@
+-- === 用戶個人資料 ===
+-- 顯示名稱、電話、語系與生日皆為選填；生日用於年齡限制的演出
+ALTER TABLE users
+    ADD COLUMN display_name TEXT,
+    ADD COLUMN phone TEXT,
+    ADD COLUMN locale TEXT,
+    ADD COLUMN date_of_birth DATE,
+    -- 行銷同意與最後一次變更同意的時間
+    ADD COLUMN marketing_consent BOOLEAN NOT NULL DEFAULT false,
+    ADD COLUMN marketing_consent_at TIMESTAMP,
+    -- 申請變更、尚未驗證的新電子郵件，驗證後才取代 email
+    ADD COLUMN pending_email TEXT,
+    ADD COLUMN created_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP;
+
+-- === 變更電子郵件令牌 ===
+ALTER TABLE user_tokens DROP CONSTRAINT user_tokens_purpose_check;
+ALTER TABLE user_tokens ADD CONSTRAINT user_tokens_purpose_check
+    CHECK (purpose IN ('verify_email', 'reset_password', 'login_challenge', 'change_email'));
@@ -0,0 +1,48 @@
+-- === 訂單保留 ===
+-- 訂單是法定須保存的會計記錄，刪除用戶不可連帶刪除訂單；帳號刪除一律以匿名化處理
+ALTER TABLE orders DROP CONSTRAINT orders_user_id_fkey;
+ALTER TABLE orders ADD CONSTRAINT orders_user_id_fkey
+    FOREIGN KEY (user_id) REFERENCES users(id) ON DELETE RESTRICT;
+
+-- 匿名化時間，匿名化後用戶列只保留 ID 供訂單與金流記錄參照
+ALTER TABLE users ADD COLUMN anonymized_at TIMESTAMP;
+
+-- === 登入記錄 ===
+-- 每次成功登入（簽發新的令牌 family）記錄一筆，供用戶匯出個人資料
+CREATE TABLE login_events (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    user_id UUID NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    -- password、oidc:{provider} 或 two_factor
+    method TEXT NOT NULL,
+    ip_address TEXT,
+    created_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP
+);
+
+CREATE INDEX idx_login_events_user_id ON login_events(user_id, created_at);
+
+-- === 刪除帳號申請 ===
+-- 用戶自行刪除或客服代為受理的刪除申請；每個用戶同時只能有一筆待處理的申請
+CREATE TABLE erasure_requests (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    user_id UUID NOT NULL REFERENCES users(id),
+    source TEXT NOT NULL CHECK (source IN ('self_service', 'support')),
+    status TEXT NOT NULL DEFAULT 'pending' CHECK (status IN ('pending', 'completed', 'rejected')),
+    reason TEXT,
+    requested_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    processed_at TIMESTAMP
+);
+
+CREATE UNIQUE INDEX idx_erasure_requests_pending ON erasure_requests(user_id) WHERE status = 'pending';
+CREATE INDEX idx_erasure_requests_status ON erasure_requests(status, requested_at);
+
+-- 刪除申請的稽核記錄，只新增不修改
+CREATE TABLE erasure_request_events (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    request_id UUID NOT NULL REFERENCES erasure_requests(id),
+    action TEXT NOT NULL CHECK (action IN ('requested', 'completed', 'rejected')),
+    actor_id UUID REFERENCES users(id) ON DELETE SET NULL,
+    note TEXT,
+    created_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP
+);
+
+CREATE INDEX idx_erasure_request_events_request_id ON erasure_request_events(request_id, created_at);
@@ -0,0 +1,4 @@
+-- === 配額移轉 ===
+-- 配額移轉只調整 stock，移入（正）或移出（負）的淨數量另外記錄，
+-- initial_stock 維持票種原始配額；已售出數量 = initial_stock + allocation_adjustment - stock
+ALTER TABLE tickets ADD COLUMN allocation_adjustment INTEGER NOT NULL DEFAULT 0;
@@ -0,0 +1,17 @@
+-- === 座位圖租戶範圍 ===
+-- 主辦單位人員匯入的座位圖屬於該主辦單位，平台匯入的座位圖 organization_id 為 NULL
+ALTER TABLE seat_maps ADD COLUMN organization_id UUID REFERENCES organizations(id);
+
+-- 既有座位圖只被單一主辦單位的演唱會使用時歸屬該主辦單位
+UPDATE seat_maps m
+SET organization_id = u.organization_id
+FROM (
+    SELECT seat_map_id, MIN(organization_id::text)::uuid AS organization_id
+    FROM concerts
+    WHERE seat_map_id IS NOT NULL
+    GROUP BY seat_map_id
+    HAVING COUNT(DISTINCT organization_id) = 1 AND COUNT(*) = COUNT(organization_id)
+) u
+WHERE m.id = u.seat_map_id;
+
+CREATE INDEX idx_seat_maps_organization_id ON seat_maps(organization_id);
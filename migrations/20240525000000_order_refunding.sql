@@ -0,0 +1,6 @@
+-- === 退款處理中 ===
+-- 退款時先將訂單轉為 refunding 再呼叫金流，金流確認後才在同一個交易中
+-- 寫入退款分錄、更新付款狀態並歸還庫存；金流退款失敗時恢復為 paid
+ALTER TABLE orders DROP CONSTRAINT orders_status_check;
+ALTER TABLE orders ADD CONSTRAINT orders_status_check
+    CHECK (status IN ('pending', 'paid', 'refunding', 'failed', 'refunded'));
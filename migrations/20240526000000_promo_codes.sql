@@ -0,0 +1,21 @@
+-- === 優惠碼 ===
+-- 主辦單位為演唱會發放的優惠碼，下單時折抵票價，折扣寫入訂單價格明細的 discount_total
+-- 百分比折扣以票價小計計算，固定金額折扣每筆訂單折抵一次；redemptions 為已使用次數
+CREATE TABLE promo_codes (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    concert_id UUID NOT NULL REFERENCES concerts(id) ON DELETE CASCADE,
+    code TEXT NOT NULL,
+    discount_type TEXT NOT NULL CHECK (discount_type IN ('percent', 'amount')),
+    amount NUMERIC NOT NULL CHECK (amount > 0),
+    max_redemptions INTEGER CHECK (max_redemptions > 0),
+    redemptions INTEGER NOT NULL DEFAULT 0 CHECK (redemptions >= 0),
+    starts_at TIMESTAMP,
+    ends_at TIMESTAMP,
+    created_at TIMESTAMP DEFAULT CURRENT_TIMESTAMP,
+    UNIQUE (concert_id, code),
+    CHECK (discount_type <> 'percent' OR amount <= 100),
+    CHECK (max_redemptions IS NULL OR redemptions <= max_redemptions)
+);
+
+-- 訂單使用的優惠碼；付款失敗或退款時歸還使用次數
+ALTER TABLE orders ADD COLUMN promo_code_id UUID REFERENCES promo_codes(id) ON DELETE RESTRICT;
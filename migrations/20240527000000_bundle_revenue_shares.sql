@@ -0,0 +1,88 @@
+-- === 組合商品營收分配 ===
+-- 組合商品的售價依組合內容的票面價值（票價 × 每組張數）分配給各票種，
+-- 各票種所屬演唱會的主辦單位依此比例結算；跨主辦單位的組合因此依各自票面價值拆分。
+-- 比例在創建組合時固定，之後調整票價不影響已售出的組合；所有組合內容票價皆為零時依張數分配
+ALTER TABLE bundle_components ADD COLUMN revenue_share NUMERIC NOT NULL DEFAULT 0
+    CHECK (revenue_share >= 0 AND revenue_share <= 1);
+
+CREATE FUNCTION set_bundle_revenue_shares(target UUID) RETURNS VOID AS $$
+    UPDATE bundle_components bc
+    SET revenue_share = s.share
+    FROM (
+        SELECT c.ticket_id,
+               CASE WHEN SUM(t.price * c.quantity) OVER () > 0
+                    THEN t.price * c.quantity / SUM(t.price * c.quantity) OVER ()
+                    ELSE c.quantity::numeric / SUM(c.quantity) OVER ()
+               END AS share
+        FROM bundle_components c
+        JOIN tickets t ON c.ticket_id = t.id
+        WHERE c.bundle_id = target
+    ) s
+    WHERE bc.bundle_id = target AND bc.ticket_id = s.ticket_id;
+$$ LANGUAGE sql;
+
+SELECT set_bundle_revenue_shares(id) FROM bundles;
+
+-- === 訂單的演唱會歸屬 ===
+-- 票種訂單整筆歸屬該票種；組合商品訂單每個組合內容一列，金額依 revenue_share 比例分配。
+-- first_in_concert 標示每筆訂單在每場演唱會的第一列，用於計算訂單數而不重複計入同場的多個組合內容
+CREATE VIEW order_concert_shares AS
+SELECT o.id AS order_id,
+       t.concert_id,
+       t.id AS ticket_id,
+       t.ticket_type,
+       NULL::text AS bundle_name,
+       o.quantity,
+       1::numeric AS share,
+       true AS first_in_concert
+FROM orders o
+JOIN tickets t ON o.ticket_id = t.id
+UNION ALL
+SELECT o.id,
+       t.concert_id,
+       t.id,
+       t.ticket_type,
+       b.name,
+       o.quantity * bc.quantity,
+       bc.revenue_share,
+       row_number() OVER (PARTITION BY o.id, t.concert_id ORDER BY t.id) = 1
+FROM orders o
+JOIN bundles b ON o.bundle_id = b.id
+JOIN bundle_components bc ON bc.bundle_id = b.id
+JOIN tickets t ON bc.ticket_id = t.id;
+
+-- === 報表物化視圖 ===
+-- 改以訂單的演唱會歸屬彙總，組合商品訂單依比例計入各票種營收；
+-- 訂單數在每場演唱會計一筆，跨演唱會的組合商品訂單在每場各計一筆
+DROP MATERIALIZED VIEW report_sales_hourly;
+
+CREATE MATERIALIZED VIEW report_sales_hourly AS
+SELECT s.concert_id,
+       s.ticket_id,
+       date_trunc('hour', o.created_at) AS bucket,
+       o.status,
+       COUNT(*) FILTER (WHERE s.first_in_concert)::bigint AS orders,
+       SUM(s.quantity)::bigint AS tickets,
+       SUM(o.total * s.share) AS revenue
+FROM orders o
+JOIN order_concert_shares s ON s.order_id = o.id
+GROUP BY s.concert_id, s.ticket_id, date_trunc('hour', o.created_at), o.status;
+
+CREATE UNIQUE INDEX idx_report_sales_hourly_key ON report_sales_hourly(ticket_id, bucket, status);
+CREATE INDEX idx_report_sales_hourly_bucket ON report_sales_hourly(bucket);
+
+DROP MATERIALIZED VIEW report_buyer_daily;
+
+CREATE MATERIALIZED VIEW report_buyer_daily AS
+SELECT o.user_id,
+       s.concert_id,
+       o.created_at::date AS day,
+       COUNT(*) FILTER (WHERE s.first_in_concert)::bigint AS orders,
+       SUM(s.quantity)::bigint AS tickets,
+       SUM(o.total * s.share) AS spent
+FROM orders o
+JOIN order_concert_shares s ON s.order_id = o.id
+WHERE o.status = 'paid' AND o.user_id IS NOT NULL
+GROUP BY o.user_id, s.concert_id, o.created_at::date;
+
+CREATE UNIQUE INDEX idx_report_buyer_daily_key ON report_buyer_daily(user_id, concert_id, day);
@@ -0,0 +1,8 @@
+-- === 待人工退款的付款 ===
+-- 訂單已失敗（付款被拒或逾時未付款）後才確認扣款成功時，系統會自動退回款項；
+-- 自動退款失敗時付款標記為 refund_required，等待金流重送通知時重試或由人工退款
+ALTER TABLE payments DROP CONSTRAINT payments_status_check;
+ALTER TABLE payments ADD CONSTRAINT payments_status_check
+    CHECK (status IN ('pending', 'succeeded', 'failed', 'refunded', 'refund_required'));
+
+CREATE INDEX idx_payments_refund_required ON payments(created_at) WHERE status = 'refund_required';
@@ -0,0 +1,19 @@
+-- === 買家報表排除已匿名化的用戶 ===
+-- 訂單與分錄仍以用戶 ID 保留作為會計記錄，但依刪除申請匿名化的用戶不再出現在買家消費排行；
+-- 完成刪除申請時會重新整理此視圖
+DROP MATERIALIZED VIEW report_buyer_daily;
+
+CREATE MATERIALIZED VIEW report_buyer_daily AS
+SELECT o.user_id,
+       s.concert_id,
+       o.created_at::date AS day,
+       COUNT(*) FILTER (WHERE s.first_in_concert)::bigint AS orders,
+       SUM(s.quantity)::bigint AS tickets,
+       SUM(o.total * s.share) AS spent
+FROM orders o
+JOIN order_concert_shares s ON s.order_id = o.id
+JOIN users u ON o.user_id = u.id
+WHERE o.status = 'paid' AND u.anonymized_at IS NULL
+GROUP BY o.user_id, s.concert_id, o.created_at::date;
+
+CREATE UNIQUE INDEX idx_report_buyer_daily_key ON report_buyer_daily(user_id, concert_id, day);
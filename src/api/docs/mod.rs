@@ -1,9 +1,53 @@
 use utoipa::OpenApi;
 
-use crate::domain::auth::model::{LoginInput, LoginResponse, RegisterInput};
-use crate::domain::concert::model::{Concert, CreateConcert};
-use crate::domain::order::model::{CreateOrder, OrderQuery, OrderView};
-use crate::domain::ticket::model::{CreateTicket, Ticket, TicketQuery};
+use crate::domain::addon::model::{Addon, AddonVariant, AddonVariantInput, CreateAddon, RedeemOrderItem};
+use crate::domain::admission::model::{Admission, AdmissionStatus, ScanAdmission};
+use crate::domain::api_key::model::{ApiKey, ApiScope, CreateApiKey, CreatedApiKey};
+use crate::domain::auth::model::{
+    ChangeEmailInput, ChangePasswordInput, ConfirmEmailChangeInput, ForgotPasswordInput, GrantRole, Jwk, JwkSet,
+    LinkedIdentity, LoginEvent, LoginInput, LoginOutcome, LoginResponse, RecoveryCodes, RefreshTokenInput,
+    RegisterInput, ResetPasswordInput, Role, RoleAssignment, TwoFactorChallenge, TwoFactorCodeInput,
+    TwoFactorEnrollment, TwoFactorLoginInput, UpdateProfile, UserProfile, UserRoles, VerifyEmailInput,
+};
+use crate::domain::box_office::model::{
+    BoxOfficeCustomer, BoxOfficeSale, BoxOfficeSaleResponse, CloseDrawer, CreateBoxOfficeOrder, DrawerReport,
+    DrawerSession, OpenDrawer, PaymentMethod,
+};
+use crate::domain::bundle::model::{Bundle, BundleComponent, BundleComponentInput, CreateBundle};
+use crate::domain::comp::model::{Comp, CompGuest, GuestListEntry, IssueComps};
+use crate::domain::concert::model::{Concert, ConcertFee, CreateConcert, CreateConcertFee, FeeType, TaxSetting};
+use crate::domain::inventory::model::{
+    AssignPool, CreateInventoryPool, InventoryPool, InventoryPoolView, MoveAllocation, PoolTicket, UpdatePoolCapacity,
+};
+use crate::domain::ledger::model::{
+    AccountBalance, JournalImbalance, LedgerAccount, LedgerEntry, LedgerEntryType, OrderLedgerMismatch,
+    ReconciliationReport,
+};
+use crate::domain::order::model::{
+    AddonSelection, BestAvailable, CheckoutResponse, CreateBundleOrder, CreateOrder, Order, OrderBundle, OrderItem,
+    OrderItemStatus, OrderQuery, OrderStatus, OrderView, PriceBreakdown,
+};
+use crate::domain::organization::model::{
+    AssignOrganization, CreateOrganization, Organization, SetUserOrganization, UpdateCommission,
+};
+use crate::domain::privacy::model::{
+    CreateErasureRequest, DataExport, DataExportFormat, DeleteAccountInput, ErasureRequest, ErasureRequestDetail,
+    ErasureRequestEvent, ErasureSource, ErasureStatus, ProcessErasureRequest,
+};
+use crate::domain::promo_code::model::{CreatePromoCode, DiscountType, PromoCode};
+use crate::domain::report::model::{
+    CancellationReport, Granularity, ReportQuery, SalesDataPoint, TicketSalesReport, TopBuyer,
+};
+use crate::domain::seating::model::{
+    ConcertSeating, ConfigureSeating, ImportRow, ImportSeat, ImportSeatMap, ImportSection, PriceZone, PriceZoneInput,
+    Seat, SeatAvailability, SeatMap, SeatMapSummary, SeatRow, SeatSection, SeatStatus, ZoneSection,
+};
+use crate::domain::settlement::model::{Settlement, SettlementLine, SettlementStatus};
+use crate::domain::ticket::model::{
+    CreateTicket, DemandRule, DemandRuleInput, PriceTier, PriceTierInput, SetTicketPricing, Ticket, TicketPricing,
+    TicketQuery,
+};
+use crate::utils::export::ExportFormat;
 
 /// API 文檔
 #[derive(OpenApi)]
@@ -11,35 +55,266 @@ use crate::domain::ticket::model::{CreateTicket, Ticket, TicketQuery};
     paths(
         crate::api::handlers::auth_handler::register,
         crate::api::handlers::auth_handler::login,
+        crate::api::handlers::auth_handler::verify_two_factor,
+        crate::api::handlers::auth_handler::enroll_two_factor,
+        crate::api::handlers::auth_handler::activate_two_factor,
+        crate::api::handlers::auth_handler::regenerate_recovery_codes,
+        crate::api::handlers::auth_handler::disable_two_factor,
+        crate::api::handlers::auth_handler::refresh,
+        crate::api::handlers::auth_handler::logout,
+        crate::api::handlers::auth_handler::logout_all,
+        crate::api::handlers::auth_handler::verify_email,
+        crate::api::handlers::auth_handler::resend_verification,
+        crate::api::handlers::auth_handler::forgot_password,
+        crate::api::handlers::auth_handler::reset_password,
+        crate::api::handlers::auth_handler::oidc_authorize,
+        crate::api::handlers::auth_handler::oidc_callback,
+        crate::api::handlers::auth_handler::jwks,
         crate::api::handlers::auth_handler::get_me,
+        crate::api::handlers::auth_handler::update_me,
+        crate::api::handlers::auth_handler::change_email,
+        crate::api::handlers::auth_handler::confirm_email_change,
+        crate::api::handlers::auth_handler::change_password,
         crate::api::handlers::concert_handler::create_concert,
         crate::api::handlers::concert_handler::list_concerts,
+        crate::api::handlers::concert_handler::update_concert_tax,
+        crate::api::handlers::concert_handler::list_concert_fees,
+        crate::api::handlers::concert_handler::create_concert_fee,
         crate::api::handlers::ticket_handler::create_ticket,
         crate::api::handlers::ticket_handler::list_tickets,
+        crate::api::handlers::ticket_handler::get_ticket_pricing,
+        crate::api::handlers::ticket_handler::set_ticket_pricing,
         crate::api::handlers::order_handler::create_order,
         crate::api::handlers::order_handler::list_orders,
+        crate::api::handlers::order_handler::list_all_orders,
         crate::api::handlers::order_handler::get_order_by_id,
+        crate::api::handlers::order_handler::refund_order,
+        crate::api::handlers::box_office_handler::create_box_office_order,
+        crate::api::handlers::box_office_handler::open_drawer,
+        crate::api::handlers::box_office_handler::get_current_drawer,
+        crate::api::handlers::box_office_handler::get_drawer_report,
+        crate::api::handlers::box_office_handler::close_drawer,
+        crate::api::handlers::bundle_handler::list_bundles,
+        crate::api::handlers::bundle_handler::get_bundle,
+        crate::api::handlers::bundle_handler::create_bundle,
+        crate::api::handlers::bundle_handler::create_bundle_order,
+        crate::api::handlers::addon_handler::list_concert_addons,
+        crate::api::handlers::addon_handler::create_addon,
+        crate::api::handlers::promo_code_handler::list_promo_codes,
+        crate::api::handlers::promo_code_handler::create_promo_code,
+        crate::api::handlers::comp_handler::issue_comps,
+        crate::api::handlers::comp_handler::get_guest_list,
+        crate::api::handlers::admission_handler::scan_admission,
+        crate::api::handlers::admission_handler::redeem_order_item,
+        crate::api::handlers::payment_handler::payment_webhook,
+        crate::api::handlers::ledger_handler::get_reconciliation,
+        crate::api::handlers::ledger_handler::list_order_entries,
+        crate::api::handlers::organization_handler::list_organizations,
+        crate::api::handlers::organization_handler::create_organization,
+        crate::api::handlers::organization_handler::update_organization_commission,
+        crate::api::handlers::organization_handler::assign_concert_organization,
+        crate::api::handlers::settlement_handler::get_settlement,
+        crate::api::handlers::settlement_handler::lock_settlement,
+        crate::api::handlers::settlement_handler::pay_settlement,
+        crate::api::handlers::seating_handler::import_seat_map,
+        crate::api::handlers::seating_handler::import_seat_map_csv,
+        crate::api::handlers::seating_handler::list_seat_maps,
+        crate::api::handlers::seating_handler::get_seat_map,
+        crate::api::handlers::seating_handler::configure_concert_seating,
+        crate::api::handlers::seating_handler::get_concert_seats,
+        crate::api::handlers::inventory_handler::list_pools,
+        crate::api::handlers::inventory_handler::create_pool,
+        crate::api::handlers::inventory_handler::update_pool_capacity,
+        crate::api::handlers::inventory_handler::assign_ticket_pool,
+        crate::api::handlers::inventory_handler::move_allocation,
+        crate::api::handlers::report_handler::get_sales_report,
+        crate::api::handlers::report_handler::get_sales_time_series,
+        crate::api::handlers::report_handler::get_top_buyers,
+        crate::api::handlers::report_handler::get_cancellation_report,
+        crate::api::handlers::report_handler::refresh_reports,
+        crate::api::handlers::user_handler::get_user_roles,
+        crate::api::handlers::user_handler::grant_user_role,
+        crate::api::handlers::user_handler::revoke_user_role,
+        crate::api::handlers::user_handler::set_user_organization,
+        crate::api::handlers::user_handler::list_organization_members,
+        crate::api::handlers::user_handler::unlock_user,
+        crate::api::handlers::user_handler::unlock_ip,
+        crate::api::handlers::api_key_handler::create_api_key,
+        crate::api::handlers::api_key_handler::list_api_keys,
+        crate::api::handlers::api_key_handler::revoke_api_key,
+        crate::api::handlers::privacy_handler::export_my_data,
+        crate::api::handlers::privacy_handler::delete_me,
+        crate::api::handlers::privacy_handler::list_erasure_requests,
+        crate::api::handlers::privacy_handler::create_erasure_request,
+        crate::api::handlers::privacy_handler::get_erasure_request,
+        crate::api::handlers::privacy_handler::complete_erasure_request,
+        crate::api::handlers::privacy_handler::reject_erasure_request,
     ),
     components(
         schemas(
             RegisterInput,
             LoginInput,
             LoginResponse,
+            LoginOutcome,
+            TwoFactorChallenge,
+            TwoFactorLoginInput,
+            TwoFactorCodeInput,
+            TwoFactorEnrollment,
+            RecoveryCodes,
+            RefreshTokenInput,
+            VerifyEmailInput,
+            ForgotPasswordInput,
+            ResetPasswordInput,
+            UserProfile,
+            UpdateProfile,
+            ChangeEmailInput,
+            ConfirmEmailChangeInput,
+            ChangePasswordInput,
+            Jwk,
+            JwkSet,
+            Role,
+            RoleAssignment,
+            ApiScope,
+            ApiKey,
+            CreateApiKey,
+            CreatedApiKey,
+            UserRoles,
+            GrantRole,
             Concert,
             CreateConcert,
+            TaxSetting,
+            FeeType,
+            ConcertFee,
+            CreateConcertFee,
             Ticket,
             CreateTicket,
             TicketQuery,
+            PriceTier,
+            DemandRule,
+            PriceTierInput,
+            DemandRuleInput,
+            SetTicketPricing,
+            TicketPricing,
+            Order,
+            OrderStatus,
+            CheckoutResponse,
+            PaymentMethod,
+            DrawerSession,
+            OpenDrawer,
+            CloseDrawer,
+            BoxOfficeCustomer,
+            CreateBoxOfficeOrder,
+            BoxOfficeSale,
+            BoxOfficeSaleResponse,
+            DrawerReport,
+            Bundle,
+            BundleComponent,
+            BundleComponentInput,
+            CreateBundle,
+            CreateBundleOrder,
+            OrderBundle,
+            Comp,
+            IssueComps,
+            CompGuest,
+            GuestListEntry,
+            Admission,
+            AdmissionStatus,
+            ScanAdmission,
+            Addon,
+            AddonVariant,
+            CreateAddon,
+            AddonVariantInput,
+            RedeemOrderItem,
+            DiscountType,
+            PromoCode,
+            CreatePromoCode,
+            AddonSelection,
+            OrderItem,
+            OrderItemStatus,
+            LedgerAccount,
+            LedgerEntryType,
+            LedgerEntry,
+            AccountBalance,
+            JournalImbalance,
+            OrderLedgerMismatch,
+            ReconciliationReport,
+            Organization,
+            CreateOrganization,
+            UpdateCommission,
+            AssignOrganization,
+            SetUserOrganization,
+            SettlementStatus,
+            SettlementLine,
+            Settlement,
+            ExportFormat,
+            SeatMap,
+            SeatSection,
+            SeatRow,
+            Seat,
+            SeatMapSummary,
+            ImportSeatMap,
+            ImportSection,
+            ImportRow,
+            ImportSeat,
+            SeatStatus,
+            ZoneSection,
+            PriceZoneInput,
+            ConfigureSeating,
+            PriceZone,
+            SeatAvailability,
+            ConcertSeating,
+            InventoryPool,
+            PoolTicket,
+            InventoryPoolView,
+            CreateInventoryPool,
+            UpdatePoolCapacity,
+            AssignPool,
+            MoveAllocation,
+            ReportQuery,
+            Granularity,
+            TicketSalesReport,
+            SalesDataPoint,
+            TopBuyer,
+            CancellationReport,
             OrderView,
+            PriceBreakdown,
             CreateOrder,
+            BestAvailable,
             OrderQuery,
+            DataExportFormat,
+            DataExport,
+            LinkedIdentity,
+            LoginEvent,
+            DeleteAccountInput,
+            ErasureSource,
+            ErasureStatus,
+            ErasureRequest,
+            ErasureRequestEvent,
+            ErasureRequestDetail,
+            CreateErasureRequest,
+            ProcessErasureRequest,
         )
     ),
     tags(
         (name = "auth", description = "用戶認證 API"),
+        (name = "users", description = "用戶角色管理 API"),
+        (name = "api_keys", description = "合作夥伴 API 金鑰管理 API"),
+        (name = "privacy", description = "個人資料匯出、帳號刪除與刪除申請 API"),
         (name = "concerts", description = "演唱會 API"),
         (name = "tickets", description = "票券 API"),
         (name = "orders", description = "訂單 API"),
+        (name = "box_office", description = "售票處現場售票與錢櫃對帳 API"),
+        (name = "bundles", description = "組合商品 API"),
+        (name = "addons", description = "加購商品 API"),
+        (name = "promo_codes", description = "優惠碼 API"),
+        (name = "comps", description = "招待票與公關名單 API"),
+        (name = "admissions", description = "入場憑證、驗票與加購商品領取 API"),
+        (name = "payments", description = "金流 API"),
+        (name = "ledger", description = "分錄與對帳 API"),
+        (name = "settlements", description = "主辦單位結算 API"),
+        (name = "inventory", description = "共用庫存池與配額 API"),
+        (name = "reports", description = "銷售分析報表 API"),
+        (name = "seating", description = "座位圖與劃位 API"),
     ),
     info(
         title = "票務系統 API",
@@ -0,0 +1,64 @@
+use axum::{
+    extract::{Json, Path, State},
+    http::StatusCode,
+};
+use uuid::Uuid;
+
+use crate::api::middleware::permission::{perm, Authorized};
+use crate::api::routes::AppState;
+use crate::domain::addon::model::{Addon, CreateAddon};
+use crate::utils::error::AppError;
+
+/// 獲取演唱會加購商品列表處理程序
+#[axum::debug_handler]
+#[utoipa::path(
+    get,
+    path = "/concerts/{concert_id}/addons",
+    params(
+        ("concert_id" = Uuid, Path, description = "演唱會 ID")
+    ),
+    responses(
+        (status = 200, description = "成功獲取加購商品列表", body = Vec<Addon>),
+        (status = 404, description = "找不到演唱會")
+    ),
+    tag = "addons"
+)]
+pub async fn list_concert_addons(
+    State(state): State<AppState>,
+    Path(concert_id): Path<Uuid>,
+) -> Result<Json<Vec<Addon>>, AppError> {
+    let addons = state.addon_service.get_concert_addons(concert_id).await?;
+    Ok(Json(addons))
+}
+
+/// 創建加購商品處理程序
+#[axum::debug_handler]
+#[utoipa::path(
+    post,
+    path = "/admin/concerts/{concert_id}/addons",
+    params(
+        ("concert_id" = Uuid, Path, description = "演唱會 ID")
+    ),
+    request_body = CreateAddon,
+    responses(
+        (status = 201, description = "成功創建加購商品", body = Addon),
+        (status = 400, description = "無效的輸入"),
+        (status = 401, description = "未認證"),
+        (status = 403, description = "未授權"),
+        (status = 404, description = "找不到演唱會或票券"),
+        (status = 409, description = "規格名稱重複")
+    ),
+    security(
+        ("jwt_auth" = [])
+    ),
+    tag = "addons"
+)]
+pub async fn create_addon(
+    State(state): State<AppState>,
+    Authorized(user, _): Authorized<perm::ManageEvents>,
+    Path(concert_id): Path<Uuid>,
+    Json(input): Json<CreateAddon>,
+) -> Result<(StatusCode, Json<Addon>), AppError> {
+    let addon = state.addon_service.create_addon(concert_id, input, user.tenant()).await?;
+    Ok((StatusCode::CREATED, Json(addon)))
+}
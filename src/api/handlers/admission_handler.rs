@@ -0,0 +1,62 @@
+use axum::extract::{Json, State};
+
+use crate::api::middleware::permission::{perm, Authorized};
+use crate::api::routes::AppState;
+use crate::domain::addon::model::RedeemOrderItem;
+use crate::domain::admission::model::{Admission, ScanAdmission};
+use crate::domain::order::model::OrderItem;
+use crate::utils::error::AppError;
+
+/// 掃描入場憑證處理程序
+#[axum::debug_handler]
+#[utoipa::path(
+    post,
+    path = "/admin/admissions/scan",
+    request_body = ScanAdmission,
+    responses(
+        (status = 200, description = "驗票成功，憑證已標記為已使用", body = Admission),
+        (status = 401, description = "未認證"),
+        (status = 403, description = "未授權"),
+        (status = 404, description = "找不到入場憑證"),
+        (status = 409, description = "憑證已使用或已作廢")
+    ),
+    security(
+        ("jwt_auth" = [])
+    ),
+    tag = "admissions"
+)]
+pub async fn scan_admission(
+    State(state): State<AppState>,
+    Authorized(user, _): Authorized<perm::ScanAdmissions>,
+    Json(input): Json<ScanAdmission>,
+) -> Result<Json<Admission>, AppError> {
+    let admission = state.admission_service.scan(input, user.tenant()).await?;
+    Ok(Json(admission))
+}
+
+/// 兌換加購商品處理程序
+#[axum::debug_handler]
+#[utoipa::path(
+    post,
+    path = "/admin/admissions/pickup",
+    request_body = RedeemOrderItem,
+    responses(
+        (status = 200, description = "兌換成功，加購商品已標記為已領取", body = OrderItem),
+        (status = 401, description = "未認證"),
+        (status = 403, description = "未授權"),
+        (status = 404, description = "找不到加購商品"),
+        (status = 409, description = "訂單尚未付款、已領取或已作廢")
+    ),
+    security(
+        ("jwt_auth" = [])
+    ),
+    tag = "admissions"
+)]
+pub async fn redeem_order_item(
+    State(state): State<AppState>,
+    Authorized(user, _): Authorized<perm::ScanAdmissions>,
+    Json(input): Json<RedeemOrderItem>,
+) -> Result<Json<OrderItem>, AppError> {
+    let item = state.addon_service.redeem(input, user.tenant()).await?;
+    Ok(Json(item))
+}
@@ -0,0 +1,86 @@
+use axum::extract::{Json, Path, State};
+use axum::http::StatusCode;
+use uuid::Uuid;
+
+use crate::api::middleware::permission::{perm, Authorized};
+use crate::api::routes::AppState;
+use crate::domain::api_key::model::{ApiKey, CreateApiKey, CreatedApiKey};
+use crate::utils::error::AppError;
+
+/// 創建 API 金鑰處理程序
+/// 完整金鑰只在回應中出現一次，請立即交給合作夥伴保存
+#[axum::debug_handler]
+#[utoipa::path(
+    post,
+    path = "/admin/api-keys",
+    request_body = CreateApiKey,
+    responses(
+        (status = 201, description = "成功創建 API 金鑰", body = CreatedApiKey),
+        (status = 400, description = "無效的輸入數據或主辦單位不存在"),
+        (status = 401, description = "未認證"),
+        (status = 403, description = "未授權")
+    ),
+    security(
+        ("jwt_auth" = [])
+    ),
+    tag = "api_keys"
+)]
+pub async fn create_api_key(
+    State(state): State<AppState>,
+    Authorized(user, _): Authorized<perm::ManageUsers>,
+    Json(input): Json<CreateApiKey>,
+) -> Result<(StatusCode, Json<CreatedApiKey>), AppError> {
+    let created = state.api_key_service.create(input, user.id, user.tenant()).await?;
+    Ok((StatusCode::CREATED, Json(created)))
+}
+
+/// 列出 API 金鑰處理程序
+#[axum::debug_handler]
+#[utoipa::path(
+    get,
+    path = "/admin/api-keys",
+    responses(
+        (status = 200, description = "成功獲取 API 金鑰列表", body = Vec<ApiKey>),
+        (status = 401, description = "未認證"),
+        (status = 403, description = "未授權")
+    ),
+    security(
+        ("jwt_auth" = [])
+    ),
+    tag = "api_keys"
+)]
+pub async fn list_api_keys(
+    State(state): State<AppState>,
+    Authorized(user, _): Authorized<perm::ManageUsers>,
+) -> Result<Json<Vec<ApiKey>>, AppError> {
+    let keys = state.api_key_service.list(user.tenant()).await?;
+    Ok(Json(keys))
+}
+
+/// 撤銷 API 金鑰處理程序
+#[axum::debug_handler]
+#[utoipa::path(
+    delete,
+    path = "/admin/api-keys/{api_key_id}",
+    params(
+        ("api_key_id" = Uuid, Path, description = "API 金鑰 ID")
+    ),
+    responses(
+        (status = 204, description = "已撤銷 API 金鑰"),
+        (status = 401, description = "未認證"),
+        (status = 403, description = "未授權"),
+        (status = 404, description = "找不到有效的 API 金鑰")
+    ),
+    security(
+        ("jwt_auth" = [])
+    ),
+    tag = "api_keys"
+)]
+pub async fn revoke_api_key(
+    State(state): State<AppState>,
+    Authorized(user, _): Authorized<perm::ManageUsers>,
+    Path(api_key_id): Path<Uuid>,
+) -> Result<StatusCode, AppError> {
+    state.api_key_service.revoke(api_key_id, user.tenant()).await?;
+    Ok(StatusCode::NO_CONTENT)
+}
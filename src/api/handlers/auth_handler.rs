@@ -1,11 +1,18 @@
 use axum::{
-    extract::{Json, State},
+    extract::{Json, Path, Query, State},
     http::StatusCode,
+    response::Redirect,
 };
 
 use crate::api::middleware::auth::AuthUser;
+use crate::api::middleware::client_ip::ClientIp;
 use crate::api::routes::AppState;
-use crate::domain::auth::model::{LoginInput, LoginResponse, RegisterInput};
+use crate::domain::auth::model::{
+    ChangeEmailInput, ChangePasswordInput, ConfirmEmailChangeInput, ForgotPasswordInput, JwkSet, LoginInput,
+    LoginOutcome, LoginResponse, OidcAuthorizeQuery, OidcCallbackQuery, RecoveryCodes, RefreshTokenInput, RegisterInput,
+    ResetPasswordInput, TwoFactorCodeInput, TwoFactorEnrollment, TwoFactorLoginInput, UpdateProfile, UserProfile,
+    VerifyEmailInput,
+};
 use crate::utils::error::AppError;
 
 /// 用戶註冊處理程序
@@ -36,26 +43,361 @@ pub async fn register(
     path = "/api/users/login",
     request_body = LoginInput,
     responses(
-        (status = 200, description = "登入成功", body = LoginResponse),
-        (status = 400, description = "無效的登入憑證")
+        (status = 200, description = "登入成功；已啟用兩步驟驗證時返回挑戰令牌", body = LoginOutcome),
+        (status = 401, description = "無效的登入憑證，帳號或來源 IP 鎖定中時回應相同")
     ),
     tag = "auth"
 )]
 pub async fn login(
     State(state): State<AppState>,
+    ClientIp(client_ip): ClientIp,
     Json(input): Json<LoginInput>,
+) -> Result<Json<LoginOutcome>, AppError> {
+    let response = state.auth_service.login(input, client_ip).await?;
+    Ok(Json(response))
+}
+
+/// 兩步驟驗證登入處理程序
+#[axum::debug_handler]
+#[utoipa::path(
+    post,
+    path = "/auth/2fa/verify",
+    request_body = TwoFactorLoginInput,
+    responses(
+        (status = 200, description = "登入成功", body = LoginResponse),
+        (status = 401, description = "驗證碼錯誤或挑戰令牌已過期")
+    ),
+    tag = "auth"
+)]
+pub async fn verify_two_factor(
+    State(state): State<AppState>,
+    ClientIp(client_ip): ClientIp,
+    Json(input): Json<TwoFactorLoginInput>,
+) -> Result<Json<LoginResponse>, AppError> {
+    let response = state.auth_service.verify_two_factor_login(input, client_ip).await?;
+    Ok(Json(response))
+}
+
+/// 開始設定兩步驟驗證處理程序
+#[axum::debug_handler]
+#[utoipa::path(
+    post,
+    path = "/auth/2fa/enroll",
+    responses(
+        (status = 200, description = "成功產生密鑰", body = TwoFactorEnrollment),
+        (status = 401, description = "未授權訪問"),
+        (status = 409, description = "已啟用兩步驟驗證")
+    ),
+    security(
+        ("bearerAuth" = [])
+    ),
+    tag = "auth"
+)]
+pub async fn enroll_two_factor(
+    State(state): State<AppState>,
+    auth_user: AuthUser,
+) -> Result<Json<TwoFactorEnrollment>, AppError> {
+    let enrollment = state.auth_service.enroll_two_factor(&auth_user.0).await?;
+    Ok(Json(enrollment))
+}
+
+/// 啟用兩步驟驗證處理程序
+#[axum::debug_handler]
+#[utoipa::path(
+    post,
+    path = "/auth/2fa/activate",
+    request_body = TwoFactorCodeInput,
+    responses(
+        (status = 200, description = "已啟用，返回只顯示一次的救援碼", body = RecoveryCodes),
+        (status = 400, description = "驗證碼錯誤或尚未開始設定"),
+        (status = 401, description = "未授權訪問"),
+        (status = 409, description = "已啟用兩步驟驗證")
+    ),
+    security(
+        ("bearerAuth" = [])
+    ),
+    tag = "auth"
+)]
+pub async fn activate_two_factor(
+    State(state): State<AppState>,
+    auth_user: AuthUser,
+    Json(input): Json<TwoFactorCodeInput>,
+) -> Result<Json<RecoveryCodes>, AppError> {
+    let codes = state.auth_service.activate_two_factor(&auth_user.0, input).await?;
+    Ok(Json(codes))
+}
+
+/// 重新產生救援碼處理程序
+#[axum::debug_handler]
+#[utoipa::path(
+    post,
+    path = "/auth/2fa/recovery-codes",
+    request_body = TwoFactorCodeInput,
+    responses(
+        (status = 200, description = "成功產生新的救援碼，先前的救援碼全部失效", body = RecoveryCodes),
+        (status = 401, description = "未授權訪問或驗證碼錯誤")
+    ),
+    security(
+        ("bearerAuth" = [])
+    ),
+    tag = "auth"
+)]
+pub async fn regenerate_recovery_codes(
+    State(state): State<AppState>,
+    auth_user: AuthUser,
+    ClientIp(client_ip): ClientIp,
+    Json(input): Json<TwoFactorCodeInput>,
+) -> Result<Json<RecoveryCodes>, AppError> {
+    let codes = state.auth_service.regenerate_recovery_codes(&auth_user.0, input, client_ip).await?;
+    Ok(Json(codes))
+}
+
+/// 停用兩步驟驗證處理程序
+#[axum::debug_handler]
+#[utoipa::path(
+    post,
+    path = "/auth/2fa/disable",
+    request_body = TwoFactorCodeInput,
+    responses(
+        (status = 204, description = "已停用兩步驟驗證"),
+        (status = 400, description = "角色必須啟用兩步驟驗證"),
+        (status = 401, description = "未授權訪問或驗證碼錯誤")
+    ),
+    security(
+        ("bearerAuth" = [])
+    ),
+    tag = "auth"
+)]
+pub async fn disable_two_factor(
+    State(state): State<AppState>,
+    auth_user: AuthUser,
+    ClientIp(client_ip): ClientIp,
+    Json(input): Json<TwoFactorCodeInput>,
+) -> Result<StatusCode, AppError> {
+    state.auth_service.disable_two_factor(&auth_user.0, input, client_ip).await?;
+    Ok(StatusCode::NO_CONTENT)
+}
+
+/// 換發令牌處理程序
+#[axum::debug_handler]
+#[utoipa::path(
+    post,
+    path = "/auth/refresh",
+    request_body = RefreshTokenInput,
+    responses(
+        (status = 200, description = "換發成功", body = LoginResponse),
+        (status = 401, description = "刷新令牌無效、過期或已被使用")
+    ),
+    tag = "auth"
+)]
+pub async fn refresh(
+    State(state): State<AppState>,
+    Json(input): Json<RefreshTokenInput>,
 ) -> Result<Json<LoginResponse>, AppError> {
-    let response = state.auth_service.login(input).await?;
+    let response = state.auth_service.refresh(input).await?;
+    Ok(Json(response))
+}
+
+/// 登出處理程序
+#[axum::debug_handler]
+#[utoipa::path(
+    post,
+    path = "/auth/logout",
+    request_body = RefreshTokenInput,
+    responses(
+        (status = 204, description = "登出成功")
+    ),
+    tag = "auth"
+)]
+pub async fn logout(
+    State(state): State<AppState>,
+    Json(input): Json<RefreshTokenInput>,
+) -> Result<StatusCode, AppError> {
+    state.auth_service.logout(input).await?;
+    Ok(StatusCode::NO_CONTENT)
+}
+
+/// 登出所有裝置處理程序
+#[axum::debug_handler]
+#[utoipa::path(
+    post,
+    path = "/auth/logout-all",
+    responses(
+        (status = 204, description = "已登出所有裝置"),
+        (status = 401, description = "未授權訪問")
+    ),
+    security(
+        ("bearerAuth" = [])
+    ),
+    tag = "auth"
+)]
+pub async fn logout_all(
+    State(state): State<AppState>,
+    auth_user: AuthUser,
+) -> Result<StatusCode, AppError> {
+    state.auth_service.logout_all(&auth_user.0).await?;
+    Ok(StatusCode::NO_CONTENT)
+}
+
+/// 驗證電子郵件處理程序
+#[axum::debug_handler]
+#[utoipa::path(
+    post,
+    path = "/auth/verify-email",
+    request_body = VerifyEmailInput,
+    responses(
+        (status = 204, description = "電子郵件驗證成功"),
+        (status = 400, description = "驗證連結無效或已過期")
+    ),
+    tag = "auth"
+)]
+pub async fn verify_email(
+    State(state): State<AppState>,
+    Json(input): Json<VerifyEmailInput>,
+) -> Result<StatusCode, AppError> {
+    state.auth_service.verify_email(input).await?;
+    Ok(StatusCode::NO_CONTENT)
+}
+
+/// 重新寄送驗證信處理程序
+#[axum::debug_handler]
+#[utoipa::path(
+    post,
+    path = "/auth/verify-email/resend",
+    responses(
+        (status = 204, description = "驗證信已寄出"),
+        (status = 400, description = "電子郵件已驗證"),
+        (status = 401, description = "未授權訪問")
+    ),
+    security(
+        ("bearerAuth" = [])
+    ),
+    tag = "auth"
+)]
+pub async fn resend_verification(
+    State(state): State<AppState>,
+    auth_user: AuthUser,
+) -> Result<StatusCode, AppError> {
+    state.auth_service.resend_verification(&auth_user.0).await?;
+    Ok(StatusCode::NO_CONTENT)
+}
+
+/// 忘記密碼處理程序
+#[axum::debug_handler]
+#[utoipa::path(
+    post,
+    path = "/auth/forgot-password",
+    request_body = ForgotPasswordInput,
+    responses(
+        (status = 204, description = "若電子郵件已註冊，重設密碼信已寄出")
+    ),
+    tag = "auth"
+)]
+pub async fn forgot_password(
+    State(state): State<AppState>,
+    Json(input): Json<ForgotPasswordInput>,
+) -> Result<StatusCode, AppError> {
+    state.auth_service.forgot_password(input).await?;
+    Ok(StatusCode::NO_CONTENT)
+}
+
+/// 重設密碼處理程序
+#[axum::debug_handler]
+#[utoipa::path(
+    post,
+    path = "/auth/reset-password",
+    request_body = ResetPasswordInput,
+    responses(
+        (status = 204, description = "密碼已重設，所有裝置需重新登入"),
+        (status = 400, description = "重設連結無效或已過期")
+    ),
+    tag = "auth"
+)]
+pub async fn reset_password(
+    State(state): State<AppState>,
+    Json(input): Json<ResetPasswordInput>,
+) -> Result<StatusCode, AppError> {
+    state.auth_service.reset_password(input).await?;
+    Ok(StatusCode::NO_CONTENT)
+}
+
+/// 社群登入處理程序
+/// 重新導向到身分提供者的授權頁面
+#[axum::debug_handler]
+#[utoipa::path(
+    get,
+    path = "/auth/oidc/{provider}/authorize",
+    params(
+        ("provider" = String, Path, description = "身分提供者名稱"),
+        OidcAuthorizeQuery
+    ),
+    responses(
+        (status = 303, description = "重新導向到身分提供者"),
+        (status = 404, description = "未設定的身分提供者")
+    ),
+    tag = "auth"
+)]
+pub async fn oidc_authorize(
+    State(state): State<AppState>,
+    Path(provider): Path<String>,
+    Query(query): Query<OidcAuthorizeQuery>,
+) -> Result<Redirect, AppError> {
+    let url = state.oidc_service.authorize(&provider, query.login_hint.as_deref()).await?;
+    Ok(Redirect::to(&url))
+}
+
+/// 社群登入回呼處理程序
+/// 以授權碼換取並驗證 ID Token，連結或創建用戶後登入
+#[axum::debug_handler]
+#[utoipa::path(
+    get,
+    path = "/auth/oidc/{provider}/callback",
+    params(
+        ("provider" = String, Path, description = "身分提供者名稱"),
+        OidcCallbackQuery
+    ),
+    responses(
+        (status = 200, description = "登入成功；已啟用兩步驟驗證時返回挑戰令牌", body = LoginOutcome),
+        (status = 400, description = "缺少授權碼，或身分提供者未提供已驗證的電子郵件"),
+        (status = 401, description = "登入請求已過期、授權碼無效或 ID Token 驗證失敗"),
+        (status = 404, description = "未設定的身分提供者"),
+        (status = 409, description = "電子郵件已註冊但尚未驗證")
+    ),
+    tag = "auth"
+)]
+pub async fn oidc_callback(
+    State(state): State<AppState>,
+    Path(provider): Path<String>,
+    ClientIp(client_ip): ClientIp,
+    Query(query): Query<OidcCallbackQuery>,
+) -> Result<Json<LoginOutcome>, AppError> {
+    let response = state.oidc_service.callback(&provider, query, client_ip).await?;
     Ok(Json(response))
 }
 
+/// JWT 驗證金鑰處理程序
+#[axum::debug_handler]
+#[utoipa::path(
+    get,
+    path = "/.well-known/jwks.json",
+    responses(
+        (status = 200, description = "成功獲取驗證金鑰", body = JwkSet)
+    ),
+    tag = "auth"
+)]
+pub async fn jwks(
+    State(state): State<AppState>,
+) -> Json<JwkSet> {
+    Json(state.auth_service.jwks().clone())
+}
+
 /// 獲取當前用戶信息處理程序
 #[axum::debug_handler]
 #[utoipa::path(
     get,
-    path = "/api/users/me",
+    path = "/auth/me",
     responses(
-        (status = 200, description = "成功獲取用戶信息"),
+        (status = 200, description = "成功獲取用戶信息", body = UserProfile),
         (status = 401, description = "未授權訪問")
     ),
     security(
@@ -64,13 +406,108 @@ pub async fn login(
     tag = "auth"
 )]
 pub async fn get_me(
+    State(state): State<AppState>,
+    auth_user: AuthUser,
+) -> Result<Json<UserProfile>, AppError> {
+    let profile = state.auth_service.profile(&auth_user.0).await?;
+    Ok(Json(profile))
+}
+
+/// 修改個人資料處理程序
+#[axum::debug_handler]
+#[utoipa::path(
+    patch,
+    path = "/auth/me",
+    request_body = UpdateProfile,
+    responses(
+        (status = 200, description = "個人資料已更新", body = UserProfile),
+        (status = 400, description = "無效的輸入數據"),
+        (status = 401, description = "未授權訪問")
+    ),
+    security(
+        ("bearerAuth" = [])
+    ),
+    tag = "auth"
+)]
+pub async fn update_me(
+    State(state): State<AppState>,
     auth_user: AuthUser,
-) -> Result<Json<serde_json::Value>, AppError> {
-    let user = auth_user.0;
-    
-    Ok(Json(serde_json::json!({
-        "id": user.id,
-        "email": user.email,
-        "is_admin": user.is_admin
-    })))
+    Json(input): Json<UpdateProfile>,
+) -> Result<Json<UserProfile>, AppError> {
+    let profile = state.auth_service.update_profile(&auth_user.0, input).await?;
+    Ok(Json(profile))
+}
+
+/// 申請變更電子郵件處理程序
+#[axum::debug_handler]
+#[utoipa::path(
+    post,
+    path = "/auth/me/email",
+    request_body = ChangeEmailInput,
+    responses(
+        (status = 202, description = "確認信已寄到新的電子郵件"),
+        (status = 400, description = "無效的輸入數據"),
+        (status = 401, description = "未授權訪問或目前的密碼錯誤"),
+        (status = 409, description = "電子郵件已被使用")
+    ),
+    security(
+        ("bearerAuth" = [])
+    ),
+    tag = "auth"
+)]
+pub async fn change_email(
+    State(state): State<AppState>,
+    auth_user: AuthUser,
+    ClientIp(client_ip): ClientIp,
+    Json(input): Json<ChangeEmailInput>,
+) -> Result<StatusCode, AppError> {
+    state.auth_service.request_email_change(&auth_user.0, input, client_ip).await?;
+    Ok(StatusCode::ACCEPTED)
+}
+
+/// 確認變更電子郵件處理程序
+#[axum::debug_handler]
+#[utoipa::path(
+    post,
+    path = "/auth/me/email/confirm",
+    request_body = ConfirmEmailChangeInput,
+    responses(
+        (status = 204, description = "電子郵件已變更"),
+        (status = 400, description = "確認連結無效或已過期"),
+        (status = 409, description = "電子郵件已被使用")
+    ),
+    tag = "auth"
+)]
+pub async fn confirm_email_change(
+    State(state): State<AppState>,
+    Json(input): Json<ConfirmEmailChangeInput>,
+) -> Result<StatusCode, AppError> {
+    state.auth_service.confirm_email_change(input).await?;
+    Ok(StatusCode::NO_CONTENT)
+}
+
+/// 變更密碼處理程序
+#[axum::debug_handler]
+#[utoipa::path(
+    post,
+    path = "/auth/me/password",
+    request_body = ChangePasswordInput,
+    responses(
+        (status = 204, description = "密碼已變更，所有令牌已撤銷"),
+        (status = 400, description = "新密碼不符合密碼政策"),
+        (status = 401, description = "未授權訪問或目前的密碼錯誤")
+    ),
+    security(
+        ("bearerAuth" = [])
+    ),
+    tag = "auth"
+)]
+pub async fn change_password(
+    State(state): State<AppState>,
+    auth_user: AuthUser,
+    ClientIp(client_ip): ClientIp,
+    Json(input): Json<ChangePasswordInput>,
+) -> Result<StatusCode, AppError> {
+    state.auth_service.change_password(&auth_user.0, input, client_ip).await?;
+    Ok(StatusCode::NO_CONTENT)
 }
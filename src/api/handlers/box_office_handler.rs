@@ -0,0 +1,151 @@
+use axum::{
+    extract::{Json, Path, State},
+    http::StatusCode,
+};
+use uuid::Uuid;
+
+use crate::api::middleware::permission::{perm, Authorized};
+use crate::api::routes::AppState;
+use crate::domain::box_office::model::{
+    BoxOfficeSaleResponse, CloseDrawer, CreateBoxOfficeOrder, DrawerReport, DrawerSession, OpenDrawer,
+};
+use crate::utils::error::AppError;
+
+/// 開啟錢櫃班次處理程序
+#[axum::debug_handler]
+#[utoipa::path(
+    post,
+    path = "/box-office/drawers",
+    request_body = OpenDrawer,
+    responses(
+        (status = 201, description = "成功開啟錢櫃班次", body = DrawerSession),
+        (status = 400, description = "無效的輸入"),
+        (status = 401, description = "未認證"),
+        (status = 403, description = "需要售票處權限"),
+        (status = 409, description = "已有尚未結束的班次")
+    ),
+    security(
+        ("jwt_auth" = [])
+    ),
+    tag = "box_office"
+)]
+pub async fn open_drawer(
+    State(state): State<AppState>,
+    Authorized(staff, _): Authorized<perm::SellTickets>,
+    Json(input): Json<OpenDrawer>,
+) -> Result<(StatusCode, Json<DrawerSession>), AppError> {
+    let session = state.box_office_service.open_drawer(&staff, input).await?;
+    Ok((StatusCode::CREATED, Json(session)))
+}
+
+/// 獲取目前錢櫃班次處理程序
+#[axum::debug_handler]
+#[utoipa::path(
+    get,
+    path = "/box-office/drawers/current",
+    responses(
+        (status = 200, description = "目前班次的對帳報告", body = DrawerReport),
+        (status = 401, description = "未認證"),
+        (status = 403, description = "需要售票處權限"),
+        (status = 404, description = "目前沒有開啟的班次")
+    ),
+    security(
+        ("jwt_auth" = [])
+    ),
+    tag = "box_office"
+)]
+pub async fn get_current_drawer(
+    State(state): State<AppState>,
+    Authorized(staff, _): Authorized<perm::SellTickets>,
+) -> Result<Json<DrawerReport>, AppError> {
+    let report = state.box_office_service.current_drawer(&staff).await?;
+    Ok(Json(report))
+}
+
+/// 獲取錢櫃對帳報告處理程序
+#[axum::debug_handler]
+#[utoipa::path(
+    get,
+    path = "/box-office/drawers/{session_id}",
+    params(
+        ("session_id" = Uuid, Path, description = "錢櫃班次 ID")
+    ),
+    responses(
+        (status = 200, description = "班次的對帳報告", body = DrawerReport),
+        (status = 401, description = "未認證"),
+        (status = 403, description = "不是自己的班次"),
+        (status = 404, description = "找不到班次")
+    ),
+    security(
+        ("jwt_auth" = [])
+    ),
+    tag = "box_office"
+)]
+pub async fn get_drawer_report(
+    State(state): State<AppState>,
+    Authorized(staff, _): Authorized<perm::SellTickets>,
+    Path(session_id): Path<Uuid>,
+) -> Result<Json<DrawerReport>, AppError> {
+    let report = state.box_office_service.get_drawer_report(&staff, session_id).await?;
+    Ok(Json(report))
+}
+
+/// 結束錢櫃班次處理程序
+#[axum::debug_handler]
+#[utoipa::path(
+    post,
+    path = "/box-office/drawers/{session_id}/close",
+    params(
+        ("session_id" = Uuid, Path, description = "錢櫃班次 ID")
+    ),
+    request_body = CloseDrawer,
+    responses(
+        (status = 200, description = "交班對帳報告", body = DrawerReport),
+        (status = 400, description = "無效的輸入"),
+        (status = 401, description = "未認證"),
+        (status = 403, description = "不是自己的班次"),
+        (status = 404, description = "找不到班次"),
+        (status = 409, description = "班次已結束")
+    ),
+    security(
+        ("jwt_auth" = [])
+    ),
+    tag = "box_office"
+)]
+pub async fn close_drawer(
+    State(state): State<AppState>,
+    Authorized(staff, _): Authorized<perm::SellTickets>,
+    Path(session_id): Path<Uuid>,
+    Json(input): Json<CloseDrawer>,
+) -> Result<Json<DrawerReport>, AppError> {
+    let report = state.box_office_service.close_drawer(&staff, session_id, input).await?;
+    Ok(Json(report))
+}
+
+/// 現場售票處理程序
+#[axum::debug_handler]
+#[utoipa::path(
+    post,
+    path = "/box-office/orders",
+    request_body = CreateBoxOfficeOrder,
+    responses(
+        (status = 201, description = "售票成功，訂單已付款", body = BoxOfficeSaleResponse),
+        (status = 400, description = "無效的輸入、庫存不足、收取現金不足或尚未開班"),
+        (status = 401, description = "未認證"),
+        (status = 403, description = "需要售票處權限"),
+        (status = 404, description = "找不到票券"),
+        (status = 409, description = "座位已被選走")
+    ),
+    security(
+        ("jwt_auth" = [])
+    ),
+    tag = "box_office"
+)]
+pub async fn create_box_office_order(
+    State(state): State<AppState>,
+    Authorized(staff, _): Authorized<perm::SellTickets>,
+    Json(input): Json<CreateBoxOfficeOrder>,
+) -> Result<(StatusCode, Json<BoxOfficeSaleResponse>), AppError> {
+    let response = state.box_office_service.sell(&staff, input).await?;
+    Ok((StatusCode::CREATED, Json(response)))
+}
@@ -0,0 +1,105 @@
+use axum::{
+    extract::{Json, Path, State},
+    http::StatusCode,
+};
+use uuid::Uuid;
+
+use crate::api::middleware::auth::AuthUser;
+use crate::api::middleware::permission::{perm, Authorized};
+use crate::api::routes::AppState;
+use crate::domain::bundle::model::{Bundle, CreateBundle};
+use crate::domain::order::model::{CheckoutResponse, CreateBundleOrder};
+use crate::utils::error::AppError;
+
+/// 獲取組合商品列表處理程序
+#[axum::debug_handler]
+#[utoipa::path(
+    get,
+    path = "/bundles",
+    responses(
+        (status = 200, description = "成功獲取組合商品列表", body = Vec<Bundle>)
+    ),
+    tag = "bundles"
+)]
+pub async fn list_bundles(
+    State(state): State<AppState>,
+) -> Result<Json<Vec<Bundle>>, AppError> {
+    let bundles = state.bundle_service.get_all_bundles().await?;
+    Ok(Json(bundles))
+}
+
+/// 獲取組合商品詳情處理程序
+#[axum::debug_handler]
+#[utoipa::path(
+    get,
+    path = "/bundles/{bundle_id}",
+    params(
+        ("bundle_id" = Uuid, Path, description = "組合商品 ID")
+    ),
+    responses(
+        (status = 200, description = "成功獲取組合商品", body = Bundle),
+        (status = 404, description = "找不到組合商品")
+    ),
+    tag = "bundles"
+)]
+pub async fn get_bundle(
+    State(state): State<AppState>,
+    Path(bundle_id): Path<Uuid>,
+) -> Result<Json<Bundle>, AppError> {
+    let bundle = state.bundle_service.get_bundle_by_id(bundle_id).await?;
+    Ok(Json(bundle))
+}
+
+/// 創建組合商品處理程序
+#[axum::debug_handler]
+#[utoipa::path(
+    post,
+    path = "/admin/bundles",
+    request_body = CreateBundle,
+    responses(
+        (status = 201, description = "成功創建組合商品", body = Bundle),
+        (status = 400, description = "無效的輸入"),
+        (status = 401, description = "未認證"),
+        (status = 403, description = "需先驗證電子郵件"),
+        (status = 403, description = "未授權"),
+        (status = 404, description = "找不到票券")
+    ),
+    security(
+        ("jwt_auth" = [])
+    ),
+    tag = "bundles"
+)]
+pub async fn create_bundle(
+    State(state): State<AppState>,
+    Authorized(user, _): Authorized<perm::ManageEvents>,
+    Json(input): Json<CreateBundle>,
+) -> Result<(StatusCode, Json<Bundle>), AppError> {
+    let bundle = state.bundle_service.create_bundle(input, user.tenant()).await?;
+    Ok((StatusCode::CREATED, Json(bundle)))
+}
+
+/// 購買組合商品處理程序
+#[axum::debug_handler]
+#[utoipa::path(
+    post,
+    path = "/orders/bundle",
+    request_body = CreateBundleOrder,
+    responses(
+        (status = 201, description = "訂單創建成功，訂單狀態為 pending 時需等待金流確認", body = CheckoutResponse),
+        (status = 400, description = "無效的輸入或庫存不足"),
+        (status = 401, description = "未認證"),
+        (status = 404, description = "找不到組合商品")
+    ),
+    security(
+        ("jwt_auth" = [])
+    ),
+    tag = "bundles"
+)]
+pub async fn create_bundle_order(
+    State(state): State<AppState>,
+    auth_user: AuthUser,
+    Json(input): Json<CreateBundleOrder>,
+) -> Result<(StatusCode, Json<CheckoutResponse>), AppError> {
+    let checkout = state.order_service.create_bundle_order(&auth_user.0, input).await?;
+    Ok((StatusCode::CREATED, Json(checkout)))
+}
@@ -0,0 +1,79 @@
+use axum::{
+    extract::{Json, Path, Query, State},
+    http::StatusCode,
+    response::{IntoResponse, Response},
+};
+use uuid::Uuid;
+
+use crate::api::middleware::permission::{perm, Authorized};
+use crate::api::routes::AppState;
+use crate::domain::comp::model::{Comp, GuestListQuery, IssueComps};
+use crate::utils::error::AppError;
+use crate::utils::export::{csv_response, to_csv, ExportFormat};
+
+/// 批次發出招待票處理程序
+#[axum::debug_handler]
+#[utoipa::path(
+    post,
+    path = "/admin/concerts/{concert_id}/comps",
+    params(
+        ("concert_id" = Uuid, Path, description = "演唱會 ID")
+    ),
+    request_body = IssueComps,
+    responses(
+        (status = 201, description = "成功發出招待票與入場憑證", body = Vec<Comp>),
+        (status = 400, description = "無效的輸入、票種不屬於此演唱會或庫存不足"),
+        (status = 401, description = "未認證"),
+        (status = 403, description = "未授權"),
+        (status = 404, description = "找不到演唱會或票券")
+    ),
+    security(
+        ("jwt_auth" = [])
+    ),
+    tag = "comps"
+)]
+pub async fn issue_comps(
+    State(state): State<AppState>,
+    Authorized(issuer, _): Authorized<perm::ManageComps>,
+    Path(concert_id): Path<Uuid>,
+    Json(input): Json<IssueComps>,
+) -> Result<(StatusCode, Json<Vec<Comp>>), AppError> {
+    let comps = state.comp_service.issue_comps(concert_id, input, issuer.id, issuer.tenant()).await?;
+    Ok((StatusCode::CREATED, Json(comps)))
+}
+
+/// 獲取公關名單處理程序
+/// format=csv 時以 CSV 檔案下載
+#[axum::debug_handler]
+#[utoipa::path(
+    get,
+    path = "/admin/concerts/{concert_id}/guest-list",
+    params(
+        ("concert_id" = Uuid, Path, description = "演唱會 ID"),
+        GuestListQuery
+    ),
+    responses(
+        (status = 200, description = "成功獲取公關名單", body = Vec<GuestListEntry>, content_type = "application/json"),
+        (status = 200, description = "公關名單 CSV", body = String, content_type = "text/csv"),
+        (status = 401, description = "未認證"),
+        (status = 403, description = "未授權"),
+        (status = 404, description = "找不到演唱會")
+    ),
+    security(
+        ("jwt_auth" = [])
+    ),
+    tag = "comps"
+)]
+pub async fn get_guest_list(
+    State(state): State<AppState>,
+    Authorized(user, _): Authorized<perm::ManageComps>,
+    Path(concert_id): Path<Uuid>,
+    Query(query): Query<GuestListQuery>,
+) -> Result<Response, AppError> {
+    let guests = state.comp_service.get_guest_list(concert_id, user.tenant()).await?;
+
+    match query.format.unwrap_or_default() {
+        ExportFormat::Json => Ok(Json(guests).into_response()),
+        ExportFormat::Csv => Ok(csv_response(&format!("guest-list-{}.csv", concert_id), to_csv(&guests)?)),
+    }
+}
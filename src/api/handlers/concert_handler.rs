@@ -1,10 +1,11 @@
 use axum::{
-    extract::{Json, State},
+    extract::{Json, Path, State},
 };
+use uuid::Uuid;
 
-use crate::api::middleware::auth::AdminUser;
+use crate::api::middleware::permission::{perm, Authorized};
 use crate::api::routes::AppState;
-use crate::domain::concert::model::{Concert, CreateConcert};
+use crate::domain::concert::model::{Concert, ConcertFee, CreateConcert, CreateConcertFee, TaxSetting};
 use crate::utils::error::AppError;
 
 /// 創建音樂會處理程序
@@ -17,6 +18,7 @@ use crate::utils::error::AppError;
         (status = 200, description = "成功創建音樂會", body = Concert),
         (status = 401, description = "未認證"),
         (status = 403, description = "未授權"),
+        (status = 404, description = "找不到主辦單位"),
         (status = 500, description = "內部伺服器錯誤")
     ),
     security(
@@ -25,10 +27,10 @@ use crate::utils::error::AppError;
 )]
 pub async fn create_concert(
     State(state): State<AppState>,
-    _admin_user: AdminUser,
+    Authorized(user, _): Authorized<perm::ManageEvents>,
     Json(input): Json<CreateConcert>,
 ) -> Result<Json<Concert>, AppError> {
-    let concert = state.concert_service.create_concert(input, true).await?;
+    let concert = state.concert_service.create_concert(input, user.tenant()).await?;
     Ok(Json(concert))
 }
 
@@ -47,3 +49,84 @@ pub async fn list_concerts(
     let concerts = state.concert_service.get_all_concerts().await?;
     Ok(Json(concerts))
 }
+
+/// 更新演唱會稅率設定處理程序
+#[axum::debug_handler]
+#[utoipa::path(
+    put,
+    path = "/concerts/{concert_id}/tax",
+    params(
+        ("concert_id" = Uuid, Path, description = "演唱會 ID")
+    ),
+    request_body = TaxSetting,
+    responses(
+        (status = 200, description = "成功更新稅率設定", body = Concert),
+        (status = 400, description = "無效的輸入數據"),
+        (status = 401, description = "未認證"),
+        (status = 403, description = "未授權"),
+        (status = 404, description = "演唱會不存在")
+    ),
+    security(
+        ("jwt_auth" = [])
+    )
+)]
+pub async fn update_concert_tax(
+    State(state): State<AppState>,
+    Authorized(user, _): Authorized<perm::ManageEvents>,
+    Path(concert_id): Path<Uuid>,
+    Json(input): Json<TaxSetting>,
+) -> Result<Json<Concert>, AppError> {
+    let concert = state.concert_service.update_tax(concert_id, input, user.tenant()).await?;
+    Ok(Json(concert))
+}
+
+/// 獲取演唱會服務費處理程序
+#[axum::debug_handler]
+#[utoipa::path(
+    get,
+    path = "/concerts/{concert_id}/fees",
+    params(
+        ("concert_id" = Uuid, Path, description = "演唱會 ID")
+    ),
+    responses(
+        (status = 200, description = "成功獲取服務費列表", body = Vec<ConcertFee>),
+        (status = 404, description = "演唱會不存在")
+    )
+)]
+pub async fn list_concert_fees(
+    State(state): State<AppState>,
+    Path(concert_id): Path<Uuid>,
+) -> Result<Json<Vec<ConcertFee>>, AppError> {
+    let fees = state.concert_service.get_concert_fees(concert_id).await?;
+    Ok(Json(fees))
+}
+
+/// 新增演唱會服務費處理程序
+#[axum::debug_handler]
+#[utoipa::path(
+    post,
+    path = "/concerts/{concert_id}/fees",
+    params(
+        ("concert_id" = Uuid, Path, description = "演唱會 ID")
+    ),
+    request_body = CreateConcertFee,
+    responses(
+        (status = 200, description = "成功新增服務費", body = ConcertFee),
+        (status = 400, description = "無效的輸入數據"),
+        (status = 401, description = "未認證"),
+        (status = 403, description = "未授權"),
+        (status = 404, description = "演唱會不存在")
+    ),
+    security(
+        ("jwt_auth" = [])
+    )
+)]
+pub async fn create_concert_fee(
+    State(state): State<AppState>,
+    Authorized(user, _): Authorized<perm::ManageEvents>,
+    Path(concert_id): Path<Uuid>,
+    Json(input): Json<CreateConcertFee>,
+) -> Result<Json<ConcertFee>, AppError> {
+    let fee = state.concert_service.create_concert_fee(concert_id, input, user.tenant()).await?;
+    Ok(Json(fee))
+}
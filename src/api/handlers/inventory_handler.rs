@@ -0,0 +1,167 @@
+use axum::{
+    extract::{Json, Path, State},
+    http::StatusCode,
+};
+use uuid::Uuid;
+
+use crate::api::middleware::api_key::{scope, Scoped};
+use crate::api::middleware::permission::{perm, Authorized};
+use crate::api::routes::AppState;
+use crate::domain::inventory::model::{
+    AssignPool, CreateInventoryPool, InventoryPool, InventoryPoolView, MoveAllocation, UpdatePoolCapacity,
+};
+use crate::domain::ticket::model::Ticket;
+use crate::utils::error::AppError;
+
+/// 獲取演唱會庫存池處理程序
+#[axum::debug_handler]
+#[utoipa::path(
+    get,
+    path = "/admin/concerts/{concert_id}/pools",
+    params(
+        ("concert_id" = Uuid, Path, description = "演唱會 ID")
+    ),
+    responses(
+        (status = 200, description = "成功獲取庫存池列表", body = Vec<InventoryPoolView>),
+        (status = 401, description = "未認證"),
+        (status = 403, description = "未授權"),
+        (status = 404, description = "找不到演唱會"),
+        (status = 429, description = "API 金鑰超過每分鐘呼叫上限")
+    ),
+    security(
+        ("jwt_auth" = []),
+        ("api_key" = [])
+    ),
+    tag = "inventory"
+)]
+pub async fn list_pools(
+    State(state): State<AppState>,
+    Scoped(caller, _): Scoped<scope::ConcertsRead>,
+    Path(concert_id): Path<Uuid>,
+) -> Result<Json<Vec<InventoryPoolView>>, AppError> {
+    let pools = state.inventory_service.get_pools(concert_id, caller.tenant()).await?;
+    Ok(Json(pools))
+}
+
+/// 創建庫存池處理程序
+#[axum::debug_handler]
+#[utoipa::path(
+    post,
+    path = "/admin/concerts/{concert_id}/pools",
+    params(
+        ("concert_id" = Uuid, Path, description = "演唱會 ID")
+    ),
+    request_body = CreateInventoryPool,
+    responses(
+        (status = 201, description = "成功創建庫存池", body = InventoryPool),
+        (status = 400, description = "無效的輸入"),
+        (status = 401, description = "未認證"),
+        (status = 403, description = "未授權"),
+        (status = 404, description = "找不到演唱會"),
+        (status = 409, description = "庫存池名稱重複")
+    ),
+    security(
+        ("jwt_auth" = [])
+    ),
+    tag = "inventory"
+)]
+pub async fn create_pool(
+    State(state): State<AppState>,
+    Authorized(user, _): Authorized<perm::ManageEvents>,
+    Path(concert_id): Path<Uuid>,
+    Json(input): Json<CreateInventoryPool>,
+) -> Result<(StatusCode, Json<InventoryPool>), AppError> {
+    let pool = state.inventory_service.create_pool(concert_id, input, user.tenant()).await?;
+    Ok((StatusCode::CREATED, Json(pool)))
+}
+
+/// 調整庫存池容量處理程序
+#[axum::debug_handler]
+#[utoipa::path(
+    put,
+    path = "/admin/pools/{pool_id}/capacity",
+    params(
+        ("pool_id" = Uuid, Path, description = "庫存池 ID")
+    ),
+    request_body = UpdatePoolCapacity,
+    responses(
+        (status = 200, description = "成功調整容量", body = InventoryPool),
+        (status = 400, description = "無效的輸入"),
+        (status = 401, description = "未認證"),
+        (status = 403, description = "未授權"),
+        (status = 404, description = "找不到庫存池"),
+        (status = 409, description = "容量低於已售出數量")
+    ),
+    security(
+        ("jwt_auth" = [])
+    ),
+    tag = "inventory"
+)]
+pub async fn update_pool_capacity(
+    State(state): State<AppState>,
+    Authorized(user, _): Authorized<perm::ManageEvents>,
+    Path(pool_id): Path<Uuid>,
+    Json(input): Json<UpdatePoolCapacity>,
+) -> Result<Json<InventoryPool>, AppError> {
+    let pool = state.inventory_service.update_capacity(pool_id, input, user.tenant()).await?;
+    Ok(Json(pool))
+}
+
+/// 指定票種庫存池處理程序
+#[axum::debug_handler]
+#[utoipa::path(
+    put,
+    path = "/admin/tickets/{ticket_id}/pool",
+    params(
+        ("ticket_id" = Uuid, Path, description = "票券 ID")
+    ),
+    request_body = AssignPool,
+    responses(
+        (status = 200, description = "成功指定庫存池", body = Ticket),
+        (status = 400, description = "票種與庫存池不屬於同一場演唱會"),
+        (status = 401, description = "未認證"),
+        (status = 403, description = "未授權"),
+        (status = 404, description = "找不到票券或庫存池"),
+        (status = 409, description = "庫存池剩餘數量不足")
+    ),
+    security(
+        ("jwt_auth" = [])
+    ),
+    tag = "inventory"
+)]
+pub async fn assign_ticket_pool(
+    State(state): State<AppState>,
+    Authorized(user, _): Authorized<perm::ManageEvents>,
+    Path(ticket_id): Path<Uuid>,
+    Json(input): Json<AssignPool>,
+) -> Result<Json<Ticket>, AppError> {
+    let ticket = state.inventory_service.assign_pool(ticket_id, input, user.tenant()).await?;
+    Ok(Json(ticket))
+}
+
+/// 票種間移轉配額處理程序
+#[axum::debug_handler]
+#[utoipa::path(
+    post,
+    path = "/admin/tickets/allocations",
+    request_body = MoveAllocation,
+    responses(
+        (status = 200, description = "成功移轉配額，回傳來源與目標票種", body = Vec<Ticket>),
+        (status = 400, description = "無效的輸入或未售出配額不足"),
+        (status = 401, description = "未認證"),
+        (status = 403, description = "未授權"),
+        (status = 404, description = "找不到票券")
+    ),
+    security(
+        ("jwt_auth" = [])
+    ),
+    tag = "inventory"
+)]
+pub async fn move_allocation(
+    State(state): State<AppState>,
+    Authorized(user, _): Authorized<perm::ManageEvents>,
+    Json(input): Json<MoveAllocation>,
+) -> Result<Json<Vec<Ticket>>, AppError> {
+    let tickets = state.inventory_service.move_allocation(input, user.tenant()).await?;
+    Ok(Json(tickets))
+}
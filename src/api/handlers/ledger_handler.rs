@@ -0,0 +1,59 @@
+use axum::{
+    extract::{Json, Path, State},
+};
+use uuid::Uuid;
+
+use crate::api::middleware::permission::{perm, Authorized};
+use crate::api::routes::AppState;
+use crate::domain::ledger::model::{LedgerEntry, ReconciliationReport};
+use crate::utils::error::AppError;
+
+/// 對帳報告處理程序
+#[axum::debug_handler]
+#[utoipa::path(
+    get,
+    path = "/admin/ledger/reconciliation",
+    responses(
+        (status = 200, description = "成功產生對帳報告", body = ReconciliationReport),
+        (status = 401, description = "未認證"),
+        (status = 403, description = "未授權")
+    ),
+    security(
+        ("jwt_auth" = [])
+    ),
+    tag = "ledger"
+)]
+pub async fn get_reconciliation(
+    State(state): State<AppState>,
+    _authorized: Authorized<perm::ManageFinance>,
+) -> Result<Json<ReconciliationReport>, AppError> {
+    let report = state.ledger_service.reconcile().await?;
+    Ok(Json(report))
+}
+
+/// 獲取訂單分錄處理程序
+#[axum::debug_handler]
+#[utoipa::path(
+    get,
+    path = "/admin/ledger/orders/{order_id}",
+    params(
+        ("order_id" = Uuid, Path, description = "訂單 ID")
+    ),
+    responses(
+        (status = 200, description = "成功獲取訂單分錄", body = Vec<LedgerEntry>),
+        (status = 401, description = "未認證"),
+        (status = 403, description = "未授權")
+    ),
+    security(
+        ("jwt_auth" = [])
+    ),
+    tag = "ledger"
+)]
+pub async fn list_order_entries(
+    State(state): State<AppState>,
+    _authorized: Authorized<perm::ManageFinance>,
+    Path(order_id): Path<Uuid>,
+) -> Result<Json<Vec<LedgerEntry>>, AppError> {
+    let entries = state.ledger_service.get_order_entries(order_id).await?;
+    Ok(Json(entries))
+}
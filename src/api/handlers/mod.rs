@@ -1,4 +1,20 @@
+pub mod addon_handler;
+pub mod admission_handler;
+pub mod api_key_handler;
 pub mod auth_handler;
+pub mod box_office_handler;
+pub mod bundle_handler;
+pub mod comp_handler;
 pub mod concert_handler;
+pub mod inventory_handler;
+pub mod ledger_handler;
 pub mod order_handler;
+pub mod organization_handler;
+pub mod payment_handler;
+pub mod privacy_handler;
+pub mod promo_code_handler;
+pub mod report_handler;
+pub mod seating_handler;
+pub mod settlement_handler;
 pub mod ticket_handler;
+pub mod user_handler;
@@ -4,9 +4,11 @@ use axum::{
 };
 use uuid::Uuid;
 
+use crate::api::middleware::api_key::{scope, Scoped};
 use crate::api::middleware::auth::AuthUser;
+use crate::api::middleware::permission::{perm, Authorized};
 use crate::api::routes::AppState;
-use crate::domain::order::model::{CreateOrder, OrderQuery, OrderView};
+use crate::domain::order::model::{CheckoutResponse, CreateOrder, Order, OrderQuery, OrderView};
 use crate::utils::error::AppError;
 
 /// 創建訂單處理程序
@@ -16,9 +18,10 @@ use crate::utils::error::AppError;
     path = "/orders",
     request_body = CreateOrder,
     responses(
-        (status = 201, description = "訂單創建成功"),
+        (status = 201, description = "訂單創建成功，訂單狀態為 pending 時需等待金流確認", body = CheckoutResponse),
         (status = 400, description = "無效的輸入數據"),
         (status = 401, description = "未認證"),
+        (status = 403, description = "需先驗證電子郵件"),
         (status = 404, description = "票券不存在"),
         (status = 500, description = "內部伺服器錯誤")
     ),
@@ -30,9 +33,9 @@ pub async fn create_order(
     State(state): State<AppState>,
     auth_user: AuthUser,
     Json(input): Json<CreateOrder>,
-) -> Result<StatusCode, AppError> {
-    state.order_service.create_order(auth_user.0.id, input).await?;
-    Ok(StatusCode::CREATED)
+) -> Result<(StatusCode, Json<CheckoutResponse>), AppError> {
+    let checkout = state.order_service.create_order(&auth_user.0, input).await?;
+    Ok((StatusCode::CREATED, Json(checkout)))
 }
 
 /// 獲取用戶訂單列表處理程序
@@ -61,6 +64,36 @@ pub async fn list_orders(
     Ok(Json(orders))
 }
 
+/// 獲取所有訂單處理程序
+/// 供客服與合作夥伴查詢租戶範圍內所有用戶的訂單
+#[axum::debug_handler]
+#[utoipa::path(
+    get,
+    path = "/admin/orders",
+    params(
+        OrderQuery
+    ),
+    responses(
+        (status = 200, description = "成功獲取訂單列表", body = Vec<OrderView>),
+        (status = 401, description = "未認證"),
+        (status = 403, description = "未授權"),
+        (status = 429, description = "API 金鑰超過每分鐘呼叫上限")
+    ),
+    security(
+        ("jwt_auth" = []),
+        ("api_key" = [])
+    ),
+    tag = "orders"
+)]
+pub async fn list_all_orders(
+    State(state): State<AppState>,
+    Scoped(caller, _): Scoped<scope::OrdersRead>,
+    Query(query): Query<OrderQuery>,
+) -> Result<Json<Vec<OrderView>>, AppError> {
+    let orders = state.order_service.list_orders(query, caller.tenant()).await?;
+    Ok(Json(orders))
+}
+
 /// 獲取訂單詳情處理程序
 #[axum::debug_handler]
 #[utoipa::path(
@@ -87,3 +120,32 @@ pub async fn get_order_by_id(
     let order = state.order_service.get_order_by_id(order_id, auth_user.0.id).await?;
     Ok(Json(order))
 }
+
+/// 訂單退款處理程序
+#[axum::debug_handler]
+#[utoipa::path(
+    post,
+    path = "/orders/{order_id}/refund",
+    params(
+        ("order_id" = Uuid, Path, description = "訂單 ID")
+    ),
+    responses(
+        (status = 200, description = "退款成功", body = Order),
+        (status = 401, description = "未認證"),
+        (status = 403, description = "未授權"),
+        (status = 404, description = "訂單不存在"),
+        (status = 409, description = "訂單狀態不允許退款")
+    ),
+    security(
+        ("jwt_auth" = [])
+    ),
+    tag = "orders"
+)]
+pub async fn refund_order(
+    State(state): State<AppState>,
+    Authorized(user, _): Authorized<perm::ManageOrders>,
+    Path(order_id): Path<Uuid>,
+) -> Result<Json<Order>, AppError> {
+    let order = state.order_service.refund_order(order_id, user.tenant()).await?;
+    Ok(Json(order))
+}
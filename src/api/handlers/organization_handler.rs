@@ -0,0 +1,120 @@
+use axum::{
+    extract::{Json, Path, State},
+    http::StatusCode,
+};
+use uuid::Uuid;
+
+use crate::api::middleware::permission::{perm, Authorized};
+use crate::api::routes::AppState;
+use crate::domain::organization::model::{AssignOrganization, CreateOrganization, Organization, UpdateCommission};
+use crate::utils::error::AppError;
+
+/// 獲取主辦單位列表處理程序
+#[axum::debug_handler]
+#[utoipa::path(
+    get,
+    path = "/admin/organizations",
+    responses(
+        (status = 200, description = "成功獲取主辦單位列表", body = Vec<Organization>),
+        (status = 401, description = "未認證"),
+        (status = 403, description = "未授權")
+    ),
+    security(
+        ("jwt_auth" = [])
+    ),
+    tag = "settlements"
+)]
+pub async fn list_organizations(
+    State(state): State<AppState>,
+    _authorized: Authorized<perm::ManageFinance>,
+) -> Result<Json<Vec<Organization>>, AppError> {
+    let organizations = state.organization_service.get_all_organizations().await?;
+    Ok(Json(organizations))
+}
+
+/// 創建主辦單位處理程序
+#[axum::debug_handler]
+#[utoipa::path(
+    post,
+    path = "/admin/organizations",
+    request_body = CreateOrganization,
+    responses(
+        (status = 201, description = "成功創建主辦單位", body = Organization),
+        (status = 400, description = "無效的輸入"),
+        (status = 401, description = "未認證"),
+        (status = 403, description = "未授權")
+    ),
+    security(
+        ("jwt_auth" = [])
+    ),
+    tag = "settlements"
+)]
+pub async fn create_organization(
+    State(state): State<AppState>,
+    _authorized: Authorized<perm::ManageFinance>,
+    Json(input): Json<CreateOrganization>,
+) -> Result<(StatusCode, Json<Organization>), AppError> {
+    let organization = state.organization_service.create_organization(input).await?;
+    Ok((StatusCode::CREATED, Json(organization)))
+}
+
+/// 更新主辦單位抽成比例處理程序
+#[axum::debug_handler]
+#[utoipa::path(
+    put,
+    path = "/admin/organizations/{organization_id}/commission",
+    params(
+        ("organization_id" = Uuid, Path, description = "主辦單位 ID")
+    ),
+    request_body = UpdateCommission,
+    responses(
+        (status = 200, description = "成功更新抽成比例", body = Organization),
+        (status = 400, description = "無效的輸入"),
+        (status = 401, description = "未認證"),
+        (status = 403, description = "未授權"),
+        (status = 404, description = "找不到主辦單位")
+    ),
+    security(
+        ("jwt_auth" = [])
+    ),
+    tag = "settlements"
+)]
+pub async fn update_organization_commission(
+    State(state): State<AppState>,
+    _authorized: Authorized<perm::ManageFinance>,
+    Path(organization_id): Path<Uuid>,
+    Json(input): Json<UpdateCommission>,
+) -> Result<Json<Organization>, AppError> {
+    let organization = state.organization_service.update_commission(organization_id, input).await?;
+    Ok(Json(organization))
+}
+
+/// 指定演唱會主辦單位處理程序
+#[axum::debug_handler]
+#[utoipa::path(
+    put,
+    path = "/admin/concerts/{concert_id}/organization",
+    params(
+        ("concert_id" = Uuid, Path, description = "演唱會 ID")
+    ),
+    request_body = AssignOrganization,
+    responses(
+        (status = 200, description = "成功指定主辦單位", body = Organization),
+        (status = 401, description = "未認證"),
+        (status = 403, description = "未授權"),
+        (status = 404, description = "找不到演唱會或主辦單位")
+    ),
+    security(
+        ("jwt_auth" = [])
+    ),
+    tag = "settlements"
+)]
+pub async fn assign_concert_organization(
+    State(state): State<AppState>,
+    _authorized: Authorized<perm::ManageFinance>,
+    Path(concert_id): Path<Uuid>,
+    Json(input): Json<AssignOrganization>,
+) -> Result<Json<Organization>, AppError> {
+    let organization = state.organization_service.assign_concert(concert_id, input.organization_id).await?;
+    Ok(Json(organization))
+}
@@ -0,0 +1,42 @@
+use axum::{
+    body::Bytes,
+    extract::State,
+    http::{HeaderMap, StatusCode},
+};
+
+use crate::api::routes::AppState;
+use crate::utils::error::AppError;
+
+/// 金流 Webhook 簽章標頭
+const SIGNATURE_HEADER: &str = "X-Payment-Signature";
+
+/// 金流 Webhook 處理程序
+#[axum::debug_handler]
+#[utoipa::path(
+    post,
+    path = "/payments/webhook",
+    request_body(content = String, description = "金流供應商送出的事件內容", content_type = "application/json"),
+    params(
+        ("X-Payment-Signature" = String, Header, description = "請求內容的簽章")
+    ),
+    responses(
+        (status = 200, description = "事件已處理"),
+        (status = 400, description = "無效的事件內容"),
+        (status = 401, description = "簽章驗證失敗"),
+        (status = 404, description = "找不到對應的付款")
+    ),
+    tag = "payments"
+)]
+pub async fn payment_webhook(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+    body: Bytes,
+) -> Result<StatusCode, AppError> {
+    let signature = headers
+        .get(SIGNATURE_HEADER)
+        .and_then(|h| h.to_str().ok())
+        .ok_or_else(|| AppError::Unauthorized("缺少 Webhook 簽章".to_string()))?;
+
+    state.order_service.handle_payment_webhook(&body, signature).await?;
+    Ok(StatusCode::OK)
+}
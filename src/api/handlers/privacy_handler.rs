@@ -0,0 +1,242 @@
+use axum::{
+    extract::{Json, Path, Query, State},
+    http::StatusCode,
+    response::{IntoResponse, Response},
+};
+use serde::Serialize;
+use uuid::Uuid;
+
+use crate::api::middleware::auth::AuthUser;
+use crate::api::middleware::client_ip::ClientIp;
+use crate::api::middleware::permission::{perm, Authorized};
+use crate::api::routes::AppState;
+use crate::domain::privacy::model::{
+    CreateErasureRequest, DataExportFormat, DataExportQuery, DeleteAccountInput, ErasureOutcome, ErasureRequest,
+    ErasureRequestDetail, ErasureRequestQuery, ProcessErasureRequest,
+};
+use crate::utils::error::AppError;
+use crate::utils::export::{to_zip, zip_response};
+
+/// 將匯出的一個類別序列化為 ZIP 內的 JSON 檔
+fn json_file<'a, T: Serialize>(name: &'a str, value: &T) -> Result<(&'a str, Vec<u8>), AppError> {
+    let content = serde_json::to_vec_pretty(value)
+        .map_err(|e| AppError::Internal(format!("產生匯出檔案 {} 失敗: {}", name, e)))?;
+    Ok((name, content))
+}
+
+/// 匯出個人資料處理程序
+/// format=zip 時以 ZIP 檔案下載，每個類別一個 JSON 檔
+#[axum::debug_handler]
+#[utoipa::path(
+    get,
+    path = "/me/export",
+    params(DataExportQuery),
+    responses(
+        (status = 200, description = "個人資料", body = DataExport, content_type = "application/json"),
+        (status = 200, description = "個人資料 ZIP", body = Vec<u8>, content_type = "application/zip"),
+        (status = 401, description = "未認證")
+    ),
+    security(
+        ("jwt_auth" = [])
+    ),
+    tag = "privacy"
+)]
+pub async fn export_my_data(
+    State(state): State<AppState>,
+    auth_user: AuthUser,
+    Query(query): Query<DataExportQuery>,
+) -> Result<Response, AppError> {
+    let export = state.privacy_service.export(&auth_user.0).await?;
+
+    match query.format.unwrap_or_default() {
+        DataExportFormat::Json => Ok(Json(export).into_response()),
+        DataExportFormat::Zip => {
+            let body = to_zip(&[
+                json_file("profile.json", &export.profile)?,
+                json_file("identities.json", &export.identities)?,
+                json_file("orders.json", &export.orders)?,
+                json_file("tickets.json", &export.tickets)?,
+                json_file("login_history.json", &export.login_history)?,
+            ])?;
+            Ok(zip_response(&format!("personal-data-{}.zip", export.exported_at.format("%Y%m%d")), body))
+        }
+    }
+}
+
+/// 刪除帳號處理程序
+/// 個人資料會被匿名化，訂單與會計記錄依法保留。仍有付款中的訂單時申請排入佇列，返回 202
+#[axum::debug_handler]
+#[utoipa::path(
+    delete,
+    path = "/me",
+    request_body = DeleteAccountInput,
+    responses(
+        (status = 204, description = "帳號已刪除，所有令牌已撤銷"),
+        (status = 202, description = "仍有付款中的訂單，刪除申請已排入佇列", body = ErasureRequest),
+        (status = 400, description = "未提供目前的密碼"),
+        (status = 401, description = "未認證或目前的密碼錯誤"),
+        (status = 409, description = "用戶是唯一的平台管理員")
+    ),
+    security(
+        ("jwt_auth" = [])
+    ),
+    tag = "privacy"
+)]
+pub async fn delete_me(
+    State(state): State<AppState>,
+    auth_user: AuthUser,
+    ClientIp(client_ip): ClientIp,
+    Json(input): Json<DeleteAccountInput>,
+) -> Result<Response, AppError> {
+    match state.privacy_service.delete_account(&auth_user.0, input, client_ip).await? {
+        ErasureOutcome::Completed => Ok(StatusCode::NO_CONTENT.into_response()),
+        ErasureOutcome::Queued(request) => Ok((StatusCode::ACCEPTED, Json(request)).into_response()),
+    }
+}
+
+/// 列出刪除申請處理程序
+#[axum::debug_handler]
+#[utoipa::path(
+    get,
+    path = "/admin/erasure-requests",
+    params(ErasureRequestQuery),
+    responses(
+        (status = 200, description = "成功獲取刪除申請列表", body = Vec<ErasureRequest>),
+        (status = 401, description = "未認證"),
+        (status = 403, description = "未授權")
+    ),
+    security(
+        ("jwt_auth" = [])
+    ),
+    tag = "privacy"
+)]
+pub async fn list_erasure_requests(
+    State(state): State<AppState>,
+    _authorized: Authorized<perm::ManageUsers>,
+    Query(query): Query<ErasureRequestQuery>,
+) -> Result<Json<Vec<ErasureRequest>>, AppError> {
+    let requests = state.privacy_service.list_requests(query).await?;
+    Ok(Json(requests))
+}
+
+/// 受理刪除申請處理程序
+/// 用於以電子郵件或書面提出的申請，由客服確認申請人身分後建立
+#[axum::debug_handler]
+#[utoipa::path(
+    post,
+    path = "/admin/erasure-requests",
+    request_body = CreateErasureRequest,
+    responses(
+        (status = 201, description = "成功受理刪除申請", body = ErasureRequest),
+        (status = 400, description = "無效的輸入數據"),
+        (status = 401, description = "未認證"),
+        (status = 403, description = "未授權"),
+        (status = 404, description = "找不到用戶或用戶已匿名化"),
+        (status = 409, description = "用戶已有待處理的刪除申請")
+    ),
+    security(
+        ("jwt_auth" = [])
+    ),
+    tag = "privacy"
+)]
+pub async fn create_erasure_request(
+    State(state): State<AppState>,
+    Authorized(user, _): Authorized<perm::ManageUsers>,
+    Json(input): Json<CreateErasureRequest>,
+) -> Result<(StatusCode, Json<ErasureRequest>), AppError> {
+    let request = state.privacy_service.create_request(&user, input).await?;
+    Ok((StatusCode::CREATED, Json(request)))
+}
+
+/// 獲取刪除申請處理程序
+#[axum::debug_handler]
+#[utoipa::path(
+    get,
+    path = "/admin/erasure-requests/{request_id}",
+    params(
+        ("request_id" = Uuid, Path, description = "刪除申請 ID")
+    ),
+    responses(
+        (status = 200, description = "成功獲取刪除申請與稽核記錄", body = ErasureRequestDetail),
+        (status = 401, description = "未認證"),
+        (status = 403, description = "未授權"),
+        (status = 404, description = "找不到刪除申請")
+    ),
+    security(
+        ("jwt_auth" = [])
+    ),
+    tag = "privacy"
+)]
+pub async fn get_erasure_request(
+    State(state): State<AppState>,
+    _authorized: Authorized<perm::ManageUsers>,
+    Path(request_id): Path<Uuid>,
+) -> Result<Json<ErasureRequestDetail>, AppError> {
+    let detail = state.privacy_service.get_request(request_id).await?;
+    Ok(Json(detail))
+}
+
+/// 完成刪除申請處理程序
+/// 匿名化用戶並撤銷所有令牌
+#[axum::debug_handler]
+#[utoipa::path(
+    post,
+    path = "/admin/erasure-requests/{request_id}/complete",
+    params(
+        ("request_id" = Uuid, Path, description = "刪除申請 ID")
+    ),
+    request_body = ProcessErasureRequest,
+    responses(
+        (status = 200, description = "用戶已匿名化", body = ErasureRequestDetail),
+        (status = 400, description = "無效的輸入數據"),
+        (status = 401, description = "未認證"),
+        (status = 403, description = "未授權"),
+        (status = 404, description = "找不到刪除申請"),
+        (status = 409, description = "申請已處理、用戶仍有付款中的訂單或是唯一的平台管理員")
+    ),
+    security(
+        ("jwt_auth" = [])
+    ),
+    tag = "privacy"
+)]
+pub async fn complete_erasure_request(
+    State(state): State<AppState>,
+    Authorized(user, _): Authorized<perm::ManageUsers>,
+    Path(request_id): Path<Uuid>,
+    Json(input): Json<ProcessErasureRequest>,
+) -> Result<Json<ErasureRequestDetail>, AppError> {
+    let detail = state.privacy_service.complete_request(&user, request_id, input).await?;
+    Ok(Json(detail))
+}
+
+/// 拒絕刪除申請處理程序
+#[axum::debug_handler]
+#[utoipa::path(
+    post,
+    path = "/admin/erasure-requests/{request_id}/reject",
+    params(
+        ("request_id" = Uuid, Path, description = "刪除申請 ID")
+    ),
+    request_body = ProcessErasureRequest,
+    responses(
+        (status = 200, description = "已拒絕刪除申請", body = ErasureRequestDetail),
+        (status = 400, description = "未說明拒絕原因"),
+        (status = 401, description = "未認證"),
+        (status = 403, description = "未授權"),
+        (status = 404, description = "找不到刪除申請"),
+        (status = 409, description = "申請已處理")
+    ),
+    security(
+        ("jwt_auth" = [])
+    ),
+    tag = "privacy"
+)]
+pub async fn reject_erasure_request(
+    State(state): State<AppState>,
+    Authorized(user, _): Authorized<perm::ManageUsers>,
+    Path(request_id): Path<Uuid>,
+    Json(input): Json<ProcessErasureRequest>,
+) -> Result<Json<ErasureRequestDetail>, AppError> {
+    let detail = state.privacy_service.reject_request(&user, request_id, input).await?;
+    Ok(Json(detail))
+}
@@ -0,0 +1,69 @@
+use axum::{
+    extract::{Json, Path, State},
+    http::StatusCode,
+};
+use uuid::Uuid;
+
+use crate::api::middleware::permission::{perm, Authorized};
+use crate::api::routes::AppState;
+use crate::domain::promo_code::model::{CreatePromoCode, PromoCode};
+use crate::utils::error::AppError;
+
+/// 獲取演唱會優惠碼列表處理程序
+#[axum::debug_handler]
+#[utoipa::path(
+    get,
+    path = "/admin/concerts/{concert_id}/promo-codes",
+    params(
+        ("concert_id" = Uuid, Path, description = "演唱會 ID")
+    ),
+    responses(
+        (status = 200, description = "成功獲取優惠碼列表", body = Vec<PromoCode>),
+        (status = 401, description = "未認證"),
+        (status = 403, description = "未授權")
+    ),
+    security(
+        ("jwt_auth" = [])
+    ),
+    tag = "promo_codes"
+)]
+pub async fn list_promo_codes(
+    State(state): State<AppState>,
+    Authorized(user, _): Authorized<perm::ManageEvents>,
+    Path(concert_id): Path<Uuid>,
+) -> Result<Json<Vec<PromoCode>>, AppError> {
+    let promo_codes = state.promo_code_service.get_concert_promo_codes(concert_id, user.tenant()).await?;
+    Ok(Json(promo_codes))
+}
+
+/// 創建優惠碼處理程序
+#[axum::debug_handler]
+#[utoipa::path(
+    post,
+    path = "/admin/concerts/{concert_id}/promo-codes",
+    params(
+        ("concert_id" = Uuid, Path, description = "演唱會 ID")
+    ),
+    request_body = CreatePromoCode,
+    responses(
+        (status = 201, description = "成功創建優惠碼", body = PromoCode),
+        (status = 400, description = "無效的輸入"),
+        (status = 401, description = "未認證"),
+        (status = 403, description = "未授權"),
+        (status = 404, description = "找不到演唱會"),
+        (status = 409, description = "優惠碼已存在")
+    ),
+    security(
+        ("jwt_auth" = [])
+    ),
+    tag = "promo_codes"
+)]
+pub async fn create_promo_code(
+    State(state): State<AppState>,
+    Authorized(user, _): Authorized<perm::ManageEvents>,
+    Path(concert_id): Path<Uuid>,
+    Json(input): Json<CreatePromoCode>,
+) -> Result<(StatusCode, Json<PromoCode>), AppError> {
+    let promo_code = state.promo_code_service.create_promo_code(concert_id, input, user.tenant()).await?;
+    Ok((StatusCode::CREATED, Json(promo_code)))
+}
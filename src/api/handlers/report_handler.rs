@@ -0,0 +1,160 @@
+use axum::{
+    extract::{Json, Query, State},
+    http::StatusCode,
+    response::{IntoResponse, Response},
+};
+use serde::Serialize;
+
+use crate::api::middleware::api_key::{scope, Scoped};
+use crate::api::middleware::permission::{perm, Authorized};
+use crate::api::routes::AppState;
+use crate::domain::report::model::{ReportQuery, TimeSeriesQuery, TopBuyersQuery};
+use crate::utils::error::AppError;
+use crate::utils::export::{csv_response, to_csv, ExportFormat};
+
+/// 依查詢參數以 JSON 或 CSV 回傳報表
+fn report_response<T: Serialize>(rows: Vec<T>, query: &ReportQuery, name: &str) -> Result<Response, AppError> {
+    match query.format.unwrap_or_default() {
+        ExportFormat::Json => Ok(Json(rows).into_response()),
+        ExportFormat::Csv => Ok(csv_response(&format!("{}.csv", name), to_csv(&rows)?)),
+    }
+}
+
+/// 票種銷售報表處理程序
+#[axum::debug_handler]
+#[utoipa::path(
+    get,
+    path = "/admin/reports/sales",
+    params(ReportQuery),
+    responses(
+        (status = 200, description = "成功獲取銷售報表", body = Vec<TicketSalesReport>),
+        (status = 400, description = "無效的日期區間"),
+        (status = 401, description = "未認證"),
+        (status = 403, description = "未授權"),
+        (status = 429, description = "API 金鑰超過每分鐘呼叫上限")
+    ),
+    security(
+        ("jwt_auth" = []),
+        ("api_key" = [])
+    ),
+    tag = "reports"
+)]
+pub async fn get_sales_report(
+    State(state): State<AppState>,
+    Scoped(caller, _): Scoped<scope::ReportsRead>,
+    Query(query): Query<ReportQuery>,
+) -> Result<Response, AppError> {
+    let rows = state.report_service.sales_by_ticket(&query, caller.tenant()).await?;
+    report_response(rows, &query, "sales")
+}
+
+/// 銷售時間序列處理程序
+#[axum::debug_handler]
+#[utoipa::path(
+    get,
+    path = "/admin/reports/timeseries",
+    params(ReportQuery, TimeSeriesQuery),
+    responses(
+        (status = 200, description = "成功獲取銷售時間序列", body = Vec<SalesDataPoint>),
+        (status = 400, description = "無效的日期區間"),
+        (status = 401, description = "未認證"),
+        (status = 403, description = "未授權"),
+        (status = 429, description = "API 金鑰超過每分鐘呼叫上限")
+    ),
+    security(
+        ("jwt_auth" = []),
+        ("api_key" = [])
+    ),
+    tag = "reports"
+)]
+pub async fn get_sales_time_series(
+    State(state): State<AppState>,
+    Scoped(caller, _): Scoped<scope::ReportsRead>,
+    Query(query): Query<ReportQuery>,
+    Query(series): Query<TimeSeriesQuery>,
+) -> Result<Response, AppError> {
+    let granularity = series.granularity.unwrap_or_default();
+    let rows = state.report_service.time_series(&query, granularity, caller.tenant()).await?;
+    report_response(rows, &query, &format!("sales-{}", granularity.as_str()))
+}
+
+/// 購買者排行處理程序
+#[axum::debug_handler]
+#[utoipa::path(
+    get,
+    path = "/admin/reports/top-buyers",
+    params(ReportQuery, TopBuyersQuery),
+    responses(
+        (status = 200, description = "成功獲取購買者排行", body = Vec<TopBuyer>),
+        (status = 400, description = "無效的日期區間"),
+        (status = 401, description = "未認證"),
+        (status = 403, description = "未授權"),
+        (status = 429, description = "API 金鑰超過每分鐘呼叫上限")
+    ),
+    security(
+        ("jwt_auth" = []),
+        ("api_key" = [])
+    ),
+    tag = "reports"
+)]
+pub async fn get_top_buyers(
+    State(state): State<AppState>,
+    Scoped(caller, _): Scoped<scope::ReportsRead>,
+    Query(query): Query<ReportQuery>,
+    Query(buyers): Query<TopBuyersQuery>,
+) -> Result<Response, AppError> {
+    let rows = state.report_service.top_buyers(&query, buyers.limit, caller.tenant()).await?;
+    report_response(rows, &query, "top-buyers")
+}
+
+/// 取消率報表處理程序
+#[axum::debug_handler]
+#[utoipa::path(
+    get,
+    path = "/admin/reports/cancellations",
+    params(ReportQuery),
+    responses(
+        (status = 200, description = "成功獲取取消率報表", body = Vec<CancellationReport>),
+        (status = 400, description = "無效的日期區間"),
+        (status = 401, description = "未認證"),
+        (status = 403, description = "未授權"),
+        (status = 429, description = "API 金鑰超過每分鐘呼叫上限")
+    ),
+    security(
+        ("jwt_auth" = []),
+        ("api_key" = [])
+    ),
+    tag = "reports"
+)]
+pub async fn get_cancellation_report(
+    State(state): State<AppState>,
+    Scoped(caller, _): Scoped<scope::ReportsRead>,
+    Query(query): Query<ReportQuery>,
+) -> Result<Response, AppError> {
+    let rows = state.report_service.cancellations(&query, caller.tenant()).await?;
+    report_response(rows, &query, "cancellations")
+}
+
+/// 重新整理報表處理程序
+/// 背景任務會定期重新整理，此端點用於需要立即看到最新資料時
+#[axum::debug_handler]
+#[utoipa::path(
+    post,
+    path = "/admin/reports/refresh",
+    responses(
+        (status = 204, description = "成功重新整理報表"),
+        (status = 401, description = "未認證"),
+        (status = 403, description = "未授權")
+    ),
+    security(
+        ("jwt_auth" = [])
+    ),
+    tag = "reports"
+)]
+pub async fn refresh_reports(
+    State(state): State<AppState>,
+    _authorized: Authorized<perm::ViewReports>,
+) -> Result<StatusCode, AppError> {
+    state.report_service.refresh().await?;
+    Ok(StatusCode::NO_CONTENT)
+}
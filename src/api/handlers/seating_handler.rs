@@ -0,0 +1,174 @@
+use axum::{
+    extract::{Json, Path, Query, State},
+    http::StatusCode,
+};
+use uuid::Uuid;
+
+use crate::api::middleware::permission::{perm, Authorized};
+use crate::api::routes::AppState;
+use crate::domain::seating::model::{
+    ConcertSeating, ConfigureSeating, CsvImportQuery, ImportSeatMap, SeatMap, SeatMapSummary,
+};
+use crate::utils::error::AppError;
+
+/// 匯入座位圖處理程序（JSON）
+#[axum::debug_handler]
+#[utoipa::path(
+    post,
+    path = "/admin/seat-maps",
+    request_body = ImportSeatMap,
+    responses(
+        (status = 201, description = "成功匯入座位圖", body = SeatMap),
+        (status = 400, description = "無效的座位圖"),
+        (status = 401, description = "未認證"),
+        (status = 403, description = "未授權")
+    ),
+    security(
+        ("jwt_auth" = [])
+    ),
+    tag = "seating"
+)]
+pub async fn import_seat_map(
+    State(state): State<AppState>,
+    Authorized(user, _): Authorized<perm::ManageEvents>,
+    Json(input): Json<ImportSeatMap>,
+) -> Result<(StatusCode, Json<SeatMap>), AppError> {
+    let seat_map = state.seating_service.import_seat_map(input, user.tenant()).await?;
+    Ok((StatusCode::CREATED, Json(seat_map)))
+}
+
+/// 匯入座位圖處理程序（CSV）
+/// 請求內容為含標題列的 CSV：section,row,seat,x,y
+#[axum::debug_handler]
+#[utoipa::path(
+    post,
+    path = "/admin/seat-maps/csv",
+    params(CsvImportQuery),
+    request_body(content = String, content_type = "text/csv", description = "section,row,seat,x,y"),
+    responses(
+        (status = 201, description = "成功匯入座位圖", body = SeatMap),
+        (status = 400, description = "無效的 CSV 或座位圖"),
+        (status = 401, description = "未認證"),
+        (status = 403, description = "未授權")
+    ),
+    security(
+        ("jwt_auth" = [])
+    ),
+    tag = "seating"
+)]
+pub async fn import_seat_map_csv(
+    State(state): State<AppState>,
+    Authorized(user, _): Authorized<perm::ManageEvents>,
+    Query(query): Query<CsvImportQuery>,
+    body: String,
+) -> Result<(StatusCode, Json<SeatMap>), AppError> {
+    let seat_map = state.seating_service
+        .import_seat_map_csv(query.name, query.venue, &body, user.tenant())
+        .await?;
+    Ok((StatusCode::CREATED, Json(seat_map)))
+}
+
+/// 獲取座位圖列表處理程序
+#[axum::debug_handler]
+#[utoipa::path(
+    get,
+    path = "/admin/seat-maps",
+    responses(
+        (status = 200, description = "成功獲取座位圖列表", body = Vec<SeatMapSummary>),
+        (status = 401, description = "未認證"),
+        (status = 403, description = "未授權")
+    ),
+    security(
+        ("jwt_auth" = [])
+    ),
+    tag = "seating"
+)]
+pub async fn list_seat_maps(
+    State(state): State<AppState>,
+    Authorized(user, _): Authorized<perm::ManageEvents>,
+) -> Result<Json<Vec<SeatMapSummary>>, AppError> {
+    let seat_maps = state.seating_service.get_all_seat_maps(user.tenant()).await?;
+    Ok(Json(seat_maps))
+}
+
+/// 獲取座位圖處理程序
+#[axum::debug_handler]
+#[utoipa::path(
+    get,
+    path = "/admin/seat-maps/{seat_map_id}",
+    params(
+        ("seat_map_id" = Uuid, Path, description = "座位圖 ID")
+    ),
+    responses(
+        (status = 200, description = "成功獲取座位圖", body = SeatMap),
+        (status = 401, description = "未認證"),
+        (status = 403, description = "未授權"),
+        (status = 404, description = "找不到座位圖")
+    ),
+    security(
+        ("jwt_auth" = [])
+    ),
+    tag = "seating"
+)]
+pub async fn get_seat_map(
+    State(state): State<AppState>,
+    Authorized(user, _): Authorized<perm::ManageEvents>,
+    Path(seat_map_id): Path<Uuid>,
+) -> Result<Json<SeatMap>, AppError> {
+    let seat_map = state.seating_service.get_seat_map(seat_map_id, user.tenant()).await?;
+    Ok(Json(seat_map))
+}
+
+/// 設定演唱會座位處理程序
+#[axum::debug_handler]
+#[utoipa::path(
+    put,
+    path = "/admin/concerts/{concert_id}/seating",
+    params(
+        ("concert_id" = Uuid, Path, description = "演唱會 ID")
+    ),
+    request_body = ConfigureSeating,
+    responses(
+        (status = 200, description = "成功設定座位", body = ConcertSeating),
+        (status = 400, description = "無效的價區設定"),
+        (status = 401, description = "未認證"),
+        (status = 403, description = "未授權"),
+        (status = 404, description = "找不到演唱會、座位圖或票券"),
+        (status = 409, description = "已有座位被保留或售出")
+    ),
+    security(
+        ("jwt_auth" = [])
+    ),
+    tag = "seating"
+)]
+pub async fn configure_concert_seating(
+    State(state): State<AppState>,
+    Authorized(user, _): Authorized<perm::ManageEvents>,
+    Path(concert_id): Path<Uuid>,
+    Json(input): Json<ConfigureSeating>,
+) -> Result<Json<ConcertSeating>, AppError> {
+    let seating = state.seating_service.configure_concert(concert_id, input, user.tenant()).await?;
+    Ok(Json(seating))
+}
+
+/// 獲取演唱會座位可售狀態處理程序
+#[axum::debug_handler]
+#[utoipa::path(
+    get,
+    path = "/concerts/{concert_id}/seats",
+    params(
+        ("concert_id" = Uuid, Path, description = "演唱會 ID")
+    ),
+    responses(
+        (status = 200, description = "成功獲取座位可售狀態", body = ConcertSeating),
+        (status = 404, description = "演唱會尚未設定座位圖")
+    ),
+    tag = "seating"
+)]
+pub async fn get_concert_seats(
+    State(state): State<AppState>,
+    Path(concert_id): Path<Uuid>,
+) -> Result<Json<ConcertSeating>, AppError> {
+    let seating = state.seating_service.get_concert_seating(concert_id).await?;
+    Ok(Json(seating))
+}
@@ -0,0 +1,109 @@
+use axum::{
+    extract::{Json, Path, Query, State},
+    response::{IntoResponse, Response},
+};
+use uuid::Uuid;
+
+use crate::api::middleware::permission::{perm, Authorized};
+use crate::api::routes::AppState;
+use crate::domain::settlement::model::{Settlement, StatementQuery};
+use crate::utils::error::AppError;
+use crate::utils::export::{csv_response, ExportFormat};
+
+/// 獲取結算單處理程序
+/// format=csv 時以 CSV 檔案下載
+#[axum::debug_handler]
+#[utoipa::path(
+    get,
+    path = "/admin/settlements/{concert_id}",
+    params(
+        ("concert_id" = Uuid, Path, description = "演唱會 ID"),
+        StatementQuery
+    ),
+    responses(
+        (status = 200, description = "成功獲取結算單", body = Settlement, content_type = "application/json"),
+        (status = 200, description = "結算單 CSV", body = String, content_type = "text/csv"),
+        (status = 400, description = "演唱會尚未指定主辦單位"),
+        (status = 401, description = "未認證"),
+        (status = 403, description = "未授權"),
+        (status = 404, description = "找不到演唱會")
+    ),
+    security(
+        ("jwt_auth" = [])
+    ),
+    tag = "settlements"
+)]
+pub async fn get_settlement(
+    State(state): State<AppState>,
+    _authorized: Authorized<perm::ManageFinance>,
+    Path(concert_id): Path<Uuid>,
+    Query(query): Query<StatementQuery>,
+) -> Result<Response, AppError> {
+    let settlement = state.settlement_service.get_settlement(concert_id).await?;
+
+    match query.format.unwrap_or_default() {
+        ExportFormat::Json => Ok(Json(settlement).into_response()),
+        ExportFormat::Csv => {
+            let body = state.settlement_service.to_csv(&settlement)?;
+            Ok(csv_response(&format!("settlement-{}.csv", concert_id), body))
+        }
+    }
+}
+
+/// 鎖定結算單處理程序
+#[axum::debug_handler]
+#[utoipa::path(
+    post,
+    path = "/admin/settlements/{concert_id}/lock",
+    params(
+        ("concert_id" = Uuid, Path, description = "演唱會 ID")
+    ),
+    responses(
+        (status = 200, description = "成功鎖定結算單", body = Settlement),
+        (status = 400, description = "演唱會尚未結束或未指定主辦單位"),
+        (status = 401, description = "未認證"),
+        (status = 403, description = "未授權"),
+        (status = 409, description = "結算單已鎖定")
+    ),
+    security(
+        ("jwt_auth" = [])
+    ),
+    tag = "settlements"
+)]
+pub async fn lock_settlement(
+    State(state): State<AppState>,
+    _authorized: Authorized<perm::ManageFinance>,
+    Path(concert_id): Path<Uuid>,
+) -> Result<Json<Settlement>, AppError> {
+    let settlement = state.settlement_service.lock_settlement(concert_id).await?;
+    Ok(Json(settlement))
+}
+
+/// 結算撥款處理程序
+#[axum::debug_handler]
+#[utoipa::path(
+    post,
+    path = "/admin/settlements/{concert_id}/pay",
+    params(
+        ("concert_id" = Uuid, Path, description = "演唱會 ID")
+    ),
+    responses(
+        (status = 200, description = "成功撥款", body = Settlement),
+        (status = 401, description = "未認證"),
+        (status = 403, description = "未授權"),
+        (status = 404, description = "結算單尚未鎖定"),
+        (status = 409, description = "結算單不是已鎖定狀態")
+    ),
+    security(
+        ("jwt_auth" = [])
+    ),
+    tag = "settlements"
+)]
+pub async fn pay_settlement(
+    State(state): State<AppState>,
+    _authorized: Authorized<perm::ManageFinance>,
+    Path(concert_id): Path<Uuid>,
+) -> Result<Json<Settlement>, AppError> {
+    let settlement = state.settlement_service.pay_settlement(concert_id).await?;
+    Ok(Json(settlement))
+}
@@ -1,10 +1,11 @@
 use axum::{
-    extract::{Json, Query, State},
+    extract::{Json, Path, Query, State},
 };
+use uuid::Uuid;
 
-use crate::api::middleware::auth::AdminUser;
+use crate::api::middleware::permission::{perm, Authorized};
 use crate::api::routes::AppState;
-use crate::domain::ticket::model::{CreateTicket, Ticket, TicketQuery};
+use crate::domain::ticket::model::{CreateTicket, SetTicketPricing, Ticket, TicketPricing, TicketQuery};
 use crate::utils::error::AppError;
 
 /// 創建票券處理程序
@@ -27,10 +28,10 @@ use crate::utils::error::AppError;
 )]
 pub async fn create_ticket(
     State(state): State<AppState>,
-    _admin_user: AdminUser,
+    Authorized(user, _): Authorized<perm::ManageEvents>,
     Json(input): Json<CreateTicket>,
 ) -> Result<Json<Ticket>, AppError> {
-    let ticket = state.ticket_service.create_ticket(input, true).await?;
+    let ticket = state.ticket_service.create_ticket(input, user.tenant()).await?;
     Ok(Json(ticket))
 }
 
@@ -55,3 +56,62 @@ pub async fn list_tickets(
     let tickets = state.ticket_service.get_tickets_by_concert_id(query.concert_id).await?;
     Ok(Json(tickets))
 }
+
+/// 獲取票種定價處理程序
+#[axum::debug_handler]
+#[utoipa::path(
+    get,
+    path = "/admin/tickets/{ticket_id}/pricing",
+    params(
+        ("ticket_id" = Uuid, Path, description = "票券 ID")
+    ),
+    responses(
+        (status = 200, description = "成功獲取票種定價", body = TicketPricing),
+        (status = 401, description = "未認證"),
+        (status = 403, description = "未授權"),
+        (status = 404, description = "找不到票券")
+    ),
+    security(
+        ("jwt_auth" = [])
+    ),
+    tag = "tickets"
+)]
+pub async fn get_ticket_pricing(
+    State(state): State<AppState>,
+    Authorized(user, _): Authorized<perm::ManageEvents>,
+    Path(ticket_id): Path<Uuid>,
+) -> Result<Json<TicketPricing>, AppError> {
+    let pricing = state.ticket_service.get_pricing(ticket_id, user.tenant()).await?;
+    Ok(Json(pricing))
+}
+
+/// 設定票種定價處理程序
+#[axum::debug_handler]
+#[utoipa::path(
+    put,
+    path = "/admin/tickets/{ticket_id}/pricing",
+    params(
+        ("ticket_id" = Uuid, Path, description = "票券 ID")
+    ),
+    request_body = SetTicketPricing,
+    responses(
+        (status = 200, description = "成功設定票種定價", body = TicketPricing),
+        (status = 400, description = "無效的輸入"),
+        (status = 401, description = "未認證"),
+        (status = 403, description = "未授權"),
+        (status = 404, description = "找不到票券")
+    ),
+    security(
+        ("jwt_auth" = [])
+    ),
+    tag = "tickets"
+)]
+pub async fn set_ticket_pricing(
+    State(state): State<AppState>,
+    Authorized(user, _): Authorized<perm::ManageEvents>,
+    Path(ticket_id): Path<Uuid>,
+    Json(input): Json<SetTicketPricing>,
+) -> Result<Json<TicketPricing>, AppError> {
+    let pricing = state.ticket_service.set_pricing(ticket_id, input, user.tenant()).await?;
+    Ok(Json(pricing))
+}
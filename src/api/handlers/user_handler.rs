@@ -0,0 +1,215 @@
+use std::net::IpAddr;
+
+use axum::extract::{Json, Path, State};
+use axum::http::StatusCode;
+use uuid::Uuid;
+
+use crate::api::middleware::permission::{perm, Authorized};
+use crate::api::routes::AppState;
+use crate::domain::auth::model::{GrantRole, Role, UserRoles};
+use crate::domain::organization::model::SetUserOrganization;
+use crate::utils::error::AppError;
+
+/// 獲取用戶角色處理程序
+#[axum::debug_handler]
+#[utoipa::path(
+    get,
+    path = "/admin/users/{user_id}/roles",
+    params(
+        ("user_id" = Uuid, Path, description = "用戶 ID")
+    ),
+    responses(
+        (status = 200, description = "成功獲取用戶角色", body = UserRoles),
+        (status = 401, description = "未認證"),
+        (status = 403, description = "未授權"),
+        (status = 404, description = "找不到用戶")
+    ),
+    security(
+        ("jwt_auth" = [])
+    ),
+    tag = "users"
+)]
+pub async fn get_user_roles(
+    State(state): State<AppState>,
+    _authorized: Authorized<perm::ManageUsers>,
+    Path(user_id): Path<Uuid>,
+) -> Result<Json<UserRoles>, AppError> {
+    let roles = state.auth_service.get_user_roles(user_id).await?;
+    Ok(Json(roles))
+}
+
+/// 指派角色處理程序
+#[axum::debug_handler]
+#[utoipa::path(
+    post,
+    path = "/admin/users/{user_id}/roles",
+    params(
+        ("user_id" = Uuid, Path, description = "用戶 ID")
+    ),
+    request_body = GrantRole,
+    responses(
+        (status = 200, description = "成功指派角色", body = UserRoles),
+        (status = 401, description = "未認證"),
+        (status = 403, description = "未授權"),
+        (status = 404, description = "找不到用戶"),
+        (status = 409, description = "用戶已擁有此角色")
+    ),
+    security(
+        ("jwt_auth" = [])
+    ),
+    tag = "users"
+)]
+pub async fn grant_user_role(
+    State(state): State<AppState>,
+    Authorized(actor, _): Authorized<perm::ManageUsers>,
+    Path(user_id): Path<Uuid>,
+    Json(input): Json<GrantRole>,
+) -> Result<Json<UserRoles>, AppError> {
+    let roles = state.auth_service.grant_role(&actor, user_id, input.role).await?;
+    Ok(Json(roles))
+}
+
+/// 移除角色處理程序
+#[axum::debug_handler]
+#[utoipa::path(
+    delete,
+    path = "/admin/users/{user_id}/roles/{role}",
+    params(
+        ("user_id" = Uuid, Path, description = "用戶 ID"),
+        ("role" = Role, Path, description = "角色")
+    ),
+    responses(
+        (status = 200, description = "成功移除角色", body = UserRoles),
+        (status = 400, description = "不可移除自己的管理員角色"),
+        (status = 401, description = "未認證"),
+        (status = 403, description = "未授權"),
+        (status = 404, description = "找不到用戶或用戶沒有此角色")
+    ),
+    security(
+        ("jwt_auth" = [])
+    ),
+    tag = "users"
+)]
+pub async fn revoke_user_role(
+    State(state): State<AppState>,
+    Authorized(actor, _): Authorized<perm::ManageUsers>,
+    Path((user_id, role)): Path<(Uuid, Role)>,
+) -> Result<Json<UserRoles>, AppError> {
+    let roles = state.auth_service.revoke_role(&actor, user_id, role).await?;
+    Ok(Json(roles))
+}
+
+/// 設定用戶所屬主辦單位處理程序
+#[axum::debug_handler]
+#[utoipa::path(
+    put,
+    path = "/admin/users/{user_id}/organization",
+    params(
+        ("user_id" = Uuid, Path, description = "用戶 ID")
+    ),
+    request_body = SetUserOrganization,
+    responses(
+        (status = 200, description = "成功設定所屬主辦單位", body = UserRoles),
+        (status = 400, description = "擁有主辦單位角色的用戶必須隸屬主辦單位"),
+        (status = 401, description = "未認證"),
+        (status = 403, description = "未授權"),
+        (status = 404, description = "找不到用戶或主辦單位")
+    ),
+    security(
+        ("jwt_auth" = [])
+    ),
+    tag = "users"
+)]
+pub async fn set_user_organization(
+    State(state): State<AppState>,
+    Authorized(actor, _): Authorized<perm::ManageUsers>,
+    Path(user_id): Path<Uuid>,
+    Json(input): Json<SetUserOrganization>,
+) -> Result<Json<UserRoles>, AppError> {
+    let roles = state.auth_service.set_user_organization(&actor, user_id, input).await?;
+    Ok(Json(roles))
+}
+
+/// 獲取主辦單位工作人員處理程序
+#[axum::debug_handler]
+#[utoipa::path(
+    get,
+    path = "/admin/organizations/{organization_id}/members",
+    params(
+        ("organization_id" = Uuid, Path, description = "主辦單位 ID")
+    ),
+    responses(
+        (status = 200, description = "成功獲取工作人員", body = Vec<UserRoles>),
+        (status = 401, description = "未認證"),
+        (status = 403, description = "未授權"),
+        (status = 404, description = "找不到主辦單位")
+    ),
+    security(
+        ("jwt_auth" = [])
+    ),
+    tag = "users"
+)]
+pub async fn list_organization_members(
+    State(state): State<AppState>,
+    _authorized: Authorized<perm::ManageUsers>,
+    Path(organization_id): Path<Uuid>,
+) -> Result<Json<Vec<UserRoles>>, AppError> {
+    let members = state.organization_service.get_members(organization_id).await?;
+    Ok(Json(members))
+}
+
+/// 解除用戶登入鎖定處理程序
+#[axum::debug_handler]
+#[utoipa::path(
+    delete,
+    path = "/admin/users/{user_id}/lockout",
+    params(
+        ("user_id" = Uuid, Path, description = "用戶 ID")
+    ),
+    responses(
+        (status = 204, description = "已解除登入鎖定並清除失敗次數"),
+        (status = 401, description = "未認證"),
+        (status = 403, description = "未授權"),
+        (status = 404, description = "找不到用戶")
+    ),
+    security(
+        ("jwt_auth" = [])
+    ),
+    tag = "users"
+)]
+pub async fn unlock_user(
+    State(state): State<AppState>,
+    Authorized(actor, _): Authorized<perm::ManageUsers>,
+    Path(user_id): Path<Uuid>,
+) -> Result<StatusCode, AppError> {
+    state.auth_service.unlock_user(&actor, user_id).await?;
+    Ok(StatusCode::NO_CONTENT)
+}
+
+/// 解除來源 IP 登入鎖定處理程序
+#[axum::debug_handler]
+#[utoipa::path(
+    delete,
+    path = "/admin/ip-lockouts/{ip}",
+    params(
+        ("ip" = String, Path, description = "來源 IP")
+    ),
+    responses(
+        (status = 204, description = "已解除登入鎖定並清除失敗次數"),
+        (status = 400, description = "無效的 IP"),
+        (status = 401, description = "未認證"),
+        (status = 403, description = "未授權")
+    ),
+    security(
+        ("jwt_auth" = [])
+    ),
+    tag = "users"
+)]
+pub async fn unlock_ip(
+    State(state): State<AppState>,
+    Authorized(actor, _): Authorized<perm::ManageUsers>,
+    Path(ip): Path<IpAddr>,
+) -> Result<StatusCode, AppError> {
+    state.auth_service.unlock_ip(&actor, ip).await?;
+    Ok(StatusCode::NO_CONTENT)
+}
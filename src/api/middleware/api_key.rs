@@ -0,0 +1,117 @@
+// 引入標準庫中的零大小型別標記
+use std::marker::PhantomData;
+
+// 引入 Axum 框架的相關功能
+use axum::{
+    async_trait,
+    extract::FromRequestParts,
+    http::request::Parts,
+    response::{IntoResponse, Response},
+};
+
+use crate::api::middleware::permission::{perm, Authorized, RequiredPermission};
+use crate::api::routes::AppState;
+use crate::domain::api_key::model::{ApiKey, ApiScope};
+use crate::domain::auth::model::User;
+use crate::domain::organization::model::Tenant;
+use crate::utils::error::AppError;
+
+/// API 金鑰標頭
+pub const API_KEY_HEADER: &str = "x-api-key";
+
+/// 權限範圍標記
+/// 每個權限範圍對應一個零大小的標記型別，並指定以 JWT 登入的用戶需要的權限
+pub trait RequiredScope: Send + Sync {
+    /// API 金鑰需要的權限範圍
+    const SCOPE: ApiScope;
+    /// 以 JWT 登入的用戶需要的權限
+    type Permission: RequiredPermission;
+}
+
+/// 宣告權限範圍標記型別
+macro_rules! scopes {
+    ($($name:ident => $permission:ident),* $(,)?) => {
+        $(
+            #[doc = concat!("需要 `ApiScope::", stringify!($name), "` 權限範圍或 `Permission::", stringify!($permission), "` 權限")]
+            pub struct $name;
+
+            impl RequiredScope for $name {
+                const SCOPE: ApiScope = ApiScope::$name;
+                type Permission = perm::$permission;
+            }
+        )*
+    };
+}
+
+/// 權限範圍標記型別，名稱與 ApiScope 的成員相同
+pub mod scope {
+    use super::{perm, ApiScope, RequiredScope};
+
+    scopes!(
+        ConcertsRead => ManageEvents,
+        OrdersRead => ManageOrders,
+        ReportsRead => ViewReports,
+    );
+}
+
+/// 呼叫者：以 JWT 登入的用戶或 API 金鑰
+pub enum Caller {
+    User(User),
+    ApiKey(ApiKey),
+}
+
+impl Caller {
+    /// 呼叫者的租戶範圍
+    pub fn tenant(&self) -> Tenant {
+        match self {
+            Caller::User(user) => user.tenant(),
+            Caller::ApiKey(api_key) => api_key.tenant(),
+        }
+    }
+}
+
+/// 權限範圍認證提取器
+/// 請求帶有 `X-Api-Key` 標頭時驗證 API 金鑰並檢查權限範圍與呼叫頻率，
+/// 否則與 `Authorized` 相同，以 Bearer JWT 驗證用戶並檢查對應的權限
+///
+/// # 範例
+/// ```ignore
+/// async fn handler(Scoped(caller, ..): Scoped<scope::ReportsRead>) { ... }
+/// ```
+pub struct Scoped<S: RequiredScope>(pub Caller, pub PhantomData<S>);
+
+#[async_trait]
+impl<St, S> FromRequestParts<St> for Scoped<S>
+where
+    St: Send + Sync,
+    S: RequiredScope,
+{
+    // 如果提取失敗，返回的錯誤類型
+    type Rejection = Response;
+
+    /// 從 HTTP 請求部分中提取呼叫者
+    ///
+    /// # 返回值
+    /// 未認證或金鑰無效時返回 401，缺少權限範圍或權限時返回 403，API 金鑰超過呼叫上限時返回 429
+    async fn from_request_parts(parts: &mut Parts, state: &St) -> Result<Self, Self::Rejection> {
+        let Some(header) = parts.headers.get(API_KEY_HEADER) else {
+            let Authorized(user, _) = Authorized::<S::Permission>::from_request_parts(parts, state).await?;
+            return Ok(Self(Caller::User(user), PhantomData));
+        };
+
+        let key = header
+            .to_str()
+            .map_err(|_| AppError::Unauthorized("無效的 API 金鑰".to_string()).into_response())?;
+        let app_state = parts
+            .extensions
+            .get::<AppState>()
+            .ok_or_else(|| AppError::Internal("無法獲取應用程式狀態".to_string()).into_response())?;
+
+        let api_key = app_state.api_key_service.authenticate(key).await.map_err(|e| e.into_response())?;
+        if !api_key.has_scope(S::SCOPE) {
+            return Err(AppError::Forbidden(format!("API 金鑰缺少 {} 權限範圍", S::SCOPE.as_str())).into_response());
+        }
+
+        Ok(Self(Caller::ApiKey(api_key), PhantomData))
+    }
+}
@@ -43,6 +43,11 @@ impl AuthMiddleware {
     }
 }
 
+/// 存取令牌是否經過兩步驟驗證簽發
+/// 認證提取器驗證令牌後放進請求擴展，供權限提取器檢查必須兩步驟驗證的角色
+#[derive(Debug, Clone, Copy)]
+pub struct TwoFactorVerified(pub bool);
+
 /// 用戶認證提取器
 /// 這個結構體用於從 HTTP 請求中提取已認證的用戶
 /// 它包裝了一個 User 實例，表示當前認證的用戶
@@ -102,55 +107,12 @@ where
         // 驗證令牌並獲取用戶
         // 調用認證服務的方法來驗證令牌並獲取對應的用戶
         // 如果失敗，將錯誤轉換為 HTTP 響應
-        let user = auth_service.get_user_from_token(token)
+        let (user, two_factor) = auth_service.get_user_from_token(token)
             .await
             .map_err(|e| e.into_response())?;
+        parts.extensions.insert(TwoFactorVerified(two_factor));
 
         // 返回包含用戶的 AuthUser 實例
         Ok(Self(user))
     }
 }
-
-/// 管理員認證提取器
-/// 這個結構體用於從 HTTP 請求中提取已認證的管理員用戶
-/// 它包裝了一個 User 實例，表示當前認證的管理員用戶
-pub struct AdminUser(pub User);
-
-// async_trait 宏允許在 trait 中使用異步函數
-#[async_trait]
-// 實現 FromRequestParts trait，使 AdminUser 可以從 HTTP 請求中提取
-impl<S> FromRequestParts<S> for AdminUser
-where
-    S: Send + Sync,
-{
-    // 如果提取失敗，返回的錯誤類型
-    type Rejection = Response;
-
-    /// 從 HTTP 請求部分中提取管理員用戶
-    /// 
-    /// # 參數
-    /// * `parts` - HTTP 請求的各個部分
-    /// * `state` - 應用程序狀態
-    /// 
-    /// # 返回值
-    /// 如果成功，返回包含管理員用戶的 AdminUser
-    /// 如果失敗，返回錯誤響應
-    async fn from_request_parts(parts: &mut Parts, state: &S) -> Result<Self, Self::Rejection> {
-        // 先驗證用戶認證
-        // 使用 AuthUser 提取器獲取已認證的用戶
-        match AuthUser::from_request_parts(parts, state).await {
-            Ok(AuthUser(user)) => {
-                // 檢查是否為管理員
-                // 如果用戶不是管理員，返回禁止訪問錯誤
-                if !user.is_admin {
-                    Err(AppError::Forbidden("需要管理員權限".to_string()).into_response())
-                } else {
-                    // 如果是管理員，返回包含用戶的 AdminUser 實例
-                    Ok(Self(user))
-                }
-            },
-            // 如果用戶認證失敗，直接返回錯誤
-            Err(e) => Err(e),
-        }
-    }
-}
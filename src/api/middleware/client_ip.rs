@@ -0,0 +1,81 @@
+// 引入標準庫中的網絡地址類型
+use std::convert::Infallible;
+use std::net::{IpAddr, SocketAddr};
+
+// 引入 Axum 框架的相關功能
+use axum::{
+    async_trait,
+    extract::{ConnectInfo, FromRequestParts},
+    http::request::Parts,
+};
+
+/// 信任的反向代理層數，為 0 時不信任 X-Forwarded-For 標頭
+/// 服務部署在反向代理之後時設定，由 main 以 Extension 放進請求擴展
+#[derive(Debug, Clone, Copy)]
+pub struct TrustedProxyHops(pub usize);
+
+/// 來源 IP 提取器
+/// 預設使用 TCP 連線的對端位址；信任反向代理時改用 X-Forwarded-For 中由最外層信任代理附加的位址。
+/// 無法判斷來源時為 None，例如未以 `into_make_service_with_connect_info` 啟動服務
+pub struct ClientIp(pub Option<IpAddr>);
+
+#[async_trait]
+impl<S> FromRequestParts<S> for ClientIp
+where
+    S: Send + Sync,
+{
+    type Rejection = Infallible;
+
+    async fn from_request_parts(parts: &mut Parts, _state: &S) -> Result<Self, Self::Rejection> {
+        let hops = parts.extensions.get::<TrustedProxyHops>().map_or(0, |hops| hops.0);
+        if hops > 0 {
+            let forwarded = parts
+                .headers
+                .get("x-forwarded-for")
+                .and_then(|value| value.to_str().ok())
+                .and_then(|value| forwarded_client(value, hops));
+            if forwarded.is_some() {
+                return Ok(Self(forwarded));
+            }
+        }
+
+        let peer = parts
+            .extensions
+            .get::<ConnectInfo<SocketAddr>>()
+            .map(|ConnectInfo(addr)| addr.ip());
+        Ok(Self(peer))
+    }
+}
+
+/// 從 X-Forwarded-For 取出客戶端位址
+/// 每層代理都在標頭最右側附加其對端位址，左側的內容可由客戶端任意填寫，
+/// 因此只採用從右數第 `hops` 個位址，也就是最外層信任代理看到的來源；位址數量不足或格式錯誤時為 None
+fn forwarded_client(header: &str, hops: usize) -> Option<IpAddr> {
+    let entries: Vec<&str> = header.split(',').map(str::trim).collect();
+    let index = entries.len().checked_sub(hops)?;
+    entries[index].parse().ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn single_proxy_uses_rightmost_entry() {
+        // 客戶端自行填入的位址不會被採用
+        let ip = forwarded_client("1.1.1.1, 2.2.2.2, 203.0.113.7", 1);
+        assert_eq!(ip, Some("203.0.113.7".parse().unwrap()));
+    }
+
+    #[test]
+    fn multiple_proxies_skip_trusted_hops() {
+        let ip = forwarded_client("1.1.1.1, 203.0.113.7, 10.0.0.2", 2);
+        assert_eq!(ip, Some("203.0.113.7".parse().unwrap()));
+    }
+
+    #[test]
+    fn too_few_entries_or_invalid_address_is_rejected() {
+        assert_eq!(forwarded_client("203.0.113.7", 2), None);
+        assert_eq!(forwarded_client("1.1.1.1, not-an-ip", 1), None);
+    }
+}
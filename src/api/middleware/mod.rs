@@ -1 +1,4 @@
+pub mod api_key;
 pub mod auth;
+pub mod client_ip;
+pub mod permission;
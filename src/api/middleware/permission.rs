@@ -0,0 +1,94 @@
+// 引入標準庫中的零大小型別標記
+use std::marker::PhantomData;
+
+// 引入 Axum 框架的相關功能
+use axum::{
+    async_trait,
+    extract::FromRequestParts,
+    http::request::Parts,
+    response::{IntoResponse, Response},
+};
+
+use crate::api::middleware::auth::{AuthUser, TwoFactorVerified};
+use crate::api::routes::AppState;
+use crate::domain::auth::model::{Permission, User};
+use crate::utils::error::AppError;
+
+/// 權限標記
+/// 每個權限對應一個零大小的標記型別，讓處理程序在簽名中宣告所需權限
+pub trait RequiredPermission: Send + Sync {
+    /// 需要的權限
+    const PERMISSION: Permission;
+}
+
+/// 宣告權限標記型別
+macro_rules! permissions {
+    ($($name:ident),* $(,)?) => {
+        $(
+            #[doc = concat!("需要 `Permission::", stringify!($name), "` 權限")]
+            pub struct $name;
+
+            impl RequiredPermission for $name {
+                const PERMISSION: Permission = Permission::$name;
+            }
+        )*
+    };
+}
+
+/// 權限標記型別，名稱與 Permission 的成員相同
+pub mod perm {
+    use super::{Permission, RequiredPermission};
+
+    permissions!(
+        ManageEvents,
+        ManageComps,
+        SellTickets,
+        ScanAdmissions,
+        ManageOrders,
+        ViewReports,
+        ManageFinance,
+        ManageUsers,
+    );
+}
+
+/// 權限認證提取器
+/// 先驗證使用者身分，再檢查使用者的角色是否包含所需權限
+///
+/// # 範例
+/// ```ignore
+/// async fn handler(Authorized(user, ..): Authorized<perm::ManageEvents>) { ... }
+/// ```
+pub struct Authorized<P: RequiredPermission>(pub User, pub PhantomData<P>);
+
+#[async_trait]
+impl<S, P> FromRequestParts<S> for Authorized<P>
+where
+    S: Send + Sync,
+    P: RequiredPermission,
+{
+    // 如果提取失敗，返回的錯誤類型
+    type Rejection = Response;
+
+    /// 從 HTTP 請求部分中提取擁有指定權限的使用者
+    ///
+    /// # 返回值
+    /// 未認證時返回 401，缺少權限或必須兩步驟驗證的角色未以兩步驟驗證登入時返回 403
+    async fn from_request_parts(parts: &mut Parts, state: &S) -> Result<Self, Self::Rejection> {
+        let AuthUser(user) = AuthUser::from_request_parts(parts, state).await?;
+        if !user.has_permission(P::PERMISSION) {
+            return Err(AppError::Forbidden("權限不足".to_string()).into_response());
+        }
+
+        // 認證提取器已確認 AppState 存在
+        let two_factor = parts.extensions.get::<TwoFactorVerified>().is_some_and(|verified| verified.0);
+        let requires_two_factor = parts
+            .extensions
+            .get::<AppState>()
+            .is_some_and(|app_state| app_state.auth_service.requires_two_factor(&user));
+        if requires_two_factor && !two_factor {
+            return Err(AppError::Forbidden("此帳號必須啟用兩步驟驗證並以驗證碼登入".to_string()).into_response());
+        }
+
+        Ok(Self(user, PhantomData))
+    }
+}
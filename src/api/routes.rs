@@ -6,26 +6,95 @@ use axum::{
     // 引入 HTTP 請求方法路由功能
     // get 用於處理 GET 請求（獲取資源）
     // post 用於處理 POST 請求（創建資源）
-    routing::{get, post},
+    // put 用於處理 PUT 請求（更新資源）
+    // delete 用於處理 DELETE 請求（刪除資源）
+    routing::{delete, get, post, put},
     // Router 是 Axum 的核心組件，用於定義 API 路由
     Router,
+    // Extension 用於把狀態放進請求擴展，讓認證提取器可以取得服務
+    Extension,
 };
 
 // 引入我們自己定義的處理器函數
 use crate::api::handlers::{
+    // 加購商品相關處理器
+    addon_handler::{create_addon, list_concert_addons},
+    // 入場憑證相關處理器
+    admission_handler::{redeem_order_item, scan_admission},
+    // API 金鑰相關處理器
+    api_key_handler::{create_api_key, list_api_keys, revoke_api_key},
     // 認證相關處理器
-    auth_handler::{get_me, login, register},
+    auth_handler::{
+        activate_two_factor, change_email, change_password, confirm_email_change, disable_two_factor,
+        enroll_two_factor, forgot_password, get_me, jwks, login, logout, logout_all, oidc_authorize, oidc_callback,
+        refresh, regenerate_recovery_codes, register, resend_verification, reset_password, update_me, verify_email,
+        verify_two_factor,
+    },
+    // 售票處相關處理器
+    box_office_handler::{
+        close_drawer, create_box_office_order, get_current_drawer, get_drawer_report, open_drawer,
+    },
+    // 組合商品相關處理器
+    bundle_handler::{create_bundle, create_bundle_order, get_bundle, list_bundles},
+    // 招待票相關處理器
+    comp_handler::{get_guest_list, issue_comps},
     // 演唱會相關處理器
-    concert_handler::{create_concert, list_concerts},
+    concert_handler::{create_concert, create_concert_fee, list_concert_fees, list_concerts, update_concert_tax},
+    // 庫存池相關處理器
+    inventory_handler::{assign_ticket_pool, create_pool, list_pools, move_allocation, update_pool_capacity},
+    // 分錄相關處理器
+    ledger_handler::{get_reconciliation, list_order_entries},
     // 訂單相關處理器
-    order_handler::{create_order, get_order_by_id, list_orders},
+    order_handler::{create_order, get_order_by_id, list_all_orders, list_orders, refund_order},
+    // 主辦單位相關處理器
+    organization_handler::{
+        assign_concert_organization, create_organization, list_organizations, update_organization_commission,
+    },
+    // 金流相關處理器
+    payment_handler::payment_webhook,
+    // 個人資料相關處理器
+    privacy_handler::{
+        complete_erasure_request, create_erasure_request, delete_me, export_my_data, get_erasure_request,
+        list_erasure_requests, reject_erasure_request,
+    },
+    // 優惠碼相關處理器
+    promo_code_handler::{create_promo_code, list_promo_codes},
+    // 報表相關處理器
+    report_handler::{get_cancellation_report, get_sales_report, get_sales_time_series, get_top_buyers, refresh_reports},
+    // 座位相關處理器
+    seating_handler::{
+        configure_concert_seating, get_concert_seats, get_seat_map, import_seat_map, import_seat_map_csv,
+        list_seat_maps,
+    },
+    // 結算相關處理器
+    settlement_handler::{get_settlement, lock_settlement, pay_settlement},
     // 票券相關處理器
-    ticket_handler::{create_ticket, list_tickets},
+    ticket_handler::{create_ticket, get_ticket_pricing, list_tickets, set_ticket_pricing},
+    // 用戶角色相關處理器
+    user_handler::{
+        get_user_roles, grant_user_role, list_organization_members, revoke_user_role, set_user_organization,
+        unlock_ip, unlock_user,
+    },
 };
 // 引入應用服務
+use crate::application::addon::service::AddonService;
+use crate::application::admission::service::AdmissionService;
+use crate::application::api_key::service::ApiKeyService;
+use crate::application::auth::oidc::OidcService;
 use crate::application::auth::service::AuthService;
+use crate::application::box_office::service::BoxOfficeService;
+use crate::application::bundle::service::BundleService;
+use crate::application::comp::service::CompService;
 use crate::application::concert::service::ConcertService;
+use crate::application::inventory::service::InventoryService;
+use crate::application::ledger::service::LedgerService;
 use crate::application::order::service::OrderService;
+use crate::application::organization::service::OrganizationService;
+use crate::application::privacy::service::PrivacyService;
+use crate::application::promo_code::service::PromoCodeService;
+use crate::application::report::service::ReportService;
+use crate::application::seating::service::SeatingService;
+use crate::application::settlement::service::SettlementService;
 use crate::application::ticket::service::TicketService;
 
 // 定義應用程式狀態類型
@@ -35,40 +104,54 @@ use crate::application::ticket::service::TicketService;
 pub struct AppState {
     // 認證服務，處理用戶登錄、註冊等功能
     pub auth_service: Arc<AuthService>,
+    // 社群登入服務，處理 OpenID Connect 授權碼流程與帳號連結
+    pub oidc_service: Arc<OidcService>,
     // 演唱會服務，處理演唱會相關邏輯
     pub concert_service: Arc<ConcertService>,
     // 票券服務，處理票券相關邏輯
     pub ticket_service: Arc<TicketService>,
     // 訂單服務，處理訂單相關邏輯
     pub order_service: Arc<OrderService>,
+    // 分錄服務，處理對帳與分錄查詢
+    pub ledger_service: Arc<LedgerService>,
+    // 主辦單位服務，處理主辦單位與抽成比例
+    pub organization_service: Arc<OrganizationService>,
+    // 結算服務，處理主辦單位結算與撥款
+    pub settlement_service: Arc<SettlementService>,
+    // 報表服務，處理銷售分析報表
+    pub report_service: Arc<ReportService>,
+    // 座位服務，處理座位圖、價區與座位可售狀態
+    pub seating_service: Arc<SeatingService>,
+    // 庫存服務，處理共用庫存池與票種配額
+    pub inventory_service: Arc<InventoryService>,
+    // 組合商品服務，處理組合商品目錄
+    pub bundle_service: Arc<BundleService>,
+    // 入場憑證服務，處理入口驗票
+    pub admission_service: Arc<AdmissionService>,
+    // 加購商品服務，處理加購商品與現場領取
+    pub addon_service: Arc<AddonService>,
+    // 優惠碼服務，處理演唱會優惠碼
+    pub promo_code_service: Arc<PromoCodeService>,
+    // 招待票服務，處理招待票與公關名單
+    pub comp_service: Arc<CompService>,
+    // 售票處服務，處理現場售票與錢櫃班次
+    pub box_office_service: Arc<BoxOfficeService>,
+    // API 金鑰服務，處理合作夥伴金鑰的管理與驗證
+    pub api_key_service: Arc<ApiKeyService>,
+    // 個人資料服務，處理個人資料匯出、帳號刪除與刪除申請
+    pub privacy_service: Arc<PrivacyService>,
 }
 
 /// 創建 API 路由
 /// 這個函數定義了所有 API 端點及其對應的處理器函數
 /// 
 /// # 參數
-/// * `auth_service` - 認證服務的引用
-/// * `concert_service` - 演唱會服務的引用
-/// * `ticket_service` - 票券服務的引用
-/// * `order_service` - 訂單服務的引用
+/// * `state` - 應用程式狀態，包含所有服務的引用
+///   這個狀態將被傳遞給所有處理器函數
 /// 
 /// # 返回值
 /// 返回配置好的 Axum Router 實例，包含所有 API 路由
-pub fn create_router(
-    auth_service: Arc<AuthService>,
-    concert_service: Arc<ConcertService>,
-    ticket_service: Arc<TicketService>,
-    order_service: Arc<OrderService>,
-) -> Router {
-    // 創建共享狀態
-    // 這個狀態將被傳遞給所有處理器函數
-    let state = AppState {
-        auth_service,
-        concert_service,
-        ticket_service,
-        order_service,
-    };
-    
+pub fn create_router(state: AppState) -> Router {
     // 創建新的路由器並定義所有 API 端點
     Router::new()
         // === 用戶認證 API ===
@@ -76,8 +159,57 @@ pub fn create_router(
         .route("/auth/register", post(register))
         // 用戶登錄端點：接收 POST 請求，驗證用戶憑證並返回 JWT 令牌
         .route("/auth/login", post(login))
-        // 獲取當前用戶信息端點：接收 GET 請求，返回已認證用戶的詳細信息
-        .route("/auth/me", get(get_me))
+        // 兩步驟驗證登入端點：接收 POST 請求，以挑戰令牌與驗證碼換取登入令牌
+        .route("/auth/2fa/verify", post(verify_two_factor))
+        // 兩步驟驗證設定端點（需要認證）：
+        // - 開始設定並取得 QR Code
+        // - 以驗證碼確認啟用並取得救援碼
+        // - 重新產生救援碼
+        // - 停用兩步驟驗證
+        .route("/auth/2fa/enroll", post(enroll_two_factor))
+        .route("/auth/2fa/activate", post(activate_two_factor))
+        .route("/auth/2fa/recovery-codes", post(regenerate_recovery_codes))
+        .route("/auth/2fa/disable", post(disable_two_factor))
+        // 換發令牌端點：接收 POST 請求，以刷新令牌換發新的存取令牌與刷新令牌
+        .route("/auth/refresh", post(refresh))
+        // 登出端點：接收 POST 請求，撤銷刷新令牌與一起簽發的存取令牌
+        .route("/auth/logout", post(logout))
+        // 登出所有裝置端點：接收 POST 請求，撤銷用戶所有的令牌
+        .route("/auth/logout-all", post(logout_all))
+        // 驗證電子郵件端點：接收 POST 請求，以驗證信中的令牌完成驗證
+        .route("/auth/verify-email", post(verify_email))
+        // 重新寄送驗證信端點：接收 POST 請求，寄出新的驗證信給已認證用戶
+        .route("/auth/verify-email/resend", post(resend_verification))
+        // 忘記密碼端點：接收 POST 請求，寄出重設密碼信
+        .route("/auth/forgot-password", post(forgot_password))
+        // 重設密碼端點：接收 POST 請求，以重設密碼信中的令牌設定新密碼
+        .route("/auth/reset-password", post(reset_password))
+        // 社群登入端點：
+        // - 重新導向到身分提供者的授權頁面
+        // - 身分提供者回呼，完成登入並返回令牌
+        .route("/auth/oidc/:provider/authorize", get(oidc_authorize))
+        .route("/auth/oidc/:provider/callback", get(oidc_callback))
+        // 當前用戶端點：
+        // - GET 請求返回已認證用戶的個人資料
+        // - PATCH 請求修改個人資料
+        .route("/auth/me",
+            get(get_me)
+            .patch(update_me)
+        )
+        // 變更電子郵件端點：接收 POST 請求，驗證目前的密碼後寄出確認信到新的電子郵件
+        .route("/auth/me/email", post(change_email))
+        // 確認變更電子郵件端點：接收 POST 請求，以確認信中的令牌完成變更
+        .route("/auth/me/email/confirm", post(confirm_email_change))
+        // 變更密碼端點：接收 POST 請求，驗證目前的密碼後設定新密碼並撤銷所有令牌
+        .route("/auth/me/password", post(change_password))
+        // 驗證金鑰端點：接收 GET 請求，返回 JWKS 供其他服務驗證令牌
+        .route("/.well-known/jwks.json", get(jwks))
+
+        // === 個人資料 API ===
+        // 個人資料匯出端點（需要認證）：GET 請求返回個人資料、訂單、入場憑證與登入記錄，format=zip 時下載 ZIP
+        .route("/me/export", get(export_my_data))
+        // 刪除帳號端點（需要認證）：DELETE 請求匿名化個人資料，訂單與會計記錄保留
+        .route("/me", delete(delete_me))
         
         // === 音樂會 API ===
         // 演唱會端點：
@@ -87,6 +219,17 @@ pub fn create_router(
             get(list_concerts)
             .post(create_concert)
         )
+        // 演唱會服務費端點：
+        // - GET 請求獲取演唱會的服務費列表
+        // - POST 請求新增服務費（需要管理員權限）
+        .route("/concerts/:concert_id/fees",
+            get(list_concert_fees)
+            .post(create_concert_fee)
+        )
+        // 演唱會稅率端點：PUT 請求更新稅率設定（需要管理員權限）
+        .route("/concerts/:concert_id/tax", put(update_concert_tax))
+        // 演唱會座位端點：GET 請求獲取座位圖與每個座位的可售狀態
+        .route("/concerts/:concert_id/seats", get(get_concert_seats))
         
         // === 票券 API ===
         // 票券端點：
@@ -96,6 +239,13 @@ pub fn create_router(
             get(list_tickets)
             .post(create_ticket)
         )
+        // 票種定價端點（需要管理員權限）：
+        // - GET 請求獲取價格階梯、需求加價規則與目前售價
+        // - PUT 請求整組取代價格階梯與需求加價規則
+        .route("/admin/tickets/:ticket_id/pricing",
+            get(get_ticket_pricing)
+            .put(set_ticket_pricing)
+        )
         
         // === 訂單 API ===
         // 訂單端點：
@@ -108,6 +258,177 @@ pub fn create_router(
         // 獲取特定訂單詳情端點：接收 GET 請求，返回指定 ID 的訂單詳情
         // :order_id 是路徑參數，表示訂單的唯一標識符
         .route("/orders/:order_id", get(get_order_by_id))
+        // 訂單退款端點：接收 POST 請求，退款並釋放庫存（需要管理員權限）
+        .route("/orders/:order_id/refund", post(refund_order))
+        // 所有訂單端點：GET 請求獲取租戶範圍內所有用戶的訂單（需要訂單管理權限或 orders:read API 金鑰）
+        .route("/admin/orders", get(list_all_orders))
+        // 購買組合商品端點：一併扣減組合與所有組合內容的庫存
+        .route("/orders/bundle", post(create_bundle_order))
+        
+        // === 售票處 API（需要售票權限） ===
+        // 現場售票端點：POST 請求代客下單並記錄現金或刷卡收款
+        .route("/box-office/orders", post(create_box_office_order))
+        // 開啟錢櫃班次端點：POST 請求登記零用金
+        .route("/box-office/drawers", post(open_drawer))
+        // 目前班次端點：GET 請求獲取目前班次的即時對帳報告
+        .route("/box-office/drawers/current", get(get_current_drawer))
+        // 班次對帳報告端點
+        .route("/box-office/drawers/:session_id", get(get_drawer_report))
+        // 結束班次端點：POST 請求登記點算現金並回傳交班對帳報告
+        .route("/box-office/drawers/:session_id/close", post(close_drawer))
+        
+        // === 組合商品 API ===
+        // 組合商品列表端點：GET 請求獲取所有組合商品與組合內容
+        .route("/bundles", get(list_bundles))
+        // 組合商品詳情端點
+        .route("/bundles/:bundle_id", get(get_bundle))
+        // 創建組合商品端點（需要管理員權限）
+        .route("/admin/bundles", post(create_bundle))
+        
+        // === 加購商品 API ===
+        // 演唱會加購商品列表端點：GET 請求獲取加購商品、規格庫存與限定票種
+        .route("/concerts/:concert_id/addons", get(list_concert_addons))
+        // 創建加購商品端點（需要管理員權限）
+        .route("/admin/concerts/:concert_id/addons", post(create_addon))
+        
+        // === 優惠碼 API（需要管理員權限） ===
+        // 演唱會優惠碼端點：GET 請求獲取優惠碼與使用次數，POST 請求創建優惠碼
+        .route("/admin/concerts/:concert_id/promo-codes", 
+            get(list_promo_codes)
+            .post(create_promo_code)
+        )
+        
+        // === 招待票 API（需要管理員權限） ===
+        // 批次發出招待票端點：POST 請求依來賓 email 清單發出免費票與入場憑證
+        .route("/admin/concerts/:concert_id/comps", post(issue_comps))
+        // 公關名單端點：GET 請求獲取來賓、張數與入場狀態，format=csv 時下載 CSV
+        .route("/admin/concerts/:concert_id/guest-list", get(get_guest_list))
+        
+        // === 入場憑證 API（需要管理員權限） ===
+        // 驗票端點：POST 請求掃描入場憑證代碼並標記為已使用
+        .route("/admin/admissions/scan", post(scan_admission))
+        // 加購商品領取端點：POST 請求掃描領取代碼並標記為已領取
+        .route("/admin/admissions/pickup", post(redeem_order_item))
+        
+        // === 金流 API ===
+        // 金流 Webhook 端點：接收金流供應商的付款結果通知
+        .route("/payments/webhook", post(payment_webhook))
+        
+        // === 分錄 API（需要管理員權限） ===
+        // 對帳端點：回報借貸不平衡的傳票與金額不一致的訂單
+        .route("/admin/ledger/reconciliation", get(get_reconciliation))
+        // 訂單分錄端點：列出指定訂單的所有分錄
+        .route("/admin/ledger/orders/:order_id", get(list_order_entries))
+        
+        // === 結算 API（需要管理員權限） ===
+        // 主辦單位端點：
+        // - GET 請求獲取所有主辦單位
+        // - POST 請求創建新主辦單位
+        .route("/admin/organizations",
+            get(list_organizations)
+            .post(create_organization)
+        )
+        // 主辦單位抽成端點：PUT 請求更新抽成比例
+        .route("/admin/organizations/:organization_id/commission", put(update_organization_commission))
+        // 演唱會主辦單位端點：PUT 請求指定演唱會的主辦單位
+        .route("/admin/concerts/:concert_id/organization", put(assign_concert_organization))
+        // 結算單端點：GET 請求獲取結算單（?format=csv 下載 CSV）
+        .route("/admin/settlements/:concert_id", get(get_settlement))
+        // 鎖定結算端點：POST 請求鎖定結算金額
+        .route("/admin/settlements/:concert_id/lock", post(lock_settlement))
+        // 撥款端點：POST 請求標記已撥款並記錄分錄
+        .route("/admin/settlements/:concert_id/pay", post(pay_settlement))
+        
+        // === 座位 API（需要管理員權限） ===
+        // 座位圖端點：
+        // - GET 請求獲取所有座位圖
+        // - POST 請求以 JSON 匯入座位圖
+        .route("/admin/seat-maps",
+            get(list_seat_maps)
+            .post(import_seat_map)
+        )
+        // CSV 匯入端點：POST 請求以 CSV 匯入座位圖
+        .route("/admin/seat-maps/csv", post(import_seat_map_csv))
+        // 座位圖詳情端點：GET 請求獲取區、排、座位
+        .route("/admin/seat-maps/:seat_map_id", get(get_seat_map))
+        // 演唱會座位設定端點：PUT 請求設定座位圖與價區
+        .route("/admin/concerts/:concert_id/seating", put(configure_concert_seating))
+        
+        // === 庫存池 API（需要管理員權限） ===
+        // 演唱會庫存池端點：
+        // - GET 請求獲取庫存池與所屬票種
+        // - POST 請求創建庫存池
+        .route("/admin/concerts/:concert_id/pools",
+            get(list_pools)
+            .post(create_pool)
+        )
+        // 庫存池容量端點：PUT 請求調整容量
+        .route("/admin/pools/:pool_id/capacity", put(update_pool_capacity))
+        // 票種庫存池端點：PUT 請求加入或移出庫存池
+        .route("/admin/tickets/:ticket_id/pool", put(assign_ticket_pool))
+        // 配額移轉端點：POST 請求將未售出配額移到同場演唱會的另一個票種
+        .route("/admin/tickets/allocations", post(move_allocation))
+        
+        // === 報表 API（需要管理員權限） ===
+        // 支援 from、to 日期區間與 concert_id 過濾，?format=csv 下載 CSV
+        // 票種銷售與售出率端點
+        .route("/admin/reports/sales", get(get_sales_report))
+        // 銷售時間序列端點：?granularity=hour|day
+        .route("/admin/reports/timeseries", get(get_sales_time_series))
+        // 購買者排行端點：?limit=N
+        .route("/admin/reports/top-buyers", get(get_top_buyers))
+        // 取消率端點
+        .route("/admin/reports/cancellations", get(get_cancellation_report))
+        // 立即重新整理報表物化視圖
+        .route("/admin/reports/refresh", post(refresh_reports))
+        
+        // === 用戶角色 API（需要用戶管理權限） ===
+        // 用戶角色端點：
+        // - GET 請求獲取用戶的角色與指派記錄
+        // - POST 請求指派角色
+        .route("/admin/users/:user_id/roles",
+            get(get_user_roles)
+            .post(grant_user_role)
+        )
+        // 移除角色端點：DELETE 請求移除用戶的指定角色，立即生效
+        .route("/admin/users/:user_id/roles/:role", delete(revoke_user_role))
+        // 所屬主辦單位端點：PUT 請求設定用戶所屬的主辦單位，null 為平台工作人員
+        .route("/admin/users/:user_id/organization", put(set_user_organization))
+        // 主辦單位工作人員端點：GET 請求列出隸屬主辦單位的用戶與角色
+        .route("/admin/organizations/:organization_id/members", get(list_organization_members))
+        // 登入鎖定端點：DELETE 請求解除用戶或來源 IP 的登入鎖定
+        .route("/admin/users/:user_id/lockout", delete(unlock_user))
+        .route("/admin/ip-lockouts/:ip", delete(unlock_ip))
+        
+        // === API 金鑰 API（需要用戶管理權限） ===
+        // API 金鑰端點：
+        // - GET 請求列出 API 金鑰
+        // - POST 請求創建 API 金鑰，完整金鑰只返回一次
+        .route("/admin/api-keys",
+            get(list_api_keys)
+            .post(create_api_key)
+        )
+        // 撤銷 API 金鑰端點：DELETE 請求撤銷 API 金鑰，立即生效
+        .route("/admin/api-keys/:api_key_id", delete(revoke_api_key))
+
+        // === 刪除申請 API（需要用戶管理權限） ===
+        // 刪除申請端點：
+        // - GET 請求依狀態列出刪除申請，預設只列出待處理的申請
+        // - POST 請求代用戶受理刪除申請
+        .route("/admin/erasure-requests",
+            get(list_erasure_requests)
+            .post(create_erasure_request)
+        )
+        // 刪除申請詳情端點：GET 請求返回刪除申請與稽核記錄
+        .route("/admin/erasure-requests/:request_id", get(get_erasure_request))
+        // 完成刪除申請端點：POST 請求匿名化用戶
+        .route("/admin/erasure-requests/:request_id/complete", post(complete_erasure_request))
+        // 拒絕刪除申請端點：POST 請求拒絕刪除申請，必須說明原因
+        .route("/admin/erasure-requests/:request_id/reject", post(reject_erasure_request))
+        
+        // === 添加請求擴展 ===
+        // 認證提取器（AuthUser、Authorized、Scoped）從請求擴展中讀取 AppState
+        .layer(Extension(state.clone()))
         
         // === 添加應用狀態 ===
         // 將創建的狀態附加到路由器
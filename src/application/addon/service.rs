@@ -0,0 +1,84 @@
+use std::sync::Arc;
+use uuid::Uuid;
+use validator::Validate;
+
+use crate::domain::addon::model::{Addon, CreateAddon, RedeemOrderItem};
+use crate::domain::addon::repository::AddonRepository;
+use crate::domain::concert::repository::ConcertRepository;
+use crate::domain::order::model::{OrderItem, OrderItemStatus};
+use crate::domain::organization::model::Tenant;
+use crate::domain::ticket::repository::TicketRepository;
+use crate::utils::error::AppError;
+
+/// 加購商品服務
+pub struct AddonService {
+    addon_repository: Arc<dyn AddonRepository>,
+    concert_repository: Arc<dyn ConcertRepository>,
+    ticket_repository: Arc<dyn TicketRepository>,
+}
+
+impl AddonService {
+    /// 創建新的加購商品服務實例
+    pub fn new(
+        addon_repository: Arc<dyn AddonRepository>,
+        concert_repository: Arc<dyn ConcertRepository>,
+        ticket_repository: Arc<dyn TicketRepository>,
+    ) -> Self {
+        Self {
+            addon_repository,
+            concert_repository,
+            ticket_repository,
+        }
+    }
+
+    /// 獲取演唱會的加購商品
+    pub async fn get_concert_addons(&self, concert_id: Uuid) -> Result<Vec<Addon>, AppError> {
+        self.concert_repository.find_by_id(concert_id, Tenant::Platform).await?
+            .ok_or_else(|| AppError::NotFound(format!("找不到 ID 為 {} 的演唱會", concert_id)))?;
+
+        self.addon_repository.find_by_concert_id(concert_id, Tenant::Platform).await
+    }
+
+    /// 創建新加購商品
+    /// 限定的票種必須屬於同一場演唱會
+    pub async fn create_addon(&self, concert_id: Uuid, input: CreateAddon, tenant: Tenant) -> Result<Addon, AppError> {
+        input.validate().map_err(|e| AppError::BadRequest(e.to_string()))?;
+
+        self.concert_repository.find_by_id(concert_id, tenant).await?
+            .ok_or_else(|| AppError::NotFound(format!("找不到 ID 為 {} 的演唱會", concert_id)))?;
+        for ticket_id in &input.ticket_ids {
+            let ticket = self.ticket_repository.find_by_id(*ticket_id, tenant).await?
+                .ok_or_else(|| AppError::NotFound(format!("找不到 ID 為 {} 的票券", ticket_id)))?;
+            if ticket.concert_id != concert_id {
+                return Err(AppError::BadRequest(format!("票種 {} 不屬於此演唱會", ticket.ticket_type)));
+            }
+        }
+
+        self.addon_repository.create(concert_id, &input, tenant).await
+    }
+
+    /// 兌換加購商品
+    /// 可領取的明細標記為已領取；待付款、已領取或已作廢的明細回傳 Conflict
+    /// 其他主辦單位的明細視為不存在
+    pub async fn redeem(&self, input: RedeemOrderItem, tenant: Tenant) -> Result<OrderItem, AppError> {
+        input.validate().map_err(|e| AppError::BadRequest(e.to_string()))?;
+        let code = input.code.trim().to_uppercase();
+
+        if let Some(item) = self.addon_repository.redeem_item(&code, tenant).await? {
+            return Ok(item);
+        }
+
+        let item = self.addon_repository.find_item_by_code(&code, tenant).await?
+            .ok_or_else(|| AppError::NotFound(format!("找不到代碼為 {} 的加購商品", code)))?;
+        match item.status {
+            OrderItemStatus::Redeemed => Err(AppError::Conflict(format!(
+                "加購商品已於 {} 領取",
+                item.redeemed_at.map(|redeemed_at| redeemed_at.format("%Y-%m-%d %H:%M:%S").to_string()).unwrap_or_default()
+            ))),
+            OrderItemStatus::Pending => Err(AppError::Conflict("訂單尚未付款".to_string())),
+            OrderItemStatus::Void => Err(AppError::Conflict("加購商品已作廢".to_string())),
+            // 與其他兌換同時發生時可能剛好被標記為已領取
+            OrderItemStatus::Valid => Err(AppError::Conflict("加購商品正在被其他裝置兌換".to_string())),
+        }
+    }
+}
@@ -0,0 +1,43 @@
+use std::sync::Arc;
+use validator::Validate;
+
+use crate::domain::admission::model::{Admission, AdmissionStatus, ScanAdmission};
+use crate::domain::admission::repository::AdmissionRepository;
+use crate::domain::organization::model::Tenant;
+use crate::utils::error::AppError;
+
+/// 入場憑證服務
+pub struct AdmissionService {
+    admission_repository: Arc<dyn AdmissionRepository>,
+}
+
+impl AdmissionService {
+    /// 創建新的入場憑證服務實例
+    pub fn new(admission_repository: Arc<dyn AdmissionRepository>) -> Self {
+        Self { admission_repository }
+    }
+
+    /// 掃描入場憑證
+    /// 可入場的憑證標記為已使用；已使用或已作廢的憑證回傳 Conflict
+    /// 其他主辦單位的憑證視為不存在
+    pub async fn scan(&self, input: ScanAdmission, tenant: Tenant) -> Result<Admission, AppError> {
+        input.validate().map_err(|e| AppError::BadRequest(e.to_string()))?;
+        let code = input.code.trim().to_uppercase();
+
+        if let Some(admission) = self.admission_repository.mark_used(&code, tenant).await? {
+            return Ok(admission);
+        }
+
+        let admission = self.admission_repository.find_by_code(&code, tenant).await?
+            .ok_or_else(|| AppError::NotFound(format!("找不到代碼為 {} 的入場憑證", code)))?;
+        match admission.status {
+            AdmissionStatus::Used => Err(AppError::Conflict(format!(
+                "入場憑證已於 {} 使用",
+                admission.used_at.map(|used_at| used_at.format("%Y-%m-%d %H:%M:%S").to_string()).unwrap_or_default()
+            ))),
+            AdmissionStatus::Void => Err(AppError::Conflict("入場憑證已作廢".to_string())),
+            // 與其他掃描同時發生時可能剛好被標記為已使用
+            AdmissionStatus::Valid => Err(AppError::Conflict("入場憑證正在被其他裝置掃描".to_string())),
+        }
+    }
+}
@@ -0,0 +1,105 @@
+use std::collections::HashMap;
+use std::sync::{Mutex, MutexGuard};
+use std::time::{Duration, Instant};
+
+use uuid::Uuid;
+
+/// 計數期間
+const WINDOW: Duration = Duration::from_secs(60);
+
+/// 單一金鑰在目前計數期間的呼叫次數
+struct Window {
+    started_at: Instant,
+    count: i32,
+}
+
+/// API 金鑰呼叫頻率限制
+/// 以每分鐘的固定計數期間計算；計數只存在單一實例的記憶體中，多個實例時每個實例各自計算
+#[derive(Default)]
+pub struct RateLimiter {
+    windows: Mutex<HashMap<Uuid, Window>>,
+}
+
+impl RateLimiter {
+    /// 創建新的呼叫頻率限制
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// 記錄一次呼叫，超過每分鐘上限時返回距離下一個計數期間的秒數
+    pub fn acquire(&self, key_id: Uuid, limit_per_minute: i32) -> Result<(), u64> {
+        self.acquire_at(key_id, limit_per_minute, Instant::now())
+    }
+
+    fn acquire_at(&self, key_id: Uuid, limit_per_minute: i32, now: Instant) -> Result<(), u64> {
+        let mut windows = self.windows();
+
+        // 順便清除已結束的計數期間，避免記憶體無限成長
+        if windows.len() > 1024 {
+            windows.retain(|_, window| now.duration_since(window.started_at) < WINDOW);
+        }
+
+        let window = windows.entry(key_id).or_insert(Window { started_at: now, count: 0 });
+        if now.duration_since(window.started_at) >= WINDOW {
+            window.started_at = now;
+            window.count = 0;
+        }
+        if window.count >= limit_per_minute {
+            let retry_after = WINDOW.saturating_sub(now.duration_since(window.started_at));
+            return Err(retry_after.as_secs().max(1));
+        }
+
+        window.count += 1;
+        Ok(())
+    }
+
+    fn windows(&self) -> MutexGuard<'_, HashMap<Uuid, Window>> {
+        // 持有鎖的期間不會 panic，鎖中毒時沿用內部資料即可
+        self.windows.lock().unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn calls_beyond_limit_are_rejected_until_window_ends() {
+        let limiter = RateLimiter::new();
+        let key_id = Uuid::new_v4();
+        let start = Instant::now();
+
+        for _ in 0..3 {
+            assert_eq!(limiter.acquire_at(key_id, 3, start), Ok(()));
+        }
+        assert_eq!(limiter.acquire_at(key_id, 3, start), Err(60));
+        assert_eq!(limiter.acquire_at(key_id, 3, start + Duration::from_secs(45)), Err(15));
+        // 不足一秒時至少等待一秒
+        assert_eq!(limiter.acquire_at(key_id, 3, start + Duration::from_millis(59_500)), Err(1));
+    }
+
+    #[test]
+    fn limit_refills_when_next_window_starts() {
+        let limiter = RateLimiter::new();
+        let key_id = Uuid::new_v4();
+        let start = Instant::now();
+
+        assert_eq!(limiter.acquire_at(key_id, 1, start), Ok(()));
+        assert!(limiter.acquire_at(key_id, 1, start + Duration::from_secs(30)).is_err());
+
+        let next = start + WINDOW;
+        assert_eq!(limiter.acquire_at(key_id, 1, next), Ok(()));
+        assert!(limiter.acquire_at(key_id, 1, next).is_err());
+    }
+
+    #[test]
+    fn keys_are_limited_independently() {
+        let limiter = RateLimiter::new();
+        let start = Instant::now();
+        let (first, second) = (Uuid::new_v4(), Uuid::new_v4());
+
+        assert_eq!(limiter.acquire_at(first, 1, start), Ok(()));
+        assert!(limiter.acquire_at(first, 1, start).is_err());
+        assert_eq!(limiter.acquire_at(second, 1, start), Ok(()));
+    }
+}
@@ -0,0 +1,101 @@
+use std::sync::Arc;
+
+use argon2::password_hash::rand_core::{OsRng, RngCore};
+use chrono::Duration;
+use uuid::Uuid;
+use validator::Validate;
+
+use crate::application::api_key::rate_limit::RateLimiter;
+use crate::domain::api_key::model::{ApiKey, CreateApiKey, CreatedApiKey, NewApiKey};
+use crate::domain::api_key::repository::ApiKeyRepository;
+use crate::domain::organization::model::Tenant;
+use crate::infrastructure::security::token;
+use crate::utils::error::AppError;
+
+/// 金鑰前綴，方便在日誌與程式碼中辨識外洩的金鑰
+const KEY_PREFIX: &str = "tsk_";
+
+/// 預設有效天數
+const DEFAULT_EXPIRES_IN_DAYS: i64 = 90;
+
+/// 預設每分鐘可呼叫次數
+const DEFAULT_RATE_LIMIT_PER_MINUTE: i32 = 60;
+
+/// API 金鑰服務
+pub struct ApiKeyService {
+    api_key_repository: Arc<dyn ApiKeyRepository>,
+    rate_limiter: RateLimiter,
+}
+
+impl ApiKeyService {
+    /// 創建新的 API 金鑰服務實例
+    pub fn new(api_key_repository: Arc<dyn ApiKeyRepository>) -> Self {
+        Self {
+            api_key_repository,
+            rate_limiter: RateLimiter::new(),
+        }
+    }
+
+    /// 創建 API 金鑰，完整金鑰只在此時返回一次
+    /// 金鑰格式為 tsk_{8 位可見前綴}_{64 位隨機值}，資料庫只保存前綴與整把金鑰的雜湊；
+    /// 主辦單位範圍的用戶只能創建限於自己主辦單位的金鑰
+    pub async fn create(&self, input: CreateApiKey, created_by: Uuid, tenant: Tenant) -> Result<CreatedApiKey, AppError> {
+        input.validate().map_err(|e| AppError::BadRequest(e.to_string()))?;
+
+        let organization_id = match tenant {
+            Tenant::Platform => input.organization_id,
+            Tenant::Organization(id) => Some(id),
+        };
+
+        let mut prefix_bytes = [0u8; 4];
+        OsRng.fill_bytes(&mut prefix_bytes);
+        let prefix = format!("{}{}", KEY_PREFIX, hex::encode(prefix_bytes));
+        let key = format!("{}_{}", prefix, token::generate_token());
+
+        let mut scopes = input.scopes;
+        scopes.sort_by_key(|scope| scope.as_str());
+        scopes.dedup();
+
+        let api_key = self.api_key_repository.create(&NewApiKey {
+            name: input.name.trim().to_string(),
+            prefix,
+            key_hash: token::hash_token(&key),
+            scopes,
+            organization_id,
+            rate_limit_per_minute: input.rate_limit_per_minute.unwrap_or(DEFAULT_RATE_LIMIT_PER_MINUTE),
+            ttl: Duration::days(input.expires_in_days.unwrap_or(DEFAULT_EXPIRES_IN_DAYS)),
+            created_by,
+        }).await?;
+
+        tracing::info!("用戶 {} 創建 API 金鑰 {}（{}）", created_by, api_key.prefix, api_key.name);
+        Ok(CreatedApiKey { key, api_key })
+    }
+
+    /// 列出租戶範圍內的 API 金鑰
+    pub async fn list(&self, tenant: Tenant) -> Result<Vec<ApiKey>, AppError> {
+        self.api_key_repository.list(tenant).await
+    }
+
+    /// 撤銷 API 金鑰，立即生效
+    pub async fn revoke(&self, id: Uuid, tenant: Tenant) -> Result<(), AppError> {
+        if !self.api_key_repository.revoke(id, tenant).await? {
+            return Err(AppError::NotFound(format!("找不到 ID 為 {} 的有效 API 金鑰", id)));
+        }
+        tracing::info!("已撤銷 API 金鑰 {}", id);
+        Ok(())
+    }
+
+    /// 驗證 API 金鑰並計入呼叫次數
+    /// 金鑰不存在、已撤銷或已過期時返回 401，超過每分鐘上限時返回 429
+    pub async fn authenticate(&self, key: &str) -> Result<ApiKey, AppError> {
+        let api_key = self.api_key_repository.find_active_by_hash(&token::hash_token(key)).await?
+            .ok_or_else(|| AppError::Unauthorized("無效的 API 金鑰".to_string()))?;
+
+        if let Err(retry_after) = self.rate_limiter.acquire(api_key.id, api_key.rate_limit_per_minute) {
+            return Err(AppError::TooManyRequests(format!("已超過每分鐘呼叫上限，請 {} 秒後再試", retry_after)));
+        }
+
+        self.api_key_repository.touch(api_key.id).await?;
+        Ok(api_key)
+    }
+}
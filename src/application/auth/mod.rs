@@ -1 +1,5 @@
+pub mod oidc;
+pub mod password_policy;
 pub mod service;
+pub mod throttle;
+pub mod two_factor;
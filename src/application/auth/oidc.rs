@@ -0,0 +1,148 @@
+use std::collections::HashMap;
+use std::net::IpAddr;
+use std::sync::Arc;
+
+use chrono::Duration;
+
+use crate::application::auth::service::AuthService;
+use crate::config::OidcProviderConfig;
+use crate::domain::auth::model::{ExternalIdentity, LoginMethod, LoginOutcome, OidcCallbackQuery, OidcLoginState, User};
+use crate::domain::auth::repository::{IdentityRepository, UserRepository};
+use crate::infrastructure::oidc::OidcClient;
+use crate::infrastructure::security::token;
+use crate::utils::error::AppError;
+
+/// 授權碼流程的有效期（分鐘），用戶需在此時間內完成身分提供者的登入
+const LOGIN_STATE_TTL_MINUTES: i64 = 10;
+
+/// 社群登入服務
+/// 以 OpenID Connect 授權碼流程驗證外部身分，再連結或創建本地用戶
+pub struct OidcService {
+    identity_repository: Arc<dyn IdentityRepository>,
+    user_repository: Arc<dyn UserRepository>,
+    auth_service: Arc<AuthService>,
+    providers: HashMap<String, OidcClient>,
+    /// 回呼網址的前綴
+    public_base_url: String,
+}
+
+impl OidcService {
+    /// 創建新的社群登入服務實例
+    pub fn new(
+        identity_repository: Arc<dyn IdentityRepository>,
+        user_repository: Arc<dyn UserRepository>,
+        auth_service: Arc<AuthService>,
+        providers: Vec<OidcProviderConfig>,
+        public_base_url: String,
+    ) -> Self {
+        Self {
+            identity_repository,
+            user_repository,
+            auth_service,
+            providers: providers
+                .into_iter()
+                .map(|config| (config.name.clone(), OidcClient::new(config)))
+                .collect(),
+            public_base_url: public_base_url.trim_end_matches('/').to_string(),
+        }
+    }
+
+    /// 開始社群登入，返回身分提供者的授權網址
+    /// state、PKCE 驗證碼與 nonce 皆為一次性隨機值，state 只以雜湊保存
+    pub async fn authorize(&self, provider: &str, login_hint: Option<&str>) -> Result<String, AppError> {
+        let client = self.client(provider)?;
+
+        let state = token::generate_token();
+        let login_state = OidcLoginState {
+            code_verifier: token::generate_token(),
+            nonce: token::generate_token(),
+        };
+
+        let url = client.authorization_url(&self.redirect_uri(provider), &state, &login_state, login_hint).await?;
+        self.identity_repository
+            .save_login_state(&token::hash_token(&state), provider, &login_state, Duration::minutes(LOGIN_STATE_TTL_MINUTES))
+            .await?;
+
+        Ok(url)
+    }
+
+    /// 處理身分提供者的回呼，完成登入
+    /// 已啟用兩步驟驗證的帳號與密碼登入相同，需再以驗證碼換取令牌
+    pub async fn callback(
+        &self,
+        provider: &str,
+        query: OidcCallbackQuery,
+        client_ip: Option<IpAddr>,
+    ) -> Result<LoginOutcome, AppError> {
+        let client = self.client(provider)?;
+
+        if let Some(error) = query.error {
+            return Err(AppError::Unauthorized(format!("身分提供者拒絕登入: {}", error)));
+        }
+        let (Some(code), Some(state)) = (query.code, query.state) else {
+            return Err(AppError::BadRequest("缺少授權碼或 state".to_string()));
+        };
+
+        let login_state = self
+            .identity_repository
+            .take_login_state(&token::hash_token(&state), provider)
+            .await?
+            .ok_or_else(|| AppError::Unauthorized("登入請求無效或已過期，請重新登入".to_string()))?;
+
+        let identity = client.exchange_code(&code, &self.redirect_uri(provider), &login_state).await?;
+        let user = self.resolve_user(&identity).await?;
+
+        self.auth_service
+            .complete_login(&user, LoginMethod::Social(provider.to_string()), client_ip)
+            .await
+    }
+
+    /// 找出外部身分對應的本地用戶
+    /// 已連結的身分直接登入；否則以身分提供者驗證過的電子郵件連結既有用戶，沒有則創建新用戶
+    async fn resolve_user(&self, identity: &ExternalIdentity) -> Result<User, AppError> {
+        if let Some(user_id) = self.identity_repository.find_user_id(&identity.provider, &identity.subject).await? {
+            return self.user_repository.find_by_id(user_id).await?
+                .ok_or_else(|| AppError::Unauthorized("用戶不存在".to_string()));
+        }
+
+        // 未經驗證的電子郵件可能屬於他人，不可用來連結帳號
+        let email = match &identity.email {
+            Some(email) if identity.email_verified => email,
+            _ => return Err(AppError::BadRequest("身分提供者未提供已驗證的電子郵件".to_string())),
+        };
+
+        let user_id = match self.user_repository.find_by_email(email).await? {
+            Some(user) => {
+                // 本地帳號的電子郵件未驗證時，可能是他人搶先以此地址註冊，連結後會讓對方取得帳號
+                if user.email_verified_at.is_none() {
+                    return Err(AppError::Conflict(
+                        "此電子郵件已註冊但尚未驗證，請先驗證電子郵件或重設密碼".to_string(),
+                    ));
+                }
+                self.identity_repository.link(user.id, identity).await?;
+                tracing::info!("用戶 {} 已連結 {} 帳號", user.id, identity.provider);
+                user.id
+            }
+            None => {
+                let user_id = self.identity_repository.create_user(email, identity).await?;
+                tracing::info!("以 {} 帳號創建用戶 {}", identity.provider, user_id);
+                user_id
+            }
+        };
+
+        self.user_repository.find_by_id(user_id).await?
+            .ok_or_else(|| AppError::Unauthorized("用戶不存在".to_string()))
+    }
+
+    /// 依名稱取得身分提供者
+    fn client(&self, provider: &str) -> Result<&OidcClient, AppError> {
+        self.providers
+            .get(provider)
+            .ok_or_else(|| AppError::NotFound(format!("未設定的身分提供者: {}", provider)))
+    }
+
+    /// 向身分提供者註冊的回呼網址
+    fn redirect_uri(&self, provider: &str) -> String {
+        format!("{}/auth/oidc/{}/callback", self.public_base_url, provider)
+    }
+}
@@ -0,0 +1,69 @@
+use std::sync::Arc;
+
+use crate::infrastructure::security::breached_passwords::{self, BreachedPasswordSource};
+use crate::infrastructure::security::password::PasswordHashing;
+use crate::utils::error::AppError;
+
+/// 密碼長度上限（字元），避免以超長密碼消耗雜湊資源
+const MAX_PASSWORD_LENGTH: usize = 128;
+
+/// 密碼政策
+/// 設定新密碼時檢查長度並比對外洩密碼清單，通過後才雜湊；
+/// 登入時驗證密碼，並在既有雜湊的參數弱於目前設定時重新雜湊
+pub struct PasswordPolicy {
+    hashing: PasswordHashing,
+    /// 最短長度（字元）
+    min_length: usize,
+    /// 外洩密碼清單，未設定時不檢查
+    breached_passwords: Option<Arc<dyn BreachedPasswordSource>>,
+}
+
+impl PasswordPolicy {
+    /// 創建新的密碼政策實例
+    pub fn new(
+        hashing: PasswordHashing,
+        min_length: usize,
+        breached_passwords: Option<Arc<dyn BreachedPasswordSource>>,
+    ) -> Self {
+        Self { hashing, min_length, breached_passwords }
+    }
+
+    /// 檢查新密碼是否符合政策
+    pub async fn check(&self, password: &str) -> Result<(), AppError> {
+        let length = password.chars().count();
+        if length < self.min_length {
+            return Err(AppError::BadRequest(format!("密碼至少需要 {} 個字元", self.min_length)));
+        }
+        if length > MAX_PASSWORD_LENGTH {
+            return Err(AppError::BadRequest(format!("密碼不可超過 {} 個字元", MAX_PASSWORD_LENGTH)));
+        }
+
+        if let Some(source) = &self.breached_passwords
+            && breached_passwords::is_breached(source.as_ref(), password).await?
+        {
+            return Err(AppError::BadRequest("此密碼曾出現在外洩的密碼清單中，請改用其他密碼".to_string()));
+        }
+
+        Ok(())
+    }
+
+    /// 檢查新密碼並返回雜湊
+    pub async fn hash_new_password(&self, password: &str) -> Result<String, AppError> {
+        self.check(password).await?;
+        self.hashing.hash(password)
+    }
+
+    /// 驗證密碼
+    pub fn verify(&self, password: &str, hash: &str) -> Result<bool, AppError> {
+        self.hashing.verify(password, hash)
+    }
+
+    /// 已通過驗證的密碼若以較弱的參數雜湊，返回以目前參數重新計算的雜湊
+    /// 只在登入成功後呼叫，不檢查政策，舊密碼不符合新政策時仍可登入
+    pub fn rehash_if_needed(&self, password: &str, hash: &str) -> Result<Option<String>, AppError> {
+        if !self.hashing.needs_rehash(hash) {
+            return Ok(None);
+        }
+        self.hashing.hash(password).map(Some)
+    }
+}
@@ -1,103 +1,686 @@
+use std::net::IpAddr;
 use std::sync::Arc;
 
 use chrono::{Duration, Utc};
-use jsonwebtoken::{encode, EncodingKey, Header};
+use uuid::Uuid;
+use validator::Validate;
 
-use crate::domain::auth::model::{Claims, LoginInput, LoginResponse, RegisterInput, User};
-use crate::domain::auth::repository::UserRepository;
-use crate::infrastructure::security::password;
+use crate::application::auth::password_policy::PasswordPolicy;
+use crate::application::auth::throttle::LoginThrottle;
+use crate::application::auth::two_factor::TwoFactorAuth;
+use crate::domain::auth::model::{
+    ChangeEmailInput, ChangePasswordInput, Claims, ConfirmEmailChangeInput, ForgotPasswordInput, JwkSet, LoginInput,
+    LoginMethod, LoginOutcome, LoginResponse, NewRefreshToken, RecoveryCodes, RefreshTokenInput, RegisterInput,
+    ResetPasswordInput, Role, TwoFactorChallenge, TwoFactorCodeInput, TwoFactorEnrollment, TwoFactorLoginInput,
+    UpdateProfile, User, UserProfile, UserRoles, UserTokenPurpose, VerifyEmailInput,
+};
+use crate::domain::auth::repository::{LoginEventRepository, TokenRepository, UserRepository};
+use crate::domain::organization::model::SetUserOrganization;
+use crate::infrastructure::mail::{Email, Mailer};
+use crate::infrastructure::security::jwt::JwtKeys;
+use crate::infrastructure::security::token;
 use crate::utils::error::AppError;
 
+/// 電子郵件驗證令牌有效期（小時）
+const VERIFY_EMAIL_TTL_HOURS: i64 = 24;
+
+/// 重設密碼令牌有效期（小時）
+const RESET_PASSWORD_TTL_HOURS: i64 = 1;
+
+/// 變更電子郵件確認令牌有效期（小時）
+const CHANGE_EMAIL_TTL_HOURS: i64 = 24;
+
+/// 兩步驟驗證挑戰令牌有效期（分鐘）
+const LOGIN_CHALLENGE_TTL_MINUTES: i64 = 5;
+
 /// 認證服務
 pub struct AuthService {
     user_repository: Arc<dyn UserRepository>,
-    jwt_secret: String,
+    token_repository: Arc<dyn TokenRepository>,
+    login_event_repository: Arc<dyn LoginEventRepository>,
+    jwt_keys: JwtKeys,
+    password_policy: Arc<PasswordPolicy>,
+    login_throttle: LoginThrottle,
+    two_factor: TwoFactorAuth,
+    mailer: Arc<dyn Mailer>,
+    /// 郵件連結的網址前綴
+    public_base_url: String,
+    access_token_ttl: Duration,
+    refresh_token_ttl: Duration,
 }
 
 impl AuthService {
     /// 創建新的認證服務實例
-    pub fn new(user_repository: Arc<dyn UserRepository>, jwt_secret: String) -> Self {
+    #[allow(clippy::too_many_arguments)]
+    pub fn new(
+        user_repository: Arc<dyn UserRepository>,
+        token_repository: Arc<dyn TokenRepository>,
+        login_event_repository: Arc<dyn LoginEventRepository>,
+        jwt_keys: JwtKeys,
+        password_policy: Arc<PasswordPolicy>,
+        login_throttle: LoginThrottle,
+        two_factor: TwoFactorAuth,
+        mailer: Arc<dyn Mailer>,
+        public_base_url: String,
+        access_token_ttl_secs: i64,
+        refresh_token_ttl_days: i64,
+    ) -> Self {
         Self {
             user_repository,
-            jwt_secret,
+            token_repository,
+            login_event_repository,
+            jwt_keys,
+            password_policy,
+            login_throttle,
+            two_factor,
+            mailer,
+            public_base_url: public_base_url.trim_end_matches('/').to_string(),
+            access_token_ttl: Duration::seconds(access_token_ttl_secs),
+            refresh_token_ttl: Duration::days(refresh_token_ttl_days),
         }
     }
 
-    /// 註冊新用戶
+    /// 註冊新用戶，並寄出電子郵件驗證信
     pub async fn register(&self, input: RegisterInput) -> Result<(), AppError> {
+        input.validate().map_err(|e| AppError::BadRequest(e.to_string()))?;
+
         // 檢查電子郵件是否已存在
         if self.user_repository.email_exists(&input.email).await? {
             return Err(AppError::Conflict("電子郵件已存在".to_string()));
         }
 
-        // 雜湊密碼
-        let password_hash = password::hash_password(&input.password)?;
+        // 檢查密碼政策並雜湊密碼
+        let password_hash = self.password_policy.hash_new_password(&input.password).await?;
 
         // 創建用戶
-        self.user_repository.create(&input, &password_hash).await?;
+        let user = self.user_repository.create(&input, &password_hash).await?;
+
+        // 寄信失敗不影響註冊，用戶可稍後重新寄送驗證信
+        if let Err(e) = self.send_verification_email(&user).await {
+            tracing::error!("寄送驗證信給用戶 {} 失敗: {}", user.id, e);
+        }
 
         Ok(())
     }
 
-    /// 用戶登入
-    pub async fn login(&self, input: LoginInput) -> Result<LoginResponse, AppError> {
-        // 查找用戶
-        let user = self.user_repository.find_by_email(&input.email).await?
-            .ok_or_else(|| AppError::Unauthorized("無效的憑證".to_string()))?;
+    /// 驗證電子郵件
+    pub async fn verify_email(&self, input: VerifyEmailInput) -> Result<(), AppError> {
+        let user_id = self.token_repository
+            .consume_user_token(UserTokenPurpose::VerifyEmail, &token::hash_token(&input.token)).await?
+            .ok_or_else(|| AppError::BadRequest("驗證連結無效或已過期".to_string()))?;
+
+        self.user_repository.set_email_verified(user_id).await?;
+        tracing::info!("用戶 {} 已驗證電子郵件", user_id);
+        Ok(())
+    }
+
+    /// 重新寄送電子郵件驗證信，先前寄出的驗證連結隨即失效
+    pub async fn resend_verification(&self, user: &User) -> Result<(), AppError> {
+        if user.email_verified_at.is_some() {
+            return Err(AppError::BadRequest("電子郵件已驗證".to_string()));
+        }
+        self.send_verification_email(user).await
+    }
+
+    /// 忘記密碼：寄出重設密碼信
+    /// 不論電子郵件是否已註冊都回傳成功，避免被用來探測帳號
+    pub async fn forgot_password(&self, input: ForgotPasswordInput) -> Result<(), AppError> {
+        let Some(user) = self.user_repository.find_by_email(&input.email).await? else {
+            return Ok(());
+        };
+
+        let ttl = Duration::hours(RESET_PASSWORD_TTL_HOURS);
+        let reset_token = self.create_user_token(&user, UserTokenPurpose::ResetPassword, ttl).await?;
+        let email = Email {
+            to: user.email.clone(),
+            subject: "重設密碼".to_string(),
+            body: format!(
+                "請在 {} 小時內開啟以下連結重設密碼：\n{}/reset-password?token={}\n\n如果您沒有申請重設密碼，請忽略這封郵件。",
+                RESET_PASSWORD_TTL_HOURS, self.public_base_url, reset_token,
+            ),
+        };
+        // 寄信失敗同樣回傳成功，以免回應差異洩漏帳號是否存在
+        if let Err(e) = self.mailer.send(&email).await {
+            tracing::error!("寄送重設密碼信給用戶 {} 失敗: {}", user.id, e);
+        }
+
+        Ok(())
+    }
+
+    /// 以重設密碼信中的令牌設定新密碼
+    /// 重設後撤銷所有已簽發的令牌，其他裝置需以新密碼重新登入
+    pub async fn reset_password(&self, input: ResetPasswordInput) -> Result<(), AppError> {
+        let user_id = self.token_repository
+            .consume_user_token(UserTokenPurpose::ResetPassword, &token::hash_token(&input.token)).await?
+            .ok_or_else(|| AppError::BadRequest("重設連結無效或已過期".to_string()))?;
+
+        let password_hash = self.password_policy.hash_new_password(&input.new_password).await?;
+        self.user_repository.update_password(user_id, &password_hash).await?;
+        self.token_repository.revoke_user(user_id).await?;
+
+        // 能收到重設密碼信即代表擁有該信箱
+        self.user_repository.set_email_verified(user_id).await?;
+
+        tracing::info!("用戶 {} 已重設密碼", user_id);
+        Ok(())
+    }
+
+    /// 獲取用戶的個人資料
+    pub async fn profile(&self, user: &User) -> Result<UserProfile, AppError> {
+        self.user_repository.find_profile(user.id).await?
+            .ok_or_else(|| AppError::NotFound("用戶不存在".to_string()))
+    }
+
+    /// 修改個人資料，返回修改後的個人資料
+    pub async fn update_profile(&self, user: &User, input: UpdateProfile) -> Result<UserProfile, AppError> {
+        input.validate().map_err(|e| AppError::BadRequest(e.to_string()))?;
+        if let Some(Some(date_of_birth)) = input.date_of_birth {
+            let today = chrono::Local::now().date_naive();
+            if date_of_birth > today || today.years_since(date_of_birth).is_none_or(|age| age > 150) {
+                return Err(AppError::BadRequest("無效的生日".to_string()));
+            }
+        }
+
+        let mut details = self.profile(user).await?.details();
+        input.apply(&mut details);
+        self.user_repository.update_profile(user.id, &details).await?;
+
+        self.profile(user).await
+    }
+
+    /// 申請變更電子郵件，需提交目前的密碼
+    /// 確認信寄到新的電子郵件，確認前仍以原本的電子郵件登入；同時通知原本的電子郵件
+    pub async fn request_email_change(
+        &self,
+        user: &User,
+        input: ChangeEmailInput,
+        client_ip: Option<IpAddr>,
+    ) -> Result<(), AppError> {
+        input.validate().map_err(|e| AppError::BadRequest(e.to_string()))?;
+        self.check_current_password(user, &input.current_password, client_ip).await?;
+
+        if input.new_email == user.email {
+            return Err(AppError::BadRequest("新的電子郵件與目前的相同".to_string()));
+        }
+        if self.user_repository.email_exists(&input.new_email).await? {
+            return Err(AppError::Conflict("電子郵件已存在".to_string()));
+        }
+
+        self.user_repository.set_pending_email(user.id, &input.new_email).await?;
+        let ttl = Duration::hours(CHANGE_EMAIL_TTL_HOURS);
+        let confirm_token = self.create_user_token(user, UserTokenPurpose::ChangeEmail, ttl).await?;
+        self.mailer.send(&Email {
+            to: input.new_email.clone(),
+            subject: "確認變更電子郵件".to_string(),
+            body: format!(
+                "請在 {} 小時內開啟以下連結，確認以此電子郵件作為帳號的登入信箱：\n{}/confirm-email-change?token={}",
+                CHANGE_EMAIL_TTL_HOURS, self.public_base_url, confirm_token,
+            ),
+        }).await?;
+
+        self.send_notice(user, "帳號電子郵件變更申請", &format!(
+            "您的帳號申請將電子郵件變更為 {}，確認後將無法再以此信箱登入。\n如果不是您本人操作，請立即重設密碼。",
+            input.new_email,
+        )).await;
+
+        tracing::info!("用戶 {} 申請變更電子郵件", user.id);
+        Ok(())
+    }
+
+    /// 以寄到新電子郵件的令牌確認變更
+    pub async fn confirm_email_change(&self, input: ConfirmEmailChangeInput) -> Result<(), AppError> {
+        let user_id = self.token_repository
+            .consume_user_token(UserTokenPurpose::ChangeEmail, &token::hash_token(&input.token)).await?
+            .ok_or_else(|| AppError::BadRequest("確認連結無效或已過期".to_string()))?;
+
+        self.user_repository.confirm_email_change(user_id).await?
+            .ok_or_else(|| AppError::BadRequest("確認連結無效或已過期".to_string()))?;
+
+        tracing::info!("用戶 {} 已變更電子郵件", user_id);
+        Ok(())
+    }
+
+    /// 變更密碼，需提交目前的密碼
+    /// 變更後撤銷所有已簽發的令牌，包括目前的登入，所有裝置需以新密碼重新登入
+    pub async fn change_password(
+        &self,
+        user: &User,
+        input: ChangePasswordInput,
+        client_ip: Option<IpAddr>,
+    ) -> Result<(), AppError> {
+        self.check_current_password(user, &input.current_password, client_ip).await?;
+        if input.new_password == input.current_password {
+            return Err(AppError::BadRequest("新密碼不可與目前的密碼相同".to_string()));
+        }
+
+        let password_hash = self.password_policy.hash_new_password(&input.new_password).await?;
+        self.user_repository.update_password(user.id, &password_hash).await?;
+        self.token_repository.revoke_user(user.id).await?;
+
+        self.send_notice(user, "密碼已變更", "您帳號的密碼已變更，所有裝置都已登出。\n如果不是您本人操作，請立即重設密碼。").await;
 
-        // 驗證密碼
-        if !password::verify_password(&input.password, &user.password_hash)? {
+        tracing::info!("用戶 {} 已變更密碼", user.id);
+        Ok(())
+    }
+
+    /// 用戶登入
+    /// 帳號或來源 IP 連續登入失敗達到門檻後暫時鎖定；不論帳號不存在、密碼錯誤或鎖定中，回應都相同。
+    /// 已啟用兩步驟驗證的帳號返回挑戰令牌，需再以驗證碼換取登入令牌
+    pub async fn login(&self, input: LoginInput, client_ip: Option<IpAddr>) -> Result<LoginOutcome, AppError> {
+        // 鎖定期間不驗證密碼，避免持續猜測
+        if self.login_throttle.is_locked(&input.email, client_ip).await? {
             return Err(AppError::Unauthorized("無效的憑證".to_string()));
         }
 
-        // 生成 JWT
-        let token = self.generate_token(&user)?;
+        // 查找用戶並驗證密碼
+        let user = match self.user_repository.find_by_email(&input.email).await? {
+            Some(user) if self.verify_user_password(&user, &input.password)? => user,
+            _ => {
+                self.login_throttle.record_failure(&input.email, client_ip).await?;
+                return Err(AppError::Unauthorized("無效的憑證".to_string()));
+            }
+        };
+        self.login_throttle.record_success(&input.email).await?;
+        self.upgrade_password_hash(&user, &input.password).await;
 
-        Ok(LoginResponse {
-            token,
-            is_admin: user.is_admin,
-        })
+        self.complete_login(&user, LoginMethod::Password, client_ip).await
+    }
+
+    /// 完成第一階段驗證後的登入
+    /// 已啟用兩步驟驗證的帳號返回挑戰令牌，否則直接簽發令牌；密碼登入與社群登入共用
+    pub async fn complete_login(
+        &self,
+        user: &User,
+        method: LoginMethod,
+        client_ip: Option<IpAddr>,
+    ) -> Result<LoginOutcome, AppError> {
+        if self.two_factor.is_enabled(user.id).await? {
+            let ttl = Duration::minutes(LOGIN_CHALLENGE_TTL_MINUTES);
+            let challenge_token = self.create_user_token(user, UserTokenPurpose::LoginChallenge, ttl).await?;
+            return Ok(LoginOutcome::TwoFactorRequired(TwoFactorChallenge {
+                two_factor_required: true,
+                challenge_token,
+                expires_in: ttl.num_seconds(),
+            }));
+        }
+
+        Ok(LoginOutcome::Tokens(self.start_session(user, false, method, client_ip).await?))
     }
 
-    /// 驗證 JWT 並獲取用戶
-    pub async fn get_user_from_token(&self, token: &str) -> Result<User, AppError> {
-        // 解析 JWT 獲取用戶 ID
-        let claims = jsonwebtoken::decode::<Claims>(
-            token,
-            &jsonwebtoken::DecodingKey::from_secret(self.jwt_secret.as_bytes()),
-            &jsonwebtoken::Validation::default(),
-        )
-        .map_err(|_| AppError::Unauthorized("無效的令牌".to_string()))?
-        .claims;
+    /// 以兩步驟驗證碼完成登入
+    /// 驗證碼錯誤計入登入失敗次數，挑戰令牌在有效期內可重試，成功後即失效
+    pub async fn verify_two_factor_login(
+        &self,
+        input: TwoFactorLoginInput,
+        client_ip: Option<IpAddr>,
+    ) -> Result<LoginResponse, AppError> {
+        let challenge_hash = token::hash_token(&input.challenge_token);
+        let user_id = self.token_repository.find_user_token(UserTokenPurpose::LoginChallenge, &challenge_hash).await?
+            .ok_or_else(|| AppError::Unauthorized("登入驗證已過期，請重新登入".to_string()))?;
+        let user = self.user_repository.find_by_id(user_id).await?
+            .ok_or_else(|| AppError::Unauthorized("用戶不存在".to_string()))?;
+
+        self.check_second_factor(&user, &input.code, client_ip).await?;
+
+        // 同一個挑戰令牌同時提交時只有一方成功
+        if self.token_repository.consume_user_token(UserTokenPurpose::LoginChallenge, &challenge_hash).await?.is_none() {
+            return Err(AppError::Unauthorized("登入驗證已過期，請重新登入".to_string()));
+        }
+
+        self.start_session(&user, true, LoginMethod::TwoFactor, client_ip).await
+    }
+
+    /// 開始設定兩步驟驗證，返回密鑰、設定網址與 QR Code
+    pub async fn enroll_two_factor(&self, user: &User) -> Result<TwoFactorEnrollment, AppError> {
+        self.two_factor.enroll(user).await
+    }
+
+    /// 以驗證碼確認並啟用兩步驟驗證，返回救援碼
+    /// 啟用前簽發的令牌未經兩步驟驗證，需重新登入才能使用必須兩步驟驗證的權限
+    pub async fn activate_two_factor(&self, user: &User, input: TwoFactorCodeInput) -> Result<RecoveryCodes, AppError> {
+        let codes = self.two_factor.activate(user.id, &input.code).await?;
+        tracing::info!("用戶 {} 已啟用兩步驟驗證", user.id);
+        Ok(codes)
+    }
+
+    /// 重新產生救援碼，需提交目前的驗證碼
+    pub async fn regenerate_recovery_codes(
+        &self,
+        user: &User,
+        input: TwoFactorCodeInput,
+        client_ip: Option<IpAddr>,
+    ) -> Result<RecoveryCodes, AppError> {
+        self.check_second_factor(user, &input.code, client_ip).await?;
+        self.two_factor.regenerate_recovery_codes(user.id).await
+    }
+
+    /// 停用兩步驟驗證，需提交目前的驗證碼；角色必須啟用兩步驟驗證的用戶不可停用
+    pub async fn disable_two_factor(
+        &self,
+        user: &User,
+        input: TwoFactorCodeInput,
+        client_ip: Option<IpAddr>,
+    ) -> Result<(), AppError> {
+        if self.two_factor.is_required(user) {
+            return Err(AppError::BadRequest("您的角色必須啟用兩步驟驗證".to_string()));
+        }
+        self.check_second_factor(user, &input.code, client_ip).await?;
+
+        self.two_factor.disable(user.id).await?;
+        tracing::info!("用戶 {} 已停用兩步驟驗證", user.id);
+        Ok(())
+    }
+
+    /// 用戶的角色是否必須以兩步驟驗證登入
+    pub fn requires_two_factor(&self, user: &User) -> bool {
+        self.two_factor.is_required(user)
+    }
+
+    /// 以刷新令牌換發新的一組令牌
+    /// 刷新令牌只能使用一次；已使用或已撤銷的令牌再次出現代表可能外洩，撤銷整個 family
+    pub async fn refresh(&self, input: RefreshTokenInput) -> Result<LoginResponse, AppError> {
+        let stored = self.token_repository.find_refresh_token(&token::hash_token(&input.refresh_token)).await?
+            .ok_or_else(|| AppError::Unauthorized("無效的刷新令牌".to_string()))?;
+
+        if stored.used_at.is_some() || stored.revoked_at.is_some() {
+            return self.reject_reuse(stored.user_id, stored.family_id).await;
+        }
+        if stored.expired {
+            return Err(AppError::Unauthorized("刷新令牌已過期".to_string()));
+        }
+        // 與其他請求同時換發同一個令牌時只有一方成功，另一方視同重複使用
+        if !self.token_repository.consume_refresh_token(stored.id).await? {
+            return self.reject_reuse(stored.user_id, stored.family_id).await;
+        }
+
+        // 角色以資料庫目前的狀態為準，換發後新指派的角色即生效；是否通過兩步驟驗證沿用原本的登入
+        let user = self.user_repository.find_by_id(stored.user_id).await?
+            .ok_or_else(|| AppError::Unauthorized("用戶不存在".to_string()))?;
+        self.issue_tokens(&user, stored.family_id, stored.two_factor).await
+    }
+
+    /// 登出：撤銷刷新令牌所屬的 family 與一起簽發的存取令牌
+    /// 令牌不存在或已撤銷時同樣視為成功
+    pub async fn logout(&self, input: RefreshTokenInput) -> Result<(), AppError> {
+        if let Some(stored) = self.token_repository.find_refresh_token(&token::hash_token(&input.refresh_token)).await? {
+            self.token_repository.revoke_family(stored.family_id).await?;
+        }
+        Ok(())
+    }
+
+    /// 登出所有裝置：撤銷用戶所有的刷新令牌與尚未過期的存取令牌
+    pub async fn logout_all(&self, user: &User) -> Result<(), AppError> {
+        self.token_repository.revoke_user(user.id).await?;
+        tracing::info!("用戶 {} 已登出所有裝置", user.id);
+        Ok(())
+    }
+
+    /// 刪除帳號前結束用戶的所有登入：撤銷所有令牌並清除帳號的登入失敗記錄
+    pub async fn end_all_sessions(&self, user: &User) -> Result<(), AppError> {
+        self.token_repository.revoke_user(user.id).await?;
+        self.login_throttle.unlock_account(&user.email).await
+    }
+
+    /// 驗證 JWT 並獲取用戶，同時返回令牌是否經過兩步驟驗證簽發
+    pub async fn get_user_from_token(&self, token: &str) -> Result<(User, bool), AppError> {
+        // 依令牌標頭的 kid 選用公鑰驗證並解析 JWT
+        let claims: Claims = self.jwt_keys.verify(token)?;
+
+        // 檢查令牌是否已登出撤銷
+        let jti = Uuid::parse_str(&claims.jti)
+            .map_err(|_| AppError::Unauthorized("無效的令牌".to_string()))?;
+        if self.token_repository.is_revoked(jti).await? {
+            return Err(AppError::Unauthorized("令牌已撤銷".to_string()));
+        }
 
         // 查找用戶
-        let user_id = uuid::Uuid::parse_str(&claims.sub)
+        let user_id = Uuid::parse_str(&claims.sub)
             .map_err(|_| AppError::Unauthorized("無效的令牌".to_string()))?;
 
-        let user = self.user_repository.find_by_id(user_id).await?
+        let mut user = self.user_repository.find_by_id(user_id).await?
             .ok_or_else(|| AppError::Unauthorized("用戶不存在".to_string()))?;
 
-        Ok(user)
+        // 只採用令牌中、且資料庫目前仍擁有的角色：
+        // 移除角色立即生效，新指派的角色需重新登入取得新令牌
+        user.roles.retain(|role| claims.roles.contains(role));
+
+        Ok((user, claims.amr.iter().any(|method| method == "otp")))
+    }
+
+    /// 獲取用戶的角色指派記錄
+    pub async fn get_user_roles(&self, user_id: Uuid) -> Result<UserRoles, AppError> {
+        self.user_repository.find_roles(user_id).await?
+            .ok_or_else(|| AppError::NotFound(format!("找不到 ID 為 {} 的用戶", user_id)))
+    }
+
+    /// 指派角色
+    /// 主辦單位角色只能指派給已隸屬主辦單位的用戶
+    pub async fn grant_role(&self, actor: &User, user_id: Uuid, role: Role) -> Result<UserRoles, AppError> {
+        let current = self.get_user_roles(user_id).await?;
+        if role == Role::Organizer && current.organization_id.is_none() {
+            return Err(AppError::BadRequest("請先設定用戶所屬的主辦單位".to_string()));
+        }
+        if !self.user_repository.grant_role(user_id, role, actor.id).await? {
+            return Err(AppError::Conflict(format!("用戶已擁有 {} 角色", role.as_str())));
+        }
+        tracing::info!("用戶 {} 指派 {} 角色給用戶 {}", actor.id, role.as_str(), user_id);
+
+        self.get_user_roles(user_id).await
+    }
+
+    /// 移除角色
+    /// 管理員不可移除自己的管理員角色，避免平台失去所有管理員
+    pub async fn revoke_role(&self, actor: &User, user_id: Uuid, role: Role) -> Result<UserRoles, AppError> {
+        if actor.id == user_id && role == Role::Admin {
+            return Err(AppError::BadRequest("不可移除自己的管理員角色".to_string()));
+        }
+
+        self.get_user_roles(user_id).await?;
+        if !self.user_repository.revoke_role(user_id, role).await? {
+            return Err(AppError::NotFound(format!("用戶沒有 {} 角色", role.as_str())));
+        }
+        tracing::info!("用戶 {} 移除用戶 {} 的 {} 角色", actor.id, user_id, role.as_str());
+
+        self.get_user_roles(user_id).await
+    }
+
+    /// 設定用戶所屬主辦單位
+    /// 隸屬主辦單位的工作人員只能存取該主辦單位的資料；擁有主辦單位角色的用戶不可移出主辦單位
+    pub async fn set_user_organization(
+        &self,
+        actor: &User,
+        user_id: Uuid,
+        input: SetUserOrganization,
+    ) -> Result<UserRoles, AppError> {
+        let current = self.get_user_roles(user_id).await?;
+        if input.organization_id.is_none() && current.roles.iter().any(|assignment| assignment.role == Role::Organizer) {
+            return Err(AppError::BadRequest("擁有主辦單位角色的用戶必須隸屬主辦單位".to_string()));
+        }
+
+        if !self.user_repository.set_organization(user_id, input.organization_id).await? {
+            return Err(AppError::NotFound(format!("找不到 ID 為 {} 的用戶", user_id)));
+        }
+        match input.organization_id {
+            Some(organization_id) => tracing::info!("用戶 {} 將用戶 {} 設定為主辦單位 {} 的工作人員", actor.id, user_id, organization_id),
+            None => tracing::info!("用戶 {} 將用戶 {} 移出主辦單位", actor.id, user_id),
+        }
+
+        self.get_user_roles(user_id).await
+    }
+
+    /// 寄出電子郵件驗證信
+    async fn send_verification_email(&self, user: &User) -> Result<(), AppError> {
+        let ttl = Duration::hours(VERIFY_EMAIL_TTL_HOURS);
+        let verify_token = self.create_user_token(user, UserTokenPurpose::VerifyEmail, ttl).await?;
+        self.mailer.send(&Email {
+            to: user.email.clone(),
+            subject: "驗證您的電子郵件".to_string(),
+            body: format!(
+                "請在 {} 小時內開啟以下連結完成電子郵件驗證：\n{}/verify-email?token={}",
+                VERIFY_EMAIL_TTL_HOURS, self.public_base_url, verify_token,
+            ),
+        }).await
+    }
+
+    /// 產生單次使用的用戶令牌，資料庫只保存雜湊，返回明文令牌
+    async fn create_user_token(&self, user: &User, purpose: UserTokenPurpose, ttl: Duration) -> Result<String, AppError> {
+        let user_token = token::generate_token();
+        self.token_repository
+            .create_user_token(user.id, purpose, &token::hash_token(&user_token), ttl)
+            .await?;
+        Ok(user_token)
+    }
+
+    /// 解除用戶的登入鎖定
+    pub async fn unlock_user(&self, actor: &User, user_id: Uuid) -> Result<(), AppError> {
+        let user = self.user_repository.find_by_id(user_id).await?
+            .ok_or_else(|| AppError::NotFound(format!("找不到 ID 為 {} 的用戶", user_id)))?;
+
+        self.login_throttle.unlock_account(&user.email).await?;
+        tracing::info!("用戶 {} 解除用戶 {} 的登入鎖定", actor.id, user_id);
+        Ok(())
+    }
+
+    /// 解除來源 IP 的登入鎖定
+    pub async fn unlock_ip(&self, actor: &User, ip: IpAddr) -> Result<(), AppError> {
+        self.login_throttle.unlock_ip(ip).await?;
+        tracing::info!("用戶 {} 解除 IP {} 的登入鎖定", actor.id, ip);
+        Ok(())
+    }
+
+    /// 驗證第二因素
+    /// 帳號鎖定中與驗證碼錯誤的回應相同，錯誤次數計入登入失敗
+    async fn check_second_factor(&self, user: &User, code: &str, client_ip: Option<IpAddr>) -> Result<(), AppError> {
+        if self.login_throttle.is_locked(&user.email, client_ip).await? {
+            return Err(AppError::Unauthorized("無效的驗證碼".to_string()));
+        }
+        if !self.two_factor.verify(user.id, code).await? {
+            self.login_throttle.record_failure(&user.email, client_ip).await?;
+            return Err(AppError::Unauthorized("無效的驗證碼".to_string()));
+        }
+        self.login_throttle.record_success(&user.email).await
+    }
+
+    /// 驗證目前的密碼，錯誤同樣計入登入失敗次數
+    pub async fn check_current_password(&self, user: &User, password: &str, client_ip: Option<IpAddr>) -> Result<(), AppError> {
+        if user.password_hash.is_none() {
+            return Err(AppError::BadRequest("尚未設定密碼，請先透過忘記密碼流程設定密碼".to_string()));
+        }
+        if self.login_throttle.is_locked(&user.email, client_ip).await? {
+            return Err(AppError::Unauthorized("目前的密碼錯誤".to_string()));
+        }
+        if !self.verify_user_password(user, password)? {
+            self.login_throttle.record_failure(&user.email, client_ip).await?;
+            return Err(AppError::Unauthorized("目前的密碼錯誤".to_string()));
+        }
+        self.login_throttle.record_success(&user.email).await
+    }
+
+    /// 寄送帳號安全通知，寄信失敗只記錄錯誤
+    async fn send_notice(&self, user: &User, subject: &str, body: &str) {
+        let email = Email {
+            to: user.email.clone(),
+            subject: subject.to_string(),
+            body: body.to_string(),
+        };
+        if let Err(e) = self.mailer.send(&email).await {
+            tracing::error!("寄送通知給用戶 {} 失敗: {}", user.id, e);
+        }
+    }
+
+    /// 登入成功，以新的刷新令牌 family 簽發令牌並記錄登入
+    /// 登入記錄寫入失敗只記錄錯誤，不影響登入
+    async fn start_session(
+        &self,
+        user: &User,
+        two_factor: bool,
+        method: LoginMethod,
+        client_ip: Option<IpAddr>,
+    ) -> Result<LoginResponse, AppError> {
+        let response = self.issue_tokens(user, Uuid::new_v4(), two_factor).await?;
+        if let Err(e) = self.login_event_repository.record(user.id, &method, client_ip).await {
+            tracing::error!("記錄用戶 {} 的登入失敗: {}", user.id, e);
+        }
+        Ok(response)
+    }
+
+    /// 簽發存取令牌與同一 family 的新刷新令牌
+    async fn issue_tokens(&self, user: &User, family_id: Uuid, two_factor: bool) -> Result<LoginResponse, AppError> {
+        let jti = Uuid::new_v4();
+        let access_token = self.generate_token(user, jti, two_factor)?;
+        let refresh_token = token::generate_token();
+
+        self.token_repository.create_refresh_token(&NewRefreshToken {
+            user_id: user.id,
+            family_id,
+            token_hash: token::hash_token(&refresh_token),
+            access_jti: jti,
+            access_ttl: self.access_token_ttl,
+            ttl: self.refresh_token_ttl,
+            two_factor,
+        }).await?;
+
+        Ok(LoginResponse {
+            token: access_token,
+            refresh_token,
+            expires_in: self.access_token_ttl.num_seconds(),
+            roles: user.roles.clone(),
+        })
+    }
+
+    /// 偵測到刷新令牌重複使用：撤銷整個 family 並回傳未認證錯誤
+    async fn reject_reuse(&self, user_id: Uuid, family_id: Uuid) -> Result<LoginResponse, AppError> {
+        tracing::warn!("用戶 {} 的刷新令牌被重複使用，撤銷令牌 family {}", user_id, family_id);
+        self.token_repository.revoke_family(family_id).await?;
+        Err(AppError::Unauthorized("刷新令牌已失效，請重新登入".to_string()))
     }
 
     /// 生成 JWT
-    fn generate_token(&self, user: &User) -> Result<String, AppError> {
+    fn generate_token(&self, user: &User, jti: Uuid, two_factor: bool) -> Result<String, AppError> {
         let expiration = Utc::now()
-            .checked_add_signed(Duration::hours(24))
+            .checked_add_signed(self.access_token_ttl)
             .expect("有效的時間戳")
             .timestamp() as usize;
 
         let claims = Claims {
             sub: user.id.to_string(),
-            admin: user.is_admin,
+            jti: jti.to_string(),
+            roles: user.roles.clone(),
+            amr: if two_factor { vec!["pwd".to_string(), "otp".to_string()] } else { vec!["pwd".to_string()] },
             exp: expiration,
         };
 
-        encode(
-            &Header::default(),
-            &claims,
-            &EncodingKey::from_secret(self.jwt_secret.as_bytes()),
-        )
-        .map_err(|_| AppError::Internal("無法生成令牌".to_string()))
+        self.jwt_keys.sign(&claims)
+    }
+
+    /// 公開的 JWT 驗證金鑰
+    pub fn jwks(&self) -> &JwkSet {
+        self.jwt_keys.jwks()
+    }
+
+    /// 驗證用戶密碼，只以社群帳號登入、沒有密碼的用戶一律驗證失敗
+    fn verify_user_password(&self, user: &User, password: &str) -> Result<bool, AppError> {
+        match &user.password_hash {
+            Some(password_hash) => self.password_policy.verify(password, password_hash),
+            None => Ok(false),
+        }
+    }
+
+    /// 登入成功後，若密碼雜湊的參數弱於目前設定則以新參數重新雜湊
+    /// 升級失敗只記錄錯誤，不影響登入，下次登入會再嘗試
+    async fn upgrade_password_hash(&self, user: &User, password: &str) {
+        let Some(password_hash) = &user.password_hash else {
+            return;
+        };
+        let result = match self.password_policy.rehash_if_needed(password, password_hash) {
+            Ok(Some(new_hash)) => self.user_repository.update_password(user.id, &new_hash).await.map(|_| true),
+            Ok(None) => Ok(false),
+            Err(e) => Err(e),
+        };
+        match result {
+            Ok(true) => tracing::info!("用戶 {} 的密碼雜湊已升級為目前的參數", user.id),
+            Ok(false) => {}
+            Err(e) => tracing::error!("升級用戶 {} 的密碼雜湊失敗: {}", user.id, e),
+        }
     }
 }
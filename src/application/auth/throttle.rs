@@ -0,0 +1,75 @@
+use std::net::IpAddr;
+use std::sync::Arc;
+
+use crate::domain::auth::model::{LockoutPolicy, LoginAttemptKey};
+use crate::domain::auth::repository::LoginAttemptRepository;
+use crate::utils::error::AppError;
+
+/// 登入防暴力破解
+/// 帳號與來源 IP 分別計算登入失敗次數，達到門檻後暫時鎖定，鎖定時間隨失敗次數指數延長。
+/// 鎖定期間不驗證密碼，回應與密碼錯誤相同，避免洩漏帳號是否存在或已被鎖定
+pub struct LoginThrottle {
+    repository: Arc<dyn LoginAttemptRepository>,
+    account_policy: LockoutPolicy,
+    ip_policy: LockoutPolicy,
+}
+
+impl LoginThrottle {
+    /// 創建新的登入防暴力破解實例
+    pub fn new(repository: Arc<dyn LoginAttemptRepository>, account_policy: LockoutPolicy, ip_policy: LockoutPolicy) -> Self {
+        Self { repository, account_policy, ip_policy }
+    }
+
+    /// 帳號或來源 IP 是否鎖定中
+    pub async fn is_locked(&self, email: &str, ip: Option<IpAddr>) -> Result<bool, AppError> {
+        for (key, _) in self.keys(email, ip) {
+            if self.is_key_locked(&key).await? {
+                tracing::warn!("{} 鎖定中，拒絕登入", key.as_key());
+                return Ok(true);
+            }
+        }
+        Ok(false)
+    }
+
+    /// 記錄一次登入失敗，達到門檻時鎖定
+    pub async fn record_failure(&self, email: &str, ip: Option<IpAddr>) -> Result<(), AppError> {
+        for (key, policy) in self.keys(email, ip) {
+            let key = key.as_key();
+            let failures = self.repository.record_failure(&key, policy.window).await?;
+            if let Some(lockout) = policy.lockout_for(failures) {
+                self.repository.lock(&key, lockout).await?;
+                tracing::warn!("{} 連續登入失敗 {} 次，鎖定 {} 秒", key, failures, lockout.num_seconds());
+            }
+        }
+        Ok(())
+    }
+
+    /// 登入成功，清除帳號的失敗次數
+    /// 來源 IP 的失敗次數不因登入成功而清除，避免以自己的帳號重置 IP 計數
+    pub async fn record_success(&self, email: &str) -> Result<(), AppError> {
+        self.repository.clear(&LoginAttemptKey::account(email).as_key()).await
+    }
+
+    /// 解除帳號鎖定
+    pub async fn unlock_account(&self, email: &str) -> Result<(), AppError> {
+        self.repository.clear(&LoginAttemptKey::account(email).as_key()).await
+    }
+
+    /// 解除來源 IP 鎖定
+    pub async fn unlock_ip(&self, ip: IpAddr) -> Result<(), AppError> {
+        self.repository.clear(&LoginAttemptKey::Ip(ip).as_key()).await
+    }
+
+    /// 本次登入要計數的對象與對應的鎖定策略
+    fn keys(&self, email: &str, ip: Option<IpAddr>) -> Vec<(LoginAttemptKey, LockoutPolicy)> {
+        let mut keys = vec![(LoginAttemptKey::account(email), self.account_policy)];
+        if let Some(ip) = ip {
+            keys.push((LoginAttemptKey::Ip(ip), self.ip_policy));
+        }
+        keys
+    }
+
+    async fn is_key_locked(&self, key: &LoginAttemptKey) -> Result<bool, AppError> {
+        self.repository.is_locked(&key.as_key()).await
+    }
+}
@@ -0,0 +1,198 @@
+use std::sync::Arc;
+
+use uuid::Uuid;
+
+use crate::domain::auth::model::{RecoveryCodes, Role, TwoFactorEnrollment, User};
+use crate::domain::auth::repository::TwoFactorRepository;
+use crate::infrastructure::security::{token, totp};
+use crate::utils::error::AppError;
+
+/// 每次產生的救援碼組數
+const RECOVERY_CODE_COUNT: usize = 10;
+
+/// 兩步驟驗證（TOTP 與救援碼）
+pub struct TwoFactorAuth {
+    repository: Arc<dyn TwoFactorRepository>,
+    /// 必須啟用兩步驟驗證的角色
+    required_roles: Vec<Role>,
+    /// 驗證器 App 顯示的服務名稱
+    issuer: String,
+}
+
+impl TwoFactorAuth {
+    /// 創建新的兩步驟驗證實例
+    pub fn new(repository: Arc<dyn TwoFactorRepository>, required_roles: Vec<Role>, issuer: String) -> Self {
+        Self { repository, required_roles, issuer }
+    }
+
+    /// 用戶的角色是否必須以兩步驟驗證登入
+    pub fn is_required(&self, user: &User) -> bool {
+        user.roles.iter().any(|role| self.required_roles.contains(role))
+    }
+
+    /// 用戶是否已啟用兩步驟驗證
+    pub async fn is_enabled(&self, user_id: Uuid) -> Result<bool, AppError> {
+        Ok(self.repository.find(user_id).await?.is_some_and(|totp| totp.enabled_at.is_some()))
+    }
+
+    /// 開始設定：產生新的密鑰，取代先前尚未完成的設定
+    pub async fn enroll(&self, user: &User) -> Result<TwoFactorEnrollment, AppError> {
+        let secret = totp::generate_secret();
+        if !self.repository.save_pending(user.id, &secret).await? {
+            return Err(AppError::Conflict("已啟用兩步驟驗證".to_string()));
+        }
+
+        let provisioning_uri = totp::provisioning_uri(&secret, &self.issuer, &user.email);
+        let qr_svg = totp::qr_svg(&provisioning_uri)?;
+        Ok(TwoFactorEnrollment { secret, provisioning_uri, qr_svg })
+    }
+
+    /// 以驗證碼確認設定並啟用，返回救援碼
+    pub async fn activate(&self, user_id: Uuid, code: &str) -> Result<RecoveryCodes, AppError> {
+        let pending = self.repository.find(user_id).await?
+            .ok_or_else(|| AppError::BadRequest("請先開始設定兩步驟驗證".to_string()))?;
+        if pending.enabled_at.is_some() {
+            return Err(AppError::Conflict("已啟用兩步驟驗證".to_string()));
+        }
+
+        let step = totp::verify(&pending.secret, code, chrono::Utc::now().timestamp())
+            .ok_or_else(|| AppError::BadRequest("驗證碼錯誤".to_string()))?;
+
+        let codes = generate_recovery_codes();
+        if !self.repository.enable(user_id, step, &hash_codes(&codes)).await? {
+            return Err(AppError::Conflict("已啟用兩步驟驗證".to_string()));
+        }
+        Ok(RecoveryCodes { recovery_codes: codes })
+    }
+
+    /// 驗證第二因素：6 位數的 TOTP 驗證碼或一組救援碼，兩者都只能使用一次
+    pub async fn verify(&self, user_id: Uuid, code: &str) -> Result<bool, AppError> {
+        self.verify_at(user_id, code, chrono::Utc::now().timestamp()).await
+    }
+
+    /// 以指定時間驗證第二因素
+    async fn verify_at(&self, user_id: Uuid, code: &str, unix_time: i64) -> Result<bool, AppError> {
+        let Some(user_totp) = self.repository.find(user_id).await? else {
+            return Ok(false);
+        };
+        if user_totp.enabled_at.is_none() {
+            return Ok(false);
+        }
+
+        if let Some(step) = totp::verify(&user_totp.secret, code, unix_time) {
+            return self.repository.use_step(user_id, step).await;
+        }
+
+        let code_hash = token::hash_token(&token::normalize_recovery_code(code));
+        let used = self.repository.use_recovery_code(user_id, &code_hash).await?;
+        if used {
+            tracing::warn!("用戶 {} 使用救援碼通過兩步驟驗證", user_id);
+        }
+        Ok(used)
+    }
+
+    /// 重新產生救援碼，先前的救援碼全部失效
+    pub async fn regenerate_recovery_codes(&self, user_id: Uuid) -> Result<RecoveryCodes, AppError> {
+        let codes = generate_recovery_codes();
+        self.repository.replace_recovery_codes(user_id, &hash_codes(&codes)).await?;
+        Ok(RecoveryCodes { recovery_codes: codes })
+    }
+
+    /// 停用兩步驟驗證
+    pub async fn disable(&self, user_id: Uuid) -> Result<(), AppError> {
+        self.repository.disable(user_id).await
+    }
+}
+
+fn generate_recovery_codes() -> Vec<String> {
+    (0..RECOVERY_CODE_COUNT).map(|_| token::generate_recovery_code()).collect()
+}
+
+fn hash_codes(codes: &[String]) -> Vec<String> {
+    codes.iter().map(|code| token::hash_token(&token::normalize_recovery_code(code))).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Mutex;
+
+    use async_trait::async_trait;
+
+    use super::*;
+    use crate::domain::auth::model::UserTotp;
+
+    const SECRET: &str = "GEZDGNBVGY3TQOJQGEZDGNBVGY3TQOJQ";
+
+    /// 依存儲庫介面約定記錄最後使用時間步的記憶體實作
+    struct MemoryTwoFactorRepository {
+        last_used_step: Mutex<Option<i64>>,
+    }
+
+    #[async_trait]
+    impl TwoFactorRepository for MemoryTwoFactorRepository {
+        async fn find(&self, _user_id: Uuid) -> Result<Option<UserTotp>, AppError> {
+            Ok(Some(UserTotp {
+                secret: SECRET.to_string(),
+                enabled_at: Some(chrono::NaiveDateTime::default()),
+            }))
+        }
+
+        async fn save_pending(&self, _user_id: Uuid, _secret: &str) -> Result<bool, AppError> {
+            Ok(false)
+        }
+
+        async fn enable(&self, _user_id: Uuid, _step: i64, _hashes: &[String]) -> Result<bool, AppError> {
+            Ok(false)
+        }
+
+        async fn use_step(&self, _user_id: Uuid, step: i64) -> Result<bool, AppError> {
+            let mut last_used_step = self.last_used_step.lock().unwrap();
+            if last_used_step.is_some_and(|last| last >= step) {
+                return Ok(false);
+            }
+            *last_used_step = Some(step);
+            Ok(true)
+        }
+
+        async fn use_recovery_code(&self, _user_id: Uuid, _code_hash: &str) -> Result<bool, AppError> {
+            Ok(false)
+        }
+
+        async fn replace_recovery_codes(&self, _user_id: Uuid, _code_hashes: &[String]) -> Result<(), AppError> {
+            Ok(())
+        }
+
+        async fn disable(&self, _user_id: Uuid) -> Result<(), AppError> {
+            Ok(())
+        }
+    }
+
+    fn two_factor() -> TwoFactorAuth {
+        let repository = Arc::new(MemoryTwoFactorRepository { last_used_step: Mutex::new(None) });
+        TwoFactorAuth::new(repository, Vec::new(), "test".to_string())
+    }
+
+    #[tokio::test]
+    async fn same_code_cannot_be_replayed() {
+        let auth = two_factor();
+        let user_id = Uuid::new_v4();
+        let now = 1_700_000_000;
+        let code = totp::code_at(SECRET, now);
+
+        assert!(auth.verify_at(user_id, &code, now).await.unwrap());
+        assert!(!auth.verify_at(user_id, &code, now).await.unwrap());
+        // 仍在允許誤差內的下一個時間步也不能再用同一組驗證碼
+        assert!(!auth.verify_at(user_id, &code, now + 30).await.unwrap());
+    }
+
+    #[tokio::test]
+    async fn earlier_step_is_rejected_after_later_step_is_used() {
+        let auth = two_factor();
+        let user_id = Uuid::new_v4();
+        let now = 1_700_000_000;
+
+        assert!(auth.verify_at(user_id, &totp::code_at(SECRET, now), now).await.unwrap());
+        assert!(!auth.verify_at(user_id, &totp::code_at(SECRET, now - 30), now).await.unwrap());
+        assert!(auth.verify_at(user_id, &totp::code_at(SECRET, now + 30), now + 30).await.unwrap());
+    }
+}
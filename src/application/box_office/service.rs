@@ -0,0 +1,186 @@
+use std::sync::Arc;
+use uuid::Uuid;
+use validator::Validate;
+
+use crate::application::auth::password_policy::PasswordPolicy;
+use crate::application::order::service::OrderService;
+use crate::domain::auth::model::{Permission, RegisterInput, User};
+use crate::domain::auth::repository::UserRepository;
+use crate::domain::box_office::model::{
+    BoxOfficeCustomer, BoxOfficeSale, BoxOfficeSaleResponse, CloseDrawer, CreateBoxOfficeOrder, DrawerReport,
+    DrawerSession, NewBoxOfficeSale, OpenDrawer, PaymentMethod,
+};
+use crate::domain::box_office::repository::BoxOfficeRepository;
+use crate::domain::order::model::OrderStatus;
+use crate::utils::error::AppError;
+use crate::utils::money::round_money;
+
+/// 售票處服務
+/// 現場售票沿用訂單服務的計價與庫存流程，收款與錢櫃班次由這裡記錄
+pub struct BoxOfficeService {
+    box_office_repository: Arc<dyn BoxOfficeRepository>,
+    order_service: Arc<OrderService>,
+    user_repository: Arc<dyn UserRepository>,
+    password_policy: Arc<PasswordPolicy>,
+}
+
+impl BoxOfficeService {
+    /// 創建新的售票處服務實例
+    pub fn new(
+        box_office_repository: Arc<dyn BoxOfficeRepository>,
+        order_service: Arc<OrderService>,
+        user_repository: Arc<dyn UserRepository>,
+        password_policy: Arc<PasswordPolicy>,
+    ) -> Self {
+        Self {
+            box_office_repository,
+            order_service,
+            user_repository,
+            password_policy,
+        }
+    }
+
+    /// 開啟錢櫃班次
+    pub async fn open_drawer(&self, staff: &User, input: OpenDrawer) -> Result<DrawerSession, AppError> {
+        input.validate().map_err(|e| AppError::BadRequest(e.to_string()))?;
+
+        self.box_office_repository.open_session(staff.id, input.opening_float).await
+    }
+
+    /// 獲取目前班次的對帳報告
+    pub async fn current_drawer(&self, staff: &User) -> Result<DrawerReport, AppError> {
+        let session = self.box_office_repository.find_open_session(staff.id).await?
+            .ok_or_else(|| AppError::NotFound("目前沒有開啟的錢櫃班次".to_string()))?;
+
+        self.report(session).await
+    }
+
+    /// 結束班次並回傳對帳報告
+    /// 只有開班的工作人員或管理員可以結束班次
+    pub async fn close_drawer(&self, staff: &User, session_id: Uuid, input: CloseDrawer) -> Result<DrawerReport, AppError> {
+        input.validate().map_err(|e| AppError::BadRequest(e.to_string()))?;
+
+        self.find_session(staff, session_id).await?;
+        let session = self.box_office_repository
+            .close_session(session_id, input.counted_cash, input.note.as_deref())
+            .await?
+            .ok_or_else(|| AppError::Conflict("錢櫃班次已結束".to_string()))?;
+
+        self.report(session).await
+    }
+
+    /// 獲取班次的對帳報告
+    /// 工作人員只能查看自己的班次，財務人員可以查看所屬主辦單位的所有班次
+    pub async fn get_drawer_report(&self, staff: &User, session_id: Uuid) -> Result<DrawerReport, AppError> {
+        let session = self.find_session(staff, session_id).await?;
+        self.report(session).await
+    }
+
+    /// 現場售票
+    /// 必須在開啟的錢櫃班次中進行；售出後記錄付款方式、收取現金與找零
+    pub async fn sell(&self, staff: &User, input: CreateBoxOfficeOrder) -> Result<BoxOfficeSaleResponse, AppError> {
+        input.validate().map_err(|e| AppError::BadRequest(e.to_string()))?;
+
+        match input.payment_method {
+            PaymentMethod::Cash if input.cash_tendered.is_none() => {
+                return Err(AppError::BadRequest("現金付款需提供收取金額".to_string()));
+            }
+            PaymentMethod::Card if input.cash_tendered.is_some() => {
+                return Err(AppError::BadRequest("刷卡付款不需收取現金".to_string()));
+            }
+            _ => {}
+        }
+
+        let session = self.box_office_repository.find_open_session(staff.id).await?
+            .ok_or_else(|| AppError::BadRequest("請先開啟錢櫃班次".to_string()))?;
+        let user_id = match &input.customer {
+            Some(customer) => Some(self.resolve_customer(customer).await?),
+            None => None,
+        };
+
+        let order = self.order_service
+            .create_box_office_order(user_id, input.order, input.payment_method, input.cash_tendered, staff.tenant())
+            .await?;
+
+        let sale = self.box_office_repository.record_sale(&NewBoxOfficeSale {
+            order_id: order.id,
+            session_id: session.id,
+            staff_id: staff.id,
+            payment_method: input.payment_method,
+            amount: order.breakdown.total,
+            cash_tendered: input.cash_tendered,
+            change_due: input.cash_tendered.map(|cash| round_money(cash - order.breakdown.total)),
+            card_reference: input.card_reference,
+        }).await?;
+
+        Ok(BoxOfficeSaleResponse { order, sale })
+    }
+
+    /// 找出顧客帳號，未註冊且提供密碼時當場建立
+    async fn resolve_customer(&self, customer: &BoxOfficeCustomer) -> Result<Uuid, AppError> {
+        let email = customer.email.trim();
+        if let Some(user) = self.user_repository.find_by_email(email).await? {
+            return Ok(user.id);
+        }
+
+        let password = customer.password.as_deref()
+            .ok_or_else(|| AppError::BadRequest("此 email 尚未註冊，需提供密碼以建立帳號".to_string()))?;
+        let password_hash = self.password_policy.hash_new_password(password).await?;
+        let user = self.user_repository.create(&RegisterInput {
+            email: email.to_string(),
+            password: password.to_string(),
+        }, &password_hash).await?;
+
+        Ok(user.id)
+    }
+
+    /// 查找班次並檢查查看權限
+    /// 其他主辦單位的班次視為不存在
+    async fn find_session(&self, staff: &User, session_id: Uuid) -> Result<DrawerSession, AppError> {
+        let session = self.box_office_repository.find_session(session_id, staff.tenant()).await?
+            .ok_or_else(|| AppError::NotFound(format!("找不到 ID 為 {} 的錢櫃班次", session_id)))?;
+        // 財務人員需要核對所有班次
+        if session.staff_id != staff.id && !staff.has_permission(Permission::ManageFinance) {
+            return Err(AppError::Forbidden("只能查看自己的錢櫃班次".to_string()));
+        }
+        Ok(session)
+    }
+
+    /// 彙總班次的售票記錄
+    async fn report(&self, session: DrawerSession) -> Result<DrawerReport, AppError> {
+        let sales = self.box_office_repository.find_sales(session.id).await?;
+        Ok(build_report(session, sales))
+    }
+}
+
+/// 計算錢櫃對帳報告
+/// 本班次現金售出、之後退款的訂單視為已從錢櫃退還現金
+fn build_report(session: DrawerSession, sales: Vec<BoxOfficeSale>) -> DrawerReport {
+    let total = |method: PaymentMethod, refunded_only: bool| {
+        round_money(
+            sales.iter()
+                .filter(|sale| sale.payment_method == method)
+                .filter(|sale| !refunded_only || sale.order_status == OrderStatus::Refunded)
+                .map(|sale| sale.amount)
+                .sum(),
+        )
+    };
+    let cash_sales = total(PaymentMethod::Cash, false);
+    let card_sales = total(PaymentMethod::Card, false);
+    let cash_refunds = total(PaymentMethod::Cash, true);
+    let expected_cash = round_money(session.opening_float + cash_sales - cash_refunds);
+    let counted_cash = session.counted_cash;
+
+    DrawerReport {
+        orders: sales.len() as i64,
+        tickets: sales.iter().map(|sale| sale.quantity as i64).sum(),
+        cash_sales,
+        card_sales,
+        cash_refunds,
+        expected_cash,
+        counted_cash,
+        variance: counted_cash.map(|counted| round_money(counted - expected_cash)),
+        session,
+        sales,
+    }
+}
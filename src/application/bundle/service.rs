@@ -0,0 +1,64 @@
+use std::collections::HashSet;
+use std::sync::Arc;
+use uuid::Uuid;
+use validator::Validate;
+
+use crate::domain::bundle::model::{Bundle, CreateBundle};
+use crate::domain::bundle::repository::BundleRepository;
+use crate::domain::organization::model::Tenant;
+use crate::domain::seating::repository::SeatingRepository;
+use crate::domain::ticket::repository::TicketRepository;
+use crate::utils::error::AppError;
+
+/// 組合商品服務
+pub struct BundleService {
+    bundle_repository: Arc<dyn BundleRepository>,
+    ticket_repository: Arc<dyn TicketRepository>,
+    seating_repository: Arc<dyn SeatingRepository>,
+}
+
+impl BundleService {
+    /// 創建新的組合商品服務實例
+    pub fn new(
+        bundle_repository: Arc<dyn BundleRepository>,
+        ticket_repository: Arc<dyn TicketRepository>,
+        seating_repository: Arc<dyn SeatingRepository>,
+    ) -> Self {
+        Self {
+            bundle_repository,
+            ticket_repository,
+            seating_repository,
+        }
+    }
+
+    /// 獲取所有組合商品（公開商品目錄）
+    pub async fn get_all_bundles(&self) -> Result<Vec<Bundle>, AppError> {
+        self.bundle_repository.find_all(Tenant::Platform).await
+    }
+
+    /// 根據 ID 獲取組合商品（公開商品目錄）
+    pub async fn get_bundle_by_id(&self, id: Uuid) -> Result<Bundle, AppError> {
+        self.bundle_repository.find_by_id(id, Tenant::Platform).await?
+            .ok_or_else(|| AppError::NotFound(format!("找不到 ID 為 {} 的組合商品", id)))
+    }
+
+    /// 創建新組合商品
+    /// 組合內容的票種不可重複，且不可包含需要選位的劃位票種
+    pub async fn create_bundle(&self, input: CreateBundle, tenant: Tenant) -> Result<Bundle, AppError> {
+        input.validate().map_err(|e| AppError::BadRequest(e.to_string()))?;
+
+        let unique: HashSet<Uuid> = input.components.iter().map(|component| component.ticket_id).collect();
+        if unique.len() != input.components.len() {
+            return Err(AppError::BadRequest("組合內容的票種重複".to_string()));
+        }
+        for component in &input.components {
+            let ticket = self.ticket_repository.find_by_id(component.ticket_id, tenant).await?
+                .ok_or_else(|| AppError::NotFound(format!("找不到 ID 為 {} 的票券", component.ticket_id)))?;
+            if self.seating_repository.is_seated_ticket(ticket.id).await? {
+                return Err(AppError::BadRequest(format!("劃位票種 {} 不可加入組合商品", ticket.ticket_type)));
+            }
+        }
+
+        self.bundle_repository.create(&input, tenant).await
+    }
+}
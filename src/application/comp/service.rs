@@ -0,0 +1,77 @@
+use std::collections::HashSet;
+use std::sync::Arc;
+use uuid::Uuid;
+use validator::Validate;
+
+use crate::domain::comp::model::{Comp, GuestListEntry, IssueComps};
+use crate::domain::comp::repository::CompRepository;
+use crate::domain::concert::repository::ConcertRepository;
+use crate::domain::organization::model::Tenant;
+use crate::domain::seating::repository::SeatingRepository;
+use crate::domain::ticket::repository::TicketRepository;
+use crate::utils::error::AppError;
+
+/// 招待票服務
+pub struct CompService {
+    comp_repository: Arc<dyn CompRepository>,
+    concert_repository: Arc<dyn ConcertRepository>,
+    ticket_repository: Arc<dyn TicketRepository>,
+    seating_repository: Arc<dyn SeatingRepository>,
+}
+
+impl CompService {
+    /// 創建新的招待票服務實例
+    pub fn new(
+        comp_repository: Arc<dyn CompRepository>,
+        concert_repository: Arc<dyn ConcertRepository>,
+        ticket_repository: Arc<dyn TicketRepository>,
+        seating_repository: Arc<dyn SeatingRepository>,
+    ) -> Self {
+        Self {
+            comp_repository,
+            concert_repository,
+            ticket_repository,
+            seating_repository,
+        }
+    }
+
+    /// 批次發出招待票
+    /// 票種必須屬於同一場演唱會且不可為劃位票種；同一批次的來賓 email 不可重複
+    pub async fn issue_comps(
+        &self,
+        concert_id: Uuid,
+        input: IssueComps,
+        issued_by: Uuid,
+        tenant: Tenant,
+    ) -> Result<Vec<Comp>, AppError> {
+        input.validate().map_err(|e| AppError::BadRequest(e.to_string()))?;
+
+        let unique: HashSet<String> = input.guests.iter().map(|guest| guest.email.trim().to_lowercase()).collect();
+        if unique.len() != input.guests.len() {
+            return Err(AppError::BadRequest("來賓 email 重複".to_string()));
+        }
+
+        self.concert_repository.find_by_id(concert_id, tenant).await?
+            .ok_or_else(|| AppError::NotFound(format!("找不到 ID 為 {} 的演唱會", concert_id)))?;
+        let ticket = self.ticket_repository.find_by_id(input.ticket_id, tenant).await?
+            .ok_or_else(|| AppError::NotFound(format!("找不到 ID 為 {} 的票券", input.ticket_id)))?;
+        if ticket.concert_id != concert_id {
+            return Err(AppError::BadRequest(format!("票種 {} 不屬於此演唱會", ticket.ticket_type)));
+        }
+        if self.seating_repository.is_seated_ticket(ticket.id).await? {
+            return Err(AppError::BadRequest(format!("劃位票種 {} 不可發出招待票", ticket.ticket_type)));
+        }
+
+        let comps = self.comp_repository.issue(concert_id, &input, issued_by, tenant).await?;
+        tracing::info!("演唱會 {} 已發出 {} 位來賓的招待票（{}，每位 {} 張）", concert_id, comps.len(), ticket.ticket_type, input.quantity);
+        Ok(comps)
+    }
+
+    /// 獲取演唱會的公關名單
+    pub async fn get_guest_list(&self, concert_id: Uuid, tenant: Tenant) -> Result<Vec<GuestListEntry>, AppError> {
+        self.concert_repository.find_by_id(concert_id, tenant).await?
+            .ok_or_else(|| AppError::NotFound(format!("找不到 ID 為 {} 的演唱會", concert_id)))?;
+
+        self.comp_repository.guest_list(concert_id, tenant).await
+    }
+}
@@ -1,7 +1,10 @@
 use std::sync::Arc;
+use uuid::Uuid;
+use validator::Validate;
 
-use crate::domain::concert::model::{Concert, CreateConcert};
+use crate::domain::concert::model::{Concert, ConcertFee, CreateConcert, CreateConcertFee, TaxSetting};
 use crate::domain::concert::repository::ConcertRepository;
+use crate::domain::organization::model::Tenant;
 use crate::utils::error::AppError;
 
 /// 演唱會服務
@@ -18,14 +21,20 @@ impl ConcertService {
     }
 
     /// 創建新演唱會
-    pub async fn create_concert(&self, input: CreateConcert, is_admin: bool) -> Result<Concert, AppError> {
-        // 檢查權限
-        if !is_admin {
-            return Err(AppError::Forbidden("需要管理員權限".to_string()));
-        }
+    /// 主辦單位工作人員建立的演唱會一律歸屬自己的主辦單位，平台可指定任一主辦單位
+    pub async fn create_concert(&self, input: CreateConcert, tenant: Tenant) -> Result<Concert, AppError> {
+        let organization_id = match tenant {
+            Tenant::Platform => input.organization_id,
+            Tenant::Organization(id) => {
+                if input.organization_id.is_some_and(|requested| requested != id) {
+                    return Err(AppError::Forbidden("只能在自己的主辦單位下建立演唱會".to_string()));
+                }
+                Some(id)
+            }
+        };
 
         // 創建演唱會
-        self.concert_repository.create(&input).await
+        self.concert_repository.create(&input, organization_id).await
     }
 
     /// 獲取所有演唱會
@@ -35,7 +44,33 @@ impl ConcertService {
 
     /// 根據 ID 獲取演唱會
     pub async fn get_concert_by_id(&self, id: uuid::Uuid) -> Result<Concert, AppError> {
-        self.concert_repository.find_by_id(id).await?
+        self.concert_repository.find_by_id(id, Tenant::Platform).await?
             .ok_or_else(|| AppError::NotFound(format!("找不到 ID 為 {} 的演唱會", id)))
     }
+
+    /// 更新演唱會稅率設定
+    pub async fn update_tax(&self, concert_id: Uuid, input: TaxSetting, tenant: Tenant) -> Result<Concert, AppError> {
+        // 驗證輸入
+        input.validate().map_err(|e| AppError::BadRequest(e.to_string()))?;
+
+        self.concert_repository.update_tax(concert_id, &input, tenant).await?
+            .ok_or_else(|| AppError::NotFound(format!("找不到 ID 為 {} 的演唱會", concert_id)))
+    }
+
+    /// 獲取演唱會的服務費
+    pub async fn get_concert_fees(&self, concert_id: Uuid) -> Result<Vec<ConcertFee>, AppError> {
+        // 檢查演唱會是否存在
+        self.get_concert_by_id(concert_id).await?;
+
+        self.concert_repository.find_fees(concert_id).await
+    }
+
+    /// 新增演唱會服務費
+    pub async fn create_concert_fee(&self, concert_id: Uuid, input: CreateConcertFee, tenant: Tenant) -> Result<ConcertFee, AppError> {
+        // 驗證輸入
+        input.validate().map_err(|e| AppError::BadRequest(e.to_string()))?;
+
+        self.concert_repository.create_fee(concert_id, &input, tenant).await?
+            .ok_or_else(|| AppError::NotFound(format!("找不到 ID 為 {} 的演唱會", concert_id)))
+    }
 }
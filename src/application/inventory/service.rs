@@ -0,0 +1,136 @@
+use std::sync::Arc;
+use uuid::Uuid;
+use validator::Validate;
+
+use crate::domain::concert::repository::ConcertRepository;
+use crate::domain::inventory::model::{
+    AssignPool, CreateInventoryPool, InventoryPool, InventoryPoolView, MoveAllocation, UpdatePoolCapacity,
+};
+use crate::domain::inventory::repository::InventoryRepository;
+use crate::domain::organization::model::Tenant;
+use crate::domain::seating::repository::SeatingRepository;
+use crate::domain::ticket::model::Ticket;
+use crate::domain::ticket::repository::TicketRepository;
+use crate::utils::error::AppError;
+
+/// 庫存服務
+/// 管理多個票種共用的庫存池，以及票種之間的配額移轉
+pub struct InventoryService {
+    inventory_repository: Arc<dyn InventoryRepository>,
+    ticket_repository: Arc<dyn TicketRepository>,
+    concert_repository: Arc<dyn ConcertRepository>,
+    seating_repository: Arc<dyn SeatingRepository>,
+}
+
+impl InventoryService {
+    /// 創建新的庫存服務實例
+    pub fn new(
+        inventory_repository: Arc<dyn InventoryRepository>,
+        ticket_repository: Arc<dyn TicketRepository>,
+        concert_repository: Arc<dyn ConcertRepository>,
+        seating_repository: Arc<dyn SeatingRepository>,
+    ) -> Self {
+        Self {
+            inventory_repository,
+            ticket_repository,
+            concert_repository,
+            seating_repository,
+        }
+    }
+
+    /// 獲取演唱會的庫存池與所屬票種
+    pub async fn get_pools(&self, concert_id: Uuid, tenant: Tenant) -> Result<Vec<InventoryPoolView>, AppError> {
+        self.concert_repository.find_by_id(concert_id, tenant).await?
+            .ok_or_else(|| AppError::NotFound(format!("找不到 ID 為 {} 的演唱會", concert_id)))?;
+
+        let pools = self.inventory_repository.find_pools_by_concert_id(concert_id, tenant).await?;
+        let mut views = Vec::with_capacity(pools.len());
+        for pool in pools {
+            let tickets = self.inventory_repository.find_pool_tickets(pool.id, tenant).await?;
+            views.push(InventoryPoolView { pool, tickets });
+        }
+        Ok(views)
+    }
+
+    /// 創建庫存池
+    pub async fn create_pool(
+        &self,
+        concert_id: Uuid,
+        input: CreateInventoryPool,
+        tenant: Tenant,
+    ) -> Result<InventoryPool, AppError> {
+        input.validate().map_err(|e| AppError::BadRequest(e.to_string()))?;
+        self.concert_repository.find_by_id(concert_id, tenant).await?
+            .ok_or_else(|| AppError::NotFound(format!("找不到 ID 為 {} 的演唱會", concert_id)))?;
+
+        self.inventory_repository.create_pool(concert_id, &input, tenant).await?
+            .ok_or_else(|| AppError::NotFound(format!("找不到 ID 為 {} 的演唱會", concert_id)))
+    }
+
+    /// 調整庫存池容量
+    pub async fn update_capacity(
+        &self,
+        pool_id: Uuid,
+        input: UpdatePoolCapacity,
+        tenant: Tenant,
+    ) -> Result<InventoryPool, AppError> {
+        input.validate().map_err(|e| AppError::BadRequest(e.to_string()))?;
+        self.inventory_repository.update_capacity(pool_id, input.capacity, tenant).await?
+            .ok_or_else(|| AppError::NotFound(format!("找不到 ID 為 {} 的庫存池", pool_id)))
+    }
+
+    /// 指定票種所屬的庫存池
+    /// 票種與庫存池必須屬於同一場演唱會
+    pub async fn assign_pool(&self, ticket_id: Uuid, input: AssignPool, tenant: Tenant) -> Result<Ticket, AppError> {
+        let ticket = self.find_ticket(ticket_id, tenant).await?;
+        if let Some(pool_id) = input.pool_id {
+            let pool = self.inventory_repository.find_pool(pool_id, tenant).await?
+                .ok_or_else(|| AppError::NotFound(format!("找不到 ID 為 {} 的庫存池", pool_id)))?;
+            if pool.concert_id != ticket.concert_id {
+                return Err(AppError::BadRequest("票種與庫存池不屬於同一場演唱會".to_string()));
+            }
+        }
+
+        self.inventory_repository.assign_ticket(ticket_id, input.pool_id, tenant).await?;
+        self.find_ticket(ticket_id, tenant).await
+    }
+
+    /// 將未售出配額從一個票種移到另一個票種
+    /// 兩個票種必須屬於同一場演唱會與同一個庫存池（或都不屬於庫存池），
+    /// 否則移轉會讓票種可售數量超過庫存池的實體容量；劃位票種的配額由座位決定，不可移轉
+    pub async fn move_allocation(&self, input: MoveAllocation, tenant: Tenant) -> Result<Vec<Ticket>, AppError> {
+        input.validate().map_err(|e| AppError::BadRequest(e.to_string()))?;
+        if input.from_ticket_id == input.to_ticket_id {
+            return Err(AppError::BadRequest("來源與目標票種不可相同".to_string()));
+        }
+
+        let from = self.find_ticket(input.from_ticket_id, tenant).await?;
+        let to = self.find_ticket(input.to_ticket_id, tenant).await?;
+        if from.concert_id != to.concert_id {
+            return Err(AppError::BadRequest("票種不屬於同一場演唱會".to_string()));
+        }
+        if from.pool_id != to.pool_id {
+            return Err(AppError::BadRequest("票種不屬於同一個庫存池，不可移轉配額".to_string()));
+        }
+        for ticket in [&from, &to] {
+            if self.seating_repository.is_seated_ticket(ticket.id).await? {
+                return Err(AppError::BadRequest(format!("劃位票種 {} 的配額由座位決定，不可移轉", ticket.ticket_type)));
+            }
+        }
+
+        if !self.inventory_repository
+            .move_allocation(from.id, to.id, input.quantity, tenant)
+            .await?
+        {
+            return Err(AppError::BadRequest(format!("票種 {} 未售出配額不足", from.ticket_type)));
+        }
+
+        Ok(vec![self.find_ticket(from.id, tenant).await?, self.find_ticket(to.id, tenant).await?])
+    }
+
+    /// 查找租戶範圍內的票券，不存在時回傳 NotFound
+    async fn find_ticket(&self, id: Uuid, tenant: Tenant) -> Result<Ticket, AppError> {
+        self.ticket_repository.find_by_id(id, tenant).await?
+            .ok_or_else(|| AppError::NotFound(format!("找不到 ID 為 {} 的票券", id)))
+    }
+}
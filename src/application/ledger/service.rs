@@ -0,0 +1,40 @@
+use std::sync::Arc;
+use uuid::Uuid;
+
+use crate::domain::ledger::model::{LedgerEntry, ReconciliationReport};
+use crate::domain::ledger::repository::LedgerRepository;
+use crate::utils::error::AppError;
+
+/// 分錄服務
+pub struct LedgerService {
+    ledger_repository: Arc<dyn LedgerRepository>,
+}
+
+impl LedgerService {
+    /// 創建新的分錄服務實例
+    pub fn new(ledger_repository: Arc<dyn LedgerRepository>) -> Self {
+        Self {
+            ledger_repository,
+        }
+    }
+
+    /// 產生對帳報告
+    pub async fn reconcile(&self) -> Result<ReconciliationReport, AppError> {
+        let account_balances = self.ledger_repository.account_balances().await?;
+        let unbalanced_journals = self.ledger_repository.unbalanced_journals().await?;
+        let order_mismatches = self.ledger_repository.order_mismatches().await?;
+
+        Ok(ReconciliationReport {
+            generated_at: chrono::Local::now().naive_local(),
+            is_balanced: unbalanced_journals.is_empty() && order_mismatches.is_empty(),
+            account_balances,
+            unbalanced_journals,
+            order_mismatches,
+        })
+    }
+
+    /// 獲取訂單的所有分錄
+    pub async fn get_order_entries(&self, order_id: Uuid) -> Result<Vec<LedgerEntry>, AppError> {
+        self.ledger_repository.find_by_order_id(order_id).await
+    }
+}
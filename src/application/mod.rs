@@ -1,4 +1,18 @@
+pub mod addon;
+pub mod admission;
+pub mod api_key;
 pub mod auth;
+pub mod box_office;
+pub mod bundle;
+pub mod comp;
 pub mod concert;
+pub mod inventory;
+pub mod ledger;
 pub mod order;
+pub mod organization;
+pub mod privacy;
+pub mod promo_code;
+pub mod report;
+pub mod seating;
+pub mod settlement;
 pub mod ticket;
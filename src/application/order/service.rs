@@ -1,36 +1,146 @@
+use std::collections::HashSet;
 use std::sync::Arc;
 use uuid::Uuid;
+use validator::Validate;
 
-use crate::domain::order::model::{CreateOrder, Order, OrderQuery, OrderView};
+use crate::domain::addon::repository::AddonRepository;
+use crate::domain::auth::model::User;
+use crate::domain::box_office::model::{PaymentMethod, BOX_OFFICE_PROVIDER};
+use crate::domain::bundle::repository::BundleRepository;
+use crate::domain::concert::repository::ConcertRepository;
+use crate::domain::ledger::model::Journal;
+use crate::domain::order::model::{
+    CheckoutResponse, CreateBundleOrder, CreateOrder, NewOrderItem, Order, OrderQuery, OrderStatus, OrderView,
+    PriceBreakdown, TransitionEffects,
+};
 use crate::domain::order::repository::OrderRepository;
+use crate::domain::organization::model::Tenant;
+use crate::domain::payment::model::{CreatePayment, Payment, PaymentStatus};
+use crate::domain::payment::repository::PaymentRepository;
+use crate::domain::promo_code::model::PromoCode;
+use crate::domain::promo_code::repository::PromoCodeRepository;
+use crate::domain::seating::allocator::allocate_best_available;
+use crate::domain::seating::repository::SeatingRepository;
+use crate::domain::ticket::model::Ticket;
 use crate::domain::ticket::repository::TicketRepository;
+use crate::infrastructure::payment::{PaymentGateway, WebhookEventType};
 use crate::utils::error::AppError;
+use crate::utils::money::round_money;
+
+/// 最佳位置配位遇到座位被搶先保留時的最多嘗試次數
+const MAX_ALLOCATION_ATTEMPTS: u32 = 5;
+
+/// 檢查逾時待付款訂單的間隔（秒）
+const EXPIRY_SWEEP_INTERVAL_SECS: u64 = 60;
 
 /// 訂單服務
 pub struct OrderService {
     order_repository: Arc<dyn OrderRepository>,
     ticket_repository: Arc<dyn TicketRepository>,
+    concert_repository: Arc<dyn ConcertRepository>,
+    payment_repository: Arc<dyn PaymentRepository>,
+    seating_repository: Arc<dyn SeatingRepository>,
+    bundle_repository: Arc<dyn BundleRepository>,
+    addon_repository: Arc<dyn AddonRepository>,
+    promo_code_repository: Arc<dyn PromoCodeRepository>,
+    payment_gateway: Arc<dyn PaymentGateway>,
+    currency: String,
+    /// 線上購票是否需要先驗證電子郵件
+    require_verified_email: bool,
 }
 
 impl OrderService {
     /// 創建新的訂單服務實例
+    #[allow(clippy::too_many_arguments)]
     pub fn new(
         order_repository: Arc<dyn OrderRepository>,
         ticket_repository: Arc<dyn TicketRepository>,
+        concert_repository: Arc<dyn ConcertRepository>,
+        payment_repository: Arc<dyn PaymentRepository>,
+        seating_repository: Arc<dyn SeatingRepository>,
+        bundle_repository: Arc<dyn BundleRepository>,
+        addon_repository: Arc<dyn AddonRepository>,
+        promo_code_repository: Arc<dyn PromoCodeRepository>,
+        payment_gateway: Arc<dyn PaymentGateway>,
+        currency: String,
+        require_verified_email: bool,
     ) -> Self {
         Self {
             order_repository,
             ticket_repository,
+            concert_repository,
+            payment_repository,
+            seating_repository,
+            bundle_repository,
+            addon_repository,
+            promo_code_repository,
+            payment_gateway,
+            currency,
+            require_verified_email,
         }
     }
 
     /// 創建新訂單
-    pub async fn create_order(&self, user_id: Uuid, input: CreateOrder) -> Result<Order, AppError> {
-        // 開始資料庫事務
-        // 注意：實際實現會在存儲庫層處理事務
+    /// 訂單先以待付款狀態保留庫存，再透過金流閘道請款：
+    /// 成功則轉為已付款，被拒絕則轉為失敗並釋放庫存，延遲確認則維持待付款等待 Webhook
+    pub async fn create_order(&self, user: &User, input: CreateOrder) -> Result<CheckoutResponse, AppError> {
+        self.ensure_can_purchase(user)?;
+
+        let (order, breakdown) = self.place_order(Some(user.id), input, Tenant::Platform).await?;
+        self.checkout(order, &breakdown).await
+    }
+
+    /// 售票處現場售票
+    /// 與線上下單相同地計價與保留庫存，但由售票處當場收款，不經過金流閘道；
+    /// 現金付款時收取金額不足則訂單轉為失敗並釋放庫存；
+    /// 主辦單位的售票人員只能售出該主辦單位的票種
+    pub async fn create_box_office_order(
+        &self,
+        user_id: Option<Uuid>,
+        input: CreateOrder,
+        payment_method: PaymentMethod,
+        cash_tendered: Option<f64>,
+        tenant: Tenant,
+    ) -> Result<Order, AppError> {
+        let (order, breakdown) = self.place_order(user_id, input, tenant).await?;
+
+        if payment_method == PaymentMethod::Cash && cash_tendered.unwrap_or(0.0) < breakdown.total {
+            self.order_repository
+                .transition_status(order.id, OrderStatus::Pending, OrderStatus::Failed, TransitionEffects {
+                    release_stock: true,
+                    ..Default::default()
+                })
+                .await?;
+            return Err(AppError::BadRequest(format!("收取現金不足，應收 {}", breakdown.total)));
+        }
+
+        let payment = self.payment_repository.create(&CreatePayment {
+            order_id: order.id,
+            provider: BOX_OFFICE_PROVIDER.to_string(),
+            intent_id: format!("{}_{}", BOX_OFFICE_PROVIDER, order.id),
+            amount: breakdown.total,
+            currency: self.currency.clone(),
+        }).await?;
+        self.mark_paid(&order, &payment, None).await?;
+
+        self.order_repository.find_order(order.id, Tenant::Platform).await?
+            .ok_or_else(|| AppError::NotFound(format!("找不到 ID 為 {} 的訂單", order.id)))
+    }
+
+    /// 計價並建立待付款訂單
+    /// 票種、座位與加購商品的庫存在此保留，付款流程由呼叫端決定；
+    /// 票種不在租戶範圍內時視為不存在
+    async fn place_order(
+        &self,
+        user_id: Option<Uuid>,
+        input: CreateOrder,
+        tenant: Tenant,
+    ) -> Result<(Order, PriceBreakdown), AppError> {
+        // 驗證輸入
+        input.validate().map_err(|e| AppError::BadRequest(e.to_string()))?;
 
         // 檢查票券是否存在並獲取庫存
-        let ticket = self.ticket_repository.find_by_id(input.ticket_id).await?
+        let ticket = self.ticket_repository.find_by_id(input.ticket_id, tenant).await?
             .ok_or_else(|| AppError::NotFound(format!("找不到 ID 為 {} 的票券", input.ticket_id)))?;
 
         // 檢查庫存是否足夠
@@ -38,14 +148,399 @@ impl OrderService {
             return Err(AppError::BadRequest("庫存不足".to_string()));
         }
 
-        // 更新票券庫存
-        self.ticket_repository.update_stock(input.ticket_id, input.quantity).await?;
+        // 劃位票種必須指定座位，一般票種不可指定座位
+        self.check_seat_selection(&input).await?;
+
+        // 加購商品必須屬於同一場演唱會，且購買的票種在限定範圍內
+        let items = self.check_addon_selection(&input, &ticket).await?;
+        let addon_total = round_money(items.iter().map(|item| item.unit_price * item.quantity as f64).sum());
+
+        // 計算價格明細：以目前票價、演唱會服務費與稅率為準，寫入後不再變動
+        let concert = self.concert_repository.find_by_id(ticket.concert_id, tenant).await?
+            .ok_or_else(|| AppError::NotFound(format!("找不到 ID 為 {} 的演唱會", ticket.concert_id)))?;
+        let fees = self.concert_repository.find_fees(concert.id).await?;
+        // 單價取下單當下的售價（價格階梯與需求加價），寫入價格明細後即鎖定
+        let schedule = self.ticket_repository.find_pricing(ticket.id, tenant).await?
+            .ok_or_else(|| AppError::NotFound(format!("找不到 ID 為 {} 的票券", ticket.id)))?;
+        // 同一筆訂單只適用一個價格階梯，超過目前階梯剩餘張數的訂單須分開購買
+        let now = chrono::Local::now().naive_local();
+        let unit_price = schedule.order_price(now, input.quantity).ok_or_else(|| {
+            AppError::BadRequest(format!(
+                "目前票價只剩 {} 張，請減少購買張數",
+                schedule.tier_remaining(now).unwrap_or(0)
+            ))
+        })?;
+        // 優惠碼只適用於同一場演唱會，折扣以票價小計計算
+        let promo_code = self.find_promo_code(&input, concert.id).await?;
+        let discount = promo_code.as_ref()
+            .map_or(0.0, |promo_code| promo_code.discount(unit_price * input.quantity as f64));
+        let breakdown = PriceBreakdown::calculate(unit_price, input.quantity, addon_total, &fees, discount, &concert.tax);
+
+        // 創建待付款訂單，票種（含庫存池）與加購商品庫存、座位保留在同一個交易中完成
+        // 先前的庫存檢查只是快速失敗，並發購買時以交易中的條件式扣減為準
+        let promo_code_id = promo_code.map(|promo_code| promo_code.id);
+        let order = self.create_with_seats(user_id, input, &breakdown, &items, promo_code_id).await?;
+
+        Ok((order, breakdown))
+    }
+
+    /// 購買組合商品
+    /// 組合與所有組合內容的庫存在建立訂單的交易中一併扣減，付款流程與一般訂單相同
+    pub async fn create_bundle_order(&self, user: &User, input: CreateBundleOrder) -> Result<CheckoutResponse, AppError> {
+        self.ensure_can_purchase(user)?;
+
+        // 驗證輸入
+        input.validate().map_err(|e| AppError::BadRequest(e.to_string()))?;
+
+        let bundle = self.bundle_repository.find_by_id(input.bundle_id, Tenant::Platform).await?
+            .ok_or_else(|| AppError::NotFound(format!("找不到 ID 為 {} 的組合商品", input.bundle_id)))?;
+
+        // 組合商品售價已包含所有內容，不另收演唱會服務費；稅率以組合商品的設定為準
+        // 組合商品售價即為優惠價，不適用演唱會優惠碼，因此沒有加購商品小計與折扣
+        let breakdown = PriceBreakdown::calculate(bundle.price, input.quantity, 0.0, &[], 0.0, &bundle.tax);
+        let order = self.order_repository.create_bundle_order(user.id, &input, &breakdown).await?;
+
+        self.checkout(order, &breakdown).await
+    }
+
+    /// 檢查用戶是否可以線上購票
+    /// 設定 REQUIRE_VERIFIED_EMAIL 時，尚未驗證電子郵件的用戶不可購票；售票處現場售票不受限制
+    fn ensure_can_purchase(&self, user: &User) -> Result<(), AppError> {
+        if self.require_verified_email && user.email_verified_at.is_none() {
+            return Err(AppError::Forbidden("請先驗證電子郵件再購票".to_string()));
+        }
+        Ok(())
+    }
+
+    /// 為待付款訂單建立付款意圖並請款
+    async fn checkout(&self, order: Order, breakdown: &PriceBreakdown) -> Result<CheckoutResponse, AppError> {
+        // 建立付款意圖並記錄
+        let intent = self.payment_gateway
+            .create_intent(order.id, breakdown.total, &self.currency)
+            .await?;
+        let payment = self.payment_repository.create(&CreatePayment {
+            order_id: order.id,
+            provider: self.payment_gateway.provider().to_string(),
+            intent_id: intent.intent_id.clone(),
+            amount: intent.amount,
+            currency: intent.currency.clone(),
+        }).await?;
+
+        // 請款
+        match self.payment_gateway.capture(&intent.intent_id).await? {
+            PaymentStatus::Succeeded => self.mark_paid(&order, &payment, None).await?,
+            PaymentStatus::Failed => self.mark_failed(&order, &payment, None).await?,
+            // 等待金流透過 Webhook 確認
+            PaymentStatus::Pending | PaymentStatus::Refunded | PaymentStatus::RefundRequired => {}
+        }
+
+        let order = self.order_repository.find_order(order.id, Tenant::Platform).await?
+            .ok_or_else(|| AppError::NotFound(format!("找不到 ID 為 {} 的訂單", order.id)))?;
+
+        Ok(CheckoutResponse {
+            order,
+            payment_intent_id: intent.intent_id,
+            client_secret: intent.client_secret,
+        })
+    }
+
+    /// 處理金流 Webhook
+    /// 事件記錄與訂單狀態變更在同一個交易中完成，同一事件重送或訂單已不在待付款狀態時不會重複處理；
+    /// 訂單已失敗後才送達的付款成功通知會退回扣款
+    pub async fn handle_payment_webhook(&self, payload: &[u8], signature: &str) -> Result<(), AppError> {
+        let event = self.payment_gateway.verify_webhook(payload, signature)?;
+        let provider = self.payment_gateway.provider();
+
+        // 不需處理的事件只記錄下來
+        if event.event_type == WebhookEventType::Unknown {
+            self.payment_repository.record_webhook_event(provider, &event.event_id).await?;
+            return Ok(());
+        }
+
+        let payment = self.payment_repository.find_by_intent_id(&event.intent_id).await?
+            .ok_or_else(|| AppError::NotFound(format!("找不到付款意圖 {}", event.intent_id)))?;
+        let order = self.order_repository.find_order(payment.order_id, Tenant::Platform).await?
+            .ok_or_else(|| AppError::NotFound(format!("找不到 ID 為 {} 的訂單", payment.order_id)))?;
+
+        let webhook_event = Some((provider, event.event_id.as_str()));
+        match event.event_type {
+            WebhookEventType::PaymentSucceeded => self.mark_paid(&order, &payment, webhook_event).await,
+            WebhookEventType::PaymentFailed => self.mark_failed(&order, &payment, webhook_event).await,
+            WebhookEventType::Unknown => Ok(()),
+        }
+    }
+
+    /// 啟動背景任務，定期將逾時未付款的訂單轉為失敗並釋放庫存
+    /// 處理失敗只記錄錯誤，下一輪會再嘗試
+    pub fn spawn_expiry_task(self: &Arc<Self>, timeout: chrono::Duration) {
+        let service = Arc::clone(self);
+        tokio::spawn(async move {
+            let mut ticker = tokio::time::interval(std::time::Duration::from_secs(EXPIRY_SWEEP_INTERVAL_SECS));
+            loop {
+                ticker.tick().await;
+                if let Err(e) = service.expire_pending_orders(timeout).await {
+                    tracing::error!("處理逾時訂單失敗: {}", e);
+                }
+            }
+        });
+    }
+
+    /// 將建立超過指定時間仍待付款的訂單轉為失敗，並釋放其佔用的庫存與座位
+    /// 逾時後才送達的付款成功 Webhook 不會再讓訂單轉為已付款，而是退回扣款
+    pub async fn expire_pending_orders(&self, timeout: chrono::Duration) -> Result<usize, AppError> {
+        let orders = self.order_repository.find_expired_pending(timeout).await?;
+
+        let mut expired = 0;
+        for order in &orders {
+            let payment = self.payment_repository.find_by_order_id(order.id).await?;
+            if self.order_repository
+                .transition_status(order.id, OrderStatus::Pending, OrderStatus::Failed, TransitionEffects {
+                    payment: payment.map(|payment| (payment.id, PaymentStatus::Failed)),
+                    release_stock: true,
+                    ..Default::default()
+                })
+                .await?
+            {
+                expired += 1;
+            }
+        }
+
+        if expired > 0 {
+            tracing::info!("已將 {} 筆逾時未付款的訂單轉為失敗", expired);
+        }
+        Ok(expired)
+    }
+
+    /// 退款並釋放庫存
+    /// 其他主辦單位的訂單視為不存在
+    pub async fn refund_order(&self, order_id: Uuid, tenant: Tenant) -> Result<Order, AppError> {
+        let order = self.order_repository.find_order(order_id, tenant).await?
+            .ok_or_else(|| AppError::NotFound(format!("找不到 ID 為 {} 的訂單", order_id)))?;
+        if order.status != OrderStatus::Paid {
+            return Err(AppError::Conflict("只有已付款的訂單可以退款".to_string()));
+        }
+
+        let payment = self.payment_repository.find_by_order_id(order_id).await?
+            .ok_or_else(|| AppError::NotFound(format!("找不到訂單 {} 的付款記錄", order_id)))?;
+
+        // 先將訂單轉為退款處理中再呼叫金流，避免同一訂單被重複退款
+        if !self.order_repository
+            .transition_status(order.id, OrderStatus::Paid, OrderStatus::Refunding, TransitionEffects::default())
+            .await?
+        {
+            return Err(AppError::Conflict("訂單狀態已變更".to_string()));
+        }
+        if payment.provider == BOX_OFFICE_PROVIDER {
+            // 售票處現場收款的訂單不經過金流，由售票處直接退還
+            tracing::info!("訂單 {} 已退款，由售票處退還 {}", order.id, payment.amount);
+        } else {
+            let refund = match self.payment_gateway.refund(&payment.intent_id, payment.amount).await {
+                Ok(refund) => refund,
+                Err(e) => {
+                    // 金流退款失敗：恢復已付款狀態，尚未寫入任何分錄
+                    self.order_repository
+                        .transition_status(order.id, OrderStatus::Refunding, OrderStatus::Paid, TransitionEffects::default())
+                        .await?;
+                    return Err(e);
+                }
+            };
+            tracing::info!("訂單 {} 已退款，退款編號 {}，金額 {}", order.id, refund.refund_id, refund.amount);
+        }
+
+        // 退款分錄、付款狀態與庫存歸還在同一個交易中完成
+        let refund_journal = Journal::refund(order.id, &order.breakdown);
+        self.order_repository
+            .transition_status(order.id, OrderStatus::Refunding, OrderStatus::Refunded, TransitionEffects {
+                journal: Some(&refund_journal),
+                payment: Some((payment.id, PaymentStatus::Refunded)),
+                release_stock: true,
+                ..Default::default()
+            })
+            .await?;
 
-        // 創建訂單
-        let order = self.order_repository.create(user_id, &input).await?;
+        self.order_repository.find_order(order.id, Tenant::Platform).await?
+            .ok_or_else(|| AppError::NotFound(format!("找不到 ID 為 {} 的訂單", order.id)))
+    }
 
-        // 提交事務
-        Ok(order)
+    /// 將待付款訂單標記為已付款，並在同一個交易中記錄收款分錄與更新付款狀態
+    /// 訂單已失敗（付款被拒或逾時）時扣款無法對應訂單，改為退回款項
+    async fn mark_paid(
+        &self,
+        order: &Order,
+        payment: &Payment,
+        webhook_event: Option<(&str, &str)>,
+    ) -> Result<(), AppError> {
+        let journal = Journal::payment(order.id, &order.breakdown);
+        let paid = self.order_repository
+            .transition_status(order.id, OrderStatus::Pending, OrderStatus::Paid, TransitionEffects {
+                journal: Some(&journal),
+                payment: Some((payment.id, PaymentStatus::Succeeded)),
+                release_stock: false,
+                webhook_event,
+            })
+            .await?;
+        if !paid {
+            self.refund_late_capture(order.id, payment).await?;
+        }
+        Ok(())
+    }
+
+    /// 退回已失敗訂單的扣款
+    /// 先取得退款權再呼叫金流，重送或並行的成功通知不會重複退款；訂單已付款或已退款時不做任何事。
+    /// 款項沒有進入訂單，因此不寫入分錄。自動退款失敗時標記為待人工退款並回傳錯誤，讓金流重送通知時再試一次
+    async fn refund_late_capture(&self, order_id: Uuid, payment: &Payment) -> Result<(), AppError> {
+        if !self.payment_repository.claim_late_capture_refund(payment.id).await? {
+            return Ok(());
+        }
+        tracing::warn!("訂單 {} 已失敗後才確認付款 {} 扣款成功，退回 {}", order_id, payment.id, payment.amount);
+
+        if payment.provider == BOX_OFFICE_PROVIDER {
+            self.payment_repository.flag_refund_required(payment.id).await?;
+            tracing::error!("訂單 {} 的售票處付款 {} 需由售票處退還 {}", order_id, payment.id, payment.amount);
+            return Ok(());
+        }
+
+        match self.payment_gateway.refund(&payment.intent_id, payment.amount).await {
+            Ok(refund) => {
+                tracing::info!("訂單 {} 的逾時扣款已退回，退款編號 {}，金額 {}", order_id, refund.refund_id, refund.amount);
+                Ok(())
+            }
+            Err(e) => {
+                self.payment_repository.flag_refund_required(payment.id).await?;
+                tracing::error!("訂單 {} 的付款 {} 自動退款失敗，需人工退款: {}", order_id, payment.id, e);
+                Err(e)
+            }
+        }
+    }
+
+    /// 將待付款訂單標記為失敗，並在同一個交易中更新付款狀態與釋放庫存
+    async fn mark_failed(
+        &self,
+        order: &Order,
+        payment: &Payment,
+        webhook_event: Option<(&str, &str)>,
+    ) -> Result<(), AppError> {
+        // 付款失敗沒有資金移動，不需要分錄
+        self.order_repository
+            .transition_status(order.id, OrderStatus::Pending, OrderStatus::Failed, TransitionEffects {
+                journal: None,
+                payment: Some((payment.id, PaymentStatus::Failed)),
+                release_stock: true,
+                webhook_event,
+            })
+            .await?;
+        Ok(())
+    }
+
+    /// 創建訂單並保留座位
+    /// 最佳位置模式下，配位結果可能被並行的訂單搶先保留，此時重新讀取座位狀態再配位
+    async fn create_with_seats(
+        &self,
+        user_id: Option<Uuid>,
+        mut input: CreateOrder,
+        breakdown: &PriceBreakdown,
+        items: &[NewOrderItem],
+        promo_code_id: Option<Uuid>,
+    ) -> Result<Order, AppError> {
+        let best_available = match input.best_available.clone() {
+            Some(best_available) => best_available,
+            None => return self.order_repository.create(user_id, &input, breakdown, items, promo_code_id).await,
+        };
+
+        let mut attempt = 0;
+        loop {
+            attempt += 1;
+            let seats = self.seating_repository
+                .find_allocation_seats(input.ticket_id, best_available.price_zone_id)
+                .await?;
+            input.seat_ids = allocate_best_available(&seats, input.quantity as usize, best_available.allow_split)
+                .ok_or_else(|| AppError::Conflict("沒有足夠的相鄰座位".to_string()))?;
+
+            match self.order_repository.create(user_id, &input, breakdown, items, promo_code_id).await {
+                Err(AppError::Conflict(_)) if attempt < MAX_ALLOCATION_ATTEMPTS => continue,
+                result => return result,
+            }
+        }
+    }
+
+    /// 查找訂單使用的優惠碼，未提供時返回 None
+    /// 優惠碼不存在、不屬於此演唱會或不在有效期間內時回傳 BadRequest
+    async fn find_promo_code(&self, input: &CreateOrder, concert_id: Uuid) -> Result<Option<PromoCode>, AppError> {
+        let code = match input.promo_code.as_deref().map(str::trim).filter(|code| !code.is_empty()) {
+            Some(code) => code.to_uppercase(),
+            None => return Ok(None),
+        };
+
+        self.promo_code_repository.find_active(concert_id, &code).await?
+            .map(Some)
+            .ok_or_else(|| AppError::BadRequest(format!("優惠碼 {} 無效或不在有效期間內", code)))
+    }
+
+    /// 檢查座位選擇
+    /// 座位是否仍可選購由存儲庫在保留座位時以條件式更新確認
+    async fn check_seat_selection(&self, input: &CreateOrder) -> Result<(), AppError> {
+        let seated = self.seating_repository.is_seated_ticket(input.ticket_id).await?;
+
+        if !seated {
+            if !input.seat_ids.is_empty() || input.best_available.is_some() {
+                return Err(AppError::BadRequest("此票種不需選擇座位".to_string()));
+            }
+            return Ok(());
+        }
+
+        if input.best_available.is_some() {
+            if !input.seat_ids.is_empty() {
+                return Err(AppError::BadRequest("自行選位與最佳位置只能擇一".to_string()));
+            }
+            return Ok(());
+        }
+
+        if input.seat_ids.is_empty() {
+            return Err(AppError::BadRequest("此票種需要選擇座位".to_string()));
+        }
+        let unique: HashSet<Uuid> = input.seat_ids.iter().copied().collect();
+        if unique.len() != input.seat_ids.len() {
+            return Err(AppError::BadRequest("座位重複".to_string()));
+        }
+        if input.seat_ids.len() != input.quantity as usize {
+            return Err(AppError::BadRequest("購買張數必須與座位數相同".to_string()));
+        }
+        Ok(())
+    }
+
+    /// 檢查加購商品選擇，返回以目前售價計算的訂單明細
+    /// 加購商品庫存由存儲庫在建立訂單時以條件式扣減確認
+    async fn check_addon_selection(&self, input: &CreateOrder, ticket: &Ticket) -> Result<Vec<NewOrderItem>, AppError> {
+        if input.addons.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        let variant_ids: Vec<Uuid> = input.addons.iter().map(|addon| addon.variant_id).collect();
+        let unique: HashSet<Uuid> = variant_ids.iter().copied().collect();
+        if unique.len() != variant_ids.len() {
+            return Err(AppError::BadRequest("加購商品規格重複".to_string()));
+        }
+
+        let variants = self.addon_repository.find_variants(&variant_ids).await?;
+        input.addons
+            .iter()
+            .map(|selection| {
+                let variant = variants.iter()
+                    .find(|variant| variant.variant_id == selection.variant_id)
+                    .ok_or_else(|| AppError::NotFound(format!("找不到 ID 為 {} 的加購商品規格", selection.variant_id)))?;
+                if variant.concert_id != ticket.concert_id {
+                    return Err(AppError::BadRequest(format!("加購商品 {} 不屬於此演唱會", variant.addon_name)));
+                }
+                if !variant.ticket_ids.is_empty() && !variant.ticket_ids.contains(&ticket.id) {
+                    return Err(AppError::BadRequest(format!("加購商品 {} 不開放此票種加購", variant.addon_name)));
+                }
+                Ok(NewOrderItem {
+                    variant_id: selection.variant_id,
+                    quantity: selection.quantity,
+                    unit_price: variant.price,
+                })
+            })
+            .collect()
     }
 
     /// 獲取用戶訂單列表
@@ -53,6 +548,11 @@ impl OrderService {
         self.order_repository.find_by_user_id(user_id, &query).await
     }
 
+    /// 獲取租戶範圍內所有用戶的訂單列表
+    pub async fn list_orders(&self, query: OrderQuery, tenant: Tenant) -> Result<Vec<OrderView>, AppError> {
+        self.order_repository.find_all(&query, tenant).await
+    }
+
     /// 根據 ID 獲取訂單
     pub async fn get_order_by_id(&self, id: Uuid, user_id: Uuid) -> Result<OrderView, AppError> {
         self.order_repository.find_by_id(id, user_id).await?
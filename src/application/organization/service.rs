@@ -0,0 +1,74 @@
+use std::sync::Arc;
+use uuid::Uuid;
+use validator::Validate;
+
+use crate::domain::auth::model::UserRoles;
+use crate::domain::auth::repository::UserRepository;
+use crate::domain::concert::repository::ConcertRepository;
+use crate::domain::organization::model::{CreateOrganization, Organization, Tenant, UpdateCommission};
+use crate::domain::organization::repository::OrganizationRepository;
+use crate::utils::error::AppError;
+
+/// 主辦單位服務
+pub struct OrganizationService {
+    organization_repository: Arc<dyn OrganizationRepository>,
+    concert_repository: Arc<dyn ConcertRepository>,
+    user_repository: Arc<dyn UserRepository>,
+}
+
+impl OrganizationService {
+    /// 創建新的主辦單位服務實例
+    pub fn new(
+        organization_repository: Arc<dyn OrganizationRepository>,
+        concert_repository: Arc<dyn ConcertRepository>,
+        user_repository: Arc<dyn UserRepository>,
+    ) -> Self {
+        Self {
+            organization_repository,
+            concert_repository,
+            user_repository,
+        }
+    }
+
+    /// 獲取所有主辦單位
+    pub async fn get_all_organizations(&self) -> Result<Vec<Organization>, AppError> {
+        self.organization_repository.find_all().await
+    }
+
+    /// 創建新主辦單位
+    pub async fn create_organization(&self, input: CreateOrganization) -> Result<Organization, AppError> {
+        input.validate().map_err(|e| AppError::BadRequest(e.to_string()))?;
+        self.organization_repository.create(&input).await
+    }
+
+    /// 更新主辦單位抽成比例
+    /// 已鎖定的結算單保留鎖定當下的比例，不受影響
+    pub async fn update_commission(
+        &self,
+        organization_id: Uuid,
+        input: UpdateCommission,
+    ) -> Result<Organization, AppError> {
+        input.validate().map_err(|e| AppError::BadRequest(e.to_string()))?;
+        self.organization_repository.update_commission(organization_id, input.commission_rate).await?
+            .ok_or_else(|| AppError::NotFound(format!("找不到 ID 為 {} 的主辦單位", organization_id)))
+    }
+
+    /// 指定演唱會的主辦單位
+    pub async fn assign_concert(&self, concert_id: Uuid, organization_id: Uuid) -> Result<Organization, AppError> {
+        self.concert_repository.find_by_id(concert_id, Tenant::Platform).await?
+            .ok_or_else(|| AppError::NotFound(format!("找不到 ID 為 {} 的演唱會", concert_id)))?;
+        let organization = self.organization_repository.find_by_id(organization_id).await?
+            .ok_or_else(|| AppError::NotFound(format!("找不到 ID 為 {} 的主辦單位", organization_id)))?;
+
+        self.organization_repository.assign_concert(concert_id, organization_id).await?;
+        Ok(organization)
+    }
+
+    /// 獲取主辦單位的工作人員
+    pub async fn get_members(&self, organization_id: Uuid) -> Result<Vec<UserRoles>, AppError> {
+        self.organization_repository.find_by_id(organization_id).await?
+            .ok_or_else(|| AppError::NotFound(format!("找不到 ID 為 {} 的主辦單位", organization_id)))?;
+
+        self.user_repository.find_members(organization_id).await
+    }
+}
@@ -0,0 +1,220 @@
+use std::net::IpAddr;
+use std::sync::Arc;
+
+use uuid::Uuid;
+use validator::Validate;
+
+use crate::application::auth::service::AuthService;
+use crate::application::order::service::OrderService;
+use crate::domain::auth::model::User;
+use crate::domain::auth::repository::{IdentityRepository, LoginEventRepository, UserRepository};
+use crate::domain::order::model::OrderQuery;
+use crate::domain::privacy::model::{
+    CreateErasureRequest, DataExport, DeleteAccountInput, ErasureOutcome, ErasureRequest, ErasureRequestDetail,
+    ErasureRequestQuery, ErasureSource, ErasureStatus, ProcessErasureRequest,
+};
+use crate::domain::privacy::repository::PrivacyRepository;
+use crate::utils::error::AppError;
+
+/// 匯出個人資料時每次讀取的訂單筆數
+const EXPORT_PAGE_SIZE: u32 = 100;
+
+/// 個人資料服務
+/// 提供個人資料匯出與帳號刪除。刪除帳號不會刪除用戶列，而是匿名化：
+/// 清除個人資料與登入方式，訂單、付款與會計分錄依法保留並仍參照同一個用戶 ID
+pub struct PrivacyService {
+    privacy_repository: Arc<dyn PrivacyRepository>,
+    user_repository: Arc<dyn UserRepository>,
+    identity_repository: Arc<dyn IdentityRepository>,
+    login_event_repository: Arc<dyn LoginEventRepository>,
+    order_service: Arc<OrderService>,
+    auth_service: Arc<AuthService>,
+}
+
+impl PrivacyService {
+    /// 創建新的個人資料服務實例
+    pub fn new(
+        privacy_repository: Arc<dyn PrivacyRepository>,
+        user_repository: Arc<dyn UserRepository>,
+        identity_repository: Arc<dyn IdentityRepository>,
+        login_event_repository: Arc<dyn LoginEventRepository>,
+        order_service: Arc<OrderService>,
+        auth_service: Arc<AuthService>,
+    ) -> Self {
+        Self {
+            privacy_repository,
+            user_repository,
+            identity_repository,
+            login_event_repository,
+            order_service,
+            auth_service,
+        }
+    }
+
+    /// 匯出用戶的個人資料、社群帳號、所有訂單與入場憑證及登入記錄
+    pub async fn export(&self, user: &User) -> Result<DataExport, AppError> {
+        let profile = self.auth_service.profile(user).await?;
+        let identities = self.identity_repository.find_by_user(user.id).await?;
+
+        // 分頁讀取訂單，避免一次載入大量訂單
+        let mut orders = Vec::new();
+        for page in 1.. {
+            let batch = self.order_service.get_user_orders(user.id, OrderQuery {
+                page: Some(page),
+                limit: Some(EXPORT_PAGE_SIZE),
+                from: None,
+                to: None,
+                concert_id: None,
+            }).await?;
+            let last_page = batch.len() < EXPORT_PAGE_SIZE as usize;
+            orders.extend(batch);
+            if last_page {
+                break;
+            }
+        }
+        let tickets = orders.iter().flat_map(|order| order.admissions.iter().cloned()).collect();
+        let login_history = self.login_event_repository.find_by_user(user.id).await?;
+
+        tracing::info!("用戶 {} 匯出個人資料", user.id);
+        Ok(DataExport {
+            exported_at: chrono::Local::now().naive_local(),
+            profile,
+            identities,
+            orders,
+            tickets,
+            login_history,
+        })
+    }
+
+    /// 用戶自行刪除帳號
+    /// 已設定密碼的用戶必須提供目前的密碼，唯一的平台管理員必須先移交管理員角色。
+    /// 沒有付款中的訂單時立即匿名化；否則申請排入佇列，待訂單付款完成或失敗後由客服處理，期間帳號仍可使用
+    pub async fn delete_account(
+        &self,
+        user: &User,
+        input: DeleteAccountInput,
+        client_ip: Option<IpAddr>,
+    ) -> Result<ErasureOutcome, AppError> {
+        input.validate().map_err(|e| AppError::BadRequest(e.to_string()))?;
+        if user.password_hash.is_some() {
+            let current_password = input.current_password.as_deref()
+                .ok_or_else(|| AppError::BadRequest("請提供目前的密碼".to_string()))?;
+            self.auth_service.check_current_password(user, current_password, client_ip).await?;
+        }
+        self.ensure_not_sole_admin(user.id).await?;
+
+        let request = match self.privacy_repository.find_pending_request(user.id).await? {
+            Some(request) => request,
+            None => {
+                self.privacy_repository
+                    .create_request(user.id, ErasureSource::SelfService, input.reason.as_deref(), user.id)
+                    .await?
+            }
+        };
+
+        if self.privacy_repository.has_pending_orders(user.id).await? {
+            tracing::info!("用戶 {} 仍有付款中的訂單，刪除申請 {} 已排入佇列", user.id, request.id);
+            return Ok(ErasureOutcome::Queued(request));
+        }
+
+        self.erase(user, request.id, user.id, None).await?;
+        Ok(ErasureOutcome::Completed)
+    }
+
+    /// 客服代用戶受理刪除申請
+    pub async fn create_request(&self, actor: &User, input: CreateErasureRequest) -> Result<ErasureRequest, AppError> {
+        input.validate().map_err(|e| AppError::BadRequest(e.to_string()))?;
+
+        let request = self.privacy_repository
+            .create_request(input.user_id, ErasureSource::Support, input.reason.as_deref(), actor.id)
+            .await?;
+
+        tracing::info!("用戶 {} 為用戶 {} 受理刪除申請 {}", actor.id, input.user_id, request.id);
+        Ok(request)
+    }
+
+    /// 依狀態列出刪除申請，預設只列出待處理的申請
+    pub async fn list_requests(&self, query: ErasureRequestQuery) -> Result<Vec<ErasureRequest>, AppError> {
+        self.privacy_repository.list_requests(query.status.unwrap_or(ErasureStatus::Pending)).await
+    }
+
+    /// 獲取刪除申請與稽核記錄
+    pub async fn get_request(&self, id: Uuid) -> Result<ErasureRequestDetail, AppError> {
+        let request = self.find_request(id).await?;
+        let events = self.privacy_repository.find_events(id).await?;
+        Ok(ErasureRequestDetail { request, events })
+    }
+
+    /// 完成刪除申請並匿名化用戶，用戶仍有付款中的訂單或是唯一的平台管理員時不可處理
+    pub async fn complete_request(
+        &self,
+        actor: &User,
+        id: Uuid,
+        input: ProcessErasureRequest,
+    ) -> Result<ErasureRequestDetail, AppError> {
+        input.validate().map_err(|e| AppError::BadRequest(e.to_string()))?;
+
+        let request = self.find_pending(id).await?;
+        if self.privacy_repository.has_pending_orders(request.user_id).await? {
+            return Err(AppError::Conflict("用戶仍有付款中的訂單，請待付款完成或失敗後再處理".to_string()));
+        }
+        self.ensure_not_sole_admin(request.user_id).await?;
+        let user = self.user_repository.find_by_id(request.user_id).await?
+            .ok_or_else(|| AppError::NotFound(format!("找不到 ID 為 {} 的用戶", request.user_id)))?;
+
+        self.erase(&user, id, actor.id, input.note.as_deref()).await?;
+        self.get_request(id).await
+    }
+
+    /// 拒絕刪除申請，必須說明原因
+    pub async fn reject_request(
+        &self,
+        actor: &User,
+        id: Uuid,
+        input: ProcessErasureRequest,
+    ) -> Result<ErasureRequestDetail, AppError> {
+        input.validate().map_err(|e| AppError::BadRequest(e.to_string()))?;
+        let note = input.note.as_deref().map(str::trim).filter(|note| !note.is_empty())
+            .ok_or_else(|| AppError::BadRequest("拒絕刪除申請時必須說明原因".to_string()))?;
+
+        self.find_pending(id).await?;
+        self.privacy_repository.reject_request(id, actor.id, note).await?
+            .ok_or_else(|| AppError::Conflict("此刪除申請已處理".to_string()))?;
+
+        tracing::info!("用戶 {} 拒絕刪除申請 {}", actor.id, id);
+        self.get_request(id).await
+    }
+
+    /// 結束用戶的所有登入後匿名化並完成申請
+    async fn erase(&self, user: &User, request_id: Uuid, actor_id: Uuid, note: Option<&str>) -> Result<(), AppError> {
+        self.auth_service.end_all_sessions(user).await?;
+        self.privacy_repository.complete_request(request_id, actor_id, note).await?
+            .ok_or_else(|| AppError::Conflict("此刪除申請已處理".to_string()))?;
+
+        tracing::info!("用戶 {} 已依刪除申請 {} 匿名化", user.id, request_id);
+        Ok(())
+    }
+
+    /// 匿名化會移除所有角色，唯一的平台管理員不可刪除，避免平台失去管理員
+    async fn ensure_not_sole_admin(&self, user_id: Uuid) -> Result<(), AppError> {
+        if self.privacy_repository.is_sole_admin(user_id).await? {
+            return Err(AppError::Conflict("用戶是唯一的平台管理員，請先將管理員角色移交給其他用戶".to_string()));
+        }
+        Ok(())
+    }
+
+    /// 根據 ID 查找刪除申請
+    async fn find_request(&self, id: Uuid) -> Result<ErasureRequest, AppError> {
+        self.privacy_repository.find_request(id).await?
+            .ok_or_else(|| AppError::NotFound(format!("找不到 ID 為 {} 的刪除申請", id)))
+    }
+
+    /// 查找待處理的刪除申請，已處理時返回 Conflict
+    async fn find_pending(&self, id: Uuid) -> Result<ErasureRequest, AppError> {
+        let request = self.find_request(id).await?;
+        if request.status != ErasureStatus::Pending {
+            return Err(AppError::Conflict("此刪除申請已處理".to_string()));
+        }
+        Ok(request)
+    }
+}
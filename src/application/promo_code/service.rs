@@ -0,0 +1,50 @@
+use std::sync::Arc;
+use uuid::Uuid;
+use validator::Validate;
+
+use crate::domain::organization::model::Tenant;
+use crate::domain::promo_code::model::{CreatePromoCode, DiscountType, PromoCode};
+use crate::domain::promo_code::repository::PromoCodeRepository;
+use crate::utils::error::AppError;
+
+/// 優惠碼服務
+pub struct PromoCodeService {
+    promo_code_repository: Arc<dyn PromoCodeRepository>,
+}
+
+impl PromoCodeService {
+    /// 創建新的優惠碼服務實例
+    pub fn new(promo_code_repository: Arc<dyn PromoCodeRepository>) -> Self {
+        Self { promo_code_repository }
+    }
+
+    /// 獲取演唱會的優惠碼與使用次數
+    pub async fn get_concert_promo_codes(&self, concert_id: Uuid, tenant: Tenant) -> Result<Vec<PromoCode>, AppError> {
+        self.promo_code_repository.find_by_concert_id(concert_id, tenant).await
+    }
+
+    /// 創建新優惠碼
+    /// 代碼不分大小寫；百分比折扣不可超過 100，有效期間的結束時間必須晚於開始時間
+    pub async fn create_promo_code(
+        &self,
+        concert_id: Uuid,
+        mut input: CreatePromoCode,
+        tenant: Tenant,
+    ) -> Result<PromoCode, AppError> {
+        input.validate().map_err(|e| AppError::BadRequest(e.to_string()))?;
+        input.code = input.code.trim().to_uppercase();
+        if input.code.is_empty() {
+            return Err(AppError::BadRequest("優惠碼不可為空白".to_string()));
+        }
+        if input.discount_type == DiscountType::Percent && input.amount > 100.0 {
+            return Err(AppError::BadRequest("百分比折扣不可超過 100".to_string()));
+        }
+        if let (Some(starts_at), Some(ends_at)) = (input.starts_at, input.ends_at)
+            && ends_at <= starts_at
+        {
+            return Err(AppError::BadRequest("結束時間必須晚於開始時間".to_string()));
+        }
+
+        self.promo_code_repository.create(concert_id, &input, tenant).await
+    }
+}
@@ -0,0 +1,88 @@
+use std::sync::Arc;
+use std::time::Duration;
+
+use crate::domain::organization::model::Tenant;
+use crate::domain::report::model::{
+    CancellationReport, Granularity, ReportQuery, SalesDataPoint, TicketSalesReport, TopBuyer,
+};
+use crate::domain::report::repository::ReportRepository;
+use crate::utils::error::AppError;
+
+/// 購買者排行預設筆數
+const DEFAULT_TOP_BUYERS: i64 = 10;
+/// 購買者排行最大筆數
+const MAX_TOP_BUYERS: i64 = 100;
+
+/// 報表服務
+pub struct ReportService {
+    report_repository: Arc<dyn ReportRepository>,
+}
+
+impl ReportService {
+    /// 創建新的報表服務實例
+    pub fn new(report_repository: Arc<dyn ReportRepository>) -> Self {
+        Self {
+            report_repository,
+        }
+    }
+
+    /// 啟動背景任務，定期重新整理報表物化視圖
+    /// 重新整理失敗只記錄錯誤，下一輪會再嘗試
+    pub fn spawn_refresh_task(self: &Arc<Self>, interval: Duration) {
+        let service = Arc::clone(self);
+        tokio::spawn(async move {
+            let mut ticker = tokio::time::interval(interval);
+            loop {
+                ticker.tick().await;
+                if let Err(e) = service.report_repository.refresh().await {
+                    tracing::error!("重新整理報表失敗: {}", e);
+                }
+            }
+        });
+    }
+
+    /// 立即重新整理報表
+    pub async fn refresh(&self) -> Result<(), AppError> {
+        self.report_repository.refresh().await
+    }
+
+    /// 依演唱會與票種彙總銷售與售出率
+    pub async fn sales_by_ticket(&self, query: &ReportQuery, tenant: Tenant) -> Result<Vec<TicketSalesReport>, AppError> {
+        validate_range(query)?;
+        self.report_repository.sales_by_ticket(query, tenant).await
+    }
+
+    /// 銷售時間序列
+    pub async fn time_series(
+        &self,
+        query: &ReportQuery,
+        granularity: Granularity,
+        tenant: Tenant,
+    ) -> Result<Vec<SalesDataPoint>, AppError> {
+        validate_range(query)?;
+        self.report_repository.time_series(query, granularity, tenant).await
+    }
+
+    /// 消費金額最高的購買者
+    pub async fn top_buyers(&self, query: &ReportQuery, limit: Option<i64>, tenant: Tenant) -> Result<Vec<TopBuyer>, AppError> {
+        validate_range(query)?;
+        let limit = limit.unwrap_or(DEFAULT_TOP_BUYERS).clamp(1, MAX_TOP_BUYERS);
+        self.report_repository.top_buyers(query, limit, tenant).await
+    }
+
+    /// 各演唱會的取消率
+    pub async fn cancellations(&self, query: &ReportQuery, tenant: Tenant) -> Result<Vec<CancellationReport>, AppError> {
+        validate_range(query)?;
+        self.report_repository.cancellations(query, tenant).await
+    }
+}
+
+/// 檢查日期區間
+fn validate_range(query: &ReportQuery) -> Result<(), AppError> {
+    if let (Some(from), Some(to)) = (query.from, query.to)
+        && from > to
+    {
+        return Err(AppError::BadRequest("起始日期不可晚於結束日期".to_string()));
+    }
+    Ok(())
+}
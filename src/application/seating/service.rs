@@ -0,0 +1,117 @@
+use std::sync::Arc;
+use uuid::Uuid;
+use validator::Validate;
+
+use crate::domain::concert::repository::ConcertRepository;
+use crate::domain::organization::model::Tenant;
+use crate::domain::seating::model::{
+    ConcertSeating, ConfigureSeating, ImportSeatMap, SeatCsvRow, SeatMap, SeatMapSummary,
+};
+use crate::domain::seating::repository::SeatingRepository;
+use crate::domain::ticket::repository::TicketRepository;
+use crate::utils::error::AppError;
+
+/// 座位服務
+pub struct SeatingService {
+    seating_repository: Arc<dyn SeatingRepository>,
+    concert_repository: Arc<dyn ConcertRepository>,
+    ticket_repository: Arc<dyn TicketRepository>,
+}
+
+impl SeatingService {
+    /// 創建新的座位服務實例
+    pub fn new(
+        seating_repository: Arc<dyn SeatingRepository>,
+        concert_repository: Arc<dyn ConcertRepository>,
+        ticket_repository: Arc<dyn TicketRepository>,
+    ) -> Self {
+        Self {
+            seating_repository,
+            concert_repository,
+            ticket_repository,
+        }
+    }
+
+    /// 匯入座位圖（JSON）
+    /// 主辦單位人員匯入的座位圖只有同一主辦單位可以查看與使用
+    pub async fn import_seat_map(&self, input: ImportSeatMap, tenant: Tenant) -> Result<SeatMap, AppError> {
+        input.validate().map_err(|e| AppError::BadRequest(e.to_string()))?;
+        input.check_layout().map_err(AppError::BadRequest)?;
+
+        let id = self.seating_repository.create_seat_map(&input, tenant).await?;
+        self.get_seat_map(id, tenant).await
+    }
+
+    /// 匯入座位圖（CSV，欄位為 section,row,seat,x,y）
+    pub async fn import_seat_map_csv(
+        &self,
+        name: String,
+        venue: String,
+        body: &str,
+        tenant: Tenant,
+    ) -> Result<SeatMap, AppError> {
+        let mut reader = csv::ReaderBuilder::new().trim(csv::Trim::All).from_reader(body.as_bytes());
+        let rows = reader
+            .deserialize::<SeatCsvRow>()
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(|e| AppError::BadRequest(format!("無效的 CSV: {}", e)))?;
+
+        self.import_seat_map(ImportSeatMap::from_csv_rows(name, venue, rows), tenant).await
+    }
+
+    /// 獲取座位圖
+    pub async fn get_seat_map(&self, id: Uuid, tenant: Tenant) -> Result<SeatMap, AppError> {
+        self.seating_repository.find_seat_map(id, tenant).await?
+            .ok_or_else(|| AppError::NotFound(format!("找不到 ID 為 {} 的座位圖", id)))
+    }
+
+    /// 獲取所有座位圖
+    pub async fn get_all_seat_maps(&self, tenant: Tenant) -> Result<Vec<SeatMapSummary>, AppError> {
+        self.seating_repository.find_all_seat_maps(tenant).await
+    }
+
+    /// 設定演唱會座位圖與價區
+    pub async fn configure_concert(
+        &self,
+        concert_id: Uuid,
+        input: ConfigureSeating,
+        tenant: Tenant,
+    ) -> Result<ConcertSeating, AppError> {
+        input.validate().map_err(|e| AppError::BadRequest(e.to_string()))?;
+
+        self.concert_repository.find_by_id(concert_id, tenant).await?
+            .ok_or_else(|| AppError::NotFound(format!("找不到 ID 為 {} 的演唱會", concert_id)))?;
+        self.seating_repository.find_seat_map(input.seat_map_id, tenant).await?
+            .ok_or_else(|| AppError::NotFound(format!("找不到 ID 為 {} 的座位圖", input.seat_map_id)))?;
+
+        // 價區票種必須屬於這場演唱會
+        for zone in &input.zones {
+            let ticket = self.ticket_repository.find_by_id(zone.ticket_id, tenant).await?
+                .ok_or_else(|| AppError::NotFound(format!("找不到 ID 為 {} 的票券", zone.ticket_id)))?;
+            if ticket.concert_id != concert_id {
+                return Err(AppError::BadRequest(format!("價區 {} 的票種不屬於此演唱會", zone.name)));
+            }
+        }
+
+        self.seating_repository.configure_concert(concert_id, &input, tenant).await?;
+        self.find_concert_seating(concert_id, tenant).await
+    }
+
+    /// 獲取演唱會座位圖與每個座位的可售狀態（公開選位頁面）
+    pub async fn get_concert_seating(&self, concert_id: Uuid) -> Result<ConcertSeating, AppError> {
+        self.find_concert_seating(concert_id, Tenant::Platform).await
+    }
+
+    /// 獲取租戶範圍內演唱會的座位圖與可售狀態
+    async fn find_concert_seating(&self, concert_id: Uuid, tenant: Tenant) -> Result<ConcertSeating, AppError> {
+        let seat_map_id = self.seating_repository.find_concert_seat_map_id(concert_id, tenant).await?
+            .ok_or_else(|| AppError::NotFound(format!("演唱會 {} 尚未設定座位圖", concert_id)))?;
+
+        Ok(ConcertSeating {
+            concert_id,
+            seat_map_id,
+            zones: self.seating_repository.find_zones(concert_id, tenant).await?,
+            seats: self.seating_repository.find_availability(concert_id, tenant).await?,
+        })
+    }
+}
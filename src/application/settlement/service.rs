@@ -0,0 +1,157 @@
+use std::sync::Arc;
+use uuid::Uuid;
+
+use crate::domain::concert::repository::ConcertRepository;
+use crate::domain::ledger::model::Journal;
+use crate::domain::organization::model::Tenant;
+use crate::domain::organization::repository::OrganizationRepository;
+use crate::domain::settlement::model::{Settlement, SettlementStatus};
+use crate::domain::settlement::repository::SettlementRepository;
+use crate::utils::error::AppError;
+
+/// 結算服務
+pub struct SettlementService {
+    settlement_repository: Arc<dyn SettlementRepository>,
+    organization_repository: Arc<dyn OrganizationRepository>,
+    concert_repository: Arc<dyn ConcertRepository>,
+}
+
+impl SettlementService {
+    /// 創建新的結算服務實例
+    pub fn new(
+        settlement_repository: Arc<dyn SettlementRepository>,
+        organization_repository: Arc<dyn OrganizationRepository>,
+        concert_repository: Arc<dyn ConcertRepository>,
+    ) -> Self {
+        Self {
+            settlement_repository,
+            organization_repository,
+            concert_repository,
+        }
+    }
+
+    /// 獲取演唱會結算單
+    /// 已鎖定或已撥款時回傳鎖定當下的快照，否則依目前訂單即時計算
+    pub async fn get_settlement(&self, concert_id: Uuid) -> Result<Settlement, AppError> {
+        if let Some(settlement) = self.settlement_repository.find_by_concert_id(concert_id).await? {
+            return Ok(settlement);
+        }
+
+        self.compute(concert_id).await
+    }
+
+    /// 鎖定結算單
+    /// 演唱會結束後才能鎖定，鎖定後金額不再隨訂單變動
+    pub async fn lock_settlement(&self, concert_id: Uuid) -> Result<Settlement, AppError> {
+        let settlement = self.compute(concert_id).await?;
+        let concert = self.concert_repository.find_by_id(concert_id, Tenant::Platform).await?
+            .ok_or_else(|| AppError::NotFound(format!("找不到 ID 為 {} 的演唱會", concert_id)))?;
+        if concert.date > chrono::Local::now().naive_local() {
+            return Err(AppError::BadRequest("演唱會尚未結束，無法鎖定結算".to_string()));
+        }
+
+        if !self.settlement_repository.lock(&settlement).await? {
+            return Err(AppError::Conflict("結算單已鎖定".to_string()));
+        }
+
+        self.find_stored(concert_id).await
+    }
+
+    /// 撥款給主辦單位
+    /// 同時記錄平台抽成與撥款分錄
+    pub async fn pay_settlement(&self, concert_id: Uuid) -> Result<Settlement, AppError> {
+        let settlement = self.find_stored(concert_id).await?;
+        if settlement.status != SettlementStatus::Locked {
+            return Err(AppError::Conflict("只有已鎖定的結算單可以撥款".to_string()));
+        }
+
+        let journals = [
+            Journal::commission(concert_id, settlement.platform_commission),
+            Journal::payout(concert_id, settlement.net_payable),
+        ];
+        if !self.settlement_repository.mark_paid(concert_id, &journals).await? {
+            return Err(AppError::Conflict("結算單狀態已變更".to_string()));
+        }
+        tracing::info!("演唱會 {} 已撥款 {} 給主辦單位 {}", concert_id, settlement.net_payable, settlement.organization_id);
+
+        self.find_stored(concert_id).await
+    }
+
+    /// 將結算單轉換為 CSV
+    /// 每個票種一列，最後一列為合計
+    pub fn to_csv(&self, settlement: &Settlement) -> Result<String, AppError> {
+        let mut writer = csv::Writer::from_writer(Vec::new());
+        let csv_error = |e: csv::Error| AppError::Internal(format!("產生 CSV 失敗: {}", e));
+
+        writer.write_record([
+            "concert_id", "concert_title", "organization", "status", "ticket_type",
+            "tickets_sold", "tickets_refunded", "gross_sales", "discounts", "refunds",
+            "tax_withheld", "net_sales", "commission_rate", "platform_commission", "net_payable",
+        ]).map_err(csv_error)?;
+
+        for line in &settlement.lines {
+            writer.write_record([
+                settlement.concert_id.to_string(),
+                settlement.concert_title.clone(),
+                settlement.organization_name.clone(),
+                settlement.status.as_str().to_string(),
+                line.ticket_type.clone(),
+                line.tickets_sold.to_string(),
+                line.tickets_refunded.to_string(),
+                format!("{:.2}", line.gross_sales),
+                format!("{:.2}", line.discounts),
+                format!("{:.2}", line.refunds),
+                format!("{:.2}", line.tax_withheld),
+                format!("{:.2}", line.net_sales),
+                String::new(),
+                String::new(),
+                String::new(),
+            ]).map_err(csv_error)?;
+        }
+
+        writer.write_record([
+            settlement.concert_id.to_string(),
+            settlement.concert_title.clone(),
+            settlement.organization_name.clone(),
+            settlement.status.as_str().to_string(),
+            "TOTAL".to_string(),
+            settlement.lines.iter().map(|line| line.tickets_sold).sum::<i32>().to_string(),
+            settlement.lines.iter().map(|line| line.tickets_refunded).sum::<i32>().to_string(),
+            format!("{:.2}", settlement.gross_sales),
+            format!("{:.2}", settlement.discounts),
+            format!("{:.2}", settlement.refunds),
+            format!("{:.2}", settlement.tax_withheld),
+            format!("{:.2}", settlement.net_sales),
+            settlement.commission_rate.to_string(),
+            format!("{:.2}", settlement.platform_commission),
+            format!("{:.2}", settlement.net_payable),
+        ]).map_err(csv_error)?;
+
+        let bytes = writer.into_inner().map_err(|e| AppError::Internal(format!("產生 CSV 失敗: {}", e)))?;
+        String::from_utf8(bytes).map_err(|e| AppError::Internal(format!("產生 CSV 失敗: {}", e)))
+    }
+
+    /// 依目前訂單即時計算結算單
+    async fn compute(&self, concert_id: Uuid) -> Result<Settlement, AppError> {
+        let concert = self.concert_repository.find_by_id(concert_id, Tenant::Platform).await?
+            .ok_or_else(|| AppError::NotFound(format!("找不到 ID 為 {} 的演唱會", concert_id)))?;
+        let organization = self.organization_repository.find_by_concert_id(concert_id).await?
+            .ok_or_else(|| AppError::BadRequest("演唱會尚未指定主辦單位".to_string()))?;
+        let orders = self.settlement_repository.find_orders_by_concert_id(concert_id).await?;
+
+        Ok(Settlement::compute(
+            concert.id,
+            concert.title,
+            organization.id,
+            organization.name,
+            organization.commission_rate,
+            &orders,
+        ))
+    }
+
+    /// 讀取已鎖定的結算單
+    async fn find_stored(&self, concert_id: Uuid) -> Result<Settlement, AppError> {
+        self.settlement_repository.find_by_concert_id(concert_id).await?
+            .ok_or_else(|| AppError::NotFound(format!("演唱會 {} 尚未鎖定結算", concert_id)))
+    }
+}
@@ -1,8 +1,10 @@
 use std::sync::Arc;
 use uuid::Uuid;
+use validator::Validate;
 
 use crate::domain::concert::repository::ConcertRepository;
-use crate::domain::ticket::model::{CreateTicket, Ticket};
+use crate::domain::organization::model::Tenant;
+use crate::domain::ticket::model::{CreateTicket, SetTicketPricing, Ticket, TicketPricing};
 use crate::domain::ticket::repository::TicketRepository;
 use crate::utils::error::AppError;
 
@@ -25,28 +27,67 @@ impl TicketService {
     }
 
     /// 創建新票券
-    pub async fn create_ticket(&self, input: CreateTicket, is_admin: bool) -> Result<Ticket, AppError> {
-        // 檢查權限
-        if !is_admin {
-            return Err(AppError::Forbidden("需要管理員權限".to_string()));
-        }
-
-        // 檢查音樂會是否存在
-        self.concert_repository.find_by_id(input.concert_id).await?
+    pub async fn create_ticket(&self, input: CreateTicket, tenant: Tenant) -> Result<Ticket, AppError> {
+        // 檢查音樂會是否存在且屬於租戶範圍
+        self.concert_repository.find_by_id(input.concert_id, tenant).await?
             .ok_or_else(|| AppError::NotFound(format!("找不到 ID 為 {} 的音樂會", input.concert_id)))?;
 
         // 創建票券
-        self.ticket_repository.create(&input).await
+        self.ticket_repository.create(&input, tenant).await?
+            .ok_or_else(|| AppError::NotFound(format!("找不到 ID 為 {} 的音樂會", input.concert_id)))
     }
 
     /// 根據音樂會 ID 獲取票券
+    /// 每個票種都附上目前售價（套用價格階梯與需求加價）
     pub async fn get_tickets_by_concert_id(&self, concert_id: Uuid) -> Result<Vec<Ticket>, AppError> {
-        self.ticket_repository.find_by_concert_id(concert_id).await
+        let mut tickets = self.ticket_repository.find_by_concert_id(concert_id).await?;
+        let now = chrono::Local::now().naive_local();
+        for ticket in &mut tickets {
+            if let Some(schedule) = self.ticket_repository.find_pricing(ticket.id, Tenant::Platform).await? {
+                ticket.current_price = schedule.current_price(now);
+            }
+        }
+        Ok(tickets)
     }
 
     /// 根據 ID 獲取票券
     pub async fn get_ticket_by_id(&self, id: Uuid) -> Result<Ticket, AppError> {
-        self.ticket_repository.find_by_id(id).await?
+        self.ticket_repository.find_by_id(id, Tenant::Platform).await?
             .ok_or_else(|| AppError::NotFound(format!("找不到 ID 為 {} 的票券", id)))
     }
+
+    /// 獲取票種定價設定與目前售價
+    pub async fn get_pricing(&self, id: Uuid, tenant: Tenant) -> Result<TicketPricing, AppError> {
+        let schedule = self.ticket_repository.find_pricing(id, tenant).await?
+            .ok_or_else(|| AppError::NotFound(format!("找不到 ID 為 {} 的票券", id)))?;
+        let now = chrono::Local::now().naive_local();
+
+        Ok(TicketPricing {
+            ticket_id: id,
+            base_price: schedule.base_price,
+            current_price: schedule.current_price(now),
+            current_tier: schedule.current_tier(now).map(|tier| tier.name.clone()),
+            sold: schedule.sold,
+            tiers: schedule.tiers,
+            demand: schedule.demand,
+        })
+    }
+
+    /// 設定票種價格階梯與需求加價規則
+    /// 已成立的訂單保留下單當時的價格明細，不受影響
+    pub async fn set_pricing(&self, id: Uuid, input: SetTicketPricing, tenant: Tenant) -> Result<TicketPricing, AppError> {
+        input.validate().map_err(|e| AppError::BadRequest(e.to_string()))?;
+        for tier in &input.tiers {
+            if let (Some(starts_at), Some(ends_at)) = (tier.starts_at, tier.ends_at)
+                && starts_at >= ends_at
+            {
+                return Err(AppError::BadRequest(format!("價格階梯 {} 的結束時間必須晚於開始時間", tier.name)));
+            }
+        }
+
+        if !self.ticket_repository.set_pricing(id, &input, tenant).await? {
+            return Err(AppError::NotFound(format!("找不到 ID 為 {} 的票券", id)));
+        }
+        self.get_pricing(id, tenant).await
+    }
 }
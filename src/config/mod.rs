@@ -1,6 +1,10 @@
 // 引入標準庫中的環境變量模塊，用於讀取系統環境變量
 use std::env;
 
+use chrono::Duration;
+
+use crate::domain::auth::model::{LockoutPolicy, Role};
+
 /// 應用程式配置
 /// 這個結構體包含了應用程序運行所需的所有配置信息
 /// #[derive(Clone, Debug)] 是 Rust 的屬性標記，自動為結構體實現 Clone 和 Debug 特性
@@ -12,16 +16,149 @@ pub struct AppConfig {
     /// postgres://用戶名:密碼@主機:端口/數據庫名
     pub database_url: String,
     
-    /// JWT 密鑰
+    /// JWT 簽章私鑰檔案
     /// JWT (JSON Web Token) 是用於用戶認證的令牌
-    /// 這個密鑰用於簽名和驗證 JWT，保護用戶身份信息
-    pub jwt_secret: String,
+    /// 以 Ed25519 私鑰（PKCS#8 PEM）簽發，其他服務透過 JWKS 端點取得公鑰驗證，不需要持有私鑰
+    pub jwt_signing_key_file: String,
+
+    /// JWT 驗證公鑰檔案
+    /// 金鑰輪替期間仍需接受的舊公鑰（PEM），簽章私鑰的公鑰會自動加入
+    pub jwt_verification_key_files: Vec<String>,
     
     /// 服務器端口
     /// 應用程序將在這個端口上監聽 HTTP 請求
     /// u16 是一個 16 位無符號整數，範圍是 0-65535
     /// 常用的 HTTP 端口如 8080, 3000 等都在這個範圍內
     pub port: u16,
+
+    /// 金流供應商
+    /// 目前只支援 "fake"（本地模擬金流，不連線外部服務）
+    pub payment_provider: String,
+
+    /// 模擬金流的行為模式
+    /// succeed：立即成功；decline：拒絕付款；delayed：等待 Webhook 確認
+    pub fake_payment_mode: String,
+
+    /// 金流 Webhook 簽章密鑰
+    /// 用於驗證 Webhook 請求確實來自金流供應商；只有明確選用模擬金流時可省略
+    pub payment_webhook_secret: String,
+
+    /// 訂單幣別
+    /// 建立付款意圖時傳給金流供應商，例如 TWD、USD
+    pub currency: String,
+
+    /// 待付款訂單逾時時間（秒）
+    /// 超過此時間仍未付款的訂單轉為失敗並釋放庫存，避免延遲確認的訂單永久佔用庫存
+    pub pending_order_timeout_secs: i64,
+
+    /// 報表重新整理間隔（秒）
+    /// 背景任務依此間隔重新整理報表物化視圖
+    pub report_refresh_interval_secs: u64,
+
+    /// 存取令牌有效期（秒）
+    /// 保持短效期以限制令牌外洩時的影響，過期後以刷新令牌換發
+    pub access_token_ttl_secs: i64,
+
+    /// 刷新令牌有效期（天）
+    pub refresh_token_ttl_days: i64,
+
+    /// 郵件寄送方式
+    /// file：寫入 MAIL_FILE_DIR 目錄（開發用）；smtp：透過 SMTP 轉寄伺服器寄出
+    pub mailer: String,
+
+    /// 郵件檔案目錄，MAILER=file 時使用
+    pub mail_file_dir: String,
+
+    /// SMTP 轉寄伺服器位址與埠號，MAILER=smtp 時使用
+    pub smtp_host: String,
+    pub smtp_port: u16,
+
+    /// 寄件者地址
+    pub mail_from: String,
+
+    /// 對外網址
+    /// 驗證信與重設密碼信中的連結以此為前綴
+    pub public_base_url: String,
+
+    /// 購票是否需要先驗證電子郵件
+    pub require_verified_email: bool,
+
+    /// 登入失敗記錄的存放位置
+    /// postgres：多個實例共用；memory：只存在單一實例的記憶體中，重新啟動後清空
+    pub login_attempt_store: String,
+
+    /// 同一帳號連續登入失敗達到此次數後鎖定
+    pub login_max_failures_per_account: i32,
+
+    /// 同一來源 IP 連續登入失敗達到此次數後鎖定
+    pub login_max_failures_per_ip: i32,
+
+    /// 第一次鎖定的秒數，之後每多失敗一次加倍，最多 LOGIN_MAX_LOCKOUT_SECS
+    pub login_lockout_secs: i64,
+    pub login_max_lockout_secs: i64,
+
+    /// 失敗次數的計數期間（秒），超過此時間未再失敗即重新計算
+    pub login_failure_window_secs: i64,
+
+    /// 是否信任 X-Forwarded-For 標頭判斷來源 IP
+    /// 只有服務部署在會覆寫此標頭的反向代理之後時才可啟用，否則用戶可偽造來源 IP
+    pub trust_forwarded_for: bool,
+
+    /// 服務前方的信任反向代理層數，以 X-Forwarded-For 從右數第幾個位址作為來源 IP
+    pub trusted_proxy_hops: usize,
+
+    /// 新密碼的最短長度（字元）
+    pub password_min_length: usize,
+
+    /// 外洩密碼清單檔案
+    /// 每行一筆 `SHA1雜湊[:次數]`（Have I Been Pwned 格式）或未雜湊的密碼，未設定時不檢查
+    pub breached_passwords_file: Option<String>,
+
+    /// Argon2id 密碼雜湊參數：記憶體用量（KiB）、迭代次數與平行度
+    /// 調高後既有用戶的密碼雜湊會在下次登入成功時以新參數重新雜湊
+    pub argon2_memory_kib: u32,
+    pub argon2_iterations: u32,
+    pub argon2_parallelism: u32,
+
+    /// 必須啟用兩步驟驗證的角色
+    /// 擁有這些角色的用戶需以兩步驟驗證登入，才能使用需要權限的端點
+    pub two_factor_required_roles: Vec<Role>,
+
+    /// 驗證器 App 顯示的服務名稱
+    pub totp_issuer: String,
+
+    /// 社群登入的 OpenID Connect 身分提供者
+    pub oidc_providers: Vec<OidcProviderConfig>,
+}
+
+/// OpenID Connect 身分提供者設定
+/// 端點由 `{issuer}/.well-known/openid-configuration` 自動探索，只需設定 issuer 與用戶端憑證
+#[derive(Clone, Debug)]
+pub struct OidcProviderConfig {
+    /// 提供者名稱，用於 API 路徑與外部身分記錄，例如 google
+    pub name: String,
+    /// 發行者網址，需與 ID Token 的 iss 完全相同
+    pub issuer: String,
+    pub client_id: String,
+    /// 機密用戶端的密鑰，公開用戶端只以 PKCE 保護時可不設定
+    pub client_secret: Option<String>,
+    /// 授權請求的 scope，必須包含 openid
+    pub scopes: String,
+}
+
+impl OidcProviderConfig {
+    /// 讀取 OIDC_{NAME}_* 環境變數
+    fn from_env(name: &str) -> Self {
+        let prefix = format!("OIDC_{}", name.to_uppercase().replace('-', "_"));
+        let var = |key: &str| env::var(format!("{}_{}", prefix, key));
+        Self {
+            name: name.to_string(),
+            issuer: var("ISSUER").unwrap_or_else(|_| panic!("{}_ISSUER 必須設置", prefix)),
+            client_id: var("CLIENT_ID").unwrap_or_else(|_| panic!("{}_CLIENT_ID 必須設置", prefix)),
+            client_secret: var("CLIENT_SECRET").ok().filter(|secret| !secret.is_empty()),
+            scopes: var("SCOPES").unwrap_or_else(|_| "openid email profile".to_string()),
+        }
+    }
 }
 
 impl AppConfig {
@@ -40,8 +177,14 @@ impl AppConfig {
             // expect 在變量不存在時會終止程序並顯示錯誤信息
             database_url: env::var("DATABASE_URL").expect("DATABASE_URL 必須設置"),
             
-            // 讀取 SECRET 環境變量，用於 JWT 簽名
-            jwt_secret: env::var("SECRET").expect("SECRET 必須設置"),
+            // 讀取 JWT 金鑰檔案路徑，驗證公鑰以逗號分隔
+            jwt_signing_key_file: env::var("JWT_SIGNING_KEY_FILE").expect("JWT_SIGNING_KEY_FILE 必須設置"),
+            jwt_verification_key_files: env::var("JWT_VERIFICATION_KEY_FILES")
+                .unwrap_or_default()
+                .split(',')
+                .map(|file| file.trim().to_string())
+                .filter(|file| !file.is_empty())
+                .collect(),
             
             // 讀取 PORT 環境變量
             // unwrap_or_else 提供了一個默認值（3000），如果環境變量不存在
@@ -50,6 +193,137 @@ impl AppConfig {
                 .unwrap_or_else(|_| "3000".to_string())
                 .parse()
                 .expect("PORT 必須是有效的數字"),
+
+            // 讀取金流相關設定，未設置時使用本地模擬金流
+            // Webhook 密鑰只有在明確設定 PAYMENT_PROVIDER=fake 時可以省略，避免正式環境使用預設密鑰
+            payment_provider: env::var("PAYMENT_PROVIDER").unwrap_or_else(|_| "fake".to_string()),
+            fake_payment_mode: env::var("FAKE_PAYMENT_MODE").unwrap_or_else(|_| "succeed".to_string()),
+            payment_webhook_secret: match env::var("PAYMENT_WEBHOOK_SECRET").ok().filter(|secret| !secret.is_empty()) {
+                Some(secret) => secret,
+                None if env::var("PAYMENT_PROVIDER").is_ok_and(|provider| provider == "fake") => {
+                    "fake_webhook_secret".to_string()
+                }
+                None => panic!("PAYMENT_WEBHOOK_SECRET 必須設置（僅在 PAYMENT_PROVIDER=fake 時可省略）"),
+            },
+            currency: env::var("CURRENCY").unwrap_or_else(|_| "TWD".to_string()),
+
+            // 讀取待付款訂單逾時時間，預設 15 分鐘
+            pending_order_timeout_secs: env::var("PENDING_ORDER_TIMEOUT_SECS")
+                .unwrap_or_else(|_| "900".to_string())
+                .parse()
+                .expect("PENDING_ORDER_TIMEOUT_SECS 必須是有效的數字"),
+
+            // 讀取報表重新整理間隔，預設每 5 分鐘
+            report_refresh_interval_secs: env::var("REPORT_REFRESH_INTERVAL_SECS")
+                .unwrap_or_else(|_| "300".to_string())
+                .parse()
+                .expect("REPORT_REFRESH_INTERVAL_SECS 必須是有效的數字"),
+
+            // 讀取令牌有效期，預設存取令牌 15 分鐘、刷新令牌 30 天
+            access_token_ttl_secs: env::var("ACCESS_TOKEN_TTL_SECS")
+                .unwrap_or_else(|_| "900".to_string())
+                .parse()
+                .expect("ACCESS_TOKEN_TTL_SECS 必須是有效的數字"),
+            refresh_token_ttl_days: env::var("REFRESH_TOKEN_TTL_DAYS")
+                .unwrap_or_else(|_| "30".to_string())
+                .parse()
+                .expect("REFRESH_TOKEN_TTL_DAYS 必須是有效的數字"),
+
+            // 讀取郵件設定，預設寫入本地目錄
+            mailer: env::var("MAILER").unwrap_or_else(|_| "file".to_string()),
+            mail_file_dir: env::var("MAIL_FILE_DIR").unwrap_or_else(|_| "mail".to_string()),
+            smtp_host: env::var("SMTP_HOST").unwrap_or_else(|_| "localhost".to_string()),
+            smtp_port: env::var("SMTP_PORT")
+                .unwrap_or_else(|_| "25".to_string())
+                .parse()
+                .expect("SMTP_PORT 必須是有效的數字"),
+            mail_from: env::var("MAIL_FROM").unwrap_or_else(|_| "no-reply@localhost".to_string()),
+            public_base_url: env::var("PUBLIC_BASE_URL").unwrap_or_else(|_| "http://localhost:3000".to_string()),
+
+            // 預設不要求驗證電子郵件即可購票
+            require_verified_email: env::var("REQUIRE_VERIFIED_EMAIL")
+                .unwrap_or_else(|_| "false".to_string())
+                .parse()
+                .expect("REQUIRE_VERIFIED_EMAIL 必須是 true 或 false"),
+
+            // 讀取登入鎖定設定，預設帳號 5 次、IP 20 次失敗後鎖定 1 分鐘起，最長 1 小時
+            login_attempt_store: env::var("LOGIN_ATTEMPT_STORE").unwrap_or_else(|_| "postgres".to_string()),
+            login_max_failures_per_account: env::var("LOGIN_MAX_FAILURES_PER_ACCOUNT")
+                .unwrap_or_else(|_| "5".to_string())
+                .parse()
+                .expect("LOGIN_MAX_FAILURES_PER_ACCOUNT 必須是有效的數字"),
+            login_max_failures_per_ip: env::var("LOGIN_MAX_FAILURES_PER_IP")
+                .unwrap_or_else(|_| "20".to_string())
+                .parse()
+                .expect("LOGIN_MAX_FAILURES_PER_IP 必須是有效的數字"),
+            login_lockout_secs: env::var("LOGIN_LOCKOUT_SECS")
+                .unwrap_or_else(|_| "60".to_string())
+                .parse()
+                .expect("LOGIN_LOCKOUT_SECS 必須是有效的數字"),
+            login_max_lockout_secs: env::var("LOGIN_MAX_LOCKOUT_SECS")
+                .unwrap_or_else(|_| "3600".to_string())
+                .parse()
+                .expect("LOGIN_MAX_LOCKOUT_SECS 必須是有效的數字"),
+            login_failure_window_secs: env::var("LOGIN_FAILURE_WINDOW_SECS")
+                .unwrap_or_else(|_| "900".to_string())
+                .parse()
+                .expect("LOGIN_FAILURE_WINDOW_SECS 必須是有效的數字"),
+            trust_forwarded_for: env::var("TRUST_FORWARDED_FOR")
+                .unwrap_or_else(|_| "false".to_string())
+                .parse()
+                .expect("TRUST_FORWARDED_FOR 必須是 true 或 false"),
+            trusted_proxy_hops: env::var("TRUSTED_PROXY_HOPS")
+                .unwrap_or_else(|_| "1".to_string())
+                .parse()
+                .expect("TRUSTED_PROXY_HOPS 必須是有效的數字"),
+
+            // 讀取密碼政策，預設最短 8 個字元；Argon2 參數預設為 OWASP 建議的 19 MiB、2 次迭代、平行度 1
+            password_min_length: env::var("PASSWORD_MIN_LENGTH")
+                .unwrap_or_else(|_| "8".to_string())
+                .parse()
+                .expect("PASSWORD_MIN_LENGTH 必須是有效的數字"),
+            breached_passwords_file: env::var("BREACHED_PASSWORDS_FILE").ok().filter(|file| !file.is_empty()),
+            argon2_memory_kib: env::var("ARGON2_MEMORY_KIB")
+                .unwrap_or_else(|_| "19456".to_string())
+                .parse()
+                .expect("ARGON2_MEMORY_KIB 必須是有效的數字"),
+            argon2_iterations: env::var("ARGON2_ITERATIONS")
+                .unwrap_or_else(|_| "2".to_string())
+                .parse()
+                .expect("ARGON2_ITERATIONS 必須是有效的數字"),
+            argon2_parallelism: env::var("ARGON2_PARALLELISM")
+                .unwrap_or_else(|_| "1".to_string())
+                .parse()
+                .expect("ARGON2_PARALLELISM 必須是有效的數字"),
+
+            // 讀取必須兩步驟驗證的角色，以逗號分隔，預設為管理員
+            two_factor_required_roles: env::var("TWO_FACTOR_REQUIRED_ROLES")
+                .unwrap_or_else(|_| "admin".to_string())
+                .split(',')
+                .map(|role| role.trim())
+                .filter(|role| !role.is_empty())
+                .map(|role| Role::parse(role).unwrap_or_else(|| panic!("TWO_FACTOR_REQUIRED_ROLES 含有未知的角色: {}", role)))
+                .collect(),
+            totp_issuer: env::var("TOTP_ISSUER").unwrap_or_else(|_| "Ticket Service".to_string()),
+
+            // 讀取社群登入提供者，以逗號分隔名稱，每個提供者各自設定 OIDC_{NAME}_*
+            oidc_providers: env::var("OIDC_PROVIDERS")
+                .unwrap_or_default()
+                .split(',')
+                .map(|name| name.trim().to_lowercase())
+                .filter(|name| !name.is_empty())
+                .map(|name| OidcProviderConfig::from_env(&name))
+                .collect(),
+        }
+    }
+
+    /// 依失敗次數門檻建立登入鎖定策略，帳號與來源 IP 只有門檻不同
+    pub fn lockout_policy(&self, max_failures: i32) -> LockoutPolicy {
+        LockoutPolicy {
+            max_failures,
+            lockout: Duration::seconds(self.login_lockout_secs),
+            max_lockout: Duration::seconds(self.login_max_lockout_secs),
+            window: Duration::seconds(self.login_failure_window_secs),
         }
     }
 }
@@ -0,0 +1,69 @@
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+use utoipa::ToSchema;
+use validator::Validate;
+
+/// 加購商品模型
+/// 周邊、停車、置物櫃等，隨票種訂單一併購買
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct Addon {
+    pub id: Uuid,
+    pub concert_id: Uuid,
+    pub name: String,
+    pub description: Option<String>,
+    pub price: f64,
+    pub variants: Vec<AddonVariant>,
+    /// 可加購的票種；空陣列代表不限票種
+    pub ticket_ids: Vec<Uuid>,
+}
+
+/// 加購商品規格（例如 T 恤尺寸）
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct AddonVariant {
+    pub id: Uuid,
+    pub name: String,
+    pub stock: i32,
+}
+
+/// 下單用的加購商品規格資訊
+#[derive(Debug, Clone)]
+pub struct VariantSelection {
+    pub variant_id: Uuid,
+    pub concert_id: Uuid,
+    pub addon_name: String,
+    pub price: f64,
+    pub ticket_ids: Vec<Uuid>,
+}
+
+/// 創建加購商品輸入
+#[derive(Debug, Clone, Deserialize, Validate, ToSchema)]
+pub struct CreateAddon {
+    #[validate(length(min = 1))]
+    pub name: String,
+    pub description: Option<String>,
+    #[validate(range(min = 0.0))]
+    pub price: f64,
+    /// 沒有規格區分的商品只需一個規格，例如「標準」
+    #[validate(length(min = 1))]
+    #[validate]
+    pub variants: Vec<AddonVariantInput>,
+    /// 限定可加購的票種，必須屬於同一場演唱會
+    #[serde(default)]
+    pub ticket_ids: Vec<Uuid>,
+}
+
+/// 加購商品規格輸入
+#[derive(Debug, Clone, Serialize, Deserialize, Validate, ToSchema)]
+pub struct AddonVariantInput {
+    #[validate(length(min = 1))]
+    pub name: String,
+    #[validate(range(min = 0))]
+    pub stock: i32,
+}
+
+/// 兌換加購商品輸入
+#[derive(Debug, Clone, Deserialize, Validate, ToSchema)]
+pub struct RedeemOrderItem {
+    #[validate(length(min = 1))]
+    pub code: String,
+}
@@ -0,0 +1,27 @@
+use async_trait::async_trait;
+use uuid::Uuid;
+
+use crate::domain::addon::model::{Addon, CreateAddon, VariantSelection};
+use crate::domain::order::model::OrderItem;
+use crate::domain::organization::model::Tenant;
+use crate::utils::error::AppError;
+
+/// 加購商品存儲庫接口
+#[async_trait]
+pub trait AddonRepository: Send + Sync {
+    /// 查找租戶範圍內演唱會的所有加購商品
+    async fn find_by_concert_id(&self, concert_id: Uuid, tenant: Tenant) -> Result<Vec<Addon>, AppError>;
+    
+    /// 根據規格 ID 查找下單所需的商品資訊，不存在的規格不會出現在結果中
+    async fn find_variants(&self, variant_ids: &[Uuid]) -> Result<Vec<VariantSelection>, AppError>;
+    
+    /// 創建新加購商品，演唱會或限定票種不在租戶範圍內時回傳 NotFound
+    async fn create(&self, concert_id: Uuid, input: &CreateAddon, tenant: Tenant) -> Result<Addon, AppError>;
+    
+    /// 根據代碼查找租戶範圍內的訂單明細
+    async fn find_item_by_code(&self, code: &str, tenant: Tenant) -> Result<Option<OrderItem>, AppError>;
+    
+    /// 將租戶範圍內可領取的訂單明細標記為已領取
+    /// 明細不是可領取狀態時不做任何變更並返回 None
+    async fn redeem_item(&self, code: &str, tenant: Tenant) -> Result<Option<OrderItem>, AppError>;
+}
@@ -0,0 +1,53 @@
+use chrono::NaiveDateTime;
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+use utoipa::ToSchema;
+use validator::Validate;
+
+/// 入場憑證模型
+/// 訂單付款或發出招待票後發出，入口以 code 掃描驗票
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct Admission {
+    pub id: Uuid,
+    /// 訂單憑證與招待票憑證只會有其中之一
+    pub order_id: Option<Uuid>,
+    pub comp_id: Option<Uuid>,
+    pub concert_id: Uuid,
+    pub concert_title: String,
+    /// 票種名稱或組合商品名稱
+    pub label: String,
+    pub code: String,
+    pub status: AdmissionStatus,
+    pub used_at: Option<NaiveDateTime>,
+}
+
+/// 入場憑證狀態
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum AdmissionStatus {
+    /// 可入場
+    Valid,
+    /// 已掃描入場
+    Used,
+    /// 訂單退款後作廢
+    Void,
+}
+
+impl AdmissionStatus {
+    /// 從資料庫字串解析
+    pub fn parse(value: &str) -> Option<Self> {
+        match value {
+            "valid" => Some(AdmissionStatus::Valid),
+            "used" => Some(AdmissionStatus::Used),
+            "void" => Some(AdmissionStatus::Void),
+            _ => None,
+        }
+    }
+}
+
+/// 掃描入場憑證輸入
+#[derive(Debug, Clone, Deserialize, Validate, ToSchema)]
+pub struct ScanAdmission {
+    #[validate(length(min = 1))]
+    pub code: String,
+}
@@ -0,0 +1,16 @@
+use async_trait::async_trait;
+
+use crate::domain::admission::model::Admission;
+use crate::domain::organization::model::Tenant;
+use crate::utils::error::AppError;
+
+/// 入場憑證存儲庫接口
+#[async_trait]
+pub trait AdmissionRepository: Send + Sync {
+    /// 根據代碼查找租戶範圍內的入場憑證
+    async fn find_by_code(&self, code: &str, tenant: Tenant) -> Result<Option<Admission>, AppError>;
+    
+    /// 將租戶範圍內可入場的憑證標記為已使用
+    /// 憑證不是可入場狀態時不做任何變更並返回 None，同一張憑證不會被重複使用
+    async fn mark_used(&self, code: &str, tenant: Tenant) -> Result<Option<Admission>, AppError>;
+}
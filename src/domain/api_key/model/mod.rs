@@ -0,0 +1,119 @@
+use chrono::{Duration, NaiveDateTime};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+use utoipa::ToSchema;
+use validator::Validate;
+
+use crate::domain::organization::model::Tenant;
+
+/// API 金鑰的權限範圍
+/// 只開放唯讀資料給合作夥伴與內部分析工具
+#[allow(clippy::enum_variant_names)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize, ToSchema)]
+pub enum ApiScope {
+    /// 演唱會營運資料，例如庫存池
+    #[serde(rename = "concerts:read")]
+    ConcertsRead,
+    /// 訂單列表
+    #[serde(rename = "orders:read")]
+    OrdersRead,
+    /// 銷售報表
+    #[serde(rename = "reports:read")]
+    ReportsRead,
+}
+
+impl ApiScope {
+    /// 資料庫中的字串表示
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ApiScope::ConcertsRead => "concerts:read",
+            ApiScope::OrdersRead => "orders:read",
+            ApiScope::ReportsRead => "reports:read",
+        }
+    }
+
+    /// 從資料庫字串解析
+    pub fn parse(value: &str) -> Option<Self> {
+        match value {
+            "concerts:read" => Some(ApiScope::ConcertsRead),
+            "orders:read" => Some(ApiScope::OrdersRead),
+            "reports:read" => Some(ApiScope::ReportsRead),
+            _ => None,
+        }
+    }
+}
+
+/// API 金鑰
+/// 金鑰本身只在創建時返回一次，之後只能以 prefix 辨識
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct ApiKey {
+    pub id: Uuid,
+    pub name: String,
+    /// 金鑰開頭的可見部分，例如 tsk_1a2b3c4d
+    pub prefix: String,
+    pub scopes: Vec<ApiScope>,
+    /// 有值時只能存取該主辦單位的資料
+    pub organization_id: Option<Uuid>,
+    pub rate_limit_per_minute: i32,
+    pub expires_at: NaiveDateTime,
+    pub created_by: Option<Uuid>,
+    pub created_at: NaiveDateTime,
+    pub last_used_at: Option<NaiveDateTime>,
+    pub revoked_at: Option<NaiveDateTime>,
+}
+
+impl ApiKey {
+    /// 金鑰的租戶範圍
+    pub fn tenant(&self) -> Tenant {
+        match self.organization_id {
+            Some(id) => Tenant::Organization(id),
+            None => Tenant::Platform,
+        }
+    }
+
+    /// 是否擁有指定的權限範圍
+    pub fn has_scope(&self, scope: ApiScope) -> bool {
+        self.scopes.contains(&scope)
+    }
+}
+
+/// 創建 API 金鑰輸入
+#[derive(Debug, Clone, Deserialize, Validate, ToSchema)]
+pub struct CreateApiKey {
+    /// 用途說明，例如合作夥伴名稱
+    #[validate(length(min = 1, max = 100))]
+    pub name: String,
+    #[validate(length(min = 1))]
+    pub scopes: Vec<ApiScope>,
+    /// 限制只能存取該主辦單位的資料，未指定時可存取所有主辦單位
+    pub organization_id: Option<Uuid>,
+    /// 有效天數，預設 90 天，最長 365 天
+    #[validate(range(min = 1, max = 365))]
+    pub expires_in_days: Option<i64>,
+    /// 每分鐘可呼叫次數，預設 60 次
+    #[validate(range(min = 1, max = 10000))]
+    pub rate_limit_per_minute: Option<i32>,
+}
+
+/// 新創建的 API 金鑰
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct CreatedApiKey {
+    /// 完整金鑰，只會顯示這一次
+    pub key: String,
+    pub api_key: ApiKey,
+}
+
+/// 寫入資料庫的 API 金鑰
+/// 到期時間由資料庫以目前時間加上有效期間計算
+#[derive(Debug, Clone)]
+pub struct NewApiKey {
+    pub name: String,
+    pub prefix: String,
+    pub key_hash: String,
+    pub scopes: Vec<ApiScope>,
+    pub organization_id: Option<Uuid>,
+    pub rate_limit_per_minute: i32,
+    /// 有效期間
+    pub ttl: Duration,
+    pub created_by: Uuid,
+}
@@ -0,0 +1,25 @@
+use async_trait::async_trait;
+use uuid::Uuid;
+
+use crate::domain::api_key::model::{ApiKey, NewApiKey};
+use crate::domain::organization::model::Tenant;
+use crate::utils::error::AppError;
+
+/// API 金鑰存儲庫接口
+#[async_trait]
+pub trait ApiKeyRepository: Send + Sync {
+    /// 創建 API 金鑰，主辦單位不存在時返回 BadRequest
+    async fn create(&self, input: &NewApiKey) -> Result<ApiKey, AppError>;
+
+    /// 根據金鑰雜湊查找未撤銷且未過期的金鑰，以資料庫時間判斷是否過期
+    async fn find_active_by_hash(&self, key_hash: &str) -> Result<Option<ApiKey>, AppError>;
+
+    /// 列出租戶範圍內的 API 金鑰
+    async fn list(&self, tenant: Tenant) -> Result<Vec<ApiKey>, AppError>;
+
+    /// 撤銷租戶範圍內的 API 金鑰，不存在或已撤銷時返回 false
+    async fn revoke(&self, id: Uuid, tenant: Tenant) -> Result<bool, AppError>;
+
+    /// 記錄最後使用時間，每分鐘最多寫入一次
+    async fn touch(&self, id: Uuid) -> Result<(), AppError>;
+}
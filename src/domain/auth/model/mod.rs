@@ -1,19 +1,136 @@
+use std::net::IpAddr;
+
+use chrono::{Duration, NaiveDate, NaiveDateTime};
 use serde::{Deserialize, Serialize};
 use uuid::Uuid;
-use utoipa::ToSchema;
+use utoipa::{IntoParams, ToSchema};
+use validator::Validate;
+
+use crate::domain::organization::model::Tenant;
 
 /// 使用者模型
 #[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
 pub struct User {
     pub id: Uuid,
     pub email: String,
-    pub password_hash: String,
-    pub is_admin: bool,
+    /// 密碼雜湊，只以社群帳號登入的用戶為 None
+    pub password_hash: Option<String>,
+    /// 所屬主辦單位，NULL 為平台工作人員或一般顧客
+    pub organization_id: Option<Uuid>,
+    /// 使用者擁有的角色，權限為所有角色權限的聯集
+    pub roles: Vec<Role>,
+    /// 電子郵件驗證時間，NULL 為尚未驗證
+    pub email_verified_at: Option<NaiveDateTime>,
+}
+
+impl User {
+    /// 是否擁有指定權限
+    pub fn has_permission(&self, permission: Permission) -> bool {
+        self.roles.iter().any(|role| role.permissions().contains(&permission))
+    }
+
+    /// 使用者的租戶範圍
+    /// 管理員與未隸屬主辦單位的平台工作人員可存取所有資料
+    pub fn tenant(&self) -> Tenant {
+        match self.organization_id {
+            Some(id) if !self.roles.contains(&Role::Admin) => Tenant::Organization(id),
+            _ => Tenant::Platform,
+        }
+    }
+}
+
+/// 角色
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum Role {
+    /// 平台管理員，擁有所有權限
+    Admin,
+    /// 主辦單位，管理演出、票種與招待票並查看報表
+    Organizer,
+    /// 售票處工作人員，現場售票與驗票
+    BoxOffice,
+    /// 入口驗票人員
+    Scanner,
+    /// 客服，查詢與退款訂單
+    Support,
+    /// 一般顧客，註冊時自動取得
+    Customer,
+}
+
+impl Role {
+    /// 資料庫中的字串表示
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Role::Admin => "admin",
+            Role::Organizer => "organizer",
+            Role::BoxOffice => "box_office",
+            Role::Scanner => "scanner",
+            Role::Support => "support",
+            Role::Customer => "customer",
+        }
+    }
+
+    /// 從資料庫字串解析
+    pub fn parse(value: &str) -> Option<Self> {
+        match value {
+            "admin" => Some(Role::Admin),
+            "organizer" => Some(Role::Organizer),
+            "box_office" => Some(Role::BoxOffice),
+            "scanner" => Some(Role::Scanner),
+            "support" => Some(Role::Support),
+            "customer" => Some(Role::Customer),
+            _ => None,
+        }
+    }
+
+    /// 角色擁有的權限
+    pub fn permissions(&self) -> &'static [Permission] {
+        match self {
+            Role::Admin => &[
+                Permission::ManageEvents,
+                Permission::ManageComps,
+                Permission::SellTickets,
+                Permission::ScanAdmissions,
+                Permission::ManageOrders,
+                Permission::ViewReports,
+                Permission::ManageFinance,
+                Permission::ManageUsers,
+            ],
+            Role::Organizer => &[Permission::ManageEvents, Permission::ManageComps, Permission::ViewReports],
+            Role::BoxOffice => &[Permission::SellTickets, Permission::ScanAdmissions],
+            Role::Scanner => &[Permission::ScanAdmissions],
+            Role::Support => &[Permission::ManageOrders],
+            Role::Customer => &[],
+        }
+    }
+}
+
+/// 權限
+/// 端點以權限而非角色檢查，角色與權限的對應集中在 Role::permissions
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Permission {
+    /// 管理演唱會、票種、定價、庫存、座位、組合與加購商品
+    ManageEvents,
+    /// 發出招待票與下載公關名單
+    ManageComps,
+    /// 售票處現場售票與錢櫃班次
+    SellTickets,
+    /// 驗票與加購商品領取
+    ScanAdmissions,
+    /// 退款訂單
+    ManageOrders,
+    /// 查看銷售報表
+    ViewReports,
+    /// 分錄、主辦單位與結算
+    ManageFinance,
+    /// 指派與移除角色
+    ManageUsers,
 }
 
 /// 使用者註冊輸入
-#[derive(Debug, Clone, Deserialize, ToSchema)]
+#[derive(Debug, Clone, Deserialize, Validate, ToSchema)]
 pub struct RegisterInput {
+    #[validate(email)]
     pub email: String,
     pub password: String,
 }
@@ -25,17 +142,531 @@ pub struct LoginInput {
     pub password: String,
 }
 
+/// 登入結果
+/// 啟用兩步驟驗證的帳號在密碼驗證通過後先取得挑戰令牌，再以驗證碼換取登入令牌
+#[derive(Debug, Clone, Serialize, ToSchema)]
+#[serde(untagged)]
+pub enum LoginOutcome {
+    Tokens(LoginResponse),
+    TwoFactorRequired(TwoFactorChallenge),
+}
+
+/// 兩步驟驗證挑戰
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct TwoFactorChallenge {
+    /// 固定為 true，方便前端判斷登入結果
+    pub two_factor_required: bool,
+    /// 提交驗證碼時一併送出
+    pub challenge_token: String,
+    /// 挑戰令牌的有效秒數
+    pub expires_in: i64,
+}
+
+/// 提交兩步驟驗證碼輸入
+#[derive(Debug, Clone, Deserialize, ToSchema)]
+pub struct TwoFactorLoginInput {
+    pub challenge_token: String,
+    /// 驗證器 App 的 6 位數驗證碼，或一組救援碼
+    pub code: String,
+}
+
+/// 驗證碼輸入，用於確認啟用、停用與重新產生救援碼
+#[derive(Debug, Clone, Deserialize, ToSchema)]
+pub struct TwoFactorCodeInput {
+    pub code: String,
+}
+
+/// 兩步驟驗證設定資訊
+/// 以驗證器 App 掃描 QR Code 或手動輸入密鑰後，提交驗證碼完成啟用
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct TwoFactorEnrollment {
+    /// Base32 編碼的密鑰
+    pub secret: String,
+    /// otpauth:// 設定網址
+    pub provisioning_uri: String,
+    /// 設定網址的 QR Code（SVG）
+    pub qr_svg: String,
+}
+
+/// 救援碼
+/// 只在產生時顯示一次，每組只能使用一次
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct RecoveryCodes {
+    pub recovery_codes: Vec<String>,
+}
+
+/// 用戶的 TOTP 設定
+#[derive(Debug, Clone)]
+pub struct UserTotp {
+    pub secret: String,
+    /// 啟用時間，NULL 為設定中
+    pub enabled_at: Option<NaiveDateTime>,
+}
+
 /// 登入響應
+/// token 為短效期的存取令牌，過期後以 refresh_token 換發新的一組令牌
 #[derive(Debug, Clone, Serialize, ToSchema)]
 pub struct LoginResponse {
     pub token: String,
-    pub is_admin: bool,
+    pub refresh_token: String,
+    /// 存取令牌的有效秒數
+    pub expires_in: i64,
+    pub roles: Vec<Role>,
+}
+
+/// 刷新令牌輸入，用於換發令牌與登出
+#[derive(Debug, Clone, Deserialize, ToSchema)]
+pub struct RefreshTokenInput {
+    pub refresh_token: String,
+}
+
+/// 電子郵件驗證輸入
+#[derive(Debug, Clone, Deserialize, ToSchema)]
+pub struct VerifyEmailInput {
+    /// 驗證信中的令牌
+    pub token: String,
+}
+
+/// 忘記密碼輸入
+#[derive(Debug, Clone, Deserialize, ToSchema)]
+pub struct ForgotPasswordInput {
+    pub email: String,
+}
+
+/// 重設密碼輸入
+#[derive(Debug, Clone, Deserialize, ToSchema)]
+pub struct ResetPasswordInput {
+    /// 重設密碼信中的令牌
+    pub token: String,
+    pub new_password: String,
+}
+
+/// 用戶個人資料
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct UserProfile {
+    pub id: Uuid,
+    pub email: String,
+    pub email_verified: bool,
+    /// 申請變更、尚未驗證的新電子郵件
+    pub pending_email: Option<String>,
+    /// 是否已設定密碼，只以社群帳號登入的用戶為 false
+    pub has_password: bool,
+    pub roles: Vec<Role>,
+    pub organization_id: Option<Uuid>,
+    pub display_name: Option<String>,
+    pub phone: Option<String>,
+    /// 語系，例如 zh-TW
+    pub locale: Option<String>,
+    /// 生日，用於年齡限制的演出
+    pub date_of_birth: Option<NaiveDate>,
+    /// 是否同意接收行銷訊息
+    pub marketing_consent: bool,
+    /// 最後一次變更行銷同意的時間
+    pub marketing_consent_at: Option<NaiveDateTime>,
+    pub created_at: NaiveDateTime,
+}
+
+impl UserProfile {
+    /// 用戶可自行修改的欄位
+    pub fn details(&self) -> ProfileDetails {
+        ProfileDetails {
+            display_name: self.display_name.clone(),
+            phone: self.phone.clone(),
+            locale: self.locale.clone(),
+            date_of_birth: self.date_of_birth,
+            marketing_consent: self.marketing_consent,
+        }
+    }
+}
+
+/// 用戶可自行修改的個人資料欄位
+#[derive(Debug, Clone)]
+pub struct ProfileDetails {
+    pub display_name: Option<String>,
+    pub phone: Option<String>,
+    pub locale: Option<String>,
+    pub date_of_birth: Option<NaiveDate>,
+    pub marketing_consent: bool,
+}
+
+/// 修改個人資料輸入
+/// 未提供的欄位保持不變；選填欄位傳入 null 時清除
+#[derive(Debug, Clone, Default, Deserialize, Validate, ToSchema)]
+pub struct UpdateProfile {
+    #[serde(default, deserialize_with = "nullable")]
+    #[validate(length(min = 1, max = 100))]
+    #[schema(value_type = Option<String>)]
+    pub display_name: Option<Option<String>>,
+    /// 國際格式電話號碼，例如 +886912345678
+    #[serde(default, deserialize_with = "nullable")]
+    #[validate(custom = "validate_phone")]
+    #[schema(value_type = Option<String>)]
+    pub phone: Option<Option<String>>,
+    /// BCP 47 語系標籤，例如 zh-TW
+    #[serde(default, deserialize_with = "nullable")]
+    #[validate(custom = "validate_locale")]
+    #[schema(value_type = Option<String>)]
+    pub locale: Option<Option<String>>,
+    #[serde(default, deserialize_with = "nullable")]
+    #[schema(value_type = Option<NaiveDate>)]
+    pub date_of_birth: Option<Option<NaiveDate>>,
+    pub marketing_consent: Option<bool>,
+}
+
+impl UpdateProfile {
+    /// 套用到目前的個人資料
+    pub fn apply(self, details: &mut ProfileDetails) {
+        if let Some(display_name) = self.display_name {
+            details.display_name = display_name;
+        }
+        if let Some(phone) = self.phone {
+            details.phone = phone;
+        }
+        if let Some(locale) = self.locale {
+            details.locale = locale;
+        }
+        if let Some(date_of_birth) = self.date_of_birth {
+            details.date_of_birth = date_of_birth;
+        }
+        if let Some(marketing_consent) = self.marketing_consent {
+            details.marketing_consent = marketing_consent;
+        }
+    }
+}
+
+/// 區分欄位未提供（None）與傳入 null（Some(None)）
+fn nullable<'de, D, T>(deserializer: D) -> Result<Option<Option<T>>, D::Error>
+where
+    D: serde::Deserializer<'de>,
+    T: Deserialize<'de>,
+{
+    Option::<T>::deserialize(deserializer).map(Some)
+}
+
+/// 電話號碼：可選的 + 開頭，8 到 15 位數字
+fn validate_phone(phone: &str) -> Result<(), validator::ValidationError> {
+    let digits = phone.strip_prefix('+').unwrap_or(phone);
+    if (8..=15).contains(&digits.len()) && digits.chars().all(|c| c.is_ascii_digit()) {
+        Ok(())
+    } else {
+        Err(validator::ValidationError::new("phone"))
+    }
+}
+
+/// 語系標籤：以連字號分隔的英數字段，例如 en、zh-TW、zh-Hant-TW
+fn validate_locale(locale: &str) -> Result<(), validator::ValidationError> {
+    let valid = locale.len() <= 35
+        && locale.split('-').all(|part| (1..=8).contains(&part.len()) && part.chars().all(|c| c.is_ascii_alphanumeric()));
+    if valid {
+        Ok(())
+    } else {
+        Err(validator::ValidationError::new("locale"))
+    }
+}
+
+/// 變更電子郵件輸入
+#[derive(Debug, Clone, Deserialize, Validate, ToSchema)]
+pub struct ChangeEmailInput {
+    #[validate(email)]
+    pub new_email: String,
+    pub current_password: String,
+}
+
+/// 確認變更電子郵件輸入
+#[derive(Debug, Clone, Deserialize, ToSchema)]
+pub struct ConfirmEmailChangeInput {
+    /// 寄到新電子郵件的確認令牌
+    pub token: String,
+}
+
+/// 變更密碼輸入
+#[derive(Debug, Clone, Deserialize, ToSchema)]
+pub struct ChangePasswordInput {
+    pub current_password: String,
+    pub new_password: String,
+}
+
+/// 單次使用的用戶令牌用途
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum UserTokenPurpose {
+    /// 驗證電子郵件
+    VerifyEmail,
+    /// 重設密碼
+    ResetPassword,
+    /// 密碼驗證通過、等待兩步驟驗證的登入
+    LoginChallenge,
+    /// 確認變更電子郵件，寄到新的電子郵件
+    ChangeEmail,
+}
+
+impl UserTokenPurpose {
+    /// 資料庫中的字串表示
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            UserTokenPurpose::VerifyEmail => "verify_email",
+            UserTokenPurpose::ResetPassword => "reset_password",
+            UserTokenPurpose::LoginChallenge => "login_challenge",
+            UserTokenPurpose::ChangeEmail => "change_email",
+        }
+    }
+}
+
+/// 開始社群登入的查詢參數
+#[derive(Debug, Clone, Deserialize, IntoParams)]
+pub struct OidcAuthorizeQuery {
+    /// 轉交給身分提供者的帳號提示，例如電子郵件
+    pub login_hint: Option<String>,
+}
+
+/// 身分提供者回呼的查詢參數
+#[derive(Debug, Clone, Deserialize, IntoParams)]
+pub struct OidcCallbackQuery {
+    /// 授權碼，用戶拒絕授權時不會提供
+    pub code: Option<String>,
+    pub state: Option<String>,
+    /// 身分提供者回傳的錯誤代碼
+    pub error: Option<String>,
+}
+
+/// 授權碼流程進行中的登入狀態
+#[derive(Debug, Clone)]
+pub struct OidcLoginState {
+    /// PKCE 驗證碼，換取令牌時送出
+    pub code_verifier: String,
+    /// 需與 ID Token 的 nonce 相符，防止重放
+    pub nonce: String,
+}
+
+/// 身分提供者驗證過的外部身分
+#[derive(Debug, Clone)]
+pub struct ExternalIdentity {
+    pub provider: String,
+    /// 身分提供者內的用戶 ID（ID Token 的 sub）
+    pub subject: String,
+    pub email: Option<String>,
+    /// 身分提供者是否已驗證電子郵件
+    pub email_verified: bool,
+}
+
+/// 登入失敗記錄
+#[derive(Debug, Clone)]
+pub struct LoginAttempt {
+    /// 計數期間內連續失敗的次數
+    pub failures: i32,
+    pub last_failure_at: NaiveDateTime,
+    /// 鎖定到期時間，NULL 為未鎖定
+    pub locked_until: Option<NaiveDateTime>,
+}
+
+/// 登入失敗的計數對象
+#[derive(Debug, Clone)]
+pub enum LoginAttemptKey {
+    /// 帳號，以小寫的電子郵件表示；不論帳號是否存在都計數，避免回應差異洩漏帳號
+    Account(String),
+    /// 來源 IP
+    Ip(IpAddr),
+}
+
+impl LoginAttemptKey {
+    /// 帳號的計數對象
+    pub fn account(email: &str) -> Self {
+        LoginAttemptKey::Account(email.trim().to_lowercase())
+    }
+
+    /// 存儲用的鍵值
+    pub fn as_key(&self) -> String {
+        match self {
+            LoginAttemptKey::Account(email) => format!("account:{}", email),
+            LoginAttemptKey::Ip(ip) => format!("ip:{}", ip),
+        }
+    }
+}
+
+/// 登入鎖定策略
+#[derive(Debug, Clone, Copy)]
+pub struct LockoutPolicy {
+    /// 達到此失敗次數後開始鎖定
+    pub max_failures: i32,
+    /// 第一次鎖定的時間，之後每多失敗一次加倍
+    pub lockout: Duration,
+    /// 鎖定時間上限
+    pub max_lockout: Duration,
+    /// 最後一次失敗（或鎖定到期）後超過此時間未再失敗，失敗次數重新計算
+    pub window: Duration,
+}
+
+impl LockoutPolicy {
+    /// 依失敗次數計算鎖定時間，未達門檻時返回 None
+    pub fn lockout_for(&self, failures: i32) -> Option<Duration> {
+        if failures < self.max_failures {
+            return None;
+        }
+        // 指數上限避免溢位，超過時一律以鎖定時間上限計算
+        let exponent = (failures - self.max_failures).min(16) as u32;
+        Some((self.lockout * 2_i32.pow(exponent)).min(self.max_lockout))
+    }
 }
 
 /// JWT 聲明
 #[derive(Debug, Serialize, Deserialize)]
 pub struct Claims {
     pub sub: String,
-    pub admin: bool,
+    /// 令牌 ID，登出後記入撤銷清單
+    pub jti: String,
+    /// 簽發時的角色；驗證時與資料庫目前的角色取交集，移除角色立即生效
+    pub roles: Vec<Role>,
+    /// 認證方式（RFC 8176）：pwd 為密碼，通過兩步驟驗證時另含 otp
+    #[serde(default)]
+    pub amr: Vec<String>,
     pub exp: usize,
 }
+
+/// JSON Web Key（Ed25519 公鑰）
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct Jwk {
+    pub kty: String,
+    pub crv: String,
+    pub alg: String,
+    #[serde(rename = "use")]
+    pub key_use: String,
+    /// 金鑰 ID，對應令牌標頭的 kid
+    pub kid: String,
+    /// Base64url 編碼的公鑰
+    pub x: String,
+}
+
+/// JSON Web Key Set
+/// 第一把為目前的簽章金鑰，其餘為輪替期間仍可驗證的舊金鑰
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct JwkSet {
+    pub keys: Vec<Jwk>,
+}
+
+/// 刷新令牌記錄
+/// 資料庫只保存令牌雜湊；同一次登入輪替出的令牌共用 family_id
+#[derive(Debug, Clone)]
+pub struct RefreshToken {
+    pub id: Uuid,
+    pub user_id: Uuid,
+    pub family_id: Uuid,
+    /// 是否已過期，以資料庫時間判斷
+    pub expired: bool,
+    pub used_at: Option<NaiveDateTime>,
+    pub revoked_at: Option<NaiveDateTime>,
+    /// 該次登入是否通過兩步驟驗證
+    pub two_factor: bool,
+}
+
+/// 新刷新令牌
+/// 到期時間由資料庫以目前時間加上有效期間計算
+#[derive(Debug, Clone)]
+pub struct NewRefreshToken {
+    pub user_id: Uuid,
+    pub family_id: Uuid,
+    pub token_hash: String,
+    pub access_jti: Uuid,
+    /// 一起簽發的存取令牌有效期間
+    pub access_ttl: Duration,
+    /// 刷新令牌有效期間
+    pub ttl: Duration,
+    pub two_factor: bool,
+}
+
+/// 角色指派記錄
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct RoleAssignment {
+    pub role: Role,
+    pub granted_by: Option<Uuid>,
+    pub granted_at: NaiveDateTime,
+}
+
+/// 使用者角色
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct UserRoles {
+    pub user_id: Uuid,
+    pub email: String,
+    pub organization_id: Option<Uuid>,
+    pub roles: Vec<RoleAssignment>,
+}
+
+/// 指派角色輸入
+#[derive(Debug, Clone, Deserialize, ToSchema)]
+pub struct GrantRole {
+    pub role: Role,
+}
+
+/// 登入方式
+#[derive(Debug, Clone)]
+pub enum LoginMethod {
+    /// 電子郵件與密碼
+    Password,
+    /// 社群登入，內容為身分提供者名稱
+    Social(String),
+    /// 第一階段驗證後以兩步驟驗證碼完成登入
+    TwoFactor,
+}
+
+impl LoginMethod {
+    /// 登入記錄中的字串表示
+    pub fn as_string(&self) -> String {
+        match self {
+            LoginMethod::Password => "password".to_string(),
+            LoginMethod::Social(provider) => format!("oidc:{}", provider),
+            LoginMethod::TwoFactor => "two_factor".to_string(),
+        }
+    }
+}
+
+/// 登入記錄
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct LoginEvent {
+    /// password、oidc:{provider} 或 two_factor
+    pub method: String,
+    pub ip_address: Option<String>,
+    pub created_at: NaiveDateTime,
+}
+
+/// 已連結的社群帳號
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct LinkedIdentity {
+    pub provider: String,
+    pub subject: String,
+    pub email: Option<String>,
+    pub created_at: NaiveDateTime,
+    pub last_login_at: NaiveDateTime,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn policy() -> LockoutPolicy {
+        LockoutPolicy {
+            max_failures: 5,
+            lockout: Duration::minutes(1),
+            max_lockout: Duration::minutes(30),
+            window: Duration::minutes(15),
+        }
+    }
+
+    #[test]
+    fn lockout_starts_at_max_failures() {
+        assert_eq!(policy().lockout_for(1), None);
+        assert_eq!(policy().lockout_for(4), None);
+        assert_eq!(policy().lockout_for(5), Some(Duration::minutes(1)));
+    }
+
+    #[test]
+    fn lockout_doubles_with_each_further_failure() {
+        assert_eq!(policy().lockout_for(6), Some(Duration::minutes(2)));
+        assert_eq!(policy().lockout_for(7), Some(Duration::minutes(4)));
+        assert_eq!(policy().lockout_for(9), Some(Duration::minutes(16)));
+    }
+
+    #[test]
+    fn lockout_is_capped_at_max_lockout() {
+        assert_eq!(policy().lockout_for(10), Some(Duration::minutes(30)));
+        // 指數上限避免溢位，失敗次數極大時仍回傳上限
+        assert_eq!(policy().lockout_for(i32::MAX), Some(Duration::minutes(30)));
+    }
+}
@@ -1,14 +1,20 @@
+use std::net::IpAddr;
+
 use async_trait::async_trait;
+use chrono::Duration;
 use uuid::Uuid;
 
-use crate::domain::auth::model::{RegisterInput, User};
+use crate::domain::auth::model::{
+    ExternalIdentity, LinkedIdentity, LoginEvent, LoginMethod, NewRefreshToken, OidcLoginState,
+    ProfileDetails, RefreshToken, RegisterInput, Role, User, UserProfile, UserRoles, UserTokenPurpose, UserTotp,
+};
 use crate::utils::error::AppError;
 
 /// 用戶存儲庫介面
 #[async_trait]
 pub trait UserRepository: Send + Sync {
-    /// 創建新用戶
-    async fn create(&self, input: &RegisterInput, password_hash: &str) -> Result<(), AppError>;
+    /// 創建新用戶，並指派顧客角色
+    async fn create(&self, input: &RegisterInput, password_hash: &str) -> Result<User, AppError>;
 
     /// 根據電子郵件查找用戶
     async fn find_by_email(&self, email: &str) -> Result<Option<User>, AppError>;
@@ -18,4 +24,160 @@ pub trait UserRepository: Send + Sync {
 
     /// 檢查電子郵件是否已存在
     async fn email_exists(&self, email: &str) -> Result<bool, AppError>;
+
+    /// 查找用戶的角色指派記錄，用戶不存在時返回 None
+    async fn find_roles(&self, user_id: Uuid) -> Result<Option<UserRoles>, AppError>;
+
+    /// 指派角色，用戶已擁有該角色時返回 false
+    async fn grant_role(&self, user_id: Uuid, role: Role, granted_by: Uuid) -> Result<bool, AppError>;
+
+    /// 移除角色，用戶沒有該角色時返回 false
+    async fn revoke_role(&self, user_id: Uuid, role: Role) -> Result<bool, AppError>;
+
+    /// 設定用戶所屬主辦單位，找不到用戶時返回 false，找不到主辦單位時回傳 NotFound
+    async fn set_organization(&self, user_id: Uuid, organization_id: Option<Uuid>) -> Result<bool, AppError>;
+
+    /// 查找主辦單位的工作人員
+    async fn find_members(&self, organization_id: Uuid) -> Result<Vec<UserRoles>, AppError>;
+
+    /// 標記電子郵件已驗證，已驗證時保留原本的驗證時間
+    async fn set_email_verified(&self, user_id: Uuid) -> Result<(), AppError>;
+
+    /// 更新密碼雜湊
+    async fn update_password(&self, user_id: Uuid, password_hash: &str) -> Result<(), AppError>;
+
+    /// 查找用戶的個人資料
+    async fn find_profile(&self, user_id: Uuid) -> Result<Option<UserProfile>, AppError>;
+
+    /// 更新個人資料，行銷同意改變時記錄變更時間
+    async fn update_profile(&self, user_id: Uuid, details: &ProfileDetails) -> Result<(), AppError>;
+
+    /// 記錄申請變更、尚未驗證的新電子郵件，取代先前的申請
+    async fn set_pending_email(&self, user_id: Uuid, email: &str) -> Result<(), AppError>;
+
+    /// 以待驗證的新電子郵件取代目前的電子郵件並標記為已驗證，返回新的電子郵件；
+    /// 沒有待驗證的電子郵件時返回 None，新電子郵件已被其他用戶使用時回傳 Conflict
+    async fn confirm_email_change(&self, user_id: Uuid) -> Result<Option<String>, AppError>;
+}
+
+/// 令牌存儲庫介面
+#[async_trait]
+pub trait TokenRepository: Send + Sync {
+    /// 保存新的刷新令牌
+    async fn create_refresh_token(&self, token: &NewRefreshToken) -> Result<(), AppError>;
+
+    /// 根據雜湊查找刷新令牌
+    async fn find_refresh_token(&self, token_hash: &str) -> Result<Option<RefreshToken>, AppError>;
+
+    /// 將未使用、未撤銷的刷新令牌標記為已使用
+    /// 令牌已被使用或撤銷時不做任何變更並返回 false，同一個令牌只能換發一次
+    async fn consume_refresh_token(&self, id: Uuid) -> Result<bool, AppError>;
+
+    /// 撤銷整個 family 的刷新令牌，並將一起簽發、尚未過期的存取令牌記入撤銷清單
+    async fn revoke_family(&self, family_id: Uuid) -> Result<(), AppError>;
+
+    /// 撤銷用戶所有的刷新令牌與尚未過期的存取令牌
+    async fn revoke_user(&self, user_id: Uuid) -> Result<(), AppError>;
+
+    /// 存取令牌是否已撤銷
+    async fn is_revoked(&self, jti: Uuid) -> Result<bool, AppError>;
+
+    /// 保存單次使用的用戶令牌，同一用途先前尚未使用的令牌一併失效
+    /// 到期時間由資料庫以目前時間加上 `ttl` 計算
+    async fn create_user_token(
+        &self,
+        user_id: Uuid,
+        purpose: UserTokenPurpose,
+        token_hash: &str,
+        ttl: Duration,
+    ) -> Result<(), AppError>;
+
+    /// 查找有效（未使用、未過期）的用戶令牌所屬用戶，不標記為已使用
+    async fn find_user_token(&self, purpose: UserTokenPurpose, token_hash: &str) -> Result<Option<Uuid>, AppError>;
+
+    /// 使用用戶令牌並返回所屬用戶
+    /// 令牌不存在、用途不符、已使用或已過期時返回 None，同一個令牌只能使用一次
+    async fn consume_user_token(&self, purpose: UserTokenPurpose, token_hash: &str) -> Result<Option<Uuid>, AppError>;
+}
+
+/// 登入失敗記錄存儲庫介面
+/// 可使用 PostgreSQL（多個實例共用）或記憶體（單一實例）實作
+#[async_trait]
+pub trait LoginAttemptRepository: Send + Sync {
+    /// 目前是否鎖定中
+    async fn is_locked(&self, key: &str) -> Result<bool, AppError>;
+
+    /// 記錄一次登入失敗並返回目前的失敗次數
+    /// 最後一次失敗與鎖定到期時間都已超過 `window` 時，失敗次數從 1 重新計算
+    async fn record_failure(&self, key: &str, window: Duration) -> Result<i32, AppError>;
+
+    /// 從現在起鎖定 `lockout` 的時間
+    async fn lock(&self, key: &str, lockout: Duration) -> Result<(), AppError>;
+
+    /// 清除登入失敗記錄與鎖定
+    async fn clear(&self, key: &str) -> Result<(), AppError>;
+}
+
+/// 兩步驟驗證存儲庫介面
+#[async_trait]
+pub trait TwoFactorRepository: Send + Sync {
+    /// 查找用戶的 TOTP 設定
+    async fn find(&self, user_id: Uuid) -> Result<Option<UserTotp>, AppError>;
+
+    /// 保存設定中的密鑰，取代先前尚未啟用的密鑰；已啟用時不做任何變更並返回 false
+    async fn save_pending(&self, user_id: Uuid, secret: &str) -> Result<bool, AppError>;
+
+    /// 啟用兩步驟驗證，記錄確認時使用的時間步並保存救援碼雜湊
+    /// 已啟用時不做任何變更並返回 false
+    async fn enable(&self, user_id: Uuid, step: i64, recovery_code_hashes: &[String]) -> Result<bool, AppError>;
+
+    /// 記錄使用的時間步，時間步不晚於上次使用的時間步時返回 false，同一組驗證碼只能使用一次
+    async fn use_step(&self, user_id: Uuid, step: i64) -> Result<bool, AppError>;
+
+    /// 使用救援碼，救援碼不存在或已使用時返回 false
+    async fn use_recovery_code(&self, user_id: Uuid, code_hash: &str) -> Result<bool, AppError>;
+
+    /// 以新的救援碼取代所有救援碼
+    async fn replace_recovery_codes(&self, user_id: Uuid, code_hashes: &[String]) -> Result<(), AppError>;
+
+    /// 停用兩步驟驗證，刪除密鑰與救援碼
+    async fn disable(&self, user_id: Uuid) -> Result<(), AppError>;
+}
+
+/// 外部身分存儲庫介面
+#[async_trait]
+pub trait IdentityRepository: Send + Sync {
+    /// 保存授權碼流程的登入狀態，`ttl` 後到期
+    async fn save_login_state(
+        &self,
+        state_hash: &str,
+        provider: &str,
+        login_state: &OidcLoginState,
+        ttl: Duration,
+    ) -> Result<(), AppError>;
+
+    /// 取出並刪除登入狀態，不存在、身分提供者不符或已過期時返回 None
+    async fn take_login_state(&self, state_hash: &str, provider: &str) -> Result<Option<OidcLoginState>, AppError>;
+
+    /// 根據外部身分查找已連結的用戶，並記錄登入時間
+    async fn find_user_id(&self, provider: &str, subject: &str) -> Result<Option<Uuid>, AppError>;
+
+    /// 將外部身分連結到既有用戶
+    async fn link(&self, user_id: Uuid, identity: &ExternalIdentity) -> Result<(), AppError>;
+
+    /// 以外部身分創建沒有密碼、電子郵件已驗證的新用戶，並指派顧客角色
+    async fn create_user(&self, email: &str, identity: &ExternalIdentity) -> Result<Uuid, AppError>;
+
+    /// 查找用戶已連結的外部身分
+    async fn find_by_user(&self, user_id: Uuid) -> Result<Vec<LinkedIdentity>, AppError>;
+}
+
+/// 登入記錄存儲庫介面
+#[async_trait]
+pub trait LoginEventRepository: Send + Sync {
+    /// 記錄一次成功登入
+    async fn record(&self, user_id: Uuid, method: &LoginMethod, ip: Option<IpAddr>) -> Result<(), AppError>;
+
+    /// 查找用戶的登入記錄，由新到舊排列
+    async fn find_by_user(&self, user_id: Uuid) -> Result<Vec<LoginEvent>, AppError>;
 }
@@ -0,0 +1,155 @@
+use chrono::NaiveDateTime;
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+use utoipa::ToSchema;
+use validator::Validate;
+
+use crate::domain::order::model::{CreateOrder, Order, OrderStatus};
+
+/// 售票處現場收款的付款記錄供應商名稱
+/// 這類付款不經過金流閘道，退款時由售票處直接處理
+pub const BOX_OFFICE_PROVIDER: &str = "box_office";
+
+/// 現場付款方式
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum PaymentMethod {
+    /// 現金，計入錢櫃
+    Cash,
+    /// 刷卡，以刷卡機交易序號對帳
+    Card,
+}
+
+impl PaymentMethod {
+    /// 資料庫中的字串表示
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            PaymentMethod::Cash => "cash",
+            PaymentMethod::Card => "card",
+        }
+    }
+
+    /// 從資料庫字串解析
+    pub fn parse(value: &str) -> Option<Self> {
+        match value {
+            "cash" => Some(PaymentMethod::Cash),
+            "card" => Some(PaymentMethod::Card),
+            _ => None,
+        }
+    }
+}
+
+/// 錢櫃班次
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct DrawerSession {
+    pub id: Uuid,
+    pub staff_id: Uuid,
+    pub staff_email: String,
+    /// 開班時放入錢櫃的零用金
+    pub opening_float: f64,
+    /// 交班時點算的現金，未交班時為空
+    pub counted_cash: Option<f64>,
+    pub note: Option<String>,
+    pub opened_at: NaiveDateTime,
+    pub closed_at: Option<NaiveDateTime>,
+}
+
+/// 開啟錢櫃班次輸入
+#[derive(Debug, Clone, Deserialize, Validate, ToSchema)]
+pub struct OpenDrawer {
+    #[validate(range(min = 0.0))]
+    pub opening_float: f64,
+}
+
+/// 結束錢櫃班次輸入
+#[derive(Debug, Clone, Deserialize, Validate, ToSchema)]
+pub struct CloseDrawer {
+    #[validate(range(min = 0.0))]
+    pub counted_cash: f64,
+    pub note: Option<String>,
+}
+
+/// 現場購票的顧客
+/// email 已註冊時訂單歸屬該帳號；未註冊且提供密碼時當場建立帳號
+#[derive(Debug, Clone, Deserialize, Validate, ToSchema)]
+pub struct BoxOfficeCustomer {
+    #[validate(email)]
+    pub email: String,
+    #[validate(length(min = 1))]
+    pub password: Option<String>,
+}
+
+/// 現場售票輸入
+#[derive(Debug, Clone, Deserialize, Validate, ToSchema)]
+pub struct CreateBoxOfficeOrder {
+    /// 與線上下單相同的票種、張數、座位與加購商品
+    #[serde(flatten)]
+    #[validate]
+    pub order: CreateOrder,
+    pub payment_method: PaymentMethod,
+    /// 現金付款時收取的金額，不可少於訂單總額
+    #[validate(range(min = 0.0))]
+    pub cash_tendered: Option<f64>,
+    /// 刷卡付款時的刷卡機交易序號
+    pub card_reference: Option<String>,
+    /// 不提供時為匿名訂單
+    #[validate]
+    pub customer: Option<BoxOfficeCustomer>,
+}
+
+/// 待寫入的現場售票記錄
+#[derive(Debug, Clone)]
+pub struct NewBoxOfficeSale {
+    pub order_id: Uuid,
+    pub session_id: Uuid,
+    pub staff_id: Uuid,
+    pub payment_method: PaymentMethod,
+    pub amount: f64,
+    pub cash_tendered: Option<f64>,
+    pub change_due: Option<f64>,
+    pub card_reference: Option<String>,
+}
+
+/// 現場售票記錄
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct BoxOfficeSale {
+    pub order_id: Uuid,
+    pub session_id: Uuid,
+    pub staff_id: Uuid,
+    pub payment_method: PaymentMethod,
+    pub amount: f64,
+    pub cash_tendered: Option<f64>,
+    pub change_due: Option<f64>,
+    pub card_reference: Option<String>,
+    pub quantity: i32,
+    /// 訂單目前的狀態，退款後為 refunded
+    pub order_status: OrderStatus,
+    pub created_at: NaiveDateTime,
+}
+
+/// 現場售票結果
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct BoxOfficeSaleResponse {
+    pub order: Order,
+    pub sale: BoxOfficeSale,
+}
+
+/// 錢櫃對帳報告
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct DrawerReport {
+    pub session: DrawerSession,
+    pub orders: i64,
+    pub tickets: i64,
+    /// 現金售票總額
+    pub cash_sales: f64,
+    /// 刷卡售票總額
+    pub card_sales: f64,
+    /// 本班次現金售出、之後已退款的金額，視為已從錢櫃退還
+    pub cash_refunds: f64,
+    /// 應有現金：零用金 + 現金售票 - 現金退款
+    pub expected_cash: f64,
+    pub counted_cash: Option<f64>,
+    /// 點算現金與應有現金的差額，正數為溢收、負數為短少
+    pub variance: Option<f64>,
+    pub sales: Vec<BoxOfficeSale>,
+}
@@ -0,0 +1,29 @@
+use async_trait::async_trait;
+use uuid::Uuid;
+
+use crate::domain::box_office::model::{BoxOfficeSale, DrawerSession, NewBoxOfficeSale};
+use crate::domain::organization::model::Tenant;
+use crate::utils::error::AppError;
+
+/// 售票處存儲庫接口
+#[async_trait]
+pub trait BoxOfficeRepository: Send + Sync {
+    /// 開啟錢櫃班次，工作人員已有未結束的班次時返回 Conflict
+    async fn open_session(&self, staff_id: Uuid, opening_float: f64) -> Result<DrawerSession, AppError>;
+    
+    /// 查找工作人員未結束的班次
+    async fn find_open_session(&self, staff_id: Uuid) -> Result<Option<DrawerSession>, AppError>;
+    
+    /// 根據 ID 查找租戶範圍內的班次，以開班工作人員所屬的主辦單位判斷
+    async fn find_session(&self, id: Uuid, tenant: Tenant) -> Result<Option<DrawerSession>, AppError>;
+    
+    /// 結束班次並記錄點算現金
+    /// 班次已結束時不做任何變更並返回 None
+    async fn close_session(&self, id: Uuid, counted_cash: f64, note: Option<&str>) -> Result<Option<DrawerSession>, AppError>;
+    
+    /// 記錄現場售票
+    async fn record_sale(&self, sale: &NewBoxOfficeSale) -> Result<BoxOfficeSale, AppError>;
+    
+    /// 查找班次的所有售票記錄
+    async fn find_sales(&self, session_id: Uuid) -> Result<Vec<BoxOfficeSale>, AppError>;
+}
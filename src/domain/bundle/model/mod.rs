@@ -0,0 +1,67 @@
+use chrono::NaiveDateTime;
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+use utoipa::ToSchema;
+use validator::Validate;
+
+use crate::domain::concert::model::TaxSetting;
+
+/// 組合商品模型
+/// 例如多日音樂節通行證或含停車的 VIP 組合，購買時一併扣減所有組合內容的庫存
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct Bundle {
+    pub id: Uuid,
+    pub name: String,
+    pub description: Option<String>,
+    pub price: f64,
+    /// 目前可購買組數；同時受組合本身庫存與各票種剩餘數量限制
+    pub stock: i32,
+    pub tax: TaxSetting,
+    pub components: Vec<BundleComponent>,
+}
+
+/// 組合內容
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct BundleComponent {
+    pub ticket_id: Uuid,
+    pub ticket_type: String,
+    pub concert_id: Uuid,
+    pub concert_title: String,
+    pub concert_date: NaiveDateTime,
+    /// 每組包含的張數
+    pub quantity: i32,
+    /// 組合售價分配給此組合內容的比例，依創建時的票面價值計算，用於主辦單位結算與銷售報表
+    pub revenue_share: f64,
+}
+
+/// 創建組合商品輸入
+#[derive(Debug, Clone, Deserialize, Validate, ToSchema)]
+pub struct CreateBundle {
+    #[validate(length(min = 1))]
+    pub name: String,
+    pub description: Option<String>,
+    #[validate(range(min = 0.0))]
+    pub price: f64,
+    #[validate(range(min = 0))]
+    pub stock: i32,
+    #[serde(default)]
+    #[validate]
+    pub tax: TaxSetting,
+    #[validate(length(min = 1))]
+    #[validate]
+    pub components: Vec<BundleComponentInput>,
+}
+
+/// 組合內容輸入
+#[derive(Debug, Clone, Serialize, Deserialize, Validate, ToSchema)]
+pub struct BundleComponentInput {
+    pub ticket_id: Uuid,
+    #[serde(default = "default_component_quantity")]
+    #[validate(range(min = 1))]
+    pub quantity: i32,
+}
+
+/// 組合內容預設每組一張
+fn default_component_quantity() -> i32 {
+    1
+}
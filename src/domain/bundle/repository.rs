@@ -0,0 +1,20 @@
+use async_trait::async_trait;
+use uuid::Uuid;
+
+use crate::domain::bundle::model::{Bundle, CreateBundle};
+use crate::domain::organization::model::Tenant;
+use crate::utils::error::AppError;
+
+/// 組合商品存儲庫接口
+#[async_trait]
+pub trait BundleRepository: Send + Sync {
+    /// 根據 ID 查找租戶範圍內的組合商品
+    /// 所有組合內容的票種都屬於租戶範圍的組合商品才在範圍內
+    async fn find_by_id(&self, id: Uuid, tenant: Tenant) -> Result<Option<Bundle>, AppError>;
+    
+    /// 獲取租戶範圍內所有組合商品
+    async fn find_all(&self, tenant: Tenant) -> Result<Vec<Bundle>, AppError>;
+    
+    /// 創建新組合商品，組合內容的票種不在租戶範圍內時回傳 NotFound
+    async fn create(&self, input: &CreateBundle, tenant: Tenant) -> Result<Bundle, AppError>;
+}
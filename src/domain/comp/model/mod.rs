@@ -0,0 +1,72 @@
+use chrono::NaiveDateTime;
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+use utoipa::{IntoParams, ToSchema};
+use validator::Validate;
+
+use crate::domain::admission::model::Admission;
+use crate::utils::export::ExportFormat;
+
+/// 招待票模型
+/// 不經過訂單與金流，直接從票種庫存扣減並發出入場憑證
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct Comp {
+    pub id: Uuid,
+    pub concert_id: Uuid,
+    pub ticket_id: Uuid,
+    pub ticket_type: String,
+    pub email: String,
+    pub name: Option<String>,
+    pub quantity: i32,
+    pub note: Option<String>,
+    pub issued_by: Option<Uuid>,
+    pub issued_at: NaiveDateTime,
+    pub admissions: Vec<Admission>,
+}
+
+/// 批次發出招待票輸入
+#[derive(Debug, Clone, Deserialize, Validate, ToSchema)]
+pub struct IssueComps {
+    /// 扣減庫存的票種，必須屬於同一場演唱會
+    pub ticket_id: Uuid,
+    /// 每位來賓的張數
+    #[validate(range(min = 1))]
+    pub quantity: i32,
+    #[validate(length(min = 1))]
+    #[validate]
+    pub guests: Vec<CompGuest>,
+    /// 備註，例如媒體名稱或邀請單位
+    pub note: Option<String>,
+}
+
+/// 招待票來賓
+#[derive(Debug, Clone, Serialize, Deserialize, Validate, ToSchema)]
+pub struct CompGuest {
+    #[validate(email)]
+    pub email: String,
+    pub name: Option<String>,
+}
+
+/// 公關名單資料列
+/// 欄位保持扁平，可直接輸出為 CSV
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct GuestListEntry {
+    pub comp_id: Uuid,
+    pub email: String,
+    pub name: Option<String>,
+    pub ticket_type: String,
+    pub quantity: i32,
+    /// 已掃描入場的張數
+    pub checked_in: i64,
+    /// 入場憑證代碼，以空白分隔
+    pub codes: String,
+    pub note: Option<String>,
+    pub issued_at: NaiveDateTime,
+}
+
+/// 公關名單查詢參數
+#[derive(Debug, Clone, Deserialize, IntoParams)]
+pub struct GuestListQuery {
+    /// json（預設）或 csv
+    pub format: Option<ExportFormat>,
+}
@@ -0,0 +1,24 @@
+use async_trait::async_trait;
+use uuid::Uuid;
+
+use crate::domain::comp::model::{Comp, GuestListEntry, IssueComps};
+use crate::domain::organization::model::Tenant;
+use crate::utils::error::AppError;
+
+/// 招待票存儲庫接口
+#[async_trait]
+pub trait CompRepository: Send + Sync {
+    /// 批次發出招待票
+    /// 在同一個交易中扣減票種庫存（含庫存池）並發出入場憑證，庫存不足則整批失敗
+    /// 演唱會或票種不在租戶範圍內時回傳 NotFound
+    async fn issue(
+        &self,
+        concert_id: Uuid,
+        input: &IssueComps,
+        issued_by: Uuid,
+        tenant: Tenant,
+    ) -> Result<Vec<Comp>, AppError>;
+    
+    /// 查找租戶範圍內演唱會的公關名單
+    async fn guest_list(&self, concert_id: Uuid, tenant: Tenant) -> Result<Vec<GuestListEntry>, AppError>;
+}
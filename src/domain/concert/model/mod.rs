@@ -2,6 +2,7 @@ use chrono::NaiveDateTime;
 use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 use utoipa::ToSchema;
+use validator::Validate;
 
 /// 演唱會模型
 #[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
@@ -11,6 +12,9 @@ pub struct Concert {
     pub artist: String,
     pub venue: String,
     pub date: NaiveDateTime,
+    pub tax: TaxSetting,
+    /// 所屬主辦單位
+    pub organization_id: Option<Uuid>,
 }
 
 /// 創建演唱會輸入
@@ -20,4 +24,64 @@ pub struct CreateConcert {
     pub artist: String,
     pub venue: String,
     pub date: NaiveDateTime,
+    /// 所屬主辦單位；主辦單位工作人員只能建立在自己的主辦單位下，可省略
+    pub organization_id: Option<Uuid>,
+}
+
+/// 稅率設定
+/// tax_inclusive 為 true 時票價已含稅（VAT 內含），否則稅額另外加在總額上
+#[derive(Debug, Clone, Default, Serialize, Deserialize, Validate, ToSchema)]
+pub struct TaxSetting {
+    #[validate(range(min = 0.0, max = 1.0))]
+    pub tax_rate: f64,
+    pub tax_inclusive: bool,
+}
+
+/// 服務費類型
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum FeeType {
+    /// 每張票收取
+    PerTicket,
+    /// 每筆訂單收取一次
+    PerOrder,
+}
+
+impl FeeType {
+    /// 資料庫中的字串表示
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            FeeType::PerTicket => "per_ticket",
+            FeeType::PerOrder => "per_order",
+        }
+    }
+
+    /// 從資料庫字串解析
+    pub fn parse(value: &str) -> Option<Self> {
+        match value {
+            "per_ticket" => Some(FeeType::PerTicket),
+            "per_order" => Some(FeeType::PerOrder),
+            _ => None,
+        }
+    }
+}
+
+/// 演唱會服務費
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct ConcertFee {
+    pub id: Uuid,
+    pub concert_id: Uuid,
+    pub name: String,
+    pub fee_type: FeeType,
+    pub amount: f64,
+}
+
+/// 創建服務費輸入
+#[derive(Debug, Clone, Deserialize, Validate, ToSchema)]
+pub struct CreateConcertFee {
+    #[validate(length(min = 1))]
+    pub name: String,
+    pub fee_type: FeeType,
+    #[validate(range(min = 0.0))]
+    pub amount: f64,
 }
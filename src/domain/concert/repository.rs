@@ -1,18 +1,28 @@
 use async_trait::async_trait;
 use uuid::Uuid;
 
-use crate::domain::concert::model::{Concert, CreateConcert};
+use crate::domain::concert::model::{Concert, ConcertFee, CreateConcert, CreateConcertFee, TaxSetting};
+use crate::domain::organization::model::Tenant;
 use crate::utils::error::AppError;
 
 /// 演唱會存儲庫接口
 #[async_trait]
 pub trait ConcertRepository: Send + Sync {
-    /// 根據 ID 查找演唱會
-    async fn find_by_id(&self, id: Uuid) -> Result<Option<Concert>, AppError>;
+    /// 根據 ID 查找租戶範圍內的演唱會
+    async fn find_by_id(&self, id: Uuid, tenant: Tenant) -> Result<Option<Concert>, AppError>;
     
     /// 獲取所有演唱會
     async fn find_all(&self) -> Result<Vec<Concert>, AppError>;
     
     /// 創建新演唱會
-    async fn create(&self, input: &CreateConcert) -> Result<Concert, AppError>;
+    async fn create(&self, input: &CreateConcert, organization_id: Option<Uuid>) -> Result<Concert, AppError>;
+    
+    /// 更新演唱會稅率設定
+    async fn update_tax(&self, id: Uuid, tax: &TaxSetting, tenant: Tenant) -> Result<Option<Concert>, AppError>;
+    
+    /// 獲取演唱會的所有服務費
+    async fn find_fees(&self, concert_id: Uuid) -> Result<Vec<ConcertFee>, AppError>;
+    
+    /// 新增演唱會服務費，演唱會不在租戶範圍內時返回 None
+    async fn create_fee(&self, concert_id: Uuid, input: &CreateConcertFee, tenant: Tenant) -> Result<Option<ConcertFee>, AppError>;
 }
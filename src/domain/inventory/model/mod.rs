@@ -0,0 +1,68 @@
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+use utoipa::ToSchema;
+use validator::Validate;
+
+/// 共用庫存池模型
+/// 多個票種共用同一塊實體容量，購買任一票種都會同時扣減池的剩餘數量
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct InventoryPool {
+    pub id: Uuid,
+    pub concert_id: Uuid,
+    pub name: String,
+    /// 池的總容量
+    pub capacity: i32,
+    /// 尚未售出的數量
+    pub remaining: i32,
+}
+
+/// 庫存池中的票種
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct PoolTicket {
+    pub ticket_id: Uuid,
+    pub ticket_type: String,
+    /// 票種本身的剩餘配額
+    pub stock: i32,
+    /// 已售出（含待付款）數量
+    pub sold: i32,
+}
+
+/// 庫存池視圖（包含所屬票種）
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct InventoryPoolView {
+    #[serde(flatten)]
+    pub pool: InventoryPool,
+    pub tickets: Vec<PoolTicket>,
+}
+
+/// 創建庫存池輸入
+#[derive(Debug, Clone, Deserialize, Validate, ToSchema)]
+pub struct CreateInventoryPool {
+    #[validate(length(min = 1))]
+    pub name: String,
+    #[validate(range(min = 0))]
+    pub capacity: i32,
+}
+
+/// 調整庫存池容量輸入
+/// 剩餘數量隨容量增減，已售出的部分不受影響
+#[derive(Debug, Clone, Deserialize, Validate, ToSchema)]
+pub struct UpdatePoolCapacity {
+    #[validate(range(min = 0))]
+    pub capacity: i32,
+}
+
+/// 指定票種庫存池輸入，pool_id 為 null 時移出庫存池
+#[derive(Debug, Clone, Deserialize, ToSchema)]
+pub struct AssignPool {
+    pub pool_id: Option<Uuid>,
+}
+
+/// 票種間移轉未售出配額輸入
+#[derive(Debug, Clone, Deserialize, Validate, ToSchema)]
+pub struct MoveAllocation {
+    pub from_ticket_id: Uuid,
+    pub to_ticket_id: Uuid,
+    #[validate(range(min = 1))]
+    pub quantity: i32,
+}
@@ -0,0 +1,33 @@
+use async_trait::async_trait;
+use uuid::Uuid;
+
+use crate::domain::inventory::model::{CreateInventoryPool, InventoryPool, PoolTicket};
+use crate::domain::organization::model::Tenant;
+use crate::utils::error::AppError;
+
+/// 庫存池存儲庫接口
+#[async_trait]
+pub trait InventoryRepository: Send + Sync {
+    /// 根據 ID 查找租戶範圍內的庫存池
+    async fn find_pool(&self, id: Uuid, tenant: Tenant) -> Result<Option<InventoryPool>, AppError>;
+    
+    /// 查找演唱會的所有庫存池
+    async fn find_pools_by_concert_id(&self, concert_id: Uuid, tenant: Tenant) -> Result<Vec<InventoryPool>, AppError>;
+    
+    /// 查找庫存池中租戶範圍內的票種
+    async fn find_pool_tickets(&self, pool_id: Uuid, tenant: Tenant) -> Result<Vec<PoolTicket>, AppError>;
+    
+    /// 創建庫存池，剩餘數量等於容量；演唱會不在租戶範圍內時返回 None
+    async fn create_pool(&self, concert_id: Uuid, input: &CreateInventoryPool, tenant: Tenant) -> Result<Option<InventoryPool>, AppError>;
+    
+    /// 調整庫存池容量；已售出數量超過新容量時回傳 Conflict
+    async fn update_capacity(&self, id: Uuid, capacity: i32, tenant: Tenant) -> Result<Option<InventoryPool>, AppError>;
+    
+    /// 指定票種所屬的庫存池
+    /// 票種已售出的數量從新池扣除並歸還給原本的池；新池不足時回傳 Conflict
+    async fn assign_ticket(&self, ticket_id: Uuid, pool_id: Option<Uuid>, tenant: Tenant) -> Result<(), AppError>;
+    
+    /// 將未售出配額從一個票種移到另一個票種，兩者的原始配額與已售出數量不變
+    /// 來源票種剩餘配額不足時回傳 false，兩個票種不屬於同一個庫存池時回傳 BadRequest
+    async fn move_allocation(&self, from_ticket_id: Uuid, to_ticket_id: Uuid, quantity: i32, tenant: Tenant) -> Result<bool, AppError>;
+}
@@ -0,0 +1,388 @@
+use chrono::NaiveDateTime;
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+use utoipa::ToSchema;
+
+use crate::domain::order::model::PriceBreakdown;
+use crate::utils::money::round_money;
+
+/// 會計科目
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum LedgerAccount {
+    /// 顧客（應收／已收顧客款項）
+    Customer,
+    /// 平台收入
+    PlatformRevenue,
+    /// 應付主辦單位
+    OrganizerPayable,
+    /// 應付稅款
+    TaxPayable,
+    /// 平台現金（撥款給主辦單位時使用）
+    Cash,
+}
+
+impl LedgerAccount {
+    /// 資料庫中的字串表示
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            LedgerAccount::Customer => "customer",
+            LedgerAccount::PlatformRevenue => "platform_revenue",
+            LedgerAccount::OrganizerPayable => "organizer_payable",
+            LedgerAccount::TaxPayable => "tax_payable",
+            LedgerAccount::Cash => "cash",
+        }
+    }
+
+    /// 從資料庫字串解析
+    pub fn parse(value: &str) -> Option<Self> {
+        match value {
+            "customer" => Some(LedgerAccount::Customer),
+            "platform_revenue" => Some(LedgerAccount::PlatformRevenue),
+            "organizer_payable" => Some(LedgerAccount::OrganizerPayable),
+            "tax_payable" => Some(LedgerAccount::TaxPayable),
+            "cash" => Some(LedgerAccount::Cash),
+            _ => None,
+        }
+    }
+}
+
+/// 分錄類型
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum LedgerEntryType {
+    Payment,
+    Fee,
+    Tax,
+    Discount,
+    Refund,
+    Payout,
+    Commission,
+}
+
+impl LedgerEntryType {
+    /// 資料庫中的字串表示
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            LedgerEntryType::Payment => "payment",
+            LedgerEntryType::Fee => "fee",
+            LedgerEntryType::Tax => "tax",
+            LedgerEntryType::Discount => "discount",
+            LedgerEntryType::Refund => "refund",
+            LedgerEntryType::Payout => "payout",
+            LedgerEntryType::Commission => "commission",
+        }
+    }
+
+    /// 從資料庫字串解析
+    pub fn parse(value: &str) -> Option<Self> {
+        match value {
+            "payment" => Some(LedgerEntryType::Payment),
+            "fee" => Some(LedgerEntryType::Fee),
+            "tax" => Some(LedgerEntryType::Tax),
+            "discount" => Some(LedgerEntryType::Discount),
+            "refund" => Some(LedgerEntryType::Refund),
+            "payout" => Some(LedgerEntryType::Payout),
+            "commission" => Some(LedgerEntryType::Commission),
+            _ => None,
+        }
+    }
+}
+
+/// 傳票中的一筆分錄
+#[derive(Debug, Clone, PartialEq)]
+pub struct JournalLine {
+    pub account: LedgerAccount,
+    pub entry_type: LedgerEntryType,
+    pub debit: f64,
+    pub credit: f64,
+}
+
+/// 傳票：一組借貸平衡的分錄，必須在同一個資料庫交易中寫入
+#[derive(Debug, Clone)]
+pub struct Journal {
+    pub id: Uuid,
+    pub order_id: Option<Uuid>,
+    /// 結算相關傳票（抽成、撥款）所屬的演唱會
+    pub concert_id: Option<Uuid>,
+    pub memo: String,
+    pub lines: Vec<JournalLine>,
+}
+
+impl Journal {
+    /// 創建空白傳票
+    fn new(order_id: Option<Uuid>, memo: impl Into<String>) -> Self {
+        Self {
+            id: Uuid::new_v4(),
+            order_id,
+            concert_id: None,
+            memo: memo.into(),
+            lines: Vec::new(),
+        }
+    }
+
+    /// 新增借方分錄，金額為零時略過
+    fn debit(mut self, account: LedgerAccount, entry_type: LedgerEntryType, amount: f64) -> Self {
+        if amount > 0.0 {
+            self.lines.push(JournalLine { account, entry_type, debit: amount, credit: 0.0 });
+        }
+        self
+    }
+
+    /// 新增貸方分錄，金額為零時略過
+    fn credit(mut self, account: LedgerAccount, entry_type: LedgerEntryType, amount: f64) -> Self {
+        if amount > 0.0 {
+            self.lines.push(JournalLine { account, entry_type, debit: 0.0, credit: amount });
+        }
+        self
+    }
+
+    /// 訂單收款傳票
+    ///
+    /// 借：顧客（實收總額）、應付主辦單位（折扣）
+    /// 貸：應付主辦單位（票價）、平台收入（服務費）、應付稅款（稅額）
+    ///
+    /// 含稅價格時稅額已包含在票價與服務費中，依金額比例從兩者扣除
+    pub fn payment(order_id: Uuid, breakdown: &PriceBreakdown) -> Self {
+        let (ticket_tax, fee_tax) = breakdown.tax_split();
+
+        Self::new(Some(order_id), "訂單收款")
+            .debit(LedgerAccount::Customer, LedgerEntryType::Payment, breakdown.total)
+            .debit(LedgerAccount::OrganizerPayable, LedgerEntryType::Discount, breakdown.discount_total)
+            .credit(LedgerAccount::OrganizerPayable, LedgerEntryType::Payment, round_money(breakdown.merchandise_total() - ticket_tax))
+            .credit(LedgerAccount::PlatformRevenue, LedgerEntryType::Fee, round_money(breakdown.fee_total - fee_tax))
+            .credit(LedgerAccount::TaxPayable, LedgerEntryType::Tax, breakdown.tax_total)
+    }
+
+    /// 平台抽成傳票：借 應付主辦單位，貸 平台收入
+    pub fn commission(concert_id: Uuid, amount: f64) -> Self {
+        let mut journal = Self::new(None, "主辦單位結算抽成")
+            .debit(LedgerAccount::OrganizerPayable, LedgerEntryType::Commission, amount)
+            .credit(LedgerAccount::PlatformRevenue, LedgerEntryType::Commission, amount);
+        journal.concert_id = Some(concert_id);
+        journal
+    }
+
+    /// 撥款傳票：借 應付主辦單位，貸 現金
+    pub fn payout(concert_id: Uuid, amount: f64) -> Self {
+        let mut journal = Self::new(None, "主辦單位結算撥款")
+            .debit(LedgerAccount::OrganizerPayable, LedgerEntryType::Payout, amount)
+            .credit(LedgerAccount::Cash, LedgerEntryType::Payout, amount);
+        journal.concert_id = Some(concert_id);
+        journal
+    }
+
+    /// 訂單退款傳票：沖銷收款傳票的每一筆分錄
+    pub fn refund(order_id: Uuid, breakdown: &PriceBreakdown) -> Self {
+        Self::reversal(&Self::payment(order_id, breakdown), LedgerEntryType::Refund, "訂單退款")
+    }
+
+    /// 沖銷傳票：借貸對調
+    pub fn reversal(original: &Journal, entry_type: LedgerEntryType, memo: impl Into<String>) -> Self {
+        let mut journal = Self::new(original.order_id, memo);
+        journal.concert_id = original.concert_id;
+        journal.lines = original
+            .lines
+            .iter()
+            .map(|line| JournalLine {
+                account: line.account,
+                entry_type,
+                debit: line.credit,
+                credit: line.debit,
+            })
+            .collect();
+        journal
+    }
+
+    /// 借貸總額是否相等
+    pub fn is_balanced(&self) -> bool {
+        let debit: f64 = self.lines.iter().map(|line| line.debit).sum();
+        let credit: f64 = self.lines.iter().map(|line| line.credit).sum();
+        (debit - credit).abs() < 0.005
+    }
+}
+
+/// 已寫入的分錄
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct LedgerEntry {
+    pub id: i64,
+    pub journal_id: Uuid,
+    pub order_id: Option<Uuid>,
+    pub account: LedgerAccount,
+    pub entry_type: LedgerEntryType,
+    pub debit: f64,
+    pub credit: f64,
+    pub memo: Option<String>,
+    pub created_at: NaiveDateTime,
+}
+
+/// 科目餘額
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct AccountBalance {
+    pub account: LedgerAccount,
+    pub debit: f64,
+    pub credit: f64,
+    /// 借方減貸方
+    pub balance: f64,
+}
+
+/// 借貸不平衡的傳票
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct JournalImbalance {
+    pub journal_id: Uuid,
+    pub debit: f64,
+    pub credit: f64,
+    pub difference: f64,
+}
+
+/// 分錄與訂單金額不一致的訂單
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct OrderLedgerMismatch {
+    pub order_id: Uuid,
+    pub status: String,
+    /// 依訂單狀態應收的淨額（已付款為總額，其他狀態為零）
+    pub expected: f64,
+    /// 分錄中顧客科目的淨借方金額
+    pub recorded: f64,
+}
+
+/// 對帳報告
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct ReconciliationReport {
+    pub generated_at: NaiveDateTime,
+    /// 所有傳票與訂單都一致時為 true
+    pub is_balanced: bool,
+    pub account_balances: Vec<AccountBalance>,
+    pub unbalanced_journals: Vec<JournalImbalance>,
+    pub order_mismatches: Vec<OrderLedgerMismatch>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::concert::model::{ConcertFee, FeeType, TaxSetting};
+
+    fn fees() -> Vec<ConcertFee> {
+        vec![ConcertFee {
+            id: Uuid::nil(),
+            concert_id: Uuid::nil(),
+            name: "Booking".to_string(),
+            fee_type: FeeType::PerTicket,
+            amount: 5.5,
+        }]
+    }
+
+    fn breakdown(discount: f64, tax_inclusive: bool) -> PriceBreakdown {
+        PriceBreakdown::calculate(100.0, 3, 25.0, &fees(), discount, &TaxSetting { tax_rate: 0.05, tax_inclusive })
+    }
+
+    /// 科目的淨貸方金額（貸方減借方）
+    fn net_credit(journal: &Journal, account: LedgerAccount) -> f64 {
+        let net: f64 = journal
+            .lines
+            .iter()
+            .filter(|line| line.account == account)
+            .map(|line| line.credit - line.debit)
+            .sum();
+        round_money(net)
+    }
+
+    /// 收款傳票的各科目金額與價格明細一致
+    fn assert_payment_matches(breakdown: &PriceBreakdown) {
+        let journal = Journal::payment(Uuid::new_v4(), breakdown);
+        let (ticket_tax, fee_tax) = breakdown.tax_split();
+
+        assert!(journal.is_balanced());
+        assert_eq!(net_credit(&journal, LedgerAccount::Customer), -breakdown.total);
+        assert_eq!(
+            net_credit(&journal, LedgerAccount::OrganizerPayable),
+            round_money(breakdown.merchandise_total() - ticket_tax - breakdown.discount_total)
+        );
+        assert_eq!(net_credit(&journal, LedgerAccount::PlatformRevenue), round_money(breakdown.fee_total - fee_tax));
+        assert_eq!(net_credit(&journal, LedgerAccount::TaxPayable), breakdown.tax_total);
+    }
+
+    #[test]
+    fn payment_matches_breakdown_with_exclusive_tax() {
+        assert_payment_matches(&breakdown(0.0, false));
+    }
+
+    #[test]
+    fn payment_matches_breakdown_with_inclusive_tax() {
+        assert_payment_matches(&breakdown(0.0, true));
+    }
+
+    #[test]
+    fn payment_matches_discounted_breakdown() {
+        assert_payment_matches(&breakdown(33.33, false));
+        assert_payment_matches(&breakdown(33.33, true));
+    }
+
+    #[test]
+    fn payment_matches_bundle_breakdown() {
+        // 組合商品沒有服務費，稅率依組合商品設定
+        for tax_inclusive in [false, true] {
+            let tax = TaxSetting { tax_rate: 0.08, tax_inclusive };
+            assert_payment_matches(&PriceBreakdown::calculate(359.99, 2, 0.0, &[], 0.0, &tax));
+        }
+    }
+
+    #[test]
+    fn refund_reverses_every_payment_line() {
+        let breakdown = breakdown(10.0, true);
+        let order_id = Uuid::new_v4();
+        let payment = Journal::payment(order_id, &breakdown);
+        let refund = Journal::refund(order_id, &breakdown);
+
+        assert!(refund.is_balanced());
+        assert_eq!(refund.order_id, Some(order_id));
+        assert_eq!(refund.lines.len(), payment.lines.len());
+        for account in [
+            LedgerAccount::Customer,
+            LedgerAccount::OrganizerPayable,
+            LedgerAccount::PlatformRevenue,
+            LedgerAccount::TaxPayable,
+        ] {
+            assert_eq!(net_credit(&refund, account), -net_credit(&payment, account));
+        }
+        assert!(refund.lines.iter().all(|line| line.entry_type == LedgerEntryType::Refund));
+    }
+
+    #[test]
+    fn commission_and_payout_are_balanced() {
+        let concert_id = Uuid::new_v4();
+        let commission = Journal::commission(concert_id, 12.34);
+        let payout = Journal::payout(concert_id, 111.06);
+
+        assert!(commission.is_balanced());
+        assert_eq!(commission.concert_id, Some(concert_id));
+        assert_eq!(net_credit(&commission, LedgerAccount::OrganizerPayable), -12.34);
+        assert_eq!(net_credit(&commission, LedgerAccount::PlatformRevenue), 12.34);
+
+        assert!(payout.is_balanced());
+        assert_eq!(payout.concert_id, Some(concert_id));
+        assert_eq!(net_credit(&payout, LedgerAccount::OrganizerPayable), -111.06);
+        assert_eq!(net_credit(&payout, LedgerAccount::Cash), 111.06);
+    }
+
+    #[test]
+    fn reversal_swaps_debits_and_credits() {
+        let payout = Journal::payout(Uuid::new_v4(), 50.0);
+        let reversal = Journal::reversal(&payout, LedgerEntryType::Payout, "撥款沖銷");
+
+        assert!(reversal.is_balanced());
+        assert_eq!(reversal.concert_id, payout.concert_id);
+        assert_ne!(reversal.id, payout.id);
+        assert_eq!(net_credit(&reversal, LedgerAccount::Cash), -50.0);
+        assert_eq!(net_credit(&reversal, LedgerAccount::OrganizerPayable), 50.0);
+    }
+
+    #[test]
+    fn zero_amount_lines_are_skipped() {
+        let tax = TaxSetting { tax_rate: 0.0, tax_inclusive: false };
+        let journal = Journal::payment(Uuid::new_v4(), &PriceBreakdown::calculate(100.0, 1, 0.0, &[], 0.0, &tax));
+
+        assert!(journal.is_balanced());
+        assert_eq!(journal.lines.len(), 2);
+    }
+}
@@ -0,0 +1,23 @@
+use async_trait::async_trait;
+use uuid::Uuid;
+
+use crate::domain::ledger::model::{AccountBalance, JournalImbalance, LedgerEntry, OrderLedgerMismatch};
+use crate::utils::error::AppError;
+
+/// 分錄存儲庫接口
+/// 分錄的寫入必須與訂單狀態變更在同一個交易中完成，因此由各業務存儲庫負責寫入，
+/// 這裡只提供查詢與對帳功能
+#[async_trait]
+pub trait LedgerRepository: Send + Sync {
+    /// 獲取訂單的所有分錄
+    async fn find_by_order_id(&self, order_id: Uuid) -> Result<Vec<LedgerEntry>, AppError>;
+    
+    /// 計算各科目餘額
+    async fn account_balances(&self) -> Result<Vec<AccountBalance>, AppError>;
+    
+    /// 查找借貸不平衡的傳票
+    async fn unbalanced_journals(&self) -> Result<Vec<JournalImbalance>, AppError>;
+    
+    /// 查找分錄與訂單金額不一致的訂單
+    async fn order_mismatches(&self) -> Result<Vec<OrderLedgerMismatch>, AppError>;
+}
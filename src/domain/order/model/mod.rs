@@ -4,14 +4,90 @@ use uuid::Uuid;
 use utoipa::{IntoParams, ToSchema};
 use validator::Validate;
 
+use crate::domain::admission::model::Admission;
+use crate::domain::bundle::model::BundleComponent;
+use crate::domain::concert::model::{ConcertFee, FeeType, TaxSetting};
+use crate::domain::ledger::model::Journal;
+use crate::domain::payment::model::PaymentStatus;
+use crate::utils::money::round_money;
+
 /// 訂單模型
 #[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
 pub struct Order {
     pub id: Uuid,
-    pub user_id: Uuid,
-    pub ticket_id: Uuid,
+    /// 售票處現場售出給未註冊顧客的訂單沒有購買者
+    pub user_id: Option<Uuid>,
+    /// 購買的票種（與 bundle_id 擇一）
+    pub ticket_id: Option<Uuid>,
+    /// 購買的組合商品（與 ticket_id 擇一）
+    pub bundle_id: Option<Uuid>,
     pub quantity: i32,
+    pub status: OrderStatus,
     pub created_at: NaiveDateTime,
+    pub breakdown: PriceBreakdown,
+}
+
+/// 下單結果
+/// 除了訂單本身，也回傳付款意圖資訊，讓前端在需要時完成付款（例如延遲確認的金流）
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct CheckoutResponse {
+    pub order: Order,
+    pub payment_intent_id: String,
+    pub client_secret: String,
+}
+
+/// 訂單狀態
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum OrderStatus {
+    /// 已保留庫存，等待付款
+    Pending,
+    /// 已付款
+    Paid,
+    /// 退款處理中，等待金流確認
+    Refunding,
+    /// 付款失敗，庫存已釋放
+    Failed,
+    /// 已退款，庫存已釋放
+    Refunded,
+}
+
+impl OrderStatus {
+    /// 資料庫中的字串表示
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            OrderStatus::Pending => "pending",
+            OrderStatus::Paid => "paid",
+            OrderStatus::Refunding => "refunding",
+            OrderStatus::Failed => "failed",
+            OrderStatus::Refunded => "refunded",
+        }
+    }
+
+    /// 從資料庫字串解析
+    pub fn parse(value: &str) -> Option<Self> {
+        match value {
+            "pending" => Some(OrderStatus::Pending),
+            "paid" => Some(OrderStatus::Paid),
+            "refunding" => Some(OrderStatus::Refunding),
+            "failed" => Some(OrderStatus::Failed),
+            "refunded" => Some(OrderStatus::Refunded),
+            _ => None,
+        }
+    }
+}
+
+/// 訂單狀態變更時在同一個交易中一併完成的處理
+#[derive(Debug, Clone, Copy, Default)]
+pub struct TransitionEffects<'a> {
+    /// 寫入的會計分錄
+    pub journal: Option<&'a Journal>,
+    /// 一併更新的付款記錄與付款狀態
+    pub payment: Option<(Uuid, PaymentStatus)>,
+    /// 是否歸還票種、組合商品與加購商品庫存並釋放座位
+    pub release_stock: bool,
+    /// 觸發此次變更的金流 Webhook 事件（金流名稱、事件 ID），同一事件只會處理一次
+    pub webhook_event: Option<(&'a str, &'a str)>,
 }
 
 /// 創建訂單輸入
@@ -20,6 +96,96 @@ pub struct CreateOrder {
     pub ticket_id: Uuid,
     #[validate(range(min = 1))]
     pub quantity: i32,
+    /// 劃位票種必須指定座位，張數需與座位數相同
+    #[serde(default)]
+    pub seat_ids: Vec<Uuid>,
+    /// 不自行選位時由系統配置最佳位置（與 seat_ids 擇一）
+    pub best_available: Option<BestAvailable>,
+    /// 加購商品
+    #[serde(default)]
+    #[validate]
+    pub addons: Vec<AddonSelection>,
+    /// 演唱會優惠碼，不分大小寫
+    #[validate(length(max = 32))]
+    pub promo_code: Option<String>,
+}
+
+/// 加購商品選擇
+#[derive(Debug, Clone, Serialize, Deserialize, Validate, ToSchema)]
+pub struct AddonSelection {
+    pub variant_id: Uuid,
+    #[validate(range(min = 1))]
+    pub quantity: i32,
+}
+
+/// 待寫入的訂單明細，單價為下單當時的加購商品售價
+#[derive(Debug, Clone)]
+pub struct NewOrderItem {
+    pub variant_id: Uuid,
+    pub quantity: i32,
+    pub unit_price: f64,
+}
+
+/// 訂單明細（加購商品）
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct OrderItem {
+    pub id: Uuid,
+    pub order_id: Uuid,
+    pub addon_id: Uuid,
+    pub addon_name: String,
+    pub variant_name: String,
+    pub quantity: i32,
+    pub unit_price: f64,
+    pub total: f64,
+    /// 現場領取代碼
+    pub code: String,
+    pub status: OrderItemStatus,
+    pub redeemed_at: Option<NaiveDateTime>,
+}
+
+/// 訂單明細狀態
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum OrderItemStatus {
+    /// 訂單待付款
+    Pending,
+    /// 可領取
+    Valid,
+    /// 已領取
+    Redeemed,
+    /// 付款失敗或退款後作廢
+    Void,
+}
+
+impl OrderItemStatus {
+    /// 從資料庫字串解析
+    pub fn parse(value: &str) -> Option<Self> {
+        match value {
+            "pending" => Some(OrderItemStatus::Pending),
+            "valid" => Some(OrderItemStatus::Valid),
+            "redeemed" => Some(OrderItemStatus::Redeemed),
+            "void" => Some(OrderItemStatus::Void),
+            _ => None,
+        }
+    }
+}
+
+/// 購買組合商品輸入
+#[derive(Debug, Clone, Deserialize, Validate, ToSchema)]
+pub struct CreateBundleOrder {
+    pub bundle_id: Uuid,
+    #[validate(range(min = 1))]
+    pub quantity: i32,
+}
+
+/// 最佳位置配位請求
+#[derive(Debug, Clone, Deserialize, ToSchema)]
+pub struct BestAvailable {
+    /// 限定價區，未指定時可配置票種所屬的任何價區
+    pub price_zone_id: Option<Uuid>,
+    /// 找不到同排連續座位時是否允許分開就座
+    #[serde(default)]
+    pub allow_split: bool,
 }
 
 /// 訂單視圖（包含關聯資訊）
@@ -27,11 +193,129 @@ pub struct CreateOrder {
 pub struct OrderView {
     pub id: Uuid,
     pub quantity: i32,
+    pub status: OrderStatus,
     pub created_at: NaiveDateTime,
+    /// 票種名稱；組合商品訂單為組合名稱
     pub ticket_type: String,
     pub price: f64,
+    /// 演唱會名稱；組合商品訂單為組合名稱
     pub concert_title: String,
+    /// 演唱會時間；組合商品訂單為最早一場的時間
     pub concert_date: NaiveDateTime,
+    pub breakdown: PriceBreakdown,
+    /// 訂單保留或已購買的座位，例如「A區 3-12」
+    pub seats: Vec<String>,
+    /// 組合商品訂單的組合內容
+    pub bundle: Option<OrderBundle>,
+    /// 付款後發出的入場憑證
+    pub admissions: Vec<Admission>,
+    /// 加購商品明細
+    pub items: Vec<OrderItem>,
+}
+
+/// 訂單購買的組合商品
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct OrderBundle {
+    pub bundle_id: Uuid,
+    pub name: String,
+    pub components: Vec<BundleComponent>,
+}
+
+/// 訂單價格明細
+/// 下單時計算並寫入訂單，之後票價、服務費或稅率調整都不會改變既有訂單的金額
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, ToSchema)]
+pub struct PriceBreakdown {
+    /// 下單當時的票券單價
+    pub unit_price: f64,
+    /// 票價小計（單價 × 數量）
+    pub subtotal: f64,
+    /// 加購商品小計
+    #[serde(default)]
+    pub addon_total: f64,
+    /// 服務費合計
+    pub fee_total: f64,
+    /// 折扣合計
+    pub discount_total: f64,
+    /// 下單當時的稅率
+    pub tax_rate: f64,
+    /// 票價是否已含稅
+    pub tax_inclusive: bool,
+    /// 稅額
+    pub tax_total: f64,
+    /// 應付總額
+    pub total: f64,
+}
+
+impl PriceBreakdown {
+    /// 計算訂單價格明細
+    ///
+    /// 服務費、加購商品與票價一併課稅；折扣從課稅基礎中扣除。
+    /// 含稅時總額即為課稅基礎，稅額由總額反推；未含稅時稅額另外加在總額上。
+    pub fn calculate(
+        unit_price: f64,
+        quantity: i32,
+        addon_total: f64,
+        fees: &[ConcertFee],
+        discount: f64,
+        tax: &TaxSetting,
+    ) -> Self {
+        let subtotal = round_money(unit_price * quantity as f64);
+        let addon_total = round_money(addon_total);
+
+        let fee_total = round_money(
+            fees.iter()
+                .map(|fee| match fee.fee_type {
+                    FeeType::PerTicket => fee.amount * quantity as f64,
+                    FeeType::PerOrder => fee.amount,
+                })
+                .sum(),
+        );
+
+        // 折扣由主辦單位負擔，不可超過票價與加購商品的總和；服務費屬於平台收入，不會被折抵
+        let discount_total = round_money(discount.clamp(0.0, subtotal + addon_total));
+        let taxable = subtotal + addon_total + fee_total - discount_total;
+
+        let (tax_total, total) = if tax.tax_inclusive {
+            let tax_total = round_money(taxable - taxable / (1.0 + tax.tax_rate));
+            (tax_total, round_money(taxable))
+        } else {
+            let tax_total = round_money(taxable * tax.tax_rate);
+            (tax_total, round_money(taxable + tax_total))
+        };
+
+        Self {
+            unit_price,
+            subtotal,
+            addon_total,
+            fee_total,
+            discount_total,
+            tax_rate: tax.tax_rate,
+            tax_inclusive: tax.tax_inclusive,
+            tax_total,
+            total,
+        }
+    }
+
+    /// 稅額拆分為商品部分（票價與加購商品）與服務費部分 (ticket_tax, fee_tax)
+    ///
+    /// 含稅價格時稅額已包含在票價與服務費中，依金額比例拆分；未含稅時稅額不屬於任何一方
+    pub fn tax_split(&self) -> (f64, f64) {
+        if !self.tax_inclusive {
+            return (0.0, 0.0);
+        }
+        let taxable = self.subtotal + self.addon_total + self.fee_total - self.discount_total;
+        let fee_tax = if taxable > 0.0 {
+            round_money(self.tax_total * self.fee_total / taxable)
+        } else {
+            0.0
+        };
+        (round_money(self.tax_total - fee_tax), fee_tax)
+    }
+
+    /// 歸主辦單位的商品金額（票價與加購商品）
+    pub fn merchandise_total(&self) -> f64 {
+        round_money(self.subtotal + self.addon_total)
+    }
 }
 
 /// 訂單查詢參數
@@ -43,3 +327,87 @@ pub struct OrderQuery {
     pub to: Option<NaiveDate>,
     pub concert_id: Option<Uuid>,
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn fee(fee_type: FeeType, amount: f64) -> ConcertFee {
+        ConcertFee {
+            id: Uuid::nil(),
+            concert_id: Uuid::nil(),
+            name: "fee".to_string(),
+            fee_type,
+            amount,
+        }
+    }
+
+    fn tax(tax_rate: f64, tax_inclusive: bool) -> TaxSetting {
+        TaxSetting { tax_rate, tax_inclusive }
+    }
+
+    #[test]
+    fn per_ticket_fees_scale_with_quantity_and_per_order_fees_do_not() {
+        let fees = [fee(FeeType::PerTicket, 5.5), fee(FeeType::PerOrder, 10.0)];
+
+        let one = PriceBreakdown::calculate(100.0, 1, 0.0, &fees, 0.0, &tax(0.0, false));
+        let three = PriceBreakdown::calculate(100.0, 3, 0.0, &fees, 0.0, &tax(0.0, false));
+
+        assert_eq!(one.fee_total, 15.5);
+        assert_eq!(three.subtotal, 300.0);
+        assert_eq!(three.fee_total, 26.5);
+        assert_eq!(three.total, 326.5);
+    }
+
+    #[test]
+    fn exclusive_tax_is_added_on_top() {
+        let fees = [fee(FeeType::PerOrder, 10.0)];
+        let breakdown = PriceBreakdown::calculate(100.0, 2, 20.0, &fees, 30.0, &tax(0.1, false));
+
+        // 課稅基礎 = 票價 200 + 加購 20 + 服務費 10 - 折扣 30
+        assert_eq!(breakdown.tax_total, 20.0);
+        assert_eq!(breakdown.total, 220.0);
+        assert_eq!(breakdown.tax_split(), (0.0, 0.0));
+        assert_eq!(breakdown.merchandise_total(), 220.0);
+    }
+
+    #[test]
+    fn inclusive_tax_is_extracted_from_total_and_split_by_amount() {
+        let fees = [fee(FeeType::PerOrder, 10.0)];
+        let breakdown = PriceBreakdown::calculate(100.0, 1, 0.0, &fees, 0.0, &tax(0.1, true));
+
+        assert_eq!(breakdown.total, 110.0);
+        assert_eq!(breakdown.tax_total, 10.0);
+        // 服務費佔課稅基礎 10/110
+        assert_eq!(breakdown.tax_split(), (9.09, 0.91));
+    }
+
+    #[test]
+    fn amounts_are_rounded_to_cents() {
+        let breakdown = PriceBreakdown::calculate(33.333, 3, 0.004, &[], 0.0, &tax(0.0825, false));
+
+        assert_eq!(breakdown.subtotal, 100.0);
+        assert_eq!(breakdown.addon_total, 0.0);
+        assert_eq!(breakdown.tax_total, 8.25);
+        assert_eq!(breakdown.total, 108.25);
+
+        let breakdown = PriceBreakdown::calculate(9.99, 1, 0.0, &[], 0.0, &tax(0.0825, false));
+        assert_eq!(breakdown.tax_total, 0.82);
+        assert_eq!(breakdown.total, 10.81);
+    }
+
+    #[test]
+    fn discount_is_clamped_to_merchandise() {
+        let fees = [fee(FeeType::PerOrder, 10.0)];
+        let breakdown = PriceBreakdown::calculate(100.0, 1, 20.0, &fees, 500.0, &tax(0.1, false));
+
+        // 折扣不超過票價與加購商品，服務費照收
+        assert_eq!(breakdown.discount_total, 120.0);
+        assert_eq!(breakdown.tax_total, 1.0);
+        assert_eq!(breakdown.total, 11.0);
+
+        let negative = PriceBreakdown::calculate(100.0, 1, 0.0, &[], -5.0, &tax(0.0, false));
+        assert_eq!(negative.discount_total, 0.0);
+        assert_eq!(negative.total, 100.0);
+    }
+}
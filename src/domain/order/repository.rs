@@ -1,7 +1,12 @@
 use async_trait::async_trait;
+use chrono::Duration;
 use uuid::Uuid;
 
-use crate::domain::order::model::{CreateOrder, Order, OrderQuery, OrderView};
+use crate::domain::order::model::{
+    CreateBundleOrder, CreateOrder, NewOrderItem, Order, OrderQuery, OrderStatus, OrderView, PriceBreakdown,
+    TransitionEffects,
+};
+use crate::domain::organization::model::Tenant;
 use crate::utils::error::AppError;
 
 /// 訂單存儲庫接口
@@ -13,6 +18,52 @@ pub trait OrderRepository: Send + Sync {
     /// 根據用戶 ID 查找訂單
     async fn find_by_user_id(&self, user_id: Uuid, query: &OrderQuery) -> Result<Vec<OrderView>, AppError>;
     
-    /// 創建新訂單
-    async fn create(&self, user_id: Uuid, input: &CreateOrder) -> Result<Order, AppError>;
+    /// 查找租戶範圍內所有用戶的訂單，依創建時間由新到舊排序
+    /// 主辦單位範圍只包含票種全部屬於該主辦單位的訂單
+    async fn find_all(&self, query: &OrderQuery, tenant: Tenant) -> Result<Vec<OrderView>, AppError>;
+    
+    /// 創建新訂單，並寫入下單當時的價格明細
+    /// 票種（含庫存池）庫存在同一個交易中以條件式扣減，不足時整筆失敗並回傳 BadRequest；
+    /// 扣減前鎖定票種重新計價，單價與價格明細不符時回傳 Conflict；
+    /// 有指定座位時在同一個交易中保留座位，任一座位已被保留或售出則整筆失敗；
+    /// 加購商品明細同樣在交易中扣減庫存，任一規格不足則整筆失敗；
+    /// 使用優惠碼時在交易中扣除一次使用次數，優惠碼已過期或已用完則回傳 BadRequest
+    async fn create(
+        &self,
+        user_id: Option<Uuid>,
+        input: &CreateOrder,
+        breakdown: &PriceBreakdown,
+        items: &[NewOrderItem],
+        promo_code_id: Option<Uuid>,
+    ) -> Result<Order, AppError>;
+    
+    /// 創建組合商品訂單，並在同一個交易中扣減組合與所有組合內容的庫存
+    /// 任一庫存不足時整筆失敗並返回 BadRequest
+    async fn create_bundle_order(
+        &self,
+        user_id: Uuid,
+        input: &CreateBundleOrder,
+        breakdown: &PriceBreakdown,
+    ) -> Result<Order, AppError>;
+    
+    /// 根據 ID 查找訂單（不限用戶，供付款與管理流程使用）
+    /// 主辦單位範圍只能找到票種全部屬於該主辦單位的訂單
+    async fn find_order(&self, id: Uuid, tenant: Tenant) -> Result<Option<Order>, AppError>;
+    
+    /// 查找建立超過指定時間仍待付款的訂單
+    async fn find_expired_pending(&self, timeout: Duration) -> Result<Vec<Order>, AppError>;
+    
+    /// 將訂單從指定狀態轉換為新狀態，並在同一個交易中寫入傳票、更新付款狀態與歸還庫存
+    /// 轉為已付款時，訂單保留的座位同時轉為已售出，並發出入場憑證、加購商品明細轉為可領取；
+    /// 轉為失敗或已退款時入場憑證與加購商品明細作廢；歸還庫存時已領取的加購商品不再回到庫存，
+    /// 訂單使用的優惠碼次數一併歸還
+    /// 目前狀態不符或 Webhook 事件已處理過時不做任何變更並返回 false；
+    /// 目前狀態不符時 Webhook 事件仍會被記錄
+    async fn transition_status(
+        &self,
+        id: Uuid,
+        from: OrderStatus,
+        to: OrderStatus,
+        effects: TransitionEffects<'_>,
+    ) -> Result<bool, AppError>;
 }
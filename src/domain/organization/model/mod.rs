@@ -0,0 +1,62 @@
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+use utoipa::ToSchema;
+use validator::Validate;
+
+/// 主辦單位模型
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct Organization {
+    pub id: Uuid,
+    pub name: String,
+    /// 平台抽成比例，例如 0.1 代表 10%
+    pub commission_rate: f64,
+}
+
+/// 創建主辦單位輸入
+#[derive(Debug, Clone, Deserialize, Validate, ToSchema)]
+pub struct CreateOrganization {
+    #[validate(length(min = 1))]
+    pub name: String,
+    #[validate(range(min = 0.0, max = 1.0))]
+    pub commission_rate: f64,
+}
+
+/// 更新抽成比例輸入
+#[derive(Debug, Clone, Deserialize, Validate, ToSchema)]
+pub struct UpdateCommission {
+    #[validate(range(min = 0.0, max = 1.0))]
+    pub commission_rate: f64,
+}
+
+/// 指定演唱會主辦單位輸入
+#[derive(Debug, Clone, Deserialize, ToSchema)]
+pub struct AssignOrganization {
+    pub organization_id: Uuid,
+}
+
+/// 租戶範圍
+/// 管理資料的存儲庫方法都需要傳入租戶範圍，並在 SQL 中依此過濾
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Tenant {
+    /// 平台：管理員與平台工作人員，可存取所有主辦單位的資料
+    Platform,
+    /// 主辦單位：只能存取該主辦單位的資料
+    Organization(Uuid),
+}
+
+impl Tenant {
+    /// 綁定到 SQL 的主辦單位 ID，平台範圍為 NULL 表示不過濾
+    pub fn organization_id(&self) -> Option<Uuid> {
+        match self {
+            Tenant::Platform => None,
+            Tenant::Organization(id) => Some(*id),
+        }
+    }
+}
+
+/// 設定用戶所屬主辦單位輸入
+/// organization_id 為 null 時改為平台工作人員
+#[derive(Debug, Clone, Deserialize, ToSchema)]
+pub struct SetUserOrganization {
+    pub organization_id: Option<Uuid>,
+}
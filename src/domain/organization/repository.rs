@@ -0,0 +1,27 @@
+use async_trait::async_trait;
+use uuid::Uuid;
+
+use crate::domain::organization::model::{CreateOrganization, Organization};
+use crate::utils::error::AppError;
+
+/// 主辦單位存儲庫接口
+#[async_trait]
+pub trait OrganizationRepository: Send + Sync {
+    /// 根據 ID 查找主辦單位
+    async fn find_by_id(&self, id: Uuid) -> Result<Option<Organization>, AppError>;
+    
+    /// 查找演唱會的主辦單位
+    async fn find_by_concert_id(&self, concert_id: Uuid) -> Result<Option<Organization>, AppError>;
+    
+    /// 獲取所有主辦單位
+    async fn find_all(&self) -> Result<Vec<Organization>, AppError>;
+    
+    /// 創建新主辦單位
+    async fn create(&self, input: &CreateOrganization) -> Result<Organization, AppError>;
+    
+    /// 更新抽成比例
+    async fn update_commission(&self, id: Uuid, commission_rate: f64) -> Result<Option<Organization>, AppError>;
+    
+    /// 指定演唱會的主辦單位
+    async fn assign_concert(&self, concert_id: Uuid, organization_id: Uuid) -> Result<bool, AppError>;
+}
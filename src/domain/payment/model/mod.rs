@@ -0,0 +1,68 @@
+use chrono::NaiveDateTime;
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+use utoipa::ToSchema;
+
+/// 付款狀態
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum PaymentStatus {
+    /// 等待金流確認
+    Pending,
+    /// 付款成功
+    Succeeded,
+    /// 付款失敗或被拒絕
+    Failed,
+    /// 已退款
+    Refunded,
+    /// 訂單已失敗後才扣款成功，自動退款失敗，需人工退款
+    RefundRequired,
+}
+
+impl PaymentStatus {
+    /// 資料庫中的字串表示
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            PaymentStatus::Pending => "pending",
+            PaymentStatus::Succeeded => "succeeded",
+            PaymentStatus::Failed => "failed",
+            PaymentStatus::Refunded => "refunded",
+            PaymentStatus::RefundRequired => "refund_required",
+        }
+    }
+
+    /// 從資料庫字串解析
+    pub fn parse(value: &str) -> Option<Self> {
+        match value {
+            "pending" => Some(PaymentStatus::Pending),
+            "succeeded" => Some(PaymentStatus::Succeeded),
+            "failed" => Some(PaymentStatus::Failed),
+            "refunded" => Some(PaymentStatus::Refunded),
+            "refund_required" => Some(PaymentStatus::RefundRequired),
+            _ => None,
+        }
+    }
+}
+
+/// 付款模型
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct Payment {
+    pub id: Uuid,
+    pub order_id: Uuid,
+    pub provider: String,
+    pub intent_id: String,
+    pub amount: f64,
+    pub currency: String,
+    pub status: PaymentStatus,
+    pub created_at: NaiveDateTime,
+}
+
+/// 創建付款記錄輸入
+#[derive(Debug, Clone)]
+pub struct CreatePayment {
+    pub order_id: Uuid,
+    pub provider: String,
+    pub intent_id: String,
+    pub amount: f64,
+    pub currency: String,
+}
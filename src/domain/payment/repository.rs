@@ -0,0 +1,29 @@
+use async_trait::async_trait;
+use uuid::Uuid;
+
+use crate::domain::payment::model::{CreatePayment, Payment};
+use crate::utils::error::AppError;
+
+/// 付款存儲庫接口
+#[async_trait]
+pub trait PaymentRepository: Send + Sync {
+    /// 創建付款記錄
+    async fn create(&self, input: &CreatePayment) -> Result<Payment, AppError>;
+    
+    /// 根據金流意圖 ID 查找付款
+    async fn find_by_intent_id(&self, intent_id: &str) -> Result<Option<Payment>, AppError>;
+    
+    /// 查找訂單最近一筆付款
+    async fn find_by_order_id(&self, order_id: Uuid) -> Result<Option<Payment>, AppError>;
+    
+    /// 取得已失敗訂單的扣款退款權：付款狀態轉為已退款
+    /// 訂單不是失敗狀態、或付款已退款時返回 false，重送的事件不會重複退款
+    async fn claim_late_capture_refund(&self, id: Uuid) -> Result<bool, AppError>;
+
+    /// 自動退款失敗時將已取得退款權的付款標記為待人工退款
+    async fn flag_refund_required(&self, id: Uuid) -> Result<(), AppError>;
+
+    /// 記錄不需變更訂單狀態的 Webhook 事件
+    /// 事件已記錄過時返回 false
+    async fn record_webhook_event(&self, provider: &str, event_id: &str) -> Result<bool, AppError>;
+}
@@ -0,0 +1,175 @@
+use chrono::NaiveDateTime;
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+use utoipa::{IntoParams, ToSchema};
+use validator::Validate;
+
+use crate::domain::admission::model::Admission;
+use crate::domain::auth::model::{LinkedIdentity, LoginEvent, UserProfile};
+use crate::domain::order::model::OrderView;
+
+/// 個人資料匯出格式
+#[derive(Debug, Clone, Copy, Default, Deserialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum DataExportFormat {
+    #[default]
+    Json,
+    /// 每個類別一個 JSON 檔的 ZIP 壓縮檔
+    Zip,
+}
+
+/// 個人資料匯出查詢參數
+#[derive(Debug, Clone, Default, Deserialize, IntoParams)]
+pub struct DataExportQuery {
+    /// json（預設）或 zip
+    pub format: Option<DataExportFormat>,
+}
+
+/// 個人資料匯出
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct DataExport {
+    pub exported_at: NaiveDateTime,
+    pub profile: UserProfile,
+    /// 已連結的社群帳號
+    pub identities: Vec<LinkedIdentity>,
+    /// 所有訂單，包含票券與加購商品明細
+    pub orders: Vec<OrderView>,
+    /// 所有訂單發出的入場憑證
+    pub tickets: Vec<Admission>,
+    pub login_history: Vec<LoginEvent>,
+}
+
+/// 刪除帳號輸入
+#[derive(Debug, Clone, Default, Deserialize, Validate, ToSchema)]
+pub struct DeleteAccountInput {
+    /// 目前的密碼，已設定密碼的用戶必須提供
+    pub current_password: Option<String>,
+    /// 刪除原因，選填
+    #[validate(length(max = 1000))]
+    pub reason: Option<String>,
+}
+
+/// 刪除申請的來源
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum ErasureSource {
+    /// 用戶自行刪除帳號
+    SelfService,
+    /// 客服代為受理，例如以電子郵件或書面提出的申請
+    Support,
+}
+
+impl ErasureSource {
+    /// 資料庫中的字串表示
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ErasureSource::SelfService => "self_service",
+            ErasureSource::Support => "support",
+        }
+    }
+
+    /// 從資料庫字串解析
+    pub fn parse(value: &str) -> Option<Self> {
+        match value {
+            "self_service" => Some(ErasureSource::SelfService),
+            "support" => Some(ErasureSource::Support),
+            _ => None,
+        }
+    }
+}
+
+/// 刪除申請狀態
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum ErasureStatus {
+    /// 等待處理
+    Pending,
+    /// 已匿名化
+    Completed,
+    /// 已拒絕，例如無法確認申請人身分
+    Rejected,
+}
+
+impl ErasureStatus {
+    /// 資料庫中的字串表示
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ErasureStatus::Pending => "pending",
+            ErasureStatus::Completed => "completed",
+            ErasureStatus::Rejected => "rejected",
+        }
+    }
+
+    /// 從資料庫字串解析
+    pub fn parse(value: &str) -> Option<Self> {
+        match value {
+            "pending" => Some(ErasureStatus::Pending),
+            "completed" => Some(ErasureStatus::Completed),
+            "rejected" => Some(ErasureStatus::Rejected),
+            _ => None,
+        }
+    }
+}
+
+/// 刪除帳號申請
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct ErasureRequest {
+    pub id: Uuid,
+    pub user_id: Uuid,
+    pub source: ErasureSource,
+    pub status: ErasureStatus,
+    pub reason: Option<String>,
+    pub requested_at: NaiveDateTime,
+    pub processed_at: Option<NaiveDateTime>,
+}
+
+/// 刪除申請的稽核記錄
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct ErasureRequestEvent {
+    /// requested、completed 或 rejected
+    pub action: String,
+    /// 操作者，用戶自行刪除時為用戶本人
+    pub actor_id: Option<Uuid>,
+    pub note: Option<String>,
+    pub created_at: NaiveDateTime,
+}
+
+/// 刪除申請與稽核記錄
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct ErasureRequestDetail {
+    #[serde(flatten)]
+    pub request: ErasureRequest,
+    pub events: Vec<ErasureRequestEvent>,
+}
+
+/// 刪除帳號的結果
+#[derive(Debug, Clone)]
+pub enum ErasureOutcome {
+    /// 已匿名化
+    Completed,
+    /// 仍有付款中的訂單，申請已排入佇列等待處理
+    Queued(ErasureRequest),
+}
+
+/// 客服受理刪除申請輸入
+#[derive(Debug, Clone, Deserialize, Validate, ToSchema)]
+pub struct CreateErasureRequest {
+    pub user_id: Uuid,
+    #[validate(length(max = 1000))]
+    pub reason: Option<String>,
+}
+
+/// 處理刪除申請輸入
+#[derive(Debug, Clone, Default, Deserialize, Validate, ToSchema)]
+pub struct ProcessErasureRequest {
+    /// 處理說明，會寫入稽核記錄；拒絕時必須提供
+    #[validate(length(max = 1000))]
+    pub note: Option<String>,
+}
+
+/// 刪除申請列表查詢參數
+#[derive(Debug, Clone, Default, Deserialize, IntoParams)]
+pub struct ErasureRequestQuery {
+    /// 依狀態篩選，預設只列出待處理的申請
+    pub status: Option<ErasureStatus>,
+}
@@ -0,0 +1,49 @@
+use async_trait::async_trait;
+use uuid::Uuid;
+
+use crate::domain::privacy::model::{ErasureRequest, ErasureRequestEvent, ErasureSource, ErasureStatus};
+use crate::utils::error::AppError;
+
+/// 個人資料存儲庫接口
+#[async_trait]
+pub trait PrivacyRepository: Send + Sync {
+    /// 用戶是否為唯一的平台管理員
+    async fn is_sole_admin(&self, user_id: Uuid) -> Result<bool, AppError>;
+
+    /// 用戶是否有付款或退款處理中的訂單
+    async fn has_pending_orders(&self, user_id: Uuid) -> Result<bool, AppError>;
+
+    /// 創建待處理的刪除申請並寫入稽核記錄
+    /// 用戶不存在或已匿名化時回傳 NotFound，已有待處理的申請時回傳 Conflict
+    async fn create_request(
+        &self,
+        user_id: Uuid,
+        source: ErasureSource,
+        reason: Option<&str>,
+        actor_id: Uuid,
+    ) -> Result<ErasureRequest, AppError>;
+
+    /// 根據 ID 查找刪除申請
+    async fn find_request(&self, id: Uuid) -> Result<Option<ErasureRequest>, AppError>;
+
+    /// 查找用戶待處理的刪除申請
+    async fn find_pending_request(&self, user_id: Uuid) -> Result<Option<ErasureRequest>, AppError>;
+
+    /// 依狀態列出刪除申請，由舊到新排列
+    async fn list_requests(&self, status: ErasureStatus) -> Result<Vec<ErasureRequest>, AppError>;
+
+    /// 查找刪除申請的稽核記錄
+    async fn find_events(&self, request_id: Uuid) -> Result<Vec<ErasureRequestEvent>, AppError>;
+
+    /// 完成刪除申請：在同一個交易中匿名化用戶、更新申請狀態並寫入稽核記錄
+    /// 申請不存在或已處理時返回 None，用戶是唯一的平台管理員時返回 Conflict
+    async fn complete_request(
+        &self,
+        id: Uuid,
+        actor_id: Uuid,
+        note: Option<&str>,
+    ) -> Result<Option<ErasureRequest>, AppError>;
+
+    /// 拒絕刪除申請並寫入稽核記錄，申請不存在或已處理時返回 None
+    async fn reject_request(&self, id: Uuid, actor_id: Uuid, note: &str) -> Result<Option<ErasureRequest>, AppError>;
+}
@@ -0,0 +1,82 @@
+use chrono::NaiveDateTime;
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+use utoipa::ToSchema;
+use validator::Validate;
+
+use crate::utils::money::round_money;
+
+/// 優惠碼折扣方式
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum DiscountType {
+    /// 依票價小計的百分比折扣
+    Percent,
+    /// 每筆訂單折抵固定金額
+    Amount,
+}
+
+impl DiscountType {
+    /// 資料庫中的字串表示
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            DiscountType::Percent => "percent",
+            DiscountType::Amount => "amount",
+        }
+    }
+
+    /// 從資料庫字串解析
+    pub fn parse(value: &str) -> Option<Self> {
+        match value {
+            "percent" => Some(DiscountType::Percent),
+            "amount" => Some(DiscountType::Amount),
+            _ => None,
+        }
+    }
+}
+
+/// 優惠碼模型
+/// 只適用於所屬演唱會的票種訂單
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct PromoCode {
+    pub id: Uuid,
+    pub concert_id: Uuid,
+    pub code: String,
+    pub discount_type: DiscountType,
+    /// 百分比（1-100）或折抵金額
+    pub amount: f64,
+    /// 可使用次數上限，未設定時不限次數
+    pub max_redemptions: Option<i32>,
+    /// 已使用次數，付款失敗或退款的訂單不計入
+    pub redemptions: i32,
+    pub starts_at: Option<NaiveDateTime>,
+    pub ends_at: Option<NaiveDateTime>,
+}
+
+impl PromoCode {
+    /// 計算訂單的折扣金額
+    /// 百分比折扣以票價小計計算；固定金額折扣每筆訂單折抵一次，不超過票價小計
+    pub fn discount(&self, subtotal: f64) -> f64 {
+        let discount = match self.discount_type {
+            DiscountType::Percent => subtotal * self.amount / 100.0,
+            DiscountType::Amount => self.amount,
+        };
+        round_money(discount.min(subtotal))
+    }
+}
+
+/// 創建優惠碼輸入
+#[derive(Debug, Clone, Deserialize, Validate, ToSchema)]
+pub struct CreatePromoCode {
+    /// 不分大小寫，儲存為大寫
+    #[validate(length(min = 1, max = 32))]
+    pub code: String,
+    pub discount_type: DiscountType,
+    /// 百分比折扣為 1-100，固定金額折扣為折抵金額
+    #[validate(range(min = 0.01))]
+    pub amount: f64,
+    #[validate(range(min = 1))]
+    pub max_redemptions: Option<i32>,
+    pub starts_at: Option<NaiveDateTime>,
+    pub ends_at: Option<NaiveDateTime>,
+}
@@ -0,0 +1,19 @@
+use async_trait::async_trait;
+use uuid::Uuid;
+
+use crate::domain::organization::model::Tenant;
+use crate::domain::promo_code::model::{CreatePromoCode, PromoCode};
+use crate::utils::error::AppError;
+
+/// 優惠碼存儲庫接口
+#[async_trait]
+pub trait PromoCodeRepository: Send + Sync {
+    /// 查找租戶範圍內演唱會的所有優惠碼
+    async fn find_by_concert_id(&self, concert_id: Uuid, tenant: Tenant) -> Result<Vec<PromoCode>, AppError>;
+
+    /// 查找演唱會目前在有效期間內的優惠碼，是否仍有剩餘次數由下單時的條件式更新確認
+    async fn find_active(&self, concert_id: Uuid, code: &str) -> Result<Option<PromoCode>, AppError>;
+
+    /// 創建新優惠碼，演唱會不在租戶範圍內時回傳 NotFound，代碼重複時回傳 Conflict
+    async fn create(&self, concert_id: Uuid, input: &CreatePromoCode, tenant: Tenant) -> Result<PromoCode, AppError>;
+}
@@ -0,0 +1,102 @@
+use chrono::{NaiveDate, NaiveDateTime};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+use utoipa::{IntoParams, ToSchema};
+
+use crate::utils::export::ExportFormat;
+
+/// 報表查詢參數
+/// from、to 為包含首尾的日期區間（依訂單建立時間）
+#[derive(Debug, Clone, Default, Deserialize, IntoParams, ToSchema)]
+pub struct ReportQuery {
+    pub from: Option<NaiveDate>,
+    pub to: Option<NaiveDate>,
+    pub concert_id: Option<Uuid>,
+    /// json（預設）或 csv
+    pub format: Option<ExportFormat>,
+}
+
+/// 時間序列粒度
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum Granularity {
+    Hour,
+    #[default]
+    Day,
+}
+
+impl Granularity {
+    /// PostgreSQL date_trunc 使用的單位
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Granularity::Hour => "hour",
+            Granularity::Day => "day",
+        }
+    }
+}
+
+/// 時間序列查詢參數
+#[derive(Debug, Clone, Default, Deserialize, IntoParams)]
+pub struct TimeSeriesQuery {
+    /// hour 或 day（預設）
+    pub granularity: Option<Granularity>,
+}
+
+/// 購買者排行查詢參數
+#[derive(Debug, Clone, Default, Deserialize, IntoParams)]
+pub struct TopBuyersQuery {
+    /// 回傳筆數，預設 10，最多 100
+    pub limit: Option<i64>,
+}
+
+/// 票種銷售報表
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct TicketSalesReport {
+    pub concert_id: Uuid,
+    pub concert_title: String,
+    pub ticket_id: Uuid,
+    pub ticket_type: String,
+    /// 開賣時的庫存
+    pub initial_stock: i32,
+    /// 已付款訂單的張數
+    pub tickets_sold: i64,
+    /// 已付款訂單的實收總額
+    pub revenue: f64,
+    /// 售出率（百分比）
+    pub sell_through: f64,
+    /// 發出的招待票張數，同樣佔用庫存但不計入售出張數與營收
+    pub comps_issued: i64,
+}
+
+/// 時間序列資料點
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct SalesDataPoint {
+    /// 區間起點（整點或當日零時）
+    pub bucket: NaiveDateTime,
+    pub orders: i64,
+    pub tickets_sold: i64,
+    pub revenue: f64,
+}
+
+/// 購買者排行
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct TopBuyer {
+    pub user_id: Uuid,
+    pub email: String,
+    pub orders: i64,
+    pub tickets: i64,
+    pub total_spent: f64,
+}
+
+/// 演唱會取消率報表
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct CancellationReport {
+    pub concert_id: Uuid,
+    pub concert_title: String,
+    pub total_orders: i64,
+    pub paid_orders: i64,
+    pub refunded_orders: i64,
+    pub failed_orders: i64,
+    /// 退款訂單佔成交訂單（已付款與已退款）的百分比
+    pub cancellation_rate: f64,
+}
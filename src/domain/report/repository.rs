@@ -0,0 +1,33 @@
+use async_trait::async_trait;
+
+use crate::domain::organization::model::Tenant;
+use crate::domain::report::model::{
+    CancellationReport, Granularity, ReportQuery, SalesDataPoint, TicketSalesReport, TopBuyer,
+};
+use crate::utils::error::AppError;
+
+/// 報表存儲庫接口
+/// 彙總資料來自物化視圖，資料新鮮度取決於最後一次重新整理；
+/// 查詢只包含租戶範圍內演唱會的資料
+#[async_trait]
+pub trait ReportRepository: Send + Sync {
+    /// 重新整理報表物化視圖
+    async fn refresh(&self) -> Result<(), AppError>;
+    
+    /// 依演唱會與票種彙總銷售
+    async fn sales_by_ticket(&self, query: &ReportQuery, tenant: Tenant) -> Result<Vec<TicketSalesReport>, AppError>;
+    
+    /// 銷售時間序列
+    async fn time_series(
+        &self,
+        query: &ReportQuery,
+        granularity: Granularity,
+        tenant: Tenant,
+    ) -> Result<Vec<SalesDataPoint>, AppError>;
+    
+    /// 消費金額最高的購買者
+    async fn top_buyers(&self, query: &ReportQuery, limit: i64, tenant: Tenant) -> Result<Vec<TopBuyer>, AppError>;
+    
+    /// 各演唱會的取消率
+    async fn cancellations(&self, query: &ReportQuery, tenant: Tenant) -> Result<Vec<CancellationReport>, AppError>;
+}
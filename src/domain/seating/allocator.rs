@@ -0,0 +1,267 @@
+use std::collections::BTreeMap;
+
+use uuid::Uuid;
+
+/// 配位用的座位資料
+/// 座標 y 越小越靠近舞台，x 為水平位置
+#[derive(Debug, Clone)]
+pub struct AllocationSeat {
+    pub seat_id: Uuid,
+    pub row_id: Uuid,
+    /// 座位在排內的順序，相鄰座位的順序相差 1
+    pub position: i32,
+    pub x: f64,
+    pub y: f64,
+    pub available: bool,
+}
+
+/// 一段同排連續的可選購座位
+#[derive(Debug, Clone)]
+struct Block {
+    seats: Vec<usize>,
+    score: f64,
+}
+
+/// 最佳位置配位
+///
+/// 優先在同一排找 quantity 個連續座位，分數最低（越靠前、越置中）者勝出。
+/// 找不到且允許分開時，每次取剩餘可容納的最長連續區段中分數最佳者，讓分組數最少。
+/// 可選購座位不足或不允許分開而找不到連續座位時返回 None。
+///
+/// 這是純函數：不讀寫資料庫，選出的座位是否仍可保留由呼叫端以條件式更新確認。
+pub fn allocate_best_available(seats: &[AllocationSeat], quantity: usize, allow_split: bool) -> Option<Vec<Uuid>> {
+    if quantity == 0 || seats.iter().filter(|seat| seat.available).count() < quantity {
+        return None;
+    }
+
+    let scorer = Scorer::new(seats);
+    let rows = group_rows(seats);
+    let mut taken = vec![false; seats.len()];
+
+    // 先找完整的連續區段
+    if let Some(block) = best_block(seats, &rows, &taken, quantity, &scorer) {
+        return Some(block.seats.iter().map(|&index| seats[index].seat_id).collect());
+    }
+    if !allow_split {
+        return None;
+    }
+
+    // 分開配位：每次取剩餘需求內最長的連續區段
+    let mut result = Vec::with_capacity(quantity);
+    while result.len() < quantity {
+        let remaining = quantity - result.len();
+        let block = (1..=remaining)
+            .rev()
+            .find_map(|size| best_block(seats, &rows, &taken, size, &scorer))?;
+        for &index in &block.seats {
+            taken[index] = true;
+            result.push(seats[index].seat_id);
+        }
+    }
+    Some(result)
+}
+
+/// 依排分組，排內依座位順序排列（存放座位索引）
+fn group_rows(seats: &[AllocationSeat]) -> Vec<Vec<usize>> {
+    let mut rows: BTreeMap<Uuid, Vec<usize>> = BTreeMap::new();
+    for (index, seat) in seats.iter().enumerate() {
+        rows.entry(seat.row_id).or_default().push(index);
+    }
+    rows.into_values()
+        .map(|mut row| {
+            row.sort_by_key(|&index| seats[index].position);
+            row
+        })
+        .collect()
+}
+
+/// 找出指定長度分數最佳的連續區段
+/// 分數相同時取排序較前者，結果在相同輸入下固定
+fn best_block(
+    seats: &[AllocationSeat],
+    rows: &[Vec<usize>],
+    taken: &[bool],
+    size: usize,
+    scorer: &Scorer,
+) -> Option<Block> {
+    let mut best: Option<Block> = None;
+
+    for row in rows {
+        if row.len() < size {
+            continue;
+        }
+        for window in row.windows(size) {
+            let contiguous = window
+                .windows(2)
+                .all(|pair| seats[pair[1]].position == seats[pair[0]].position + 1);
+            let free = window.iter().all(|&index| seats[index].available && !taken[index]);
+            if !contiguous || !free {
+                continue;
+            }
+
+            let score = scorer.score(seats, window);
+            if best.as_ref().is_none_or(|block| score < block.score) {
+                best = Some(Block { seats: window.to_vec(), score });
+            }
+        }
+    }
+
+    best
+}
+
+/// 區段評分：前後距離與偏離中線距離各自正規化到 0..1 後相加
+struct Scorer {
+    min_y: f64,
+    span_y: f64,
+    center_x: f64,
+    half_span_x: f64,
+}
+
+impl Scorer {
+    /// 以所有座位（含已售出）的範圍作為基準，避免售出狀態改變中線位置
+    fn new(seats: &[AllocationSeat]) -> Self {
+        let min_x = seats.iter().map(|seat| seat.x).fold(f64::INFINITY, f64::min);
+        let max_x = seats.iter().map(|seat| seat.x).fold(f64::NEG_INFINITY, f64::max);
+        let min_y = seats.iter().map(|seat| seat.y).fold(f64::INFINITY, f64::min);
+        let max_y = seats.iter().map(|seat| seat.y).fold(f64::NEG_INFINITY, f64::max);
+
+        Self {
+            min_y,
+            span_y: (max_y - min_y).max(f64::EPSILON),
+            center_x: (min_x + max_x) / 2.0,
+            half_span_x: ((max_x - min_x) / 2.0).max(f64::EPSILON),
+        }
+    }
+
+    fn score(&self, seats: &[AllocationSeat], block: &[usize]) -> f64 {
+        let count = block.len() as f64;
+        let x = block.iter().map(|&index| seats[index].x).sum::<f64>() / count;
+        let y = block.iter().map(|&index| seats[index].y).sum::<f64>() / count;
+
+        (y - self.min_y) / self.span_y + (x - self.center_x).abs() / self.half_span_x
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// 建立一排座位：y 為排號（0 最靠近舞台），x 與 position 為座位順序；
+    /// unavailable 列出已保留或已售出的座位順序
+    fn row(row: u128, len: i32, unavailable: &[i32]) -> Vec<AllocationSeat> {
+        (0..len)
+            .map(|position| AllocationSeat {
+                seat_id: seat_id(row, position),
+                row_id: Uuid::from_u128(row + 1),
+                position,
+                x: position as f64,
+                y: row as f64,
+                available: !unavailable.contains(&position),
+            })
+            .collect()
+    }
+
+    fn seat_id(row: u128, position: i32) -> Uuid {
+        Uuid::from_u128(1000 * (row + 1) + position as u128)
+    }
+
+    /// 將配位結果還原為（排號, 座位順序）並排序
+    fn positions(seats: &[AllocationSeat], result: &[Uuid]) -> Vec<(f64, i32)> {
+        let mut positions: Vec<(f64, i32)> = result
+            .iter()
+            .map(|id| {
+                let seat = seats.iter().find(|seat| seat.seat_id == *id).expect("配位結果包含未知座位");
+                (seat.y, seat.position)
+            })
+            .collect();
+        positions.sort_by(|a, b| a.partial_cmp(b).unwrap());
+        positions
+    }
+
+    #[test]
+    fn allocates_contiguous_seats_in_the_middle_of_a_row() {
+        let seats = row(0, 10, &[]);
+
+        let result = allocate_best_available(&seats, 4, false).unwrap();
+
+        assert_eq!(positions(&seats, &result), vec![(0.0, 3), (0.0, 4), (0.0, 5), (0.0, 6)]);
+    }
+
+    #[test]
+    fn prefers_the_front_row() {
+        let seats = [row(0, 6, &[]), row(1, 6, &[]), row(2, 6, &[])].concat();
+
+        let result = allocate_best_available(&seats, 2, false).unwrap();
+
+        assert_eq!(positions(&seats, &result), vec![(0.0, 2), (0.0, 3)]);
+    }
+
+    #[test]
+    fn moves_back_when_the_front_row_has_no_contiguous_block() {
+        let seats = [row(0, 6, &[1, 4]), row(1, 6, &[])].concat();
+
+        let result = allocate_best_available(&seats, 3, false).unwrap();
+
+        assert!(positions(&seats, &result).iter().all(|&(y, _)| y == 1.0));
+        assert_eq!(result.len(), 3);
+    }
+
+    #[test]
+    fn does_not_split_unless_allowed() {
+        // 每排最多兩個連續空位
+        let seats = [row(0, 5, &[2]), row(1, 5, &[2])].concat();
+
+        assert_eq!(allocate_best_available(&seats, 3, false), None);
+
+        let result = allocate_best_available(&seats, 3, true).unwrap();
+        let positions = positions(&seats, &result);
+        assert_eq!(positions.len(), 3);
+        // 分開時先取最長的區段：其中兩個座位同排相鄰
+        assert!(positions.windows(2).any(|pair| pair[0].0 == pair[1].0 && pair[1].1 == pair[0].1 + 1));
+        assert!(!positions.iter().any(|&(_, position)| position == 2));
+    }
+
+    #[test]
+    fn split_allocation_uses_the_fewest_groups() {
+        let seats = [row(0, 4, &[]), row(1, 4, &[])].concat();
+
+        let result = allocate_best_available(&seats, 6, true).unwrap();
+        let positions = positions(&seats, &result);
+
+        assert_eq!(positions.iter().filter(|&&(y, _)| y == 0.0).count(), 4);
+        assert_eq!(positions.iter().filter(|&&(y, _)| y == 1.0).count(), 2);
+    }
+
+    #[test]
+    fn returns_none_when_quantity_exceeds_available_seats() {
+        let seats = row(0, 4, &[0]);
+
+        assert_eq!(allocate_best_available(&seats, 4, false), None);
+        assert_eq!(allocate_best_available(&seats, 4, true), None);
+    }
+
+    #[test]
+    fn returns_none_for_empty_input_or_zero_quantity() {
+        assert_eq!(allocate_best_available(&[], 1, true), None);
+        assert_eq!(allocate_best_available(&row(0, 4, &[]), 0, true), None);
+    }
+
+    #[test]
+    fn never_crosses_held_or_sold_seats() {
+        let seats = row(0, 8, &[3, 4]);
+
+        let result = allocate_best_available(&seats, 3, false).unwrap();
+        let positions = positions(&seats, &result);
+
+        assert!(positions == vec![(0.0, 0), (0.0, 1), (0.0, 2)] || positions == vec![(0.0, 5), (0.0, 6), (0.0, 7)]);
+    }
+
+    #[test]
+    fn skips_missing_seat_numbers() {
+        // 座位順序 0, 1, 3, 4：1 與 3 之間沒有座位，不算連續
+        let seats: Vec<AllocationSeat> = row(0, 5, &[]).into_iter().filter(|seat| seat.position != 2).collect();
+
+        assert_eq!(allocate_best_available(&seats, 3, false), None);
+        assert_eq!(allocate_best_available(&seats, 2, false).map(|result| result.len()), Some(2));
+    }
+}
@@ -0,0 +1,3 @@
+pub mod allocator;
+pub mod model;
+pub mod repository;
@@ -0,0 +1,243 @@
+use std::collections::HashSet;
+
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+use utoipa::{IntoParams, ToSchema};
+use validator::Validate;
+
+/// 座位圖模型
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct SeatMap {
+    pub id: Uuid,
+    pub name: String,
+    pub venue: String,
+    pub sections: Vec<SeatSection>,
+}
+
+/// 座位區
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct SeatSection {
+    pub id: Uuid,
+    pub name: String,
+    pub rows: Vec<SeatRow>,
+}
+
+/// 座位排
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct SeatRow {
+    pub id: Uuid,
+    pub label: String,
+    pub seats: Vec<Seat>,
+}
+
+/// 座位
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct Seat {
+    pub id: Uuid,
+    pub number: String,
+    pub x: f64,
+    pub y: f64,
+}
+
+/// 座位圖摘要
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct SeatMapSummary {
+    pub id: Uuid,
+    pub name: String,
+    pub venue: String,
+    pub seat_count: i64,
+}
+
+/// 匯入座位圖輸入（JSON）
+#[derive(Debug, Clone, Serialize, Deserialize, Validate, ToSchema)]
+pub struct ImportSeatMap {
+    #[validate(length(min = 1))]
+    pub name: String,
+    #[validate(length(min = 1))]
+    pub venue: String,
+    #[validate(length(min = 1))]
+    pub sections: Vec<ImportSection>,
+}
+
+/// 匯入的座位區
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct ImportSection {
+    pub name: String,
+    pub rows: Vec<ImportRow>,
+}
+
+/// 匯入的座位排
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct ImportRow {
+    pub label: String,
+    pub seats: Vec<ImportSeat>,
+}
+
+/// 匯入的座位
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct ImportSeat {
+    pub number: String,
+    pub x: f64,
+    pub y: f64,
+}
+
+/// CSV 匯入的一列：section,row,seat,x,y
+#[derive(Debug, Clone, Deserialize)]
+pub struct SeatCsvRow {
+    pub section: String,
+    pub row: String,
+    pub seat: String,
+    pub x: f64,
+    pub y: f64,
+}
+
+/// CSV 匯入參數
+#[derive(Debug, Clone, Deserialize, IntoParams)]
+pub struct CsvImportQuery {
+    pub name: String,
+    pub venue: String,
+}
+
+impl ImportSeatMap {
+    /// 將 CSV 資料列組成座位圖，區、排、座位依首次出現的順序排列
+    pub fn from_csv_rows(name: String, venue: String, rows: Vec<SeatCsvRow>) -> Self {
+        let mut sections: Vec<ImportSection> = Vec::new();
+        for row in rows {
+            let section_index = match sections.iter().position(|section| section.name == row.section) {
+                Some(index) => index,
+                None => {
+                    sections.push(ImportSection { name: row.section.clone(), rows: Vec::new() });
+                    sections.len() - 1
+                }
+            };
+            let section = &mut sections[section_index];
+
+            let row_index = match section.rows.iter().position(|r| r.label == row.row) {
+                Some(index) => index,
+                None => {
+                    section.rows.push(ImportRow { label: row.row.clone(), seats: Vec::new() });
+                    section.rows.len() - 1
+                }
+            };
+            section.rows[row_index].seats.push(ImportSeat { number: row.seat, x: row.x, y: row.y });
+        }
+
+        Self { name, venue, sections }
+    }
+
+    /// 檢查區名、同區排名、同排座位編號不重複，且每排至少一個座位
+    pub fn check_layout(&self) -> Result<(), String> {
+        let mut section_names = HashSet::new();
+        for section in &self.sections {
+            if !section_names.insert(section.name.as_str()) {
+                return Err(format!("座位區 {} 重複", section.name));
+            }
+            if section.rows.is_empty() {
+                return Err(format!("座位區 {} 沒有任何排", section.name));
+            }
+
+            let mut row_labels = HashSet::new();
+            for row in &section.rows {
+                if !row_labels.insert(row.label.as_str()) {
+                    return Err(format!("座位區 {} 的第 {} 排重複", section.name, row.label));
+                }
+                if row.seats.is_empty() {
+                    return Err(format!("座位區 {} 第 {} 排沒有任何座位", section.name, row.label));
+                }
+
+                let mut numbers = HashSet::new();
+                for seat in &row.seats {
+                    if !numbers.insert(seat.number.as_str()) {
+                        return Err(format!("座位 {} {}-{} 重複", section.name, row.label, seat.number));
+                    }
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+/// 座位銷售狀態
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum SeatStatus {
+    /// 可選購
+    Available,
+    /// 待付款訂單保留中
+    Held,
+    /// 已售出
+    Sold,
+}
+
+impl SeatStatus {
+    /// 從資料庫字串解析
+    pub fn parse(value: &str) -> Option<Self> {
+        match value {
+            "available" => Some(SeatStatus::Available),
+            "held" => Some(SeatStatus::Held),
+            "sold" => Some(SeatStatus::Sold),
+            _ => None,
+        }
+    }
+}
+
+/// 價區包含的座位範圍
+/// rows 未指定時包含整個座位區
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct ZoneSection {
+    pub section: String,
+    pub rows: Option<Vec<String>>,
+}
+
+/// 價區設定輸入
+#[derive(Debug, Clone, Serialize, Deserialize, Validate, ToSchema)]
+pub struct PriceZoneInput {
+    #[validate(length(min = 1))]
+    pub name: String,
+    /// 價區對應的票種，座位以該票種的票價販售
+    pub ticket_id: Uuid,
+    #[validate(length(min = 1))]
+    pub sections: Vec<ZoneSection>,
+}
+
+/// 設定演唱會座位輸入
+#[derive(Debug, Clone, Deserialize, Validate, ToSchema)]
+pub struct ConfigureSeating {
+    pub seat_map_id: Uuid,
+    #[validate(length(min = 1))]
+    pub zones: Vec<PriceZoneInput>,
+}
+
+/// 價區
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct PriceZone {
+    pub id: Uuid,
+    pub name: String,
+    pub ticket_id: Uuid,
+    pub ticket_type: String,
+    pub price: f64,
+    pub seat_count: i64,
+    pub available_count: i64,
+}
+
+/// 座位可售狀態（繪製座位圖用）
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct SeatAvailability {
+    pub seat_id: Uuid,
+    pub section: String,
+    pub row: String,
+    pub number: String,
+    pub x: f64,
+    pub y: f64,
+    pub price_zone_id: Uuid,
+    pub status: SeatStatus,
+}
+
+/// 演唱會座位圖與可售狀態
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct ConcertSeating {
+    pub concert_id: Uuid,
+    pub seat_map_id: Uuid,
+    pub zones: Vec<PriceZone>,
+    pub seats: Vec<SeatAvailability>,
+}
@@ -0,0 +1,42 @@
+use async_trait::async_trait;
+use uuid::Uuid;
+
+use crate::domain::organization::model::Tenant;
+use crate::domain::seating::allocator::AllocationSeat;
+use crate::domain::seating::model::{
+    ConfigureSeating, ImportSeatMap, PriceZone, SeatAvailability, SeatMap, SeatMapSummary,
+};
+use crate::utils::error::AppError;
+
+/// 座位存儲庫接口
+#[async_trait]
+pub trait SeatingRepository: Send + Sync {
+    /// 匯入座位圖，返回新座位圖 ID
+    /// 主辦單位人員匯入的座位圖歸屬該主辦單位
+    async fn create_seat_map(&self, input: &ImportSeatMap, tenant: Tenant) -> Result<Uuid, AppError>;
+    
+    /// 根據 ID 查找租戶範圍內的座位圖（含區、排、座位）
+    async fn find_seat_map(&self, id: Uuid, tenant: Tenant) -> Result<Option<SeatMap>, AppError>;
+    
+    /// 獲取租戶範圍內所有座位圖摘要
+    async fn find_all_seat_maps(&self, tenant: Tenant) -> Result<Vec<SeatMapSummary>, AppError>;
+    
+    /// 設定演唱會的座位圖與價區，並建立每個座位的庫存
+    /// 價區票種的庫存會同步為價區的座位數；演唱會、座位圖或票種不在租戶範圍內時回傳 NotFound
+    async fn configure_concert(&self, concert_id: Uuid, input: &ConfigureSeating, tenant: Tenant) -> Result<(), AppError>;
+    
+    /// 租戶範圍內演唱會使用的座位圖 ID
+    async fn find_concert_seat_map_id(&self, concert_id: Uuid, tenant: Tenant) -> Result<Option<Uuid>, AppError>;
+    
+    /// 獲取租戶範圍內演唱會的價區
+    async fn find_zones(&self, concert_id: Uuid, tenant: Tenant) -> Result<Vec<PriceZone>, AppError>;
+    
+    /// 獲取租戶範圍內演唱會所有座位的可售狀態
+    async fn find_availability(&self, concert_id: Uuid, tenant: Tenant) -> Result<Vec<SeatAvailability>, AppError>;
+    
+    /// 票種是否為劃位票種（屬於某個價區）
+    async fn is_seated_ticket(&self, ticket_id: Uuid) -> Result<bool, AppError>;
+    
+    /// 獲取票種價區內的座位（含已保留或售出者）供最佳位置配位使用
+    async fn find_allocation_seats(&self, ticket_id: Uuid, price_zone_id: Option<Uuid>) -> Result<Vec<AllocationSeat>, AppError>;
+}
@@ -0,0 +1,187 @@
+use chrono::NaiveDateTime;
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+use utoipa::{IntoParams, ToSchema};
+
+use crate::domain::order::model::{OrderStatus, PriceBreakdown};
+use crate::utils::export::ExportFormat;
+use crate::utils::money::round_money;
+
+/// 結算狀態
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum SettlementStatus {
+    /// 尚未鎖定，金額隨訂單即時變動
+    Open,
+    /// 已鎖定金額，等待撥款
+    Locked,
+    /// 已撥款給主辦單位
+    Paid,
+}
+
+impl SettlementStatus {
+    /// 資料庫中的字串表示
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            SettlementStatus::Open => "open",
+            SettlementStatus::Locked => "locked",
+            SettlementStatus::Paid => "paid",
+        }
+    }
+
+    /// 從資料庫字串解析
+    pub fn parse(value: &str) -> Option<Self> {
+        match value {
+            "open" => Some(SettlementStatus::Open),
+            "locked" => Some(SettlementStatus::Locked),
+            "paid" => Some(SettlementStatus::Paid),
+            _ => None,
+        }
+    }
+}
+
+/// 結算用的訂單資料（訂單與票券關聯查詢結果）
+/// 組合商品訂單每個組合內容一筆，金額已依組合內容的營收比例分配
+#[derive(Debug, Clone)]
+pub struct SettlementOrder {
+    pub ticket_type: String,
+    pub quantity: i32,
+    pub status: OrderStatus,
+    pub breakdown: PriceBreakdown,
+}
+
+/// 結算明細（依票種彙總）
+#[derive(Debug, Clone, Default, Serialize, ToSchema)]
+pub struct SettlementLine {
+    pub ticket_type: String,
+    /// 已付款（未退款）張數
+    pub tickets_sold: i32,
+    pub tickets_refunded: i32,
+    /// 票面銷售額（含已退款訂單）
+    pub gross_sales: f64,
+    pub discounts: f64,
+    /// 已退款訂單的實收票款
+    pub refunds: f64,
+    /// 含稅票價中代扣的稅額
+    pub tax_withheld: f64,
+    pub net_sales: f64,
+}
+
+impl SettlementLine {
+    /// 累加一筆訂單
+    fn add(&mut self, order: &SettlementOrder) {
+        let breakdown = &order.breakdown;
+        self.gross_sales += breakdown.merchandise_total();
+        self.discounts += breakdown.discount_total;
+
+        match order.status {
+            OrderStatus::Refunded => {
+                self.tickets_refunded += order.quantity;
+                self.refunds += breakdown.merchandise_total() - breakdown.discount_total;
+            }
+            _ => {
+                self.tickets_sold += order.quantity;
+                self.tax_withheld += breakdown.tax_split().0;
+            }
+        }
+    }
+
+    /// 四捨五入並計算淨銷售額
+    fn finish(mut self) -> Self {
+        self.gross_sales = round_money(self.gross_sales);
+        self.discounts = round_money(self.discounts);
+        self.refunds = round_money(self.refunds);
+        self.tax_withheld = round_money(self.tax_withheld);
+        self.net_sales = round_money(self.gross_sales - self.discounts - self.refunds - self.tax_withheld);
+        self
+    }
+}
+
+/// 結算單
+///
+/// 服務費屬於平台收入，不列入主辦單位結算；
+/// 淨銷售額即收款分錄中貸記應付主辦單位的金額，扣除平台抽成後為應付金額
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct Settlement {
+    pub concert_id: Uuid,
+    pub concert_title: String,
+    pub organization_id: Uuid,
+    pub organization_name: String,
+    pub status: SettlementStatus,
+    pub commission_rate: f64,
+    pub gross_sales: f64,
+    pub discounts: f64,
+    pub refunds: f64,
+    pub tax_withheld: f64,
+    pub net_sales: f64,
+    pub platform_commission: f64,
+    pub net_payable: f64,
+    pub lines: Vec<SettlementLine>,
+    pub locked_at: Option<NaiveDateTime>,
+    pub paid_at: Option<NaiveDateTime>,
+}
+
+impl Settlement {
+    /// 依訂單即時計算尚未鎖定的結算單
+    /// 只計入已付款、退款處理中與已退款的訂單，待付款與失敗的訂單沒有資金移動
+    pub fn compute(
+        concert_id: Uuid,
+        concert_title: String,
+        organization_id: Uuid,
+        organization_name: String,
+        commission_rate: f64,
+        orders: &[SettlementOrder],
+    ) -> Self {
+        let mut lines: Vec<SettlementLine> = Vec::new();
+        for order in orders {
+            if !matches!(order.status, OrderStatus::Paid | OrderStatus::Refunding | OrderStatus::Refunded) {
+                continue;
+            }
+            let index = match lines.iter().position(|line| line.ticket_type == order.ticket_type) {
+                Some(index) => index,
+                None => {
+                    lines.push(SettlementLine {
+                        ticket_type: order.ticket_type.clone(),
+                        ..Default::default()
+                    });
+                    lines.len() - 1
+                }
+            };
+            lines[index].add(order);
+        }
+        let lines: Vec<SettlementLine> = lines.into_iter().map(SettlementLine::finish).collect();
+
+        let gross_sales = round_money(lines.iter().map(|line| line.gross_sales).sum());
+        let discounts = round_money(lines.iter().map(|line| line.discounts).sum());
+        let refunds = round_money(lines.iter().map(|line| line.refunds).sum());
+        let tax_withheld = round_money(lines.iter().map(|line| line.tax_withheld).sum());
+        let net_sales = round_money(lines.iter().map(|line| line.net_sales).sum());
+        let platform_commission = round_money(net_sales * commission_rate);
+
+        Self {
+            concert_id,
+            concert_title,
+            organization_id,
+            organization_name,
+            status: SettlementStatus::Open,
+            commission_rate,
+            gross_sales,
+            discounts,
+            refunds,
+            tax_withheld,
+            net_sales,
+            platform_commission,
+            net_payable: round_money(net_sales - platform_commission),
+            lines,
+            locked_at: None,
+            paid_at: None,
+        }
+    }
+}
+
+/// 結算單查詢參數
+#[derive(Debug, Clone, Deserialize, IntoParams)]
+pub struct StatementQuery {
+    /// json（預設）或 csv
+    pub format: Option<ExportFormat>,
+}
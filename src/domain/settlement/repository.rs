@@ -0,0 +1,22 @@
+use async_trait::async_trait;
+use uuid::Uuid;
+
+use crate::domain::ledger::model::Journal;
+use crate::domain::settlement::model::{Settlement, SettlementOrder};
+use crate::utils::error::AppError;
+
+/// 結算存儲庫接口
+#[async_trait]
+pub trait SettlementRepository: Send + Sync {
+    /// 查詢演唱會所有票種的訂單，組合商品訂單依組合內容的營收比例計入
+    async fn find_orders_by_concert_id(&self, concert_id: Uuid) -> Result<Vec<SettlementOrder>, AppError>;
+    
+    /// 查詢已鎖定或已撥款的結算單
+    async fn find_by_concert_id(&self, concert_id: Uuid) -> Result<Option<Settlement>, AppError>;
+    
+    /// 鎖定結算單，寫入金額快照；已存在時回傳 false
+    async fn lock(&self, settlement: &Settlement) -> Result<bool, AppError>;
+    
+    /// 將已鎖定的結算單標記為已撥款，並在同一交易中寫入抽成與撥款傳票
+    async fn mark_paid(&self, concert_id: Uuid, journals: &[Journal]) -> Result<bool, AppError>;
+}
@@ -1,3 +1,4 @@
+use chrono::NaiveDateTime;
 use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 use utoipa::{ToSchema, IntoParams};
@@ -9,8 +10,14 @@ pub struct Ticket {
     pub id: Uuid,
     pub concert_id: Uuid,
     pub ticket_type: String,
+    /// 基本票價
     pub price: f64,
+    /// 目前售價（套用價格階梯與需求加價後）
+    pub current_price: f64,
+    /// 目前可購買數量；加入庫存池時為票種庫存與池剩餘數量的較小者
     pub stock: i32,
+    /// 所屬的共用庫存池
+    pub pool_id: Option<Uuid>,
 }
 
 /// 創建票券輸入
@@ -27,3 +34,75 @@ pub struct CreateTicket {
 pub struct TicketQuery {
     pub concert_id: Uuid,
 }
+
+/// 價格階梯
+/// 在指定時間區間內、且票種已售出數量未達 max_sold 時適用，例如早鳥價或前 100 張優惠價
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct PriceTier {
+    pub name: String,
+    pub price: f64,
+    /// 開始時間，未設定代表立即生效
+    pub starts_at: Option<NaiveDateTime>,
+    /// 結束時間，未設定代表不限時間
+    pub ends_at: Option<NaiveDateTime>,
+    /// 累計售出達到此數量後不再適用，未設定代表不限數量
+    pub max_sold: Option<i32>,
+}
+
+/// 需求加價規則
+/// 售出率超過 start_sell_through 後，價格隨售出率線性上漲，售完時達到上限 max_increase
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct DemandRule {
+    /// 開始加價的售出率，例如 0.5 代表售出一半後開始加價
+    pub start_sell_through: f64,
+    /// 加價上限比例，例如 0.2 代表最多加價 20%
+    pub max_increase: f64,
+}
+
+/// 價格階梯輸入
+#[derive(Debug, Clone, Serialize, Deserialize, Validate, ToSchema)]
+pub struct PriceTierInput {
+    #[validate(length(min = 1))]
+    pub name: String,
+    #[validate(range(min = 0.0))]
+    pub price: f64,
+    pub starts_at: Option<NaiveDateTime>,
+    pub ends_at: Option<NaiveDateTime>,
+    #[validate(range(min = 1))]
+    pub max_sold: Option<i32>,
+}
+
+/// 需求加價規則輸入
+#[derive(Debug, Clone, Serialize, Deserialize, Validate, ToSchema)]
+pub struct DemandRuleInput {
+    #[validate(range(min = 0.0, max = 0.99))]
+    pub start_sell_through: f64,
+    #[validate(range(min = 0.0, max = 1.0))]
+    pub max_increase: f64,
+}
+
+/// 設定票種定價輸入
+/// 價格階梯依陣列順序比對，第一個適用的階梯即為目前價格；整組取代既有設定
+#[derive(Debug, Clone, Deserialize, Validate, ToSchema)]
+pub struct SetTicketPricing {
+    #[serde(default)]
+    #[validate]
+    pub tiers: Vec<PriceTierInput>,
+    #[validate]
+    pub demand: Option<DemandRuleInput>,
+}
+
+/// 票種定價視圖
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct TicketPricing {
+    pub ticket_id: Uuid,
+    /// 沒有適用階梯時的基本票價
+    pub base_price: f64,
+    /// 目前售價
+    pub current_price: f64,
+    /// 目前適用的階梯名稱
+    pub current_tier: Option<String>,
+    pub sold: i32,
+    pub tiers: Vec<PriceTier>,
+    pub demand: Option<DemandRule>,
+}
@@ -0,0 +1,136 @@
+use chrono::NaiveDateTime;
+
+use crate::domain::ticket::model::{DemandRule, PriceTier};
+use crate::utils::money::round_money;
+
+/// 票種定價設定
+/// 由存儲庫讀出基本票價、價格階梯、需求加價規則與目前售出數量，再以純函數計算目前售價
+#[derive(Debug, Clone)]
+pub struct PricingSchedule {
+    pub base_price: f64,
+    pub tiers: Vec<PriceTier>,
+    pub demand: Option<DemandRule>,
+    /// 已售出（含待付款）數量
+    pub sold: i32,
+    /// 票種原始庫存，用於計算售出率
+    pub capacity: i32,
+}
+
+impl PricingSchedule {
+    /// 目前適用的價格階梯：依順序第一個時間區間涵蓋 now 且售出數量未達上限的階梯
+    pub fn current_tier(&self, now: NaiveDateTime) -> Option<&PriceTier> {
+        self.tiers.iter().find(|tier| {
+            tier.starts_at.is_none_or(|starts_at| now >= starts_at)
+                && tier.ends_at.is_none_or(|ends_at| now < ends_at)
+                && tier.max_sold.is_none_or(|max_sold| self.sold < max_sold)
+        })
+    }
+
+    /// 目前適用階梯在達到售出上限前的剩餘張數，沒有適用階梯或階梯不限數量時為 None
+    pub fn tier_remaining(&self, now: NaiveDateTime) -> Option<i32> {
+        self.current_tier(now)
+            .and_then(|tier| tier.max_sold)
+            .map(|max_sold| max_sold - self.sold)
+    }
+
+    /// 目前售價
+    ///
+    /// 先取適用階梯的價格（沒有則為基本票價），再套用需求加價：
+    /// 售出率超過門檻後按比例線性加價，售完時加價達到上限。
+    pub fn current_price(&self, now: NaiveDateTime) -> f64 {
+        let price = self.current_tier(now).map_or(self.base_price, |tier| tier.price);
+
+        let Some(demand) = &self.demand else {
+            return round_money(price);
+        };
+        if self.capacity <= 0 {
+            return round_money(price);
+        }
+
+        let sell_through = self.sold as f64 / self.capacity as f64;
+        let progress = ((sell_through - demand.start_sell_through) / (1.0 - demand.start_sell_through)).clamp(0.0, 1.0);
+        round_money(price * (1.0 + demand.max_increase * progress))
+    }
+
+    /// 一筆訂單的單價
+    /// 同一筆訂單的所有票券適用同一個價格階梯，數量超過目前階梯的剩餘張數時回傳 None，呼叫端應拒絕訂單；
+    /// 需求加價以下單前的售出率計算
+    pub fn order_price(&self, now: NaiveDateTime, quantity: i32) -> Option<f64> {
+        if self.tier_remaining(now).is_some_and(|remaining| quantity > remaining) {
+            return None;
+        }
+        Some(self.current_price(now))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::NaiveDate;
+
+    use super::*;
+
+    fn at(day: u32) -> NaiveDateTime {
+        NaiveDate::from_ymd_opt(2024, 6, day).unwrap().and_hms_opt(12, 0, 0).unwrap()
+    }
+
+    fn tier(name: &str, price: f64, starts_at: Option<NaiveDateTime>, ends_at: Option<NaiveDateTime>, max_sold: Option<i32>) -> PriceTier {
+        PriceTier { name: name.to_string(), price, starts_at, ends_at, max_sold }
+    }
+
+    fn schedule(tiers: Vec<PriceTier>, demand: Option<DemandRule>, sold: i32) -> PricingSchedule {
+        PricingSchedule { base_price: 100.0, tiers, demand, sold, capacity: 1000 }
+    }
+
+    #[test]
+    fn tier_applies_only_within_time_window() {
+        let pricing = schedule(vec![tier("早鳥", 80.0, Some(at(10)), Some(at(20)), None)], None, 0);
+
+        assert!(pricing.current_tier(at(9)).is_none());
+        assert_eq!(pricing.current_tier(at(10)).unwrap().name, "早鳥");
+        assert_eq!(pricing.current_price(at(19)), 80.0);
+        // 結束時間不含在區間內
+        assert!(pricing.current_tier(at(20)).is_none());
+        assert_eq!(pricing.current_price(at(25)), 100.0);
+    }
+
+    #[test]
+    fn tier_ends_when_max_sold_is_reached() {
+        let tiers = vec![tier("前 100 張", 70.0, None, None, Some(100)), tier("第二階段", 90.0, None, None, None)];
+
+        assert_eq!(schedule(tiers.clone(), None, 99).current_price(at(1)), 70.0);
+        assert_eq!(schedule(tiers.clone(), None, 99).tier_remaining(at(1)), Some(1));
+        assert_eq!(schedule(tiers.clone(), None, 100).current_tier(at(1)).unwrap().name, "第二階段");
+        assert_eq!(schedule(tiers, None, 100).tier_remaining(at(1)), None);
+    }
+
+    #[test]
+    fn order_crossing_tier_boundary_is_rejected() {
+        let tiers = vec![tier("前 100 張", 70.0, None, None, Some(100)), tier("第二階段", 90.0, None, None, None)];
+
+        assert_eq!(schedule(tiers.clone(), None, 0).order_price(at(1), 100), Some(70.0));
+        assert_eq!(schedule(tiers.clone(), None, 0).order_price(at(1), 300), None);
+        assert_eq!(schedule(tiers.clone(), None, 98).order_price(at(1), 3), None);
+        assert_eq!(schedule(tiers, None, 100).order_price(at(1), 300), Some(90.0));
+    }
+
+    #[test]
+    fn demand_pricing_scales_linearly_up_to_cap() {
+        let demand = || Some(DemandRule { start_sell_through: 0.5, max_increase: 0.2 });
+
+        // 門檻前不加價
+        assert_eq!(schedule(Vec::new(), demand(), 500).current_price(at(1)), 100.0);
+        // 門檻與售完之間按比例加價
+        assert_eq!(schedule(Vec::new(), demand(), 750).current_price(at(1)), 110.0);
+        // 售完時達到上限，超賣也不超過上限
+        assert_eq!(schedule(Vec::new(), demand(), 1000).current_price(at(1)), 120.0);
+        assert_eq!(schedule(Vec::new(), demand(), 1200).current_price(at(1)), 120.0);
+    }
+
+    #[test]
+    fn demand_pricing_applies_on_top_of_tier_price() {
+        let tiers = vec![tier("早鳥", 80.0, None, None, None)];
+        let demand = Some(DemandRule { start_sell_through: 0.0, max_increase: 0.5 });
+
+        assert_eq!(schedule(tiers, demand, 500).current_price(at(1)), 100.0);
+    }
+}
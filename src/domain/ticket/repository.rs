@@ -1,21 +1,26 @@
 use async_trait::async_trait;
 use uuid::Uuid;
 
-use crate::domain::ticket::model::{CreateTicket, Ticket};
+use crate::domain::organization::model::Tenant;
+use crate::domain::ticket::model::{CreateTicket, SetTicketPricing, Ticket};
+use crate::domain::ticket::pricing::PricingSchedule;
 use crate::utils::error::AppError;
 
 /// 票券存儲庫接口
 #[async_trait]
 pub trait TicketRepository: Send + Sync {
-    /// 根據 ID 查找票券
-    async fn find_by_id(&self, id: Uuid) -> Result<Option<Ticket>, AppError>;
+    /// 根據 ID 查找租戶範圍內的票券
+    async fn find_by_id(&self, id: Uuid, tenant: Tenant) -> Result<Option<Ticket>, AppError>;
     
     /// 根據演唱會 ID 查找票券
     async fn find_by_concert_id(&self, concert_id: Uuid) -> Result<Vec<Ticket>, AppError>;
     
-    /// 創建新票券
-    async fn create(&self, input: &CreateTicket) -> Result<Ticket, AppError>;
+    /// 創建新票券，演唱會不在租戶範圍內時返回 None
+    async fn create(&self, input: &CreateTicket, tenant: Tenant) -> Result<Option<Ticket>, AppError>;
     
-    /// 更新票券庫存
-    async fn update_stock(&self, id: Uuid, quantity: i32) -> Result<(), AppError>;
+    /// 查找票種定價設定（基本票價、價格階梯、需求加價規則與售出數量）
+    async fn find_pricing(&self, id: Uuid, tenant: Tenant) -> Result<Option<PricingSchedule>, AppError>;
+    
+    /// 以新的價格階梯與需求加價規則取代既有設定，票種不在租戶範圍內時返回 false
+    async fn set_pricing(&self, id: Uuid, input: &SetTicketPricing, tenant: Tenant) -> Result<bool, AppError>;
 }
@@ -0,0 +1,281 @@
+use async_trait::async_trait;
+use sqlx::postgres::PgRow;
+use sqlx::{PgPool, Row};
+use uuid::Uuid;
+
+use crate::domain::addon::model::{Addon, AddonVariant, CreateAddon, VariantSelection};
+use crate::domain::addon::repository::AddonRepository;
+use crate::domain::order::model::{OrderItem, OrderItemStatus};
+use crate::domain::organization::model::Tenant;
+use crate::infrastructure::database::repositories::{
+    concert_in_tenant, is_unique_violation, tenant_filter, tickets_in_tenant,
+};
+use crate::utils::error::AppError;
+use crate::utils::money::to_decimal;
+
+/// PostgreSQL 加購商品存儲庫實現
+pub struct PgAddonRepository {
+    pool: PgPool,
+}
+
+impl PgAddonRepository {
+    /// 創建新的 PostgreSQL 加購商品存儲庫
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+
+    /// 讀取加購商品並附上規格與限定票種
+    async fn load(&self, rows: Vec<PgRow>) -> Result<Vec<Addon>, AppError> {
+        let mut addons = Vec::with_capacity(rows.len());
+        for row in rows {
+            let id: Uuid = row.get("id");
+
+            let variants = sqlx::query(
+                "SELECT id, name, stock FROM addon_variants WHERE addon_id = $1 ORDER BY position"
+            )
+            .bind(id)
+            .fetch_all(&self.pool)
+            .await?
+            .iter()
+            .map(|row| AddonVariant {
+                id: row.get("id"),
+                name: row.get("name"),
+                stock: row.get("stock"),
+            })
+            .collect();
+
+            addons.push(Addon {
+                id,
+                concert_id: row.get("concert_id"),
+                name: row.get("name"),
+                description: row.get("description"),
+                price: row.get("price"),
+                variants,
+                ticket_ids: find_ticket_ids(&self.pool, id).await?,
+            });
+        }
+        Ok(addons)
+    }
+}
+
+#[async_trait]
+impl AddonRepository for PgAddonRepository {
+    async fn find_by_concert_id(&self, concert_id: Uuid, tenant: Tenant) -> Result<Vec<Addon>, AppError> {
+        let rows = sqlx::query(&format!(
+            r#"
+            SELECT a.id, a.concert_id, a.name, a.description, a.price::float8
+            FROM addons a
+            JOIN concerts c ON a.concert_id = c.id
+            WHERE a.concert_id = $1 AND {}
+            ORDER BY a.created_at, a.name
+            "#,
+            tenant_filter("c.organization_id", 2)
+        ))
+        .bind(concert_id)
+        .bind(tenant.organization_id())
+        .fetch_all(&self.pool)
+        .await?;
+
+        self.load(rows).await
+    }
+
+    async fn find_variants(&self, variant_ids: &[Uuid]) -> Result<Vec<VariantSelection>, AppError> {
+        let rows = sqlx::query(
+            r#"
+            SELECT v.id AS variant_id, a.id AS addon_id, a.concert_id, a.name AS addon_name, a.price::float8
+            FROM addon_variants v
+            JOIN addons a ON v.addon_id = a.id
+            WHERE v.id = ANY($1)
+            "#
+        )
+        .bind(variant_ids)
+        .fetch_all(&self.pool)
+        .await?;
+
+        let mut selections = Vec::with_capacity(rows.len());
+        for row in rows {
+            let addon_id: Uuid = row.get("addon_id");
+            selections.push(VariantSelection {
+                variant_id: row.get("variant_id"),
+                concert_id: row.get("concert_id"),
+                addon_name: row.get("addon_name"),
+                price: row.get("price"),
+                ticket_ids: find_ticket_ids(&self.pool, addon_id).await?,
+            });
+        }
+        Ok(selections)
+    }
+
+    async fn create(&self, concert_id: Uuid, input: &CreateAddon, tenant: Tenant) -> Result<Addon, AppError> {
+        let mut tx = self.pool.begin().await?;
+
+        // 演唱會與限定票種都必須屬於租戶範圍
+        if !concert_in_tenant(&mut tx, concert_id, tenant).await?
+            || !tickets_in_tenant(&mut tx, &input.ticket_ids, tenant).await?
+        {
+            return Err(AppError::NotFound(format!("找不到 ID 為 {} 的演唱會", concert_id)));
+        }
+
+        let id: Uuid = sqlx::query(
+            "INSERT INTO addons (concert_id, name, description, price) VALUES ($1, $2, $3, $4) RETURNING id"
+        )
+        .bind(concert_id)
+        .bind(&input.name)
+        .bind(&input.description)
+        .bind(to_decimal(input.price)?)
+        .fetch_one(&mut *tx)
+        .await?
+        .get("id");
+
+        for (position, variant) in input.variants.iter().enumerate() {
+            sqlx::query(
+                r#"
+                INSERT INTO addon_variants (addon_id, name, position, stock, initial_stock)
+                VALUES ($1, $2, $3, $4, $4)
+                "#
+            )
+            .bind(id)
+            .bind(&variant.name)
+            .bind(position as i32)
+            .bind(variant.stock)
+            .execute(&mut *tx)
+            .await
+            .map_err(|e| {
+                if is_unique_violation(&e) {
+                    AppError::Conflict(format!("規格名稱 {} 重複", variant.name))
+                } else {
+                    e.into()
+                }
+            })?;
+        }
+
+        for ticket_id in &input.ticket_ids {
+            sqlx::query("INSERT INTO addon_ticket_types (addon_id, ticket_id) VALUES ($1, $2) ON CONFLICT DO NOTHING")
+                .bind(id)
+                .bind(ticket_id)
+                .execute(&mut *tx)
+                .await?;
+        }
+
+        tx.commit().await?;
+
+        let rows = sqlx::query("SELECT id, concert_id, name, description, price::float8 FROM addons WHERE id = $1")
+            .bind(id)
+            .fetch_all(&self.pool)
+            .await?;
+        self.load(rows).await?.pop()
+            .ok_or_else(|| AppError::Internal(format!("找不到剛創建的加購商品 {}", id)))
+    }
+
+    async fn find_item_by_code(&self, code: &str, tenant: Tenant) -> Result<Option<OrderItem>, AppError> {
+        let result = sqlx::query(&format!(
+            "SELECT {} FROM {} WHERE i.code = $1 AND {}",
+            ORDER_ITEM_COLUMNS, ORDER_ITEM_TABLES, tenant_filter("c.organization_id", 2)
+        ))
+        .bind(code)
+        .bind(tenant.organization_id())
+        .fetch_optional(&self.pool)
+        .await?;
+
+        result.as_ref().map(order_item_from_row).transpose()
+    }
+
+    async fn redeem_item(&self, code: &str, tenant: Tenant) -> Result<Option<OrderItem>, AppError> {
+        // 條件式更新：同一筆明細同時被兌換時只有一方成功
+        let result = sqlx::query(&format!(
+            r#"
+            WITH redeemed AS (
+                UPDATE order_items oi
+                SET status = 'redeemed', redeemed_at = CURRENT_TIMESTAMP
+                FROM addon_variants v
+                JOIN addons a ON v.addon_id = a.id
+                JOIN concerts c ON a.concert_id = c.id
+                WHERE oi.variant_id = v.id AND oi.code = $1 AND oi.status = 'valid' AND {}
+                RETURNING oi.*
+            )
+            SELECT {}
+            FROM redeemed i
+            JOIN addon_variants v ON i.variant_id = v.id
+            JOIN addons a ON v.addon_id = a.id
+            "#,
+            tenant_filter("c.organization_id", 2),
+            ORDER_ITEM_COLUMNS
+        ))
+        .bind(code)
+        .bind(tenant.organization_id())
+        .fetch_optional(&self.pool)
+        .await?;
+
+        result.as_ref().map(order_item_from_row).transpose()
+    }
+}
+
+/// 訂單明細欄位
+const ORDER_ITEM_COLUMNS: &str = r#"
+    i.id, i.order_id, a.id AS addon_id, a.name AS addon_name, v.name AS variant_name,
+    i.quantity, i.unit_price::float8, i.total::float8, i.code, i.status, i.redeemed_at
+"#;
+
+/// 訂單明細關聯表
+const ORDER_ITEM_TABLES: &str = r#"
+    order_items i
+    JOIN addon_variants v ON i.variant_id = v.id
+    JOIN addons a ON v.addon_id = a.id
+    JOIN concerts c ON a.concert_id = c.id
+"#;
+
+/// 查找加購商品限定的票種
+async fn find_ticket_ids(pool: &PgPool, addon_id: Uuid) -> Result<Vec<Uuid>, AppError> {
+    let rows = sqlx::query("SELECT ticket_id FROM addon_ticket_types WHERE addon_id = $1 ORDER BY ticket_id")
+        .bind(addon_id)
+        .fetch_all(pool)
+        .await?;
+
+    Ok(rows.iter().map(|row| row.get("ticket_id")).collect())
+}
+
+/// 查找訂單的加購商品明細
+pub async fn find_items_by_order_id(pool: &PgPool, order_id: Uuid) -> Result<Vec<OrderItem>, AppError> {
+    let rows = sqlx::query(&format!(
+        "SELECT {} FROM {} WHERE i.order_id = $1 ORDER BY a.name, v.position",
+        ORDER_ITEM_COLUMNS, ORDER_ITEM_TABLES
+    ))
+    .bind(order_id)
+    .fetch_all(pool)
+    .await?;
+
+    rows.iter().map(order_item_from_row).collect()
+}
+
+/// 一次查找多筆訂單的加購商品明細
+pub async fn find_items_by_order_ids(pool: &PgPool, order_ids: &[Uuid]) -> Result<Vec<OrderItem>, AppError> {
+    let rows = sqlx::query(&format!(
+        "SELECT {} FROM {} WHERE i.order_id = ANY($1) ORDER BY a.name, v.position",
+        ORDER_ITEM_COLUMNS, ORDER_ITEM_TABLES
+    ))
+    .bind(order_ids)
+    .fetch_all(pool)
+    .await?;
+
+    rows.iter().map(order_item_from_row).collect()
+}
+
+/// 將資料庫記錄轉換為訂單明細模型
+fn order_item_from_row(row: &PgRow) -> Result<OrderItem, AppError> {
+    let status: String = row.get("status");
+
+    Ok(OrderItem {
+        id: row.get("id"),
+        order_id: row.get("order_id"),
+        addon_id: row.get("addon_id"),
+        addon_name: row.get("addon_name"),
+        variant_name: row.get("variant_name"),
+        quantity: row.get("quantity"),
+        unit_price: row.get("unit_price"),
+        total: row.get("total"),
+        code: row.get("code"),
+        status: OrderItemStatus::parse(&status)
+            .ok_or_else(|| AppError::Internal(format!("未知的訂單明細狀態: {}", status)))?,
+        redeemed_at: row.get("redeemed_at"),
+    })
+}
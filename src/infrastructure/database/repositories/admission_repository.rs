@@ -0,0 +1,146 @@
+use async_trait::async_trait;
+use sqlx::postgres::PgRow;
+use sqlx::{PgPool, Row};
+use uuid::Uuid;
+
+use crate::domain::admission::model::{Admission, AdmissionStatus};
+use crate::domain::admission::repository::AdmissionRepository;
+use crate::domain::organization::model::Tenant;
+use crate::infrastructure::database::repositories::tenant_filter;
+use crate::utils::error::AppError;
+
+/// PostgreSQL 入場憑證存儲庫實現
+pub struct PgAdmissionRepository {
+    pool: PgPool,
+}
+
+impl PgAdmissionRepository {
+    /// 創建新的 PostgreSQL 入場憑證存儲庫
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+}
+
+#[async_trait]
+impl AdmissionRepository for PgAdmissionRepository {
+    async fn find_by_code(&self, code: &str, tenant: Tenant) -> Result<Option<Admission>, AppError> {
+        let result = sqlx::query(&format!(
+            "SELECT {} FROM admissions a JOIN concerts c ON a.concert_id = c.id WHERE a.code = $1 AND {}",
+            ADMISSION_COLUMNS,
+            tenant_filter("c.organization_id", 2)
+        ))
+        .bind(code)
+        .bind(tenant.organization_id())
+        .fetch_optional(&self.pool)
+        .await?;
+
+        result.as_ref().map(admission_from_row).transpose()
+    }
+
+    async fn mark_used(&self, code: &str, tenant: Tenant) -> Result<Option<Admission>, AppError> {
+        // 條件式更新：同一張憑證同時被掃描時只有一方成功
+        let result = sqlx::query(&format!(
+            r#"
+            WITH used AS (
+                UPDATE admissions ad
+                SET status = 'used', used_at = CURRENT_TIMESTAMP
+                FROM concerts c
+                WHERE ad.concert_id = c.id AND ad.code = $1 AND ad.status = 'valid' AND {}
+                RETURNING ad.*
+            )
+            SELECT {} FROM used a JOIN concerts c ON a.concert_id = c.id
+            "#,
+            tenant_filter("c.organization_id", 2),
+            ADMISSION_COLUMNS
+        ))
+        .bind(code)
+        .bind(tenant.organization_id())
+        .fetch_optional(&self.pool)
+        .await?;
+
+        result.as_ref().map(admission_from_row).transpose()
+    }
+}
+
+/// 入場憑證與加購商品領取代碼：16 位大寫十六進位隨機字串
+pub const ADMISSION_CODE: &str = "upper(substr(replace(gen_random_uuid()::text, '-', ''), 1, 16))";
+
+/// 入場憑證欄位
+const ADMISSION_COLUMNS: &str = r#"
+    a.id, a.order_id, a.comp_id, a.concert_id, c.title as concert_title, a.label, a.code, a.status, a.used_at
+"#;
+
+/// 查找訂單的入場憑證
+pub async fn find_admissions_by_order_id(pool: &PgPool, order_id: Uuid) -> Result<Vec<Admission>, AppError> {
+    let rows = sqlx::query(&format!(
+        r#"
+        SELECT {}
+        FROM admissions a
+        JOIN concerts c ON a.concert_id = c.id
+        WHERE a.order_id = $1
+        ORDER BY c.date, a.issued_at, a.code
+        "#,
+        ADMISSION_COLUMNS
+    ))
+    .bind(order_id)
+    .fetch_all(pool)
+    .await?;
+
+    rows.iter().map(admission_from_row).collect()
+}
+
+/// 一次查找多筆訂單的入場憑證
+pub async fn find_admissions_by_order_ids(pool: &PgPool, order_ids: &[Uuid]) -> Result<Vec<Admission>, AppError> {
+    let rows = sqlx::query(&format!(
+        r#"
+        SELECT {}
+        FROM admissions a
+        JOIN concerts c ON a.concert_id = c.id
+        WHERE a.order_id = ANY($1)
+        ORDER BY c.date, a.issued_at, a.code
+        "#,
+        ADMISSION_COLUMNS
+    ))
+    .bind(order_ids)
+    .fetch_all(pool)
+    .await?;
+
+    rows.iter().map(admission_from_row).collect()
+}
+
+/// 查找招待票的入場憑證
+pub async fn find_admissions_by_comp_id(pool: &PgPool, comp_id: Uuid) -> Result<Vec<Admission>, AppError> {
+    let rows = sqlx::query(&format!(
+        r#"
+        SELECT {}
+        FROM admissions a
+        JOIN concerts c ON a.concert_id = c.id
+        WHERE a.comp_id = $1
+        ORDER BY a.issued_at, a.code
+        "#,
+        ADMISSION_COLUMNS
+    ))
+    .bind(comp_id)
+    .fetch_all(pool)
+    .await?;
+
+    rows.iter().map(admission_from_row).collect()
+}
+
+/// 將資料庫記錄轉換為入場憑證模型
+fn admission_from_row(row: &PgRow) -> Result<Admission, AppError> {
+    let status: String = row.get("status");
+
+    Ok(Admission {
+        id: row.get("id"),
+        order_id: row.get("order_id"),
+        comp_id: row.get("comp_id"),
+        concert_id: row.get("concert_id"),
+        concert_title: row.get("concert_title"),
+        label: row.get("label"),
+        code: row.get("code"),
+        status: AdmissionStatus::parse(&status)
+            .ok_or_else(|| AppError::Internal(format!("未知的入場憑證狀態: {}", status)))?,
+        used_at: row.get("used_at"),
+    })
+}
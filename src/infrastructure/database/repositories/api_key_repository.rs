@@ -0,0 +1,149 @@
+use async_trait::async_trait;
+use sqlx::postgres::PgRow;
+use sqlx::{PgPool, Row};
+use uuid::Uuid;
+
+use super::{is_foreign_key_violation, is_unique_violation, tenant_filter};
+use crate::domain::api_key::model::{ApiKey, ApiScope, NewApiKey};
+use crate::domain::api_key::repository::ApiKeyRepository;
+use crate::domain::organization::model::Tenant;
+use crate::utils::error::AppError;
+
+/// PostgreSQL API 金鑰存儲庫實現
+pub struct PgApiKeyRepository {
+    pool: PgPool,
+}
+
+impl PgApiKeyRepository {
+    /// 創建新的 PostgreSQL API 金鑰存儲庫
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+}
+
+/// API 金鑰查詢欄位
+const API_KEY_COLUMNS: &str = r#"
+    id, name, prefix, scopes, organization_id, rate_limit_per_minute,
+    expires_at, created_by, created_at, last_used_at, revoked_at
+"#;
+
+/// 將資料列轉換為 API 金鑰
+fn api_key_from_row(row: &PgRow) -> Result<ApiKey, AppError> {
+    let scopes: Vec<String> = row.get("scopes");
+    Ok(ApiKey {
+        id: row.get("id"),
+        name: row.get("name"),
+        prefix: row.get("prefix"),
+        scopes: scopes
+            .iter()
+            .map(|scope| ApiScope::parse(scope).ok_or_else(|| AppError::Internal(format!("未知的權限範圍: {}", scope))))
+            .collect::<Result<_, _>>()?,
+        organization_id: row.get("organization_id"),
+        rate_limit_per_minute: row.get("rate_limit_per_minute"),
+        expires_at: row.get("expires_at"),
+        created_by: row.get("created_by"),
+        created_at: row.get("created_at"),
+        last_used_at: row.get("last_used_at"),
+        revoked_at: row.get("revoked_at"),
+    })
+}
+
+#[async_trait]
+impl ApiKeyRepository for PgApiKeyRepository {
+    async fn create(&self, input: &NewApiKey) -> Result<ApiKey, AppError> {
+        let scopes: Vec<&str> = input.scopes.iter().map(|scope| scope.as_str()).collect();
+        let row = sqlx::query(&format!(
+            r#"
+            INSERT INTO api_keys (name, prefix, key_hash, scopes, organization_id,
+                                  rate_limit_per_minute, expires_at, created_by)
+            VALUES ($1, $2, $3, $4, $5, $6, CURRENT_TIMESTAMP + $7 * INTERVAL '1 second', $8)
+            RETURNING {}
+            "#,
+            API_KEY_COLUMNS
+        ))
+        .bind(&input.name)
+        .bind(&input.prefix)
+        .bind(&input.key_hash)
+        .bind(&scopes)
+        .bind(input.organization_id)
+        .bind(input.rate_limit_per_minute)
+        .bind(input.ttl.num_seconds())
+        .bind(input.created_by)
+        .fetch_one(&self.pool)
+        .await
+        .map_err(|error| {
+            if is_foreign_key_violation(&error) {
+                AppError::BadRequest("主辦單位不存在".to_string())
+            } else if is_unique_violation(&error) {
+                // 隨機產生的前綴重複，機率極低，重試即可
+                AppError::Conflict("金鑰前綴重複，請重試".to_string())
+            } else {
+                AppError::from(error)
+            }
+        })?;
+
+        api_key_from_row(&row)
+    }
+
+    async fn find_active_by_hash(&self, key_hash: &str) -> Result<Option<ApiKey>, AppError> {
+        let result = sqlx::query(&format!(
+            r#"
+            SELECT {}
+            FROM api_keys
+            WHERE key_hash = $1 AND revoked_at IS NULL AND expires_at > CURRENT_TIMESTAMP
+            "#,
+            API_KEY_COLUMNS
+        ))
+        .bind(key_hash)
+        .fetch_optional(&self.pool)
+        .await?;
+
+        result.as_ref().map(api_key_from_row).transpose()
+    }
+
+    async fn list(&self, tenant: Tenant) -> Result<Vec<ApiKey>, AppError> {
+        let rows = sqlx::query(&format!(
+            "SELECT {} FROM api_keys WHERE {} ORDER BY created_at DESC",
+            API_KEY_COLUMNS,
+            tenant_filter("organization_id", 1)
+        ))
+        .bind(tenant.organization_id())
+        .fetch_all(&self.pool)
+        .await?;
+
+        rows.iter().map(api_key_from_row).collect()
+    }
+
+    async fn revoke(&self, id: Uuid, tenant: Tenant) -> Result<bool, AppError> {
+        let result = sqlx::query(&format!(
+            r#"
+            UPDATE api_keys
+            SET revoked_at = CURRENT_TIMESTAMP
+            WHERE id = $1 AND revoked_at IS NULL AND {}
+            "#,
+            tenant_filter("organization_id", 2)
+        ))
+        .bind(id)
+        .bind(tenant.organization_id())
+        .execute(&self.pool)
+        .await?;
+
+        Ok(result.rows_affected() > 0)
+    }
+
+    async fn touch(&self, id: Uuid) -> Result<(), AppError> {
+        sqlx::query(
+            r#"
+            UPDATE api_keys
+            SET last_used_at = CURRENT_TIMESTAMP
+            WHERE id = $1
+              AND (last_used_at IS NULL OR last_used_at < CURRENT_TIMESTAMP - INTERVAL '1 minute')
+            "#
+        )
+        .bind(id)
+        .execute(&self.pool)
+        .await?;
+
+        Ok(())
+    }
+}
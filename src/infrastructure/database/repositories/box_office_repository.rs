@@ -0,0 +1,188 @@
+use async_trait::async_trait;
+use sqlx::postgres::PgRow;
+use sqlx::{PgPool, Row};
+use uuid::Uuid;
+
+use crate::domain::box_office::model::{BoxOfficeSale, DrawerSession, NewBoxOfficeSale, PaymentMethod};
+use crate::domain::box_office::repository::BoxOfficeRepository;
+use crate::domain::order::model::OrderStatus;
+use crate::domain::organization::model::Tenant;
+use crate::infrastructure::database::repositories::{is_unique_violation, tenant_filter};
+use crate::utils::error::AppError;
+use crate::utils::money::to_decimal;
+
+/// PostgreSQL 售票處存儲庫實現
+pub struct PgBoxOfficeRepository {
+    pool: PgPool,
+}
+
+impl PgBoxOfficeRepository {
+    /// 創建新的 PostgreSQL 售票處存儲庫
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+}
+
+#[async_trait]
+impl BoxOfficeRepository for PgBoxOfficeRepository {
+    async fn open_session(&self, staff_id: Uuid, opening_float: f64) -> Result<DrawerSession, AppError> {
+        let id: Uuid = sqlx::query(
+            "INSERT INTO cash_drawer_sessions (staff_id, opening_float) VALUES ($1, $2) RETURNING id"
+        )
+        .bind(staff_id)
+        .bind(to_decimal(opening_float)?)
+        .fetch_one(&self.pool)
+        .await
+        .map_err(|e| {
+            if is_unique_violation(&e) {
+                AppError::Conflict("已有尚未結束的錢櫃班次".to_string())
+            } else {
+                e.into()
+            }
+        })?
+        .get("id");
+
+        self.find_session(id, Tenant::Platform).await?
+            .ok_or_else(|| AppError::Internal(format!("找不到剛開啟的錢櫃班次 {}", id)))
+    }
+
+    async fn find_open_session(&self, staff_id: Uuid) -> Result<Option<DrawerSession>, AppError> {
+        let result = sqlx::query(&format!(
+            "SELECT {} FROM {} WHERE s.staff_id = $1 AND s.closed_at IS NULL",
+            SESSION_COLUMNS, SESSION_TABLES
+        ))
+        .bind(staff_id)
+        .fetch_optional(&self.pool)
+        .await?;
+
+        Ok(result.as_ref().map(session_from_row))
+    }
+
+    async fn find_session(&self, id: Uuid, tenant: Tenant) -> Result<Option<DrawerSession>, AppError> {
+        let result = sqlx::query(&format!(
+            "SELECT {} FROM {} WHERE s.id = $1 AND {}",
+            SESSION_COLUMNS, SESSION_TABLES, tenant_filter("u.organization_id", 2)
+        ))
+        .bind(id)
+        .bind(tenant.organization_id())
+        .fetch_optional(&self.pool)
+        .await?;
+
+        Ok(result.as_ref().map(session_from_row))
+    }
+
+    async fn close_session(&self, id: Uuid, counted_cash: f64, note: Option<&str>) -> Result<Option<DrawerSession>, AppError> {
+        // 條件式更新：同一班次只能結束一次
+        let result = sqlx::query(
+            r#"
+            UPDATE cash_drawer_sessions
+            SET counted_cash = $2, note = $3, closed_at = CURRENT_TIMESTAMP
+            WHERE id = $1 AND closed_at IS NULL
+            "#
+        )
+        .bind(id)
+        .bind(to_decimal(counted_cash)?)
+        .bind(note)
+        .execute(&self.pool)
+        .await?;
+
+        if result.rows_affected() == 0 {
+            return Ok(None);
+        }
+        self.find_session(id, Tenant::Platform).await
+    }
+
+    async fn record_sale(&self, sale: &NewBoxOfficeSale) -> Result<BoxOfficeSale, AppError> {
+        sqlx::query(
+            r#"
+            INSERT INTO box_office_sales (order_id, session_id, staff_id, payment_method, amount,
+                                          cash_tendered, change_due, card_reference)
+            VALUES ($1, $2, $3, $4, $5, $6, $7, $8)
+            "#
+        )
+        .bind(sale.order_id)
+        .bind(sale.session_id)
+        .bind(sale.staff_id)
+        .bind(sale.payment_method.as_str())
+        .bind(to_decimal(sale.amount)?)
+        .bind(sale.cash_tendered.map(to_decimal).transpose()?)
+        .bind(sale.change_due.map(to_decimal).transpose()?)
+        .bind(&sale.card_reference)
+        .execute(&self.pool)
+        .await?;
+
+        let row = sqlx::query(&format!("SELECT {} FROM {} WHERE b.order_id = $1", SALE_COLUMNS, SALE_TABLES))
+            .bind(sale.order_id)
+            .fetch_one(&self.pool)
+            .await?;
+
+        sale_from_row(&row)
+    }
+
+    async fn find_sales(&self, session_id: Uuid) -> Result<Vec<BoxOfficeSale>, AppError> {
+        let rows = sqlx::query(&format!(
+            "SELECT {} FROM {} WHERE b.session_id = $1 ORDER BY b.created_at",
+            SALE_COLUMNS, SALE_TABLES
+        ))
+        .bind(session_id)
+        .fetch_all(&self.pool)
+        .await?;
+
+        rows.iter().map(sale_from_row).collect()
+    }
+}
+
+/// 錢櫃班次欄位
+const SESSION_COLUMNS: &str = r#"
+    s.id, s.staff_id, u.email AS staff_email, s.opening_float::float8, s.counted_cash::float8,
+    s.note, s.opened_at, s.closed_at
+"#;
+
+/// 錢櫃班次關聯表
+const SESSION_TABLES: &str = "cash_drawer_sessions s JOIN users u ON s.staff_id = u.id";
+
+/// 現場售票記錄欄位
+const SALE_COLUMNS: &str = r#"
+    b.order_id, b.session_id, b.staff_id, b.payment_method, b.amount::float8,
+    b.cash_tendered::float8, b.change_due::float8, b.card_reference,
+    o.quantity, o.status AS order_status, b.created_at
+"#;
+
+/// 現場售票記錄關聯表
+const SALE_TABLES: &str = "box_office_sales b JOIN orders o ON b.order_id = o.id";
+
+/// 將資料庫記錄轉換為錢櫃班次模型
+fn session_from_row(row: &PgRow) -> DrawerSession {
+    DrawerSession {
+        id: row.get("id"),
+        staff_id: row.get("staff_id"),
+        staff_email: row.get("staff_email"),
+        opening_float: row.get("opening_float"),
+        counted_cash: row.get("counted_cash"),
+        note: row.get("note"),
+        opened_at: row.get("opened_at"),
+        closed_at: row.get("closed_at"),
+    }
+}
+
+/// 將資料庫記錄轉換為現場售票記錄模型
+fn sale_from_row(row: &PgRow) -> Result<BoxOfficeSale, AppError> {
+    let payment_method: String = row.get("payment_method");
+    let order_status: String = row.get("order_status");
+
+    Ok(BoxOfficeSale {
+        order_id: row.get("order_id"),
+        session_id: row.get("session_id"),
+        staff_id: row.get("staff_id"),
+        payment_method: PaymentMethod::parse(&payment_method)
+            .ok_or_else(|| AppError::Internal(format!("未知的付款方式: {}", payment_method)))?,
+        amount: row.get("amount"),
+        cash_tendered: row.get("cash_tendered"),
+        change_due: row.get("change_due"),
+        card_reference: row.get("card_reference"),
+        quantity: row.get("quantity"),
+        order_status: OrderStatus::parse(&order_status)
+            .ok_or_else(|| AppError::Internal(format!("未知的訂單狀態: {}", order_status)))?,
+        created_at: row.get("created_at"),
+    })
+}
@@ -0,0 +1,242 @@
+use async_trait::async_trait;
+use sqlx::postgres::PgRow;
+use sqlx::{PgConnection, PgPool, Row};
+use uuid::Uuid;
+
+use crate::domain::bundle::model::{Bundle, BundleComponent, CreateBundle};
+use crate::domain::bundle::repository::BundleRepository;
+use crate::domain::concert::model::TaxSetting;
+use crate::domain::organization::model::Tenant;
+use crate::infrastructure::database::repositories::ticket_repository::update_ticket_stock;
+use crate::infrastructure::database::repositories::{tenant_filter, tickets_in_tenant};
+use crate::utils::error::AppError;
+use crate::utils::money::to_decimal;
+
+/// PostgreSQL 組合商品存儲庫實現
+pub struct PgBundleRepository {
+    pool: PgPool,
+}
+
+impl PgBundleRepository {
+    /// 創建新的 PostgreSQL 組合商品存儲庫
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+
+    /// 讀取組合商品並附上組合內容
+    async fn load(&self, rows: Vec<PgRow>) -> Result<Vec<Bundle>, AppError> {
+        let mut bundles = Vec::with_capacity(rows.len());
+        for row in rows {
+            let id: Uuid = row.get("id");
+            bundles.push(Bundle {
+                id,
+                name: row.get("name"),
+                description: row.get("description"),
+                price: row.get("price"),
+                stock: row.get("stock"),
+                tax: TaxSetting {
+                    tax_rate: row.get("tax_rate"),
+                    tax_inclusive: row.get("tax_inclusive"),
+                },
+                components: find_components(&self.pool, id).await?,
+            });
+        }
+        Ok(bundles)
+    }
+}
+
+#[async_trait]
+impl BundleRepository for PgBundleRepository {
+    async fn find_by_id(&self, id: Uuid, tenant: Tenant) -> Result<Option<Bundle>, AppError> {
+        let rows = sqlx::query(&format!(
+            "SELECT {} FROM bundles b WHERE b.id = $1 AND {}",
+            BUNDLE_COLUMNS,
+            bundle_in_tenant(2)
+        ))
+        .bind(id)
+        .bind(tenant.organization_id())
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(self.load(rows).await?.pop())
+    }
+
+    async fn find_all(&self, tenant: Tenant) -> Result<Vec<Bundle>, AppError> {
+        let rows = sqlx::query(&format!(
+            "SELECT {} FROM bundles b WHERE {} ORDER BY b.created_at",
+            BUNDLE_COLUMNS,
+            bundle_in_tenant(1)
+        ))
+        .bind(tenant.organization_id())
+        .fetch_all(&self.pool)
+        .await?;
+
+        self.load(rows).await
+    }
+
+    async fn create(&self, input: &CreateBundle, tenant: Tenant) -> Result<Bundle, AppError> {
+        let mut tx = self.pool.begin().await?;
+
+        // 組合內容的票種都必須屬於租戶範圍
+        let ticket_ids: Vec<Uuid> = input.components.iter().map(|component| component.ticket_id).collect();
+        if !tickets_in_tenant(&mut tx, &ticket_ids, tenant).await? {
+            return Err(AppError::NotFound("找不到組合內容的票券".to_string()));
+        }
+
+        let id: Uuid = sqlx::query(
+            r#"
+            INSERT INTO bundles (name, description, price, stock, initial_stock, tax_rate, tax_inclusive)
+            VALUES ($1, $2, $3, $4, $4, $5, $6)
+            RETURNING id
+            "#
+        )
+        .bind(&input.name)
+        .bind(&input.description)
+        .bind(to_decimal(input.price)?)
+        .bind(input.stock)
+        .bind(to_decimal(input.tax.tax_rate)?)
+        .bind(input.tax.tax_inclusive)
+        .fetch_one(&mut *tx)
+        .await?
+        .get("id");
+
+        for component in &input.components {
+            sqlx::query("INSERT INTO bundle_components (bundle_id, ticket_id, quantity) VALUES ($1, $2, $3)")
+                .bind(id)
+                .bind(component.ticket_id)
+                .bind(component.quantity)
+                .execute(&mut *tx)
+                .await?;
+        }
+
+        // 依創建當下的票價固定各組合內容的營收分配比例
+        sqlx::query("SELECT set_bundle_revenue_shares($1)")
+            .bind(id)
+            .execute(&mut *tx)
+            .await?;
+
+        tx.commit().await?;
+        self.find_by_id(id, tenant).await?
+            .ok_or_else(|| AppError::Internal(format!("找不到剛創建的組合商品 {}", id)))
+    }
+}
+
+/// 組合商品欄位；可購買組數取組合庫存與各組合內容可售數量換算組數的最小值
+const BUNDLE_COLUMNS: &str = r#"
+    b.id, b.name, b.description, b.price::float8,
+    LEAST(b.stock, COALESCE((
+        SELECT MIN(LEAST(t.stock, COALESCE(p.remaining, t.stock)) / bc.quantity)
+        FROM bundle_components bc
+        JOIN tickets t ON bc.ticket_id = t.id
+        LEFT JOIN inventory_pools p ON p.id = t.pool_id
+        WHERE bc.bundle_id = b.id
+    ), b.stock)) AS stock,
+    b.tax_rate::float8, b.tax_inclusive
+"#;
+
+/// 組合商品租戶範圍條件：沒有任何組合內容的票種落在租戶範圍外
+fn bundle_in_tenant(param: usize) -> String {
+    format!(
+        r#"NOT EXISTS (
+            SELECT 1
+            FROM bundle_components bc
+            JOIN tickets t ON bc.ticket_id = t.id
+            JOIN concerts c ON t.concert_id = c.id
+            WHERE bc.bundle_id = b.id AND {} IS NOT TRUE
+        )"#,
+        tenant_filter("c.organization_id", param)
+    )
+}
+
+/// 查找組合內容
+pub async fn find_components(pool: &PgPool, bundle_id: Uuid) -> Result<Vec<BundleComponent>, AppError> {
+    let rows = sqlx::query(
+        r#"
+        SELECT bc.ticket_id, t.ticket_type, c.id AS concert_id, c.title AS concert_title,
+               c.date AS concert_date, bc.quantity, bc.revenue_share::float8
+        FROM bundle_components bc
+        JOIN tickets t ON bc.ticket_id = t.id
+        JOIN concerts c ON t.concert_id = c.id
+        WHERE bc.bundle_id = $1
+        ORDER BY c.date, t.ticket_type
+        "#
+    )
+    .bind(bundle_id)
+    .fetch_all(pool)
+    .await?;
+
+    Ok(rows.iter().map(component_from_row).collect())
+}
+
+/// 一次查找多個組合商品的組合內容，回傳（組合商品 ID, 組合內容）
+pub async fn find_components_by_bundle_ids(
+    pool: &PgPool,
+    bundle_ids: &[Uuid],
+) -> Result<Vec<(Uuid, BundleComponent)>, AppError> {
+    let rows = sqlx::query(
+        r#"
+        SELECT bc.bundle_id, bc.ticket_id, t.ticket_type, c.id AS concert_id, c.title AS concert_title,
+               c.date AS concert_date, bc.quantity, bc.revenue_share::float8
+        FROM bundle_components bc
+        JOIN tickets t ON bc.ticket_id = t.id
+        JOIN concerts c ON t.concert_id = c.id
+        WHERE bc.bundle_id = ANY($1)
+        ORDER BY c.date, t.ticket_type
+        "#
+    )
+    .bind(bundle_ids)
+    .fetch_all(pool)
+    .await?;
+
+    Ok(rows.iter().map(|row| (row.get("bundle_id"), component_from_row(row))).collect())
+}
+
+/// 將資料庫記錄轉換為組合內容模型
+fn component_from_row(row: &PgRow) -> BundleComponent {
+    BundleComponent {
+        ticket_id: row.get("ticket_id"),
+        ticket_type: row.get("ticket_type"),
+        concert_id: row.get("concert_id"),
+        concert_title: row.get("concert_title"),
+        concert_date: row.get("concert_date"),
+        quantity: row.get("quantity"),
+        revenue_share: row.get("revenue_share"),
+    }
+}
+
+/// 在交易中更新組合商品與所有組合內容的庫存
+/// 正數為扣減、負數為歸還；組合或任一票種（含庫存池）不足時返回 false，呼叫端應放棄交易
+pub async fn update_bundle_stock(conn: &mut PgConnection, id: Uuid, quantity: i32) -> Result<bool, AppError> {
+    let result = sqlx::query(
+        r#"
+        UPDATE bundles
+        SET stock = stock - $2
+        WHERE id = $1 AND stock >= $2
+        "#
+    )
+    .bind(id)
+    .bind(quantity)
+    .execute(&mut *conn)
+    .await?;
+
+    if result.rows_affected() == 0 {
+        return Ok(false);
+    }
+
+    // 依票種 ID 順序更新，避免並行購買不同組合時互相等待造成死鎖
+    let components = sqlx::query(
+        "SELECT ticket_id, quantity FROM bundle_components WHERE bundle_id = $1 ORDER BY ticket_id"
+    )
+    .bind(id)
+    .fetch_all(&mut *conn)
+    .await?;
+
+    for component in components {
+        let per_bundle: i32 = component.get("quantity");
+        if !update_ticket_stock(conn, component.get("ticket_id"), per_bundle * quantity).await? {
+            return Ok(false);
+        }
+    }
+
+    Ok(true)
+}
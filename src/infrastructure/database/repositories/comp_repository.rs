@@ -0,0 +1,162 @@
+use async_trait::async_trait;
+use sqlx::{PgPool, Row};
+use uuid::Uuid;
+
+use crate::domain::comp::model::{Comp, GuestListEntry, IssueComps};
+use crate::domain::comp::repository::CompRepository;
+use crate::domain::organization::model::Tenant;
+use crate::infrastructure::database::repositories::admission_repository::{
+    find_admissions_by_comp_id, ADMISSION_CODE,
+};
+use crate::infrastructure::database::repositories::ticket_repository::update_ticket_stock;
+use crate::infrastructure::database::repositories::{concert_in_tenant, tenant_filter, tickets_in_tenant};
+use crate::utils::error::AppError;
+
+/// PostgreSQL 招待票存儲庫實現
+pub struct PgCompRepository {
+    pool: PgPool,
+}
+
+impl PgCompRepository {
+    /// 創建新的 PostgreSQL 招待票存儲庫
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+}
+
+#[async_trait]
+impl CompRepository for PgCompRepository {
+    async fn issue(
+        &self,
+        concert_id: Uuid,
+        input: &IssueComps,
+        issued_by: Uuid,
+        tenant: Tenant,
+    ) -> Result<Vec<Comp>, AppError> {
+        let mut tx = self.pool.begin().await?;
+
+        if !concert_in_tenant(&mut tx, concert_id, tenant).await?
+            || !tickets_in_tenant(&mut tx, &[input.ticket_id], tenant).await?
+        {
+            return Err(AppError::NotFound(format!("找不到 ID 為 {} 的演唱會", concert_id)));
+        }
+
+        // 整批一次扣減，與購票相同以條件式更新避免超賣
+        let total = input.quantity * input.guests.len() as i32;
+        if !update_ticket_stock(&mut tx, input.ticket_id, total).await? {
+            return Err(AppError::BadRequest("招待票庫存不足".to_string()));
+        }
+
+        let mut ids = Vec::with_capacity(input.guests.len());
+        for guest in &input.guests {
+            let id: Uuid = sqlx::query(
+                r#"
+                INSERT INTO comps (concert_id, ticket_id, email, name, quantity, note, issued_by)
+                VALUES ($1, $2, $3, $4, $5, $6, $7)
+                RETURNING id
+                "#
+            )
+            .bind(concert_id)
+            .bind(input.ticket_id)
+            .bind(guest.email.trim().to_lowercase())
+            .bind(&guest.name)
+            .bind(input.quantity)
+            .bind(&input.note)
+            .bind(issued_by)
+            .fetch_one(&mut *tx)
+            .await?
+            .get("id");
+
+            // 每張招待票一份入場憑證
+            sqlx::query(&format!(
+                r#"
+                INSERT INTO admissions (comp_id, concert_id, label, code)
+                SELECT cp.id, cp.concert_id, t.ticket_type, {}
+                FROM comps cp
+                JOIN tickets t ON cp.ticket_id = t.id
+                CROSS JOIN generate_series(1, cp.quantity)
+                WHERE cp.id = $1
+                "#,
+                ADMISSION_CODE
+            ))
+            .bind(id)
+            .execute(&mut *tx)
+            .await?;
+
+            ids.push(id);
+        }
+
+        tx.commit().await?;
+
+        let rows = sqlx::query(
+            r#"
+            SELECT cp.id, cp.concert_id, cp.ticket_id, t.ticket_type, cp.email, cp.name,
+                   cp.quantity, cp.note, cp.issued_by, cp.issued_at
+            FROM comps cp
+            JOIN tickets t ON cp.ticket_id = t.id
+            WHERE cp.id = ANY($1)
+            ORDER BY cp.email
+            "#
+        )
+        .bind(&ids)
+        .fetch_all(&self.pool)
+        .await?;
+
+        let mut comps = Vec::with_capacity(rows.len());
+        for row in rows {
+            let id: Uuid = row.get("id");
+            comps.push(Comp {
+                id,
+                concert_id: row.get("concert_id"),
+                ticket_id: row.get("ticket_id"),
+                ticket_type: row.get("ticket_type"),
+                email: row.get("email"),
+                name: row.get("name"),
+                quantity: row.get("quantity"),
+                note: row.get("note"),
+                issued_by: row.get("issued_by"),
+                issued_at: row.get("issued_at"),
+                admissions: find_admissions_by_comp_id(&self.pool, id).await?,
+            });
+        }
+        Ok(comps)
+    }
+
+    async fn guest_list(&self, concert_id: Uuid, tenant: Tenant) -> Result<Vec<GuestListEntry>, AppError> {
+        let rows = sqlx::query(&format!(
+            r#"
+            SELECT cp.id AS comp_id, cp.email, cp.name, t.ticket_type, cp.quantity,
+                   COUNT(a.id) FILTER (WHERE a.status = 'used') AS checked_in,
+                   COALESCE(string_agg(a.code, ' ' ORDER BY a.code), '') AS codes,
+                   cp.note, cp.issued_at
+            FROM comps cp
+            JOIN concerts c ON cp.concert_id = c.id
+            JOIN tickets t ON cp.ticket_id = t.id
+            LEFT JOIN admissions a ON a.comp_id = cp.id
+            WHERE cp.concert_id = $1 AND {}
+            GROUP BY cp.id, t.ticket_type
+            ORDER BY cp.name NULLS LAST, cp.email, cp.issued_at
+            "#,
+            tenant_filter("c.organization_id", 2)
+        ))
+        .bind(concert_id)
+        .bind(tenant.organization_id())
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(rows
+            .iter()
+            .map(|row| GuestListEntry {
+                comp_id: row.get("comp_id"),
+                email: row.get("email"),
+                name: row.get("name"),
+                ticket_type: row.get("ticket_type"),
+                quantity: row.get("quantity"),
+                checked_in: row.get("checked_in"),
+                codes: row.get("codes"),
+                note: row.get("note"),
+                issued_at: row.get("issued_at"),
+            })
+            .collect())
+    }
+}
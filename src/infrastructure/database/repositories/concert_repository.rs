@@ -1,9 +1,13 @@
 use async_trait::async_trait;
-use sqlx::PgPool;
+use sqlx::types::BigDecimal;
+use sqlx::{PgPool, Row};
+use std::str::FromStr;
 use uuid::Uuid;
 
-use crate::domain::concert::model::{Concert, CreateConcert};
+use crate::domain::concert::model::{Concert, ConcertFee, CreateConcert, CreateConcertFee, FeeType, TaxSetting};
 use crate::domain::concert::repository::ConcertRepository;
+use crate::domain::organization::model::Tenant;
+use crate::infrastructure::database::repositories::{is_foreign_key_violation, tenant_filter};
 use crate::utils::error::AppError;
 
 /// PostgreSQL 演唱會存儲庫實現
@@ -20,15 +24,17 @@ impl PgConcertRepository {
 
 #[async_trait]
 impl ConcertRepository for PgConcertRepository {
-    async fn find_by_id(&self, id: Uuid) -> Result<Option<Concert>, AppError> {
+    async fn find_by_id(&self, id: Uuid, tenant: Tenant) -> Result<Option<Concert>, AppError> {
         // 使用 query! 而不是 query_as! 來手動處理
         let record = sqlx::query!(
             r#"
-            SELECT id, title, venue, date
+            SELECT id, title, artist, venue, date,
+                   tax_rate::float8 as "tax_rate!", tax_inclusive, organization_id
             FROM concerts
-            WHERE id = $1
+            WHERE id = $1 AND ($2::uuid IS NULL OR organization_id = $2)
             "#,
-            id
+            id,
+            tenant.organization_id()
         )
         .fetch_optional(&self.pool)
         .await?;
@@ -37,9 +43,14 @@ impl ConcertRepository for PgConcertRepository {
         Ok(record.map(|r| Concert {
             id: r.id,
             title: r.title,
-            artist: "未知藝術家".to_string(), // 暫時使用預設值
+            artist: r.artist,
             venue: r.venue,
             date: r.date,
+            tax: TaxSetting {
+                tax_rate: r.tax_rate,
+                tax_inclusive: r.tax_inclusive,
+            },
+            organization_id: r.organization_id,
         }))
     }
 
@@ -47,7 +58,8 @@ impl ConcertRepository for PgConcertRepository {
         // 使用 query! 而不是 query_as! 來手動處理
         let records = sqlx::query!(
             r#"
-            SELECT id, title, venue, date
+            SELECT id, title, artist, venue, date,
+                   tax_rate::float8 as "tax_rate!", tax_inclusive, organization_id
             FROM concerts
             ORDER BY date
             "#
@@ -61,37 +73,148 @@ impl ConcertRepository for PgConcertRepository {
             .map(|r| Concert {
                 id: r.id,
                 title: r.title,
-                artist: "未知藝術家".to_string(), // 暫時使用預設值
+                artist: r.artist,
                 venue: r.venue,
                 date: r.date,
+                tax: TaxSetting {
+                    tax_rate: r.tax_rate,
+                    tax_inclusive: r.tax_inclusive,
+                },
+                organization_id: r.organization_id,
             })
             .collect();
 
         Ok(concerts)
     }
 
-    async fn create(&self, input: &CreateConcert) -> Result<Concert, AppError> {
+    async fn create(&self, input: &CreateConcert, organization_id: Option<Uuid>) -> Result<Concert, AppError> {
         // 使用 query! 而不是 query_as! 來手動處理
         let record = sqlx::query!(
             r#"
-            INSERT INTO concerts (title, venue, date)
-            VALUES ($1, $2, $3)
-            RETURNING id, title, venue, date
+            INSERT INTO concerts (title, artist, venue, date, organization_id)
+            VALUES ($1, $2, $3, $4, $5)
+            RETURNING id, title, artist, venue, date,
+                      tax_rate::float8 as "tax_rate!", tax_inclusive, organization_id
             "#,
             input.title,
+            input.artist,
             input.venue,
-            input.date
+            input.date,
+            organization_id
         )
         .fetch_one(&self.pool)
-        .await?;
+        .await
+        .map_err(|e| {
+            if is_foreign_key_violation(&e) {
+                AppError::NotFound(format!("找不到 ID 為 {} 的主辦單位", organization_id.unwrap_or_default()))
+            } else {
+                e.into()
+            }
+        })?;
 
         // 手動轉換為 Concert 模型
         Ok(Concert {
             id: record.id,
             title: record.title,
-            artist: input.artist.clone(), // 使用輸入的藝術家名稱，但不存儲到資料庫
+            artist: record.artist,
             venue: record.venue,
             date: record.date,
+            tax: TaxSetting {
+                tax_rate: record.tax_rate,
+                tax_inclusive: record.tax_inclusive,
+            },
+            organization_id: record.organization_id,
         })
     }
+
+    async fn update_tax(&self, id: Uuid, tax: &TaxSetting, tenant: Tenant) -> Result<Option<Concert>, AppError> {
+        let tax_rate = BigDecimal::from_str(&tax.tax_rate.to_string())
+            .map_err(|_| AppError::BadRequest("無效的稅率".to_string()))?;
+
+        let record = sqlx::query!(
+            r#"
+            UPDATE concerts
+            SET tax_rate = $2, tax_inclusive = $3
+            WHERE id = $1 AND ($4::uuid IS NULL OR organization_id = $4)
+            RETURNING id, title, artist, venue, date,
+                      tax_rate::float8 as "tax_rate!", tax_inclusive, organization_id
+            "#,
+            id,
+            tax_rate,
+            tax.tax_inclusive,
+            tenant.organization_id()
+        )
+        .fetch_optional(&self.pool)
+        .await?;
+
+        Ok(record.map(|r| Concert {
+            id: r.id,
+            title: r.title,
+            artist: r.artist,
+            venue: r.venue,
+            date: r.date,
+            tax: TaxSetting {
+                tax_rate: r.tax_rate,
+                tax_inclusive: r.tax_inclusive,
+            },
+            organization_id: r.organization_id,
+        }))
+    }
+
+    async fn find_fees(&self, concert_id: Uuid) -> Result<Vec<ConcertFee>, AppError> {
+        // 使用原生 SQL 查詢
+        let result = sqlx::query(
+            r#"
+            SELECT id, concert_id, name, fee_type, amount::float8
+            FROM concert_fees
+            WHERE concert_id = $1
+            ORDER BY created_at
+            "#
+        )
+        .bind(concert_id)
+        .fetch_all(&self.pool)
+        .await?;
+
+        result.iter().map(fee_from_row).collect()
+    }
+
+    async fn create_fee(&self, concert_id: Uuid, input: &CreateConcertFee, tenant: Tenant) -> Result<Option<ConcertFee>, AppError> {
+        // 將 f64 轉換為 BigDecimal
+        let amount_decimal = BigDecimal::from_str(&input.amount.to_string())
+            .map_err(|_| AppError::BadRequest("無效的金額".to_string()))?;
+
+        let result = sqlx::query(&format!(
+            r#"
+            INSERT INTO concert_fees (concert_id, name, fee_type, amount)
+            SELECT c.id, $2, $3, $4
+            FROM concerts c
+            WHERE c.id = $1 AND {}
+            RETURNING id, concert_id, name, fee_type, amount::float8
+            "#,
+            tenant_filter("c.organization_id", 5)
+        ))
+        .bind(concert_id)
+        .bind(&input.name)
+        .bind(input.fee_type.as_str())
+        .bind(amount_decimal)
+        .bind(tenant.organization_id())
+        .fetch_optional(&self.pool)
+        .await?;
+
+        result.as_ref().map(fee_from_row).transpose()
+    }
+}
+
+/// 將資料庫記錄轉換為服務費模型
+fn fee_from_row(row: &sqlx::postgres::PgRow) -> Result<ConcertFee, AppError> {
+    let fee_type: String = row.get("fee_type");
+
+    Ok(ConcertFee {
+        id: row.get("id"),
+        concert_id: row.get("concert_id"),
+        name: row.get("name"),
+        fee_type: FeeType::parse(&fee_type)
+            .ok_or_else(|| AppError::Internal(format!("未知的服務費類型: {}", fee_type)))?,
+        amount: row.get("amount"),
+    })
 }
@@ -0,0 +1,190 @@
+use async_trait::async_trait;
+use chrono::Duration;
+use sqlx::{PgPool, Row};
+use uuid::Uuid;
+
+use super::is_unique_violation;
+use crate::domain::auth::model::{ExternalIdentity, LinkedIdentity, OidcLoginState};
+use crate::domain::auth::repository::IdentityRepository;
+use crate::utils::error::AppError;
+
+/// PostgreSQL 外部身分存儲庫實現
+pub struct PgIdentityRepository {
+    pool: PgPool,
+}
+
+impl PgIdentityRepository {
+    /// 創建新的 PostgreSQL 外部身分存儲庫
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+}
+
+/// 外部身分已連結其他用戶
+fn identity_conflict(error: sqlx::Error) -> AppError {
+    if is_unique_violation(&error) {
+        AppError::Conflict("此社群帳號已連結其他用戶".to_string())
+    } else {
+        AppError::from(error)
+    }
+}
+
+#[async_trait]
+impl IdentityRepository for PgIdentityRepository {
+    async fn save_login_state(
+        &self,
+        state_hash: &str,
+        provider: &str,
+        login_state: &OidcLoginState,
+        ttl: Duration,
+    ) -> Result<(), AppError> {
+        // 順便清除逾期未完成的登入狀態
+        sqlx::query("DELETE FROM oidc_login_states WHERE expires_at < CURRENT_TIMESTAMP")
+            .execute(&self.pool)
+            .await?;
+
+        sqlx::query(
+            r#"
+            INSERT INTO oidc_login_states (state_hash, provider, code_verifier, nonce, expires_at)
+            VALUES ($1, $2, $3, $4, CURRENT_TIMESTAMP + $5 * INTERVAL '1 second')
+            "#
+        )
+        .bind(state_hash)
+        .bind(provider)
+        .bind(&login_state.code_verifier)
+        .bind(&login_state.nonce)
+        .bind(ttl.num_seconds())
+        .execute(&self.pool)
+        .await?;
+
+        Ok(())
+    }
+
+    async fn take_login_state(&self, state_hash: &str, provider: &str) -> Result<Option<OidcLoginState>, AppError> {
+        // 刪除並返回，同一個 state 重複回呼時只有一次成功
+        let result = sqlx::query(
+            r#"
+            DELETE FROM oidc_login_states
+            WHERE state_hash = $1
+            RETURNING provider, code_verifier, nonce, expires_at > CURRENT_TIMESTAMP AS active
+            "#
+        )
+        .bind(state_hash)
+        .fetch_optional(&self.pool)
+        .await?;
+
+        Ok(result
+            .filter(|row| row.get::<String, _>("provider") == provider)
+            .filter(|row| row.get::<bool, _>("active"))
+            .map(|row| OidcLoginState {
+                code_verifier: row.get("code_verifier"),
+                nonce: row.get("nonce"),
+            }))
+    }
+
+    async fn find_user_id(&self, provider: &str, subject: &str) -> Result<Option<Uuid>, AppError> {
+        let result = sqlx::query(
+            r#"
+            UPDATE user_identities
+            SET last_login_at = CURRENT_TIMESTAMP
+            WHERE provider = $1 AND subject = $2
+            RETURNING user_id
+            "#
+        )
+        .bind(provider)
+        .bind(subject)
+        .fetch_optional(&self.pool)
+        .await?;
+
+        Ok(result.map(|row| row.get("user_id")))
+    }
+
+    async fn link(&self, user_id: Uuid, identity: &ExternalIdentity) -> Result<(), AppError> {
+        sqlx::query(
+            r#"
+            INSERT INTO user_identities (user_id, provider, subject, email)
+            VALUES ($1, $2, $3, $4)
+            "#
+        )
+        .bind(user_id)
+        .bind(&identity.provider)
+        .bind(&identity.subject)
+        .bind(&identity.email)
+        .execute(&self.pool)
+        .await
+        .map_err(identity_conflict)?;
+
+        Ok(())
+    }
+
+    async fn create_user(&self, email: &str, identity: &ExternalIdentity) -> Result<Uuid, AppError> {
+        let mut tx = self.pool.begin().await?;
+
+        // 電子郵件已由身分提供者驗證，不需要再寄送驗證信
+        let user_id: Uuid = sqlx::query(
+            r#"
+            INSERT INTO users (email, email_verified_at)
+            VALUES ($1, CURRENT_TIMESTAMP)
+            RETURNING id
+            "#
+        )
+        .bind(email)
+        .fetch_one(&mut *tx)
+        .await
+        .map_err(|error| {
+            if is_unique_violation(&error) {
+                AppError::Conflict("電子郵件已存在".to_string())
+            } else {
+                AppError::from(error)
+            }
+        })?
+        .get("id");
+
+        sqlx::query("INSERT INTO user_roles (user_id, role) VALUES ($1, 'customer')")
+            .bind(user_id)
+            .execute(&mut *tx)
+            .await?;
+
+        sqlx::query(
+            r#"
+            INSERT INTO user_identities (user_id, provider, subject, email)
+            VALUES ($1, $2, $3, $4)
+            "#
+        )
+        .bind(user_id)
+        .bind(&identity.provider)
+        .bind(&identity.subject)
+        .bind(&identity.email)
+        .execute(&mut *tx)
+        .await
+        .map_err(identity_conflict)?;
+
+        tx.commit().await?;
+        Ok(user_id)
+    }
+
+    async fn find_by_user(&self, user_id: Uuid) -> Result<Vec<LinkedIdentity>, AppError> {
+        let rows = sqlx::query(
+            r#"
+            SELECT provider, subject, email, created_at, last_login_at
+            FROM user_identities
+            WHERE user_id = $1
+            ORDER BY created_at
+            "#,
+        )
+        .bind(user_id)
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(rows
+            .iter()
+            .map(|row| LinkedIdentity {
+                provider: row.get("provider"),
+                subject: row.get("subject"),
+                email: row.get("email"),
+                created_at: row.get("created_at"),
+                last_login_at: row.get("last_login_at"),
+            })
+            .collect())
+    }
+}
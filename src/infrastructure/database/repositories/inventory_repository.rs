@@ -0,0 +1,284 @@
+use async_trait::async_trait;
+use sqlx::postgres::PgRow;
+use sqlx::{PgPool, Row};
+use uuid::Uuid;
+
+use crate::domain::inventory::model::{CreateInventoryPool, InventoryPool, PoolTicket};
+use crate::domain::inventory::repository::InventoryRepository;
+use crate::domain::organization::model::Tenant;
+use crate::infrastructure::database::repositories::{is_unique_violation, tenant_filter, tickets_in_tenant};
+use crate::utils::error::AppError;
+
+/// PostgreSQL 庫存池存儲庫實現
+pub struct PgInventoryRepository {
+    pool: PgPool,
+}
+
+impl PgInventoryRepository {
+    /// 創建新的 PostgreSQL 庫存池存儲庫
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+}
+
+#[async_trait]
+impl InventoryRepository for PgInventoryRepository {
+    async fn find_pool(&self, id: Uuid, tenant: Tenant) -> Result<Option<InventoryPool>, AppError> {
+        let result = sqlx::query(&format!(
+            "SELECT {} FROM {} WHERE p.id = $1 AND {}",
+            POOL_COLUMNS, POOL_TABLES, tenant_filter("c.organization_id", 2)
+        ))
+        .bind(id)
+        .bind(tenant.organization_id())
+        .fetch_optional(&self.pool)
+        .await?;
+
+        Ok(result.as_ref().map(pool_from_row))
+    }
+
+    async fn find_pools_by_concert_id(&self, concert_id: Uuid, tenant: Tenant) -> Result<Vec<InventoryPool>, AppError> {
+        let result = sqlx::query(&format!(
+            "SELECT {} FROM {} WHERE p.concert_id = $1 AND {} ORDER BY p.name",
+            POOL_COLUMNS, POOL_TABLES, tenant_filter("c.organization_id", 2)
+        ))
+        .bind(concert_id)
+        .bind(tenant.organization_id())
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(result.iter().map(pool_from_row).collect())
+    }
+
+    async fn find_pool_tickets(&self, pool_id: Uuid, tenant: Tenant) -> Result<Vec<PoolTicket>, AppError> {
+        let result = sqlx::query(&format!(
+            r#"
+            SELECT t.id, t.ticket_type, t.stock, t.initial_stock + t.allocation_adjustment - t.stock AS sold
+            FROM tickets t
+            JOIN concerts c ON t.concert_id = c.id
+            WHERE t.pool_id = $1 AND {}
+            ORDER BY t.ticket_type
+            "#,
+            tenant_filter("c.organization_id", 2)
+        ))
+        .bind(pool_id)
+        .bind(tenant.organization_id())
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(result
+            .iter()
+            .map(|row| PoolTicket {
+                ticket_id: row.get("id"),
+                ticket_type: row.get("ticket_type"),
+                stock: row.get("stock"),
+                sold: row.get("sold"),
+            })
+            .collect())
+    }
+
+    async fn create_pool(
+        &self,
+        concert_id: Uuid,
+        input: &CreateInventoryPool,
+        tenant: Tenant,
+    ) -> Result<Option<InventoryPool>, AppError> {
+        let record = sqlx::query(&format!(
+            r#"
+            INSERT INTO inventory_pools (concert_id, name, capacity, remaining)
+            SELECT c.id, $2, $3, $3
+            FROM concerts c
+            WHERE c.id = $1 AND {}
+            RETURNING id, concert_id, name, capacity, remaining
+            "#,
+            tenant_filter("c.organization_id", 4)
+        ))
+        .bind(concert_id)
+        .bind(&input.name)
+        .bind(input.capacity)
+        .bind(tenant.organization_id())
+        .fetch_optional(&self.pool)
+        .await
+        .map_err(|e| {
+            if is_unique_violation(&e) {
+                AppError::Conflict(format!("演唱會已有名為 {} 的庫存池", input.name))
+            } else {
+                AppError::Database(e)
+            }
+        })?;
+
+        Ok(record.as_ref().map(pool_from_row))
+    }
+
+    async fn update_capacity(&self, id: Uuid, capacity: i32, tenant: Tenant) -> Result<Option<InventoryPool>, AppError> {
+        let mut tx = self.pool.begin().await?;
+
+        let current = sqlx::query(&format!(
+            "SELECT p.capacity, p.remaining FROM {} WHERE p.id = $1 AND {} FOR UPDATE OF p",
+            POOL_TABLES, tenant_filter("c.organization_id", 2)
+        ))
+        .bind(id)
+        .bind(tenant.organization_id())
+        .fetch_optional(&mut *tx)
+        .await?;
+
+        let Some(current) = current else {
+            return Ok(None);
+        };
+
+        // 已售出數量 = 容量 - 剩餘，新容量不可低於已售出數量
+        let sold = current.get::<i32, _>("capacity") - current.get::<i32, _>("remaining");
+        if capacity < sold {
+            return Err(AppError::Conflict(format!("庫存池已售出 {} 張，容量不可低於已售出數量", sold)));
+        }
+
+        let record = sqlx::query(
+            r#"
+            UPDATE inventory_pools
+            SET capacity = $2, remaining = $2 - $3
+            WHERE id = $1
+            RETURNING id, concert_id, name, capacity, remaining
+            "#
+        )
+        .bind(id)
+        .bind(capacity)
+        .bind(sold)
+        .fetch_one(&mut *tx)
+        .await?;
+
+        tx.commit().await?;
+        Ok(Some(pool_from_row(&record)))
+    }
+
+    async fn assign_ticket(&self, ticket_id: Uuid, pool_id: Option<Uuid>, tenant: Tenant) -> Result<(), AppError> {
+        let mut tx = self.pool.begin().await?;
+
+        let ticket = sqlx::query(&format!(
+            r#"
+            SELECT t.pool_id, t.initial_stock + t.allocation_adjustment - t.stock AS sold
+            FROM tickets t
+            JOIN concerts c ON t.concert_id = c.id
+            WHERE t.id = $1 AND {}
+            FOR UPDATE OF t
+            "#,
+            tenant_filter("c.organization_id", 2)
+        ))
+        .bind(ticket_id)
+        .bind(tenant.organization_id())
+        .fetch_optional(&mut *tx)
+        .await?
+        .ok_or_else(|| AppError::NotFound(format!("找不到 ID 為 {} 的票券", ticket_id)))?;
+
+        let current: Option<Uuid> = ticket.get("pool_id");
+        let sold: i32 = ticket.get("sold");
+        if current == pool_id {
+            return Ok(());
+        }
+
+        // 已售出的數量跟著票種移動：歸還給原本的池，並從新池扣除
+        if let Some(current) = current {
+            sqlx::query("UPDATE inventory_pools SET remaining = remaining + $2 WHERE id = $1")
+                .bind(current)
+                .bind(sold)
+                .execute(&mut *tx)
+                .await?;
+        }
+
+        if let Some(pool_id) = pool_id {
+            let result = sqlx::query(
+                "UPDATE inventory_pools SET remaining = remaining - $2 WHERE id = $1 AND remaining >= $2"
+            )
+            .bind(pool_id)
+            .bind(sold)
+            .execute(&mut *tx)
+            .await?;
+
+            if result.rows_affected() == 0 {
+                return Err(AppError::Conflict(format!("庫存池剩餘數量不足以容納票種已售出的 {} 張", sold)));
+            }
+        }
+
+        sqlx::query("UPDATE tickets SET pool_id = $2 WHERE id = $1")
+            .bind(ticket_id)
+            .bind(pool_id)
+            .execute(&mut *tx)
+            .await?;
+
+        tx.commit().await?;
+        Ok(())
+    }
+
+    async fn move_allocation(
+        &self,
+        from_ticket_id: Uuid,
+        to_ticket_id: Uuid,
+        quantity: i32,
+        tenant: Tenant,
+    ) -> Result<bool, AppError> {
+        let mut tx = self.pool.begin().await?;
+
+        if !tickets_in_tenant(&mut tx, &[from_ticket_id, to_ticket_id], tenant).await? {
+            return Err(AppError::NotFound("找不到票券".to_string()));
+        }
+
+        // 依 ID 順序鎖定兩個票種，避免反向移轉同時進行時死鎖
+        let locked = sqlx::query("SELECT id, pool_id FROM tickets WHERE id = ANY($1) ORDER BY id FOR UPDATE")
+            .bind(vec![from_ticket_id, to_ticket_id])
+            .fetch_all(&mut *tx)
+            .await?;
+
+        // 鎖定後再檢查一次庫存池，避免檢查後票種被移到其他池
+        let pool_ids: Vec<Option<Uuid>> = locked.iter().map(|row| row.get("pool_id")).collect();
+        if pool_ids.windows(2).any(|pair| pair[0] != pair[1]) {
+            return Err(AppError::BadRequest("票種不屬於同一個庫存池，不可移轉配額".to_string()));
+        }
+
+        // 只調整庫存，移轉數量記錄在 allocation_adjustment，原始配額與已售出數量不變
+        let result = sqlx::query(
+            r#"
+            UPDATE tickets
+            SET stock = stock - $2, allocation_adjustment = allocation_adjustment - $2
+            WHERE id = $1 AND stock >= $2
+            "#
+        )
+        .bind(from_ticket_id)
+        .bind(quantity)
+        .execute(&mut *tx)
+        .await?;
+
+        if result.rows_affected() == 0 {
+            return Ok(false);
+        }
+
+        sqlx::query(
+            r#"
+            UPDATE tickets
+            SET stock = stock + $2, allocation_adjustment = allocation_adjustment + $2
+            WHERE id = $1
+            "#
+        )
+        .bind(to_ticket_id)
+        .bind(quantity)
+        .execute(&mut *tx)
+        .await?;
+
+        tx.commit().await?;
+        Ok(true)
+    }
+}
+
+/// 庫存池欄位
+const POOL_COLUMNS: &str = "p.id, p.concert_id, p.name, p.capacity, p.remaining";
+
+/// 庫存池關聯表，依演唱會的主辦單位過濾租戶
+const POOL_TABLES: &str = "inventory_pools p JOIN concerts c ON p.concert_id = c.id";
+
+/// 將資料庫記錄轉換為庫存池模型
+fn pool_from_row(row: &PgRow) -> InventoryPool {
+    InventoryPool {
+        id: row.get("id"),
+        concert_id: row.get("concert_id"),
+        name: row.get("name"),
+        capacity: row.get("capacity"),
+        remaining: row.get("remaining"),
+    }
+}
@@ -0,0 +1,180 @@
+use async_trait::async_trait;
+use sqlx::postgres::PgRow;
+use sqlx::{PgConnection, PgPool, Row};
+use uuid::Uuid;
+
+use crate::domain::ledger::model::{
+    AccountBalance, Journal, JournalImbalance, LedgerAccount, LedgerEntry, LedgerEntryType, OrderLedgerMismatch,
+};
+use crate::domain::ledger::repository::LedgerRepository;
+use crate::utils::error::AppError;
+use crate::utils::money::to_decimal;
+
+/// PostgreSQL 分錄存儲庫實現
+pub struct PgLedgerRepository {
+    pool: PgPool,
+}
+
+impl PgLedgerRepository {
+    /// 創建新的 PostgreSQL 分錄存儲庫
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+}
+
+/// 在呼叫者的交易中寫入傳票
+/// 借貸平衡會在交易提交時由資料庫觸發器再次檢查
+pub async fn insert_journal(conn: &mut PgConnection, journal: &Journal) -> Result<(), AppError> {
+    if !journal.is_balanced() {
+        return Err(AppError::Internal(format!("傳票 {} 借貸不平衡", journal.id)));
+    }
+
+    for line in &journal.lines {
+        sqlx::query(
+            r#"
+            INSERT INTO ledger_entries (journal_id, order_id, concert_id, account, entry_type, debit, credit, memo)
+            VALUES ($1, $2, $3, $4, $5, $6, $7, $8)
+            "#
+        )
+        .bind(journal.id)
+        .bind(journal.order_id)
+        .bind(journal.concert_id)
+        .bind(line.account.as_str())
+        .bind(line.entry_type.as_str())
+        .bind(to_decimal(line.debit)?)
+        .bind(to_decimal(line.credit)?)
+        .bind(&journal.memo)
+        .execute(&mut *conn)
+        .await?;
+    }
+
+    Ok(())
+}
+
+#[async_trait]
+impl LedgerRepository for PgLedgerRepository {
+    async fn find_by_order_id(&self, order_id: Uuid) -> Result<Vec<LedgerEntry>, AppError> {
+        let result = sqlx::query(
+            r#"
+            SELECT id, journal_id, order_id, account, entry_type,
+                   debit::float8, credit::float8, memo, created_at
+            FROM ledger_entries
+            WHERE order_id = $1
+            ORDER BY id
+            "#
+        )
+        .bind(order_id)
+        .fetch_all(&self.pool)
+        .await?;
+
+        result.iter().map(entry_from_row).collect()
+    }
+
+    async fn account_balances(&self) -> Result<Vec<AccountBalance>, AppError> {
+        let result = sqlx::query(
+            r#"
+            SELECT account,
+                   SUM(debit)::float8 as debit,
+                   SUM(credit)::float8 as credit,
+                   (SUM(debit) - SUM(credit))::float8 as balance
+            FROM ledger_entries
+            GROUP BY account
+            ORDER BY account
+            "#
+        )
+        .fetch_all(&self.pool)
+        .await?;
+
+        result
+            .iter()
+            .map(|row| {
+                Ok(AccountBalance {
+                    account: account_from_row(row)?,
+                    debit: row.get("debit"),
+                    credit: row.get("credit"),
+                    balance: row.get("balance"),
+                })
+            })
+            .collect()
+    }
+
+    async fn unbalanced_journals(&self) -> Result<Vec<JournalImbalance>, AppError> {
+        let result = sqlx::query(
+            r#"
+            SELECT journal_id,
+                   SUM(debit)::float8 as debit,
+                   SUM(credit)::float8 as credit,
+                   (SUM(debit) - SUM(credit))::float8 as difference
+            FROM ledger_entries
+            GROUP BY journal_id
+            HAVING SUM(debit) <> SUM(credit)
+            "#
+        )
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(result
+            .iter()
+            .map(|row| JournalImbalance {
+                journal_id: row.get("journal_id"),
+                debit: row.get("debit"),
+                credit: row.get("credit"),
+                difference: row.get("difference"),
+            })
+            .collect())
+    }
+
+    async fn order_mismatches(&self) -> Result<Vec<OrderLedgerMismatch>, AppError> {
+        // 已付款與退款處理中訂單的顧客科目淨借方應等於訂單總額，其他狀態應為零
+        let result = sqlx::query(
+            r#"
+            SELECT o.id as order_id, o.status,
+                   (CASE WHEN o.status IN ('paid', 'refunding') THEN o.total ELSE 0 END)::float8 as expected,
+                   COALESCE(SUM(e.debit - e.credit), 0)::float8 as recorded
+            FROM orders o
+            LEFT JOIN ledger_entries e ON e.order_id = o.id AND e.account = 'customer'
+            GROUP BY o.id, o.status, o.total
+            HAVING COALESCE(SUM(e.debit - e.credit), 0)
+                   <> CASE WHEN o.status IN ('paid', 'refunding') THEN o.total ELSE 0 END
+            "#
+        )
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(result
+            .iter()
+            .map(|row| OrderLedgerMismatch {
+                order_id: row.get("order_id"),
+                status: row.get("status"),
+                expected: row.get("expected"),
+                recorded: row.get("recorded"),
+            })
+            .collect())
+    }
+}
+
+/// 從記錄讀取會計科目
+fn account_from_row(row: &PgRow) -> Result<LedgerAccount, AppError> {
+    let account: String = row.get("account");
+    LedgerAccount::parse(&account)
+        .ok_or_else(|| AppError::Internal(format!("未知的會計科目: {}", account)))
+}
+
+/// 將資料庫記錄轉換為分錄模型
+fn entry_from_row(row: &PgRow) -> Result<LedgerEntry, AppError> {
+    let entry_type: String = row.get("entry_type");
+    let created_at: Option<chrono::NaiveDateTime> = row.get("created_at");
+
+    Ok(LedgerEntry {
+        id: row.get("id"),
+        journal_id: row.get("journal_id"),
+        order_id: row.get("order_id"),
+        account: account_from_row(row)?,
+        entry_type: LedgerEntryType::parse(&entry_type)
+            .ok_or_else(|| AppError::Internal(format!("未知的分錄類型: {}", entry_type)))?,
+        debit: row.get("debit"),
+        credit: row.get("credit"),
+        memo: row.get("memo"),
+        created_at: created_at.unwrap_or_else(|| chrono::Local::now().naive_local()),
+    })
+}
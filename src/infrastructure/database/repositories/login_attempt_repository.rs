@@ -0,0 +1,85 @@
+use async_trait::async_trait;
+use chrono::Duration;
+use sqlx::{PgPool, Row};
+
+use crate::domain::auth::repository::LoginAttemptRepository;
+use crate::utils::error::AppError;
+
+/// PostgreSQL 登入失敗記錄存儲庫實現
+pub struct PgLoginAttemptRepository {
+    pool: PgPool,
+}
+
+impl PgLoginAttemptRepository {
+    /// 創建新的 PostgreSQL 登入失敗記錄存儲庫
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+}
+
+#[async_trait]
+impl LoginAttemptRepository for PgLoginAttemptRepository {
+    async fn is_locked(&self, key: &str) -> Result<bool, AppError> {
+        let result = sqlx::query(
+            "SELECT 1 FROM login_attempts WHERE key = $1 AND locked_until > CURRENT_TIMESTAMP"
+        )
+        .bind(key)
+        .fetch_optional(&self.pool)
+        .await?;
+
+        Ok(result.is_some())
+    }
+
+    async fn record_failure(&self, key: &str, window: Duration) -> Result<i32, AppError> {
+        let mut tx = self.pool.begin().await?;
+
+        // 順便清除已過計數期間的記錄
+        sqlx::query(
+            r#"
+            DELETE FROM login_attempts
+            WHERE last_failure_at < CURRENT_TIMESTAMP - $1 * INTERVAL '1 second'
+              AND (locked_until IS NULL OR locked_until < CURRENT_TIMESTAMP - $1 * INTERVAL '1 second')
+            "#
+        )
+        .bind(window.num_seconds())
+        .execute(&mut *tx)
+        .await?;
+
+        // 同一個對象同時失敗時以資料庫的計數為準
+        let failures: i32 = sqlx::query(
+            r#"
+            INSERT INTO login_attempts (key, failures, last_failure_at)
+            VALUES ($1, 1, CURRENT_TIMESTAMP)
+            ON CONFLICT (key) DO UPDATE
+            SET failures = login_attempts.failures + 1, last_failure_at = EXCLUDED.last_failure_at
+            RETURNING failures
+            "#
+        )
+        .bind(key)
+        .fetch_one(&mut *tx)
+        .await?
+        .get("failures");
+
+        tx.commit().await?;
+        Ok(failures)
+    }
+
+    async fn lock(&self, key: &str, lockout: Duration) -> Result<(), AppError> {
+        sqlx::query("UPDATE login_attempts SET locked_until = CURRENT_TIMESTAMP + $2 * INTERVAL '1 second' WHERE key = $1")
+            .bind(key)
+            .bind(lockout.num_seconds())
+            .execute(&self.pool)
+            .await?;
+
+        Ok(())
+    }
+
+    async fn clear(&self, key: &str) -> Result<(), AppError> {
+        sqlx::query("DELETE FROM login_attempts WHERE key = $1")
+            .bind(key)
+            .execute(&self.pool)
+            .await?;
+
+        Ok(())
+    }
+}
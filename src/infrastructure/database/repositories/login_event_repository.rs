@@ -0,0 +1,63 @@
+use std::net::IpAddr;
+
+use async_trait::async_trait;
+use sqlx::{PgPool, Row};
+use uuid::Uuid;
+
+use crate::domain::auth::model::{LoginEvent, LoginMethod};
+use crate::domain::auth::repository::LoginEventRepository;
+use crate::utils::error::AppError;
+
+/// PostgreSQL 登入記錄存儲庫實現
+pub struct PgLoginEventRepository {
+    pool: PgPool,
+}
+
+impl PgLoginEventRepository {
+    /// 創建新的 PostgreSQL 登入記錄存儲庫
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+}
+
+#[async_trait]
+impl LoginEventRepository for PgLoginEventRepository {
+    async fn record(&self, user_id: Uuid, method: &LoginMethod, ip: Option<IpAddr>) -> Result<(), AppError> {
+        sqlx::query(
+            r#"
+            INSERT INTO login_events (user_id, method, ip_address)
+            VALUES ($1, $2, $3)
+            "#
+        )
+        .bind(user_id)
+        .bind(method.as_string())
+        .bind(ip.map(|ip| ip.to_string()))
+        .execute(&self.pool)
+        .await?;
+
+        Ok(())
+    }
+
+    async fn find_by_user(&self, user_id: Uuid) -> Result<Vec<LoginEvent>, AppError> {
+        let rows = sqlx::query(
+            r#"
+            SELECT method, ip_address, created_at
+            FROM login_events
+            WHERE user_id = $1
+            ORDER BY created_at DESC
+            "#
+        )
+        .bind(user_id)
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(rows
+            .iter()
+            .map(|row| LoginEvent {
+                method: row.get("method"),
+                ip_address: row.get("ip_address"),
+                created_at: row.get("created_at"),
+            })
+            .collect())
+    }
+}
@@ -1,4 +1,91 @@
+pub mod addon_repository;
+pub mod admission_repository;
+pub mod api_key_repository;
+pub mod box_office_repository;
+pub mod bundle_repository;
+pub mod comp_repository;
 pub mod concert_repository;
+pub mod identity_repository;
+pub mod inventory_repository;
+pub mod ledger_repository;
+pub mod login_attempt_repository;
+pub mod login_event_repository;
 pub mod order_repository;
+pub mod organization_repository;
+pub mod payment_repository;
+pub mod privacy_repository;
+pub mod promo_code_repository;
+pub mod report_repository;
+pub mod seating_repository;
+pub mod settlement_repository;
 pub mod ticket_repository;
+pub mod token_repository;
+pub mod two_factor_repository;
 pub mod user_repository;
+
+use sqlx::Row;
+
+use crate::domain::organization::model::Tenant;
+use crate::utils::error::AppError;
+
+/// 是否為唯一鍵衝突
+pub(crate) fn is_unique_violation(error: &sqlx::Error) -> bool {
+    matches!(error, sqlx::Error::Database(db) if db.code().as_deref() == Some("23505"))
+}
+
+/// 是否為外鍵不存在
+pub(crate) fn is_foreign_key_violation(error: &sqlx::Error) -> bool {
+    matches!(error, sqlx::Error::Database(db) if db.code().as_deref() == Some("23503"))
+}
+
+/// 租戶過濾條件
+/// 第 `param` 個參數綁定 `Tenant::organization_id()`：平台範圍為 NULL 不過濾，
+/// 主辦單位範圍只保留 `column` 等於該主辦單位的資料列
+pub(crate) fn tenant_filter(column: &str, param: usize) -> String {
+    format!("(${param}::uuid IS NULL OR {column} = ${param})")
+}
+
+/// 演唱會是否屬於租戶範圍
+pub(crate) async fn concert_in_tenant(
+    conn: &mut sqlx::PgConnection,
+    concert_id: uuid::Uuid,
+    tenant: Tenant,
+) -> Result<bool, AppError> {
+    let found = sqlx::query(&format!(
+        "SELECT 1 FROM concerts c WHERE c.id = $1 AND {}",
+        tenant_filter("c.organization_id", 2)
+    ))
+    .bind(concert_id)
+    .bind(tenant.organization_id())
+    .fetch_optional(conn)
+    .await?;
+
+    Ok(found.is_some())
+}
+
+/// 票種是否全部屬於租戶範圍
+pub(crate) async fn tickets_in_tenant(
+    conn: &mut sqlx::PgConnection,
+    ticket_ids: &[uuid::Uuid],
+    tenant: Tenant,
+) -> Result<bool, AppError> {
+    let found: i64 = sqlx::query(&format!(
+        r#"
+        SELECT COUNT(DISTINCT t.id) AS found
+        FROM tickets t
+        JOIN concerts c ON t.concert_id = c.id
+        WHERE t.id = ANY($1) AND {}
+        "#,
+        tenant_filter("c.organization_id", 2)
+    ))
+    .bind(ticket_ids)
+    .bind(tenant.organization_id())
+    .fetch_one(conn)
+    .await?
+    .get("found");
+
+    let mut distinct = ticket_ids.to_vec();
+    distinct.sort();
+    distinct.dedup();
+    Ok(found == distinct.len() as i64)
+}
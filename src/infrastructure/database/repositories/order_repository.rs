@@ -1,11 +1,30 @@
+use std::collections::HashMap;
+
 use async_trait::async_trait;
-use sqlx::{PgPool, Row};
+use sqlx::postgres::PgRow;
+use sqlx::{PgConnection, PgPool, Postgres, QueryBuilder, Row};
 use uuid::Uuid;
-use chrono;
+use chrono::Duration;
 
-use crate::domain::order::model::{CreateOrder, Order, OrderQuery, OrderView};
+use crate::domain::order::model::{
+    CreateBundleOrder, CreateOrder, NewOrderItem, Order, OrderBundle, OrderQuery, OrderStatus, OrderView, PriceBreakdown,
+    TransitionEffects,
+};
 use crate::domain::order::repository::OrderRepository;
+use crate::domain::organization::model::Tenant;
+use crate::infrastructure::database::repositories::addon_repository::{find_items_by_order_id, find_items_by_order_ids};
+use crate::infrastructure::database::repositories::admission_repository::{
+    find_admissions_by_order_id, find_admissions_by_order_ids, ADMISSION_CODE,
+};
+use crate::infrastructure::database::repositories::bundle_repository::{
+    find_components, find_components_by_bundle_ids, update_bundle_stock,
+};
+use crate::infrastructure::database::repositories::ledger_repository::insert_journal;
+use crate::infrastructure::database::repositories::payment_repository::insert_webhook_event;
+use crate::infrastructure::database::repositories::promo_code_repository::{redeem_promo_code, release_promo_code};
+use crate::infrastructure::database::repositories::ticket_repository::{lock_pricing, update_ticket_stock};
 use crate::utils::error::AppError;
+use crate::utils::money::{round_money, to_decimal};
 
 /// PostgreSQL 訂單存儲庫實現
 pub struct PgOrderRepository {
@@ -23,131 +42,647 @@ impl PgOrderRepository {
 impl OrderRepository for PgOrderRepository {
     async fn find_by_id(&self, id: Uuid, user_id: Uuid) -> Result<Option<OrderView>, AppError> {
         // 使用原生 SQL 查詢
-        let result = sqlx::query(
+        let result = sqlx::query(&format!(
             r#"
-            SELECT o.id, o.quantity, o.created_at, 
-                   t.ticket_type, t.price::float8, 
-                   c.title as concert_title, c.date as concert_date
-            FROM orders o
-            JOIN tickets t ON o.ticket_id = t.id
-            JOIN concerts c ON t.concert_id = c.id
+            SELECT {}
             WHERE o.id = $1 AND o.user_id = $2
-            "#
-        )
+            "#,
+            ORDER_VIEW_SELECT
+        ))
         .bind(id)
         .bind(user_id)
         .fetch_optional(&self.pool)
         .await?;
 
         match result {
-            Some(row) => Ok(Some(OrderView {
-                id: row.get("id"),
-                quantity: row.get("quantity"),
-                created_at: row.get("created_at"),
-                ticket_type: row.get("ticket_type"),
-                price: row.get("price"),
-                concert_title: row.get("concert_title"),
-                concert_date: row.get("concert_date"),
-            })),
+            Some(row) => Ok(Some(self.view_with_details(&row).await?)),
             None => Ok(None),
         }
     }
 
     async fn find_by_user_id(&self, user_id: Uuid, query: &OrderQuery) -> Result<Vec<OrderView>, AppError> {
-        // 構建基本查詢
-        let mut sql = r#"
-            SELECT o.id, o.quantity, o.created_at, 
-                   t.ticket_type, t.price::float8, 
-                   c.title as concert_title, c.date as concert_date
-            FROM orders o
-            JOIN tickets t ON o.ticket_id = t.id
-            JOIN concerts c ON t.concert_id = c.id
-            WHERE o.user_id = $1
-        "#.to_string();
+        // 構建基本查詢，過濾條件皆以綁定參數傳入
+        let mut builder = QueryBuilder::<Postgres>::new(format!("SELECT {} WHERE o.user_id = ", ORDER_VIEW_SELECT));
+        builder.push_bind(user_id);
 
         // 添加過濾條件
-        let mut params = Vec::new();
-        params.push(format!("'{}'", user_id));
-
-        let mut param_index = 2;
-
-        if let Some(from) = &query.from {
-            sql.push_str(&format!(" AND o.created_at::date >= ${}", param_index));
-            params.push(format!("'{}'", from));
-            param_index += 1;
+        if let Some(from) = query.from {
+            builder.push(" AND o.created_at::date >= ").push_bind(from);
         }
 
-        if let Some(to) = &query.to {
-            sql.push_str(&format!(" AND o.created_at::date <= ${}", param_index));
-            params.push(format!("'{}'", to));
-            param_index += 1;
+        if let Some(to) = query.to {
+            builder.push(" AND o.created_at::date <= ").push_bind(to);
         }
 
         if let Some(concert_id) = query.concert_id {
-            sql.push_str(&format!(" AND t.concert_id = ${}", param_index));
-            params.push(format!("'{}'", concert_id));
-            param_index += 1;
+            // 組合商品訂單只要任一組合內容屬於該演唱會即符合
+            builder
+                .push(" AND (t.concert_id = ")
+                .push_bind(concert_id)
+                .push(" OR EXISTS (SELECT 1 FROM bundle_components bc JOIN tickets bt ON bc.ticket_id = bt.id WHERE bc.bundle_id = o.bundle_id AND bt.concert_id = ")
+                .push_bind(concert_id)
+                .push("))");
         }
 
-        // 添加排序和分頁
-        sql.push_str(" ORDER BY o.created_at DESC");
+        // 添加排序和分頁，以訂單 ID 作為次要排序確保分頁結果穩定
+        builder.push(" ORDER BY o.created_at DESC, o.id DESC");
 
         if let Some(limit) = query.limit {
-            sql.push_str(&format!(" LIMIT ${}", param_index));
-            params.push(format!("{}", limit));
-            param_index += 1;
+            builder.push(" LIMIT ").push_bind(limit as i64);
         }
 
         if let Some(page) = query.page {
-            let offset = (page - 1) * query.limit.unwrap_or(10);
-            sql.push_str(&format!(" OFFSET ${}", param_index));
-            params.push(format!("{}", offset));
+            let offset = (page.max(1) - 1) * query.limit.unwrap_or(10);
+            builder.push(" OFFSET ").push_bind(offset as i64);
+        }
+
+        // 執行查詢
+        let result = builder.build().fetch_all(&self.pool).await?;
+
+        self.views_with_details(&result).await
+    }
+
+    async fn find_all(&self, query: &OrderQuery, tenant: Tenant) -> Result<Vec<OrderView>, AppError> {
+        let limit = query.limit.unwrap_or(50).clamp(1, 500);
+        let offset = (query.page.unwrap_or(1).max(1) - 1) * limit;
+
+        // 構建基本查詢，過濾條件皆以綁定參數傳入
+        let mut builder = QueryBuilder::<Postgres>::new(format!("SELECT {} WHERE TRUE", ORDER_VIEW_SELECT));
+
+        // 添加過濾條件
+        if let Some(from) = query.from {
+            builder.push(" AND o.created_at::date >= ").push_bind(from);
+        }
+
+        if let Some(to) = query.to {
+            builder.push(" AND o.created_at::date <= ").push_bind(to);
+        }
+
+        if let Some(concert_id) = query.concert_id {
+            // 組合商品訂單只要任一組合內容屬於該演唱會即符合
+            builder
+                .push(" AND (t.concert_id = ")
+                .push_bind(concert_id)
+                .push(" OR EXISTS (SELECT 1 FROM bundle_components bc JOIN tickets bt ON bc.ticket_id = bt.id WHERE bc.bundle_id = o.bundle_id AND bt.concert_id = ")
+                .push_bind(concert_id)
+                .push("))");
         }
 
+        if let Some(organization_id) = tenant.organization_id() {
+            // 主辦單位只能看到所有票種都屬於自己演唱會的訂單
+            builder
+                .push(
+                    " AND NOT EXISTS (SELECT 1 FROM tickets tt JOIN concerts tc ON tt.concert_id = tc.id \
+                     WHERE (tt.id = o.ticket_id OR tt.id IN (SELECT ticket_id FROM bundle_components WHERE bundle_id = o.bundle_id)) \
+                     AND tc.organization_id IS DISTINCT FROM ",
+                )
+                .push_bind(organization_id)
+                .push(")");
+        }
+
+        // 添加排序和分頁，以訂單 ID 作為次要排序確保分頁結果穩定
+        builder.push(" ORDER BY o.created_at DESC, o.id DESC");
+        builder.push(" LIMIT ").push_bind(limit as i64);
+        builder.push(" OFFSET ").push_bind(offset as i64);
+
         // 執行查詢
-        let result = sqlx::query(&sql)
-            .bind(user_id)
-            .fetch_all(&self.pool)
+        let result = builder.build().fetch_all(&self.pool).await?;
+
+        self.views_with_details(&result).await
+    }
+
+    async fn create(
+        &self,
+        user_id: Option<Uuid>,
+        input: &CreateOrder,
+        breakdown: &PriceBreakdown,
+        items: &[NewOrderItem],
+        promo_code_id: Option<Uuid>,
+    ) -> Result<Order, AppError> {
+        // 票種庫存扣減、訂單建立與座位保留在同一個交易中完成，任一步驟失敗時庫存隨交易回滾
+        let mut tx = self.pool.begin().await?;
+
+        // 鎖定票種後以目前售出數量重新計價，並行的訂單不會以過期的售出數量跨越價格階梯
+        // 價格已變動（階梯已售完或時間區間已結束）時整筆失敗，由呼叫端重新計價
+        let schedule = lock_pricing(&mut tx, input.ticket_id).await?
+            .ok_or_else(|| AppError::NotFound(format!("找不到 ID 為 {} 的票券", input.ticket_id)))?;
+        let unit_price = schedule.order_price(chrono::Local::now().naive_local(), input.quantity);
+        if unit_price.is_none_or(|unit_price| (unit_price - breakdown.unit_price).abs() >= 0.005) {
+            return Err(AppError::Conflict("票價已變動，請重新下單".to_string()));
+        }
+
+        // 條件式扣減票種與庫存池，並發購買時不會超賣
+        if !update_ticket_stock(&mut tx, input.ticket_id, input.quantity).await? {
+            return Err(AppError::BadRequest("庫存不足".to_string()));
+        }
+
+        // 優惠碼次數以條件式更新扣除，並發下單時不會超過使用上限
+        if let Some(promo_code_id) = promo_code_id
+            && !redeem_promo_code(&mut tx, promo_code_id).await?
+        {
+            return Err(AppError::BadRequest("優惠碼已過期或已達使用次數上限".to_string()));
+        }
+
+        // 使用原生 SQL 查詢，價格欄位以 NUMERIC 寫入
+        let record = sqlx::query(&format!(
+            r#"
+            INSERT INTO orders (user_id, ticket_id, quantity,
+                                unit_price, subtotal, addon_total, fee_total, discount_total,
+                                tax_rate, tax_inclusive, tax_total, total, promo_code_id)
+            VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, $13)
+            RETURNING {}
+            "#,
+            ORDER_COLUMNS
+        ))
+        .bind(user_id)
+        .bind(input.ticket_id)
+        .bind(input.quantity)
+        .bind(to_decimal(breakdown.unit_price)?)
+        .bind(to_decimal(breakdown.subtotal)?)
+        .bind(to_decimal(breakdown.addon_total)?)
+        .bind(to_decimal(breakdown.fee_total)?)
+        .bind(to_decimal(breakdown.discount_total)?)
+        .bind(to_decimal(breakdown.tax_rate)?)
+        .bind(breakdown.tax_inclusive)
+        .bind(to_decimal(breakdown.tax_total)?)
+        .bind(to_decimal(breakdown.total)?)
+        .bind(promo_code_id)
+        .fetch_one(&mut *tx)
+        .await?;
+
+        let order = order_from_row(&record)?;
+
+        if !input.seat_ids.is_empty() {
+            // 條件式更新：只有屬於此票種價區且仍可選購的座位會被保留
+            // 並行的訂單搶同一個座位時，只有先取得列鎖的一方會更新成功
+            let result = sqlx::query(
+                r#"
+                UPDATE concert_seats cs
+                SET status = 'held', order_id = $1
+                FROM price_zones z
+                WHERE cs.price_zone_id = z.id
+                  AND z.ticket_id = $2
+                  AND cs.seat_id = ANY($3)
+                  AND cs.status = 'available'
+                "#
+            )
+            .bind(order.id)
+            .bind(input.ticket_id)
+            .bind(&input.seat_ids)
+            .execute(&mut *tx)
+            .await?;
+
+            if result.rows_affected() != input.seat_ids.len() as u64 {
+                return Err(AppError::Conflict("部分座位已被選走或不屬於此票種".to_string()));
+            }
+        }
+
+        for item in items {
+            // 條件式扣減加購商品庫存，與票券庫存相同避免超賣
+            let result = sqlx::query(
+                "UPDATE addon_variants SET stock = stock - $2 WHERE id = $1 AND stock >= $2"
+            )
+            .bind(item.variant_id)
+            .bind(item.quantity)
+            .execute(&mut *tx)
+            .await?;
+
+            if result.rows_affected() == 0 {
+                return Err(AppError::BadRequest("加購商品庫存不足".to_string()));
+            }
+
+            sqlx::query(&format!(
+                r#"
+                INSERT INTO order_items (order_id, variant_id, quantity, unit_price, total, code)
+                VALUES ($1, $2, $3, $4, $5, {})
+                "#,
+                ADMISSION_CODE
+            ))
+            .bind(order.id)
+            .bind(item.variant_id)
+            .bind(item.quantity)
+            .bind(to_decimal(item.unit_price)?)
+            .bind(to_decimal(round_money(item.unit_price * item.quantity as f64))?)
+            .execute(&mut *tx)
             .await?;
+        }
 
-        let orders = result
-            .iter()
-            .map(|row| OrderView {
-                id: row.get("id"),
-                quantity: row.get("quantity"),
-                created_at: row.get("created_at"),
-                ticket_type: row.get("ticket_type"),
-                price: row.get("price"),
-                concert_title: row.get("concert_title"),
-                concert_date: row.get("concert_date"),
-            })
-            .collect();
-
-        Ok(orders)
+        tx.commit().await?;
+        Ok(order)
     }
 
-    async fn create(&self, user_id: Uuid, input: &CreateOrder) -> Result<Order, AppError> {
-        // 使用 query! 而不是 query_as! 來手動處理 NaiveDateTime
-        let record = sqlx::query!(
+    async fn create_bundle_order(
+        &self,
+        user_id: Uuid,
+        input: &CreateBundleOrder,
+        breakdown: &PriceBreakdown,
+    ) -> Result<Order, AppError> {
+        // 組合與所有組合內容的庫存扣減、訂單建立在同一個交易中完成
+        let mut tx = self.pool.begin().await?;
+
+        if !update_bundle_stock(&mut tx, input.bundle_id, input.quantity).await? {
+            return Err(AppError::BadRequest("庫存不足".to_string()));
+        }
+
+        let record = sqlx::query(&format!(
             r#"
-            INSERT INTO orders (user_id, ticket_id, quantity)
-            VALUES ($1, $2, $3)
-            RETURNING id, user_id, ticket_id, quantity, created_at
+            INSERT INTO orders (user_id, bundle_id, quantity,
+                                unit_price, subtotal, addon_total, fee_total, discount_total,
+                                tax_rate, tax_inclusive, tax_total, total)
+            VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12)
+            RETURNING {}
             "#,
-            user_id,
-            input.ticket_id,
-            input.quantity
+            ORDER_COLUMNS
+        ))
+        .bind(user_id)
+        .bind(input.bundle_id)
+        .bind(input.quantity)
+        .bind(to_decimal(breakdown.unit_price)?)
+        .bind(to_decimal(breakdown.subtotal)?)
+        .bind(to_decimal(breakdown.addon_total)?)
+        .bind(to_decimal(breakdown.fee_total)?)
+        .bind(to_decimal(breakdown.discount_total)?)
+        .bind(to_decimal(breakdown.tax_rate)?)
+        .bind(breakdown.tax_inclusive)
+        .bind(to_decimal(breakdown.tax_total)?)
+        .bind(to_decimal(breakdown.total)?)
+        .fetch_one(&mut *tx)
+        .await?;
+
+        tx.commit().await?;
+        order_from_row(&record)
+    }
+
+    async fn find_order(&self, id: Uuid, tenant: Tenant) -> Result<Option<Order>, AppError> {
+        // 訂單的票種（組合商品則為所有組合內容）都必須屬於租戶範圍
+        let result = sqlx::query(&format!(
+            r#"
+            SELECT {}
+            FROM orders
+            WHERE id = $1
+              AND ($2::uuid IS NULL OR NOT EXISTS (
+                  SELECT 1
+                  FROM tickets t
+                  JOIN concerts c ON t.concert_id = c.id
+                  WHERE (t.id = orders.ticket_id
+                         OR t.id IN (SELECT ticket_id FROM bundle_components WHERE bundle_id = orders.bundle_id))
+                    AND c.organization_id IS DISTINCT FROM $2
+              ))
+            "#,
+            ORDER_COLUMNS
+        ))
+        .bind(id)
+        .bind(tenant.organization_id())
+        .fetch_optional(&self.pool)
+        .await?;
+
+        result.as_ref().map(order_from_row).transpose()
+    }
+
+    async fn find_expired_pending(&self, timeout: Duration) -> Result<Vec<Order>, AppError> {
+        // 以資料庫時鐘判斷逾時，與 created_at 的預設值一致
+        let result = sqlx::query(&format!(
+            r#"
+            SELECT {}
+            FROM orders
+            WHERE status = 'pending' AND created_at <= CURRENT_TIMESTAMP - $1 * INTERVAL '1 second'
+            ORDER BY created_at
+            "#,
+            ORDER_COLUMNS
+        ))
+        .bind(timeout.num_seconds())
+        .fetch_all(&self.pool)
+        .await?;
+
+        result.iter().map(order_from_row).collect()
+    }
+
+    async fn transition_status(
+        &self,
+        id: Uuid,
+        from: OrderStatus,
+        to: OrderStatus,
+        effects: TransitionEffects<'_>,
+    ) -> Result<bool, AppError> {
+        // 狀態變更、分錄、付款狀態與庫存歸還在同一個交易中完成
+        let mut tx = self.pool.begin().await?;
+
+        // 先記錄 Webhook 事件，同一事件同時重送時只有一個請求能寫入
+        if let Some((provider, event_id)) = effects.webhook_event
+            && !insert_webhook_event(&mut tx, provider, event_id).await?
+        {
+            return Ok(false);
+        }
+
+        // 只有在目前狀態符合時才更新，重複呼叫不會產生副作用
+        let order = sqlx::query(
+            r#"
+            UPDATE orders
+            SET status = $3
+            WHERE id = $1 AND status = $2
+            RETURNING ticket_id, bundle_id, quantity
+            "#
         )
-        .fetch_one(&self.pool)
+        .bind(id)
+        .bind(from.as_str())
+        .bind(to.as_str())
+        .fetch_optional(&mut *tx)
+        .await?;
+
+        let order = match order {
+            Some(order) => order,
+            None => {
+                tx.commit().await?;
+                return Ok(false);
+            }
+        };
+
+        match to {
+            OrderStatus::Paid => {
+                sqlx::query("UPDATE concert_seats SET status = 'sold' WHERE order_id = $1")
+                    .bind(id)
+                    .execute(&mut *tx)
+                    .await?;
+                issue_admissions(&mut tx, id).await?;
+                sqlx::query("UPDATE order_items SET status = 'valid' WHERE order_id = $1 AND status IN ('pending', 'void')")
+                    .bind(id)
+                    .execute(&mut *tx)
+                    .await?;
+            }
+            OrderStatus::Failed | OrderStatus::Refunded => {
+                sqlx::query("UPDATE admissions SET status = 'void' WHERE order_id = $1 AND status = 'valid'")
+                    .bind(id)
+                    .execute(&mut *tx)
+                    .await?;
+                sqlx::query("UPDATE order_items SET status = 'void' WHERE order_id = $1 AND status IN ('pending', 'valid')")
+                    .bind(id)
+                    .execute(&mut *tx)
+                    .await?;
+            }
+            OrderStatus::Pending | OrderStatus::Refunding => {}
+        }
+
+        if let Some(journal) = effects.journal {
+            insert_journal(&mut tx, journal).await?;
+        }
+
+        if let Some((payment_id, status)) = effects.payment {
+            sqlx::query("UPDATE payments SET status = $2, updated_at = CURRENT_TIMESTAMP WHERE id = $1")
+                .bind(payment_id)
+                .bind(status.as_str())
+                .execute(&mut *tx)
+                .await?;
+        }
+
+        if effects.release_stock {
+            let quantity: i32 = order.get("quantity");
+            if let Some(ticket_id) = order.get::<Option<Uuid>, _>("ticket_id") {
+                update_ticket_stock(&mut tx, ticket_id, -quantity).await?;
+            }
+            if let Some(bundle_id) = order.get::<Option<Uuid>, _>("bundle_id") {
+                update_bundle_stock(&mut tx, bundle_id, -quantity).await?;
+            }
+            release_items(&mut tx, id).await?;
+            release_seats(&mut tx, id).await?;
+            release_promo_code(&mut tx, id).await?;
+        }
+
+        tx.commit().await?;
+        Ok(true)
+    }
+}
+
+impl PgOrderRepository {
+    /// 將訂單視圖記錄補上組合內容與入場憑證
+    async fn view_with_details(&self, row: &PgRow) -> Result<OrderView, AppError> {
+        let mut view = order_view_from_row(row)?;
+
+        let bundle_id: Option<Uuid> = row.get("bundle_id");
+        if let Some(bundle_id) = bundle_id {
+            view.bundle = Some(OrderBundle {
+                bundle_id,
+                name: row.get("ticket_type"),
+                components: find_components(&self.pool, bundle_id).await?,
+            });
+        }
+        view.admissions = find_admissions_by_order_id(&self.pool, view.id).await?;
+        view.items = find_items_by_order_id(&self.pool, view.id).await?;
+
+        Ok(view)
+    }
+
+    /// 將多筆訂單視圖記錄補上組合內容與入場憑證
+    /// 明細以訂單 ID 一次批次查詢，避免每筆訂單各查一次
+    async fn views_with_details(&self, rows: &[PgRow]) -> Result<Vec<OrderView>, AppError> {
+        let mut views = rows.iter().map(order_view_from_row).collect::<Result<Vec<_>, _>>()?;
+        if views.is_empty() {
+            return Ok(views);
+        }
+
+        let order_ids: Vec<Uuid> = views.iter().map(|view| view.id).collect();
+        let mut bundle_ids: Vec<Uuid> = rows.iter().filter_map(|row| row.get("bundle_id")).collect();
+        bundle_ids.sort();
+        bundle_ids.dedup();
+
+        let mut components: HashMap<Uuid, Vec<_>> = HashMap::new();
+        for (bundle_id, component) in find_components_by_bundle_ids(&self.pool, &bundle_ids).await? {
+            components.entry(bundle_id).or_default().push(component);
+        }
+        let mut admissions: HashMap<Uuid, Vec<_>> = HashMap::new();
+        for admission in find_admissions_by_order_ids(&self.pool, &order_ids).await? {
+            if let Some(order_id) = admission.order_id {
+                admissions.entry(order_id).or_default().push(admission);
+            }
+        }
+        let mut items: HashMap<Uuid, Vec<_>> = HashMap::new();
+        for item in find_items_by_order_ids(&self.pool, &order_ids).await? {
+            items.entry(item.order_id).or_default().push(item);
+        }
+
+        for (view, row) in views.iter_mut().zip(rows) {
+            if let Some(bundle_id) = row.get::<Option<Uuid>, _>("bundle_id") {
+                view.bundle = Some(OrderBundle {
+                    bundle_id,
+                    name: row.get("ticket_type"),
+                    components: components.get(&bundle_id).cloned().unwrap_or_default(),
+                });
+            }
+            view.admissions = admissions.remove(&view.id).unwrap_or_default();
+            view.items = items.remove(&view.id).unwrap_or_default();
+        }
+
+        Ok(views)
+    }
+}
+
+/// 釋放訂單保留或已售出的座位
+async fn release_seats(conn: &mut PgConnection, order_id: Uuid) -> Result<(), AppError> {
+    sqlx::query(
+        r#"
+        UPDATE concert_seats
+        SET status = 'available', order_id = NULL
+        WHERE order_id = $1
+        "#
+    )
+    .bind(order_id)
+    .execute(&mut *conn)
+    .await?;
+
+    Ok(())
+}
+
+/// 歸還訂單加購商品明細的庫存
+/// 同一規格可能出現在多筆明細，先彙總數量再歸還；已領取的商品不再回到庫存
+async fn release_items(conn: &mut PgConnection, order_id: Uuid) -> Result<(), AppError> {
+    sqlx::query(
+        r#"
+        UPDATE addon_variants v
+        SET stock = v.stock + i.quantity
+        FROM (
+            SELECT variant_id, SUM(quantity)::int AS quantity
+            FROM order_items
+            WHERE order_id = $1 AND status <> 'redeemed'
+            GROUP BY variant_id
+        ) i
+        WHERE v.id = i.variant_id
+        "#
+    )
+    .bind(order_id)
+    .execute(&mut *conn)
+    .await?;
+
+    Ok(())
+}
+
+/// 發出入場憑證
+/// 票種訂單每張一份；組合商品訂單每組、每場演唱會一份。已發出過（例如退款失敗恢復付款）時改為恢復作廢的憑證
+async fn issue_admissions(conn: &mut PgConnection, order_id: Uuid) -> Result<(), AppError> {
+    sqlx::query(&format!(
+        r#"
+        INSERT INTO admissions (order_id, concert_id, label, code)
+        SELECT o.id, t.concert_id, t.ticket_type, {code}
+        FROM orders o
+        JOIN tickets t ON o.ticket_id = t.id
+        CROSS JOIN generate_series(1, o.quantity)
+        WHERE o.id = $1 AND NOT EXISTS (SELECT 1 FROM admissions a WHERE a.order_id = o.id)
+        UNION ALL
+        SELECT o.id, bc.concert_id, b.name, {code}
+        FROM orders o
+        JOIN bundles b ON o.bundle_id = b.id
+        CROSS JOIN LATERAL (
+            SELECT DISTINCT t.concert_id
+            FROM bundle_components c
+            JOIN tickets t ON c.ticket_id = t.id
+            WHERE c.bundle_id = b.id
+        ) bc
+        CROSS JOIN generate_series(1, o.quantity)
+        WHERE o.id = $1 AND NOT EXISTS (SELECT 1 FROM admissions a WHERE a.order_id = o.id)
+        "#,
+        code = ADMISSION_CODE
+    ))
+    .bind(order_id)
+    .execute(&mut *conn)
+    .await?;
+
+    sqlx::query("UPDATE admissions SET status = 'valid' WHERE order_id = $1 AND status = 'void'")
+        .bind(order_id)
+        .execute(&mut *conn)
         .await?;
 
-        // 手動轉換為 Order 模型
-        Ok(Order {
-            id: record.id,
-            user_id: record.user_id,
-            ticket_id: record.ticket_id,
-            quantity: record.quantity,
-            created_at: record.created_at.unwrap_or_else(|| chrono::Local::now().naive_local()),
-        })
+    Ok(())
+}
+
+/// 訂單表的完整欄位列表
+const ORDER_COLUMNS: &str = r#"
+    id, user_id, ticket_id, bundle_id, quantity, status, created_at,
+    unit_price::float8 as price,
+    subtotal::float8, addon_total::float8, fee_total::float8, discount_total::float8,
+    tax_rate::float8, tax_inclusive, tax_total::float8, total::float8
+"#;
+
+/// 訂單視圖的欄位與關聯
+/// 組合商品訂單沒有票種，名稱取自組合商品，演唱會時間取組合內容中最早的一場
+const ORDER_VIEW_SELECT: &str = r#"
+    o.id, o.quantity, o.status, o.created_at, o.bundle_id,
+    COALESCE(t.ticket_type, b.name) as ticket_type, o.unit_price::float8 as price,
+    COALESCE(c.title, b.name) as concert_title,
+    COALESCE(c.date, (
+        SELECT MIN(bcc.date)
+        FROM bundle_components bc
+        JOIN tickets bt ON bc.ticket_id = bt.id
+        JOIN concerts bcc ON bt.concert_id = bcc.id
+        WHERE bc.bundle_id = o.bundle_id
+    )) as concert_date,
+    o.subtotal::float8, o.addon_total::float8, o.fee_total::float8, o.discount_total::float8,
+    o.tax_rate::float8, o.tax_inclusive, o.tax_total::float8, o.total::float8,
+    ARRAY(
+        SELECT sec.name || ' ' || r.label || '-' || s.number
+        FROM concert_seats cs
+        JOIN seats s ON cs.seat_id = s.id
+        JOIN seat_rows r ON s.row_id = r.id
+        JOIN seat_sections sec ON r.section_id = sec.id
+        WHERE cs.order_id = o.id
+        ORDER BY sec.position, r.position, s.position
+    ) as seats
+    FROM orders o
+    LEFT JOIN tickets t ON o.ticket_id = t.id
+    LEFT JOIN concerts c ON t.concert_id = c.id
+    LEFT JOIN bundles b ON o.bundle_id = b.id
+"#;
+
+/// 將資料庫記錄轉換為訂單模型
+fn order_from_row(row: &PgRow) -> Result<Order, AppError> {
+    let created_at: Option<chrono::NaiveDateTime> = row.get("created_at");
+
+    Ok(Order {
+        id: row.get("id"),
+        user_id: row.get("user_id"),
+        ticket_id: row.get("ticket_id"),
+        bundle_id: row.get("bundle_id"),
+        quantity: row.get("quantity"),
+        status: status_from_row(row)?,
+        created_at: created_at.unwrap_or_else(|| chrono::Local::now().naive_local()),
+        breakdown: breakdown_from_row(row),
+    })
+}
+
+/// 將資料庫記錄轉換為訂單視圖
+fn order_view_from_row(row: &PgRow) -> Result<OrderView, AppError> {
+    Ok(OrderView {
+        id: row.get("id"),
+        quantity: row.get("quantity"),
+        status: status_from_row(row)?,
+        created_at: row.get("created_at"),
+        ticket_type: row.get("ticket_type"),
+        price: row.get("price"),
+        concert_title: row.get("concert_title"),
+        concert_date: row.get("concert_date"),
+        breakdown: breakdown_from_row(row),
+        seats: row.get("seats"),
+        bundle: None,
+        admissions: Vec::new(),
+        items: Vec::new(),
+    })
+}
+
+/// 從訂單記錄讀取訂單狀態
+fn status_from_row(row: &PgRow) -> Result<OrderStatus, AppError> {
+    let status: String = row.get("status");
+    OrderStatus::parse(&status)
+        .ok_or_else(|| AppError::Internal(format!("未知的訂單狀態: {}", status)))
+}
+
+/// 從訂單記錄讀取價格明細
+fn breakdown_from_row(row: &PgRow) -> PriceBreakdown {
+    PriceBreakdown {
+        unit_price: row.get("price"),
+        subtotal: row.get("subtotal"),
+        addon_total: row.get("addon_total"),
+        fee_total: row.get("fee_total"),
+        discount_total: row.get("discount_total"),
+        tax_rate: row.get("tax_rate"),
+        tax_inclusive: row.get("tax_inclusive"),
+        tax_total: row.get("tax_total"),
+        total: row.get("total"),
     }
 }
@@ -0,0 +1,115 @@
+use async_trait::async_trait;
+use sqlx::postgres::PgRow;
+use sqlx::{PgPool, Row};
+use uuid::Uuid;
+
+use crate::domain::organization::model::{CreateOrganization, Organization};
+use crate::domain::organization::repository::OrganizationRepository;
+use crate::utils::error::AppError;
+use crate::utils::money::to_decimal;
+
+/// PostgreSQL 主辦單位存儲庫實現
+pub struct PgOrganizationRepository {
+    pool: PgPool,
+}
+
+impl PgOrganizationRepository {
+    /// 創建新的 PostgreSQL 主辦單位存儲庫
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+}
+
+#[async_trait]
+impl OrganizationRepository for PgOrganizationRepository {
+    async fn find_by_id(&self, id: Uuid) -> Result<Option<Organization>, AppError> {
+        let result = sqlx::query(
+            "SELECT id, name, commission_rate::float8 FROM organizations WHERE id = $1"
+        )
+        .bind(id)
+        .fetch_optional(&self.pool)
+        .await?;
+
+        Ok(result.as_ref().map(organization_from_row))
+    }
+
+    async fn find_by_concert_id(&self, concert_id: Uuid) -> Result<Option<Organization>, AppError> {
+        let result = sqlx::query(
+            r#"
+            SELECT g.id, g.name, g.commission_rate::float8
+            FROM organizations g
+            JOIN concerts c ON c.organization_id = g.id
+            WHERE c.id = $1
+            "#
+        )
+        .bind(concert_id)
+        .fetch_optional(&self.pool)
+        .await?;
+
+        Ok(result.as_ref().map(organization_from_row))
+    }
+
+    async fn find_all(&self) -> Result<Vec<Organization>, AppError> {
+        let result = sqlx::query(
+            "SELECT id, name, commission_rate::float8 FROM organizations ORDER BY name"
+        )
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(result.iter().map(organization_from_row).collect())
+    }
+
+    async fn create(&self, input: &CreateOrganization) -> Result<Organization, AppError> {
+        let record = sqlx::query(
+            r#"
+            INSERT INTO organizations (name, commission_rate)
+            VALUES ($1, $2)
+            RETURNING id, name, commission_rate::float8
+            "#
+        )
+        .bind(&input.name)
+        .bind(to_decimal(input.commission_rate)?)
+        .fetch_one(&self.pool)
+        .await?;
+
+        Ok(organization_from_row(&record))
+    }
+
+    async fn update_commission(&self, id: Uuid, commission_rate: f64) -> Result<Option<Organization>, AppError> {
+        let result = sqlx::query(
+            r#"
+            UPDATE organizations
+            SET commission_rate = $2
+            WHERE id = $1
+            RETURNING id, name, commission_rate::float8
+            "#
+        )
+        .bind(id)
+        .bind(to_decimal(commission_rate)?)
+        .fetch_optional(&self.pool)
+        .await?;
+
+        Ok(result.as_ref().map(organization_from_row))
+    }
+
+    async fn assign_concert(&self, concert_id: Uuid, organization_id: Uuid) -> Result<bool, AppError> {
+        let result = sqlx::query(
+            "UPDATE concerts SET organization_id = $2 WHERE id = $1"
+        )
+        .bind(concert_id)
+        .bind(organization_id)
+        .execute(&self.pool)
+        .await?;
+
+        Ok(result.rows_affected() == 1)
+    }
+}
+
+/// 將資料庫記錄轉換為主辦單位模型
+fn organization_from_row(row: &PgRow) -> Organization {
+    Organization {
+        id: row.get("id"),
+        name: row.get("name"),
+        commission_rate: row.get("commission_rate"),
+    }
+}
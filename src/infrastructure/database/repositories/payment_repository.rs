@@ -0,0 +1,149 @@
+use async_trait::async_trait;
+use sqlx::postgres::PgRow;
+use sqlx::{PgConnection, PgPool, Row};
+use uuid::Uuid;
+
+use crate::domain::payment::model::{CreatePayment, Payment, PaymentStatus};
+use crate::domain::payment::repository::PaymentRepository;
+use crate::utils::error::AppError;
+use crate::utils::money::to_decimal;
+
+/// PostgreSQL 付款存儲庫實現
+pub struct PgPaymentRepository {
+    pool: PgPool,
+}
+
+impl PgPaymentRepository {
+    /// 創建新的 PostgreSQL 付款存儲庫
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+}
+
+#[async_trait]
+impl PaymentRepository for PgPaymentRepository {
+    async fn create(&self, input: &CreatePayment) -> Result<Payment, AppError> {
+        let result = sqlx::query(
+            r#"
+            INSERT INTO payments (order_id, provider, intent_id, amount, currency)
+            VALUES ($1, $2, $3, $4, $5)
+            RETURNING id, order_id, provider, intent_id, amount::float8, currency, status, created_at
+            "#
+        )
+        .bind(input.order_id)
+        .bind(&input.provider)
+        .bind(&input.intent_id)
+        .bind(to_decimal(input.amount)?)
+        .bind(&input.currency)
+        .fetch_one(&self.pool)
+        .await?;
+
+        payment_from_row(&result)
+    }
+
+    async fn find_by_intent_id(&self, intent_id: &str) -> Result<Option<Payment>, AppError> {
+        let result = sqlx::query(
+            r#"
+            SELECT id, order_id, provider, intent_id, amount::float8, currency, status, created_at
+            FROM payments
+            WHERE intent_id = $1
+            "#
+        )
+        .bind(intent_id)
+        .fetch_optional(&self.pool)
+        .await?;
+
+        result.as_ref().map(payment_from_row).transpose()
+    }
+
+    async fn find_by_order_id(&self, order_id: Uuid) -> Result<Option<Payment>, AppError> {
+        let result = sqlx::query(
+            r#"
+            SELECT id, order_id, provider, intent_id, amount::float8, currency, status, created_at
+            FROM payments
+            WHERE order_id = $1
+            ORDER BY created_at DESC
+            LIMIT 1
+            "#
+        )
+        .bind(order_id)
+        .fetch_optional(&self.pool)
+        .await?;
+
+        result.as_ref().map(payment_from_row).transpose()
+    }
+
+    async fn claim_late_capture_refund(&self, id: Uuid) -> Result<bool, AppError> {
+        let result = sqlx::query(
+            r#"
+            UPDATE payments p
+            SET status = 'refunded', updated_at = CURRENT_TIMESTAMP
+            FROM orders o
+            WHERE p.id = $1 AND o.id = p.order_id AND o.status = 'failed'
+              AND p.status IN ('pending', 'failed', 'refund_required')
+            "#
+        )
+        .bind(id)
+        .execute(&self.pool)
+        .await?;
+
+        Ok(result.rows_affected() > 0)
+    }
+
+    async fn flag_refund_required(&self, id: Uuid) -> Result<(), AppError> {
+        sqlx::query(
+            r#"
+            UPDATE payments
+            SET status = 'refund_required', updated_at = CURRENT_TIMESTAMP
+            WHERE id = $1 AND status = 'refunded'
+            "#
+        )
+        .bind(id)
+        .execute(&self.pool)
+        .await?;
+
+        Ok(())
+    }
+
+    async fn record_webhook_event(&self, provider: &str, event_id: &str) -> Result<bool, AppError> {
+        let mut conn = self.pool.acquire().await?;
+        insert_webhook_event(&mut conn, provider, event_id).await
+    }
+}
+
+/// 在交易中記錄 Webhook 事件
+/// 事件已記錄過時返回 false；並發重送時只有一個交易能寫入，其餘等待後返回 false
+pub async fn insert_webhook_event(conn: &mut PgConnection, provider: &str, event_id: &str) -> Result<bool, AppError> {
+    let inserted = sqlx::query(
+        r#"
+        INSERT INTO payment_webhook_events (provider, event_id)
+        VALUES ($1, $2)
+        ON CONFLICT DO NOTHING
+        RETURNING event_id
+        "#
+    )
+    .bind(provider)
+    .bind(event_id)
+    .fetch_optional(&mut *conn)
+    .await?;
+
+    Ok(inserted.is_some())
+}
+
+/// 將資料庫記錄轉換為付款模型
+fn payment_from_row(row: &PgRow) -> Result<Payment, AppError> {
+    let status: String = row.get("status");
+    let created_at: Option<chrono::NaiveDateTime> = row.get("created_at");
+
+    Ok(Payment {
+        id: row.get("id"),
+        order_id: row.get("order_id"),
+        provider: row.get("provider"),
+        intent_id: row.get("intent_id"),
+        amount: row.get("amount"),
+        currency: row.get("currency"),
+        status: PaymentStatus::parse(&status)
+            .ok_or_else(|| AppError::Internal(format!("未知的付款狀態: {}", status)))?,
+        created_at: created_at.unwrap_or_else(|| chrono::Local::now().naive_local()),
+    })
+}
@@ -0,0 +1,292 @@
+use async_trait::async_trait;
+use sqlx::postgres::PgRow;
+use sqlx::{PgConnection, PgPool, Row};
+use uuid::Uuid;
+
+use super::is_unique_violation;
+use crate::domain::privacy::model::{ErasureRequest, ErasureRequestEvent, ErasureSource, ErasureStatus};
+use crate::domain::privacy::repository::PrivacyRepository;
+use crate::utils::error::AppError;
+
+/// PostgreSQL 個人資料存儲庫實現
+pub struct PgPrivacyRepository {
+    pool: PgPool,
+}
+
+impl PgPrivacyRepository {
+    /// 創建新的 PostgreSQL 個人資料存儲庫
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+}
+
+/// 刪除申請查詢欄位
+const ERASURE_REQUEST_COLUMNS: &str = "id, user_id, source, status, reason, requested_at, processed_at";
+
+/// 將資料列轉換為刪除申請
+fn erasure_request_from_row(row: &PgRow) -> Result<ErasureRequest, AppError> {
+    let source: String = row.get("source");
+    let status: String = row.get("status");
+    Ok(ErasureRequest {
+        id: row.get("id"),
+        user_id: row.get("user_id"),
+        source: ErasureSource::parse(&source)
+            .ok_or_else(|| AppError::Internal(format!("未知的申請來源: {}", source)))?,
+        status: ErasureStatus::parse(&status)
+            .ok_or_else(|| AppError::Internal(format!("未知的申請狀態: {}", status)))?,
+        reason: row.get("reason"),
+        requested_at: row.get("requested_at"),
+        processed_at: row.get("processed_at"),
+    })
+}
+
+/// 寫入稽核記錄
+async fn record_event(
+    conn: &mut PgConnection,
+    request_id: Uuid,
+    action: &str,
+    actor_id: Uuid,
+    note: Option<&str>,
+) -> Result<(), AppError> {
+    sqlx::query(
+        r#"
+        INSERT INTO erasure_request_events (request_id, action, actor_id, note)
+        VALUES ($1, $2, $3, $4)
+        "#
+    )
+    .bind(request_id)
+    .bind(action)
+    .bind(actor_id)
+    .bind(note)
+    .execute(conn)
+    .await?;
+
+    Ok(())
+}
+
+/// 將待處理的申請改為指定狀態，申請不存在或已處理時返回 None
+async fn close_request(
+    conn: &mut PgConnection,
+    id: Uuid,
+    status: ErasureStatus,
+) -> Result<Option<ErasureRequest>, AppError> {
+    let row = sqlx::query(&format!(
+        r#"
+        UPDATE erasure_requests
+        SET status = $2, processed_at = CURRENT_TIMESTAMP
+        WHERE id = $1 AND status = 'pending'
+        RETURNING {}
+        "#,
+        ERASURE_REQUEST_COLUMNS
+    ))
+    .bind(id)
+    .bind(status.as_str())
+    .fetch_optional(conn)
+    .await?;
+
+    row.as_ref().map(erasure_request_from_row).transpose()
+}
+
+/// 用戶是否為唯一的平台管理員
+/// 鎖定所有管理員角色，避免兩位管理員同時刪除帳號後平台沒有管理員
+async fn is_sole_admin(conn: &mut PgConnection, user_id: Uuid) -> Result<bool, AppError> {
+    let admins: Vec<Uuid> = sqlx::query_scalar("SELECT user_id FROM user_roles WHERE role = 'admin' FOR UPDATE")
+        .fetch_all(conn)
+        .await?;
+
+    Ok(admins == [user_id])
+}
+
+/// 匿名化用戶
+/// 清除用戶列的個人資料並刪除登入方式、令牌與登入記錄；用戶列本身保留，
+/// 訂單、付款、分錄與入場憑證仍參照同一個用戶 ID，會計記錄不受影響。
+/// 招待票以 email 記錄來賓，發給該用戶 email 的招待票改為與用戶列相同的匿名 email 並清除姓名，
+/// 票數與入場憑證保留供主辦單位對帳；買家報表在同一個交易中重新整理，排除已匿名化的用戶
+async fn anonymize_user(conn: &mut PgConnection, user_id: Uuid) -> Result<(), AppError> {
+    // 必須在清除用戶 email 之前比對
+    sqlx::query(
+        r#"
+        UPDATE comps cp
+        SET email = 'deleted-' || u.id || '@anonymized.invalid', name = NULL
+        FROM users u
+        WHERE u.id = $1 AND cp.email = lower(trim(u.email))
+        "#
+    )
+    .bind(user_id)
+    .execute(&mut *conn)
+    .await?;
+
+    sqlx::query(
+        r#"
+        UPDATE users
+        SET email = 'deleted-' || id || '@anonymized.invalid',
+            password_hash = NULL, email_verified_at = NULL, pending_email = NULL, organization_id = NULL,
+            display_name = NULL, phone = NULL, locale = NULL, date_of_birth = NULL,
+            marketing_consent = false, marketing_consent_at = NULL,
+            anonymized_at = CURRENT_TIMESTAMP
+        WHERE id = $1
+        "#
+    )
+    .bind(user_id)
+    .execute(&mut *conn)
+    .await?;
+
+    for table in ["user_roles", "user_identities", "user_tokens", "totp_recovery_codes", "user_totp", "login_events"] {
+        sqlx::query(&format!("DELETE FROM {} WHERE user_id = $1", table))
+            .bind(user_id)
+            .execute(&mut *conn)
+            .await?;
+    }
+
+    sqlx::query("REFRESH MATERIALIZED VIEW CONCURRENTLY report_buyer_daily")
+        .execute(&mut *conn)
+        .await?;
+
+    Ok(())
+}
+
+#[async_trait]
+impl PrivacyRepository for PgPrivacyRepository {
+    async fn is_sole_admin(&self, user_id: Uuid) -> Result<bool, AppError> {
+        let mut conn = self.pool.acquire().await?;
+        is_sole_admin(&mut conn, user_id).await
+    }
+
+    async fn has_pending_orders(&self, user_id: Uuid) -> Result<bool, AppError> {
+        let row = sqlx::query("SELECT EXISTS(SELECT 1 FROM orders WHERE user_id = $1 AND status IN ('pending', 'refunding')) AS exists")
+            .bind(user_id)
+            .fetch_one(&self.pool)
+            .await?;
+
+        Ok(row.get("exists"))
+    }
+
+    async fn create_request(
+        &self,
+        user_id: Uuid,
+        source: ErasureSource,
+        reason: Option<&str>,
+        actor_id: Uuid,
+    ) -> Result<ErasureRequest, AppError> {
+        let mut tx = self.pool.begin().await?;
+
+        // 已匿名化的用戶視為不存在
+        let row = sqlx::query(&format!(
+            r#"
+            INSERT INTO erasure_requests (user_id, source, reason)
+            SELECT id, $2, $3 FROM users WHERE id = $1 AND anonymized_at IS NULL
+            RETURNING {}
+            "#,
+            ERASURE_REQUEST_COLUMNS
+        ))
+        .bind(user_id)
+        .bind(source.as_str())
+        .bind(reason)
+        .fetch_optional(&mut *tx)
+        .await
+        .map_err(|error| {
+            if is_unique_violation(&error) {
+                AppError::Conflict("此用戶已有待處理的刪除申請".to_string())
+            } else {
+                error.into()
+            }
+        })?
+        .ok_or_else(|| AppError::NotFound(format!("找不到 ID 為 {} 的用戶", user_id)))?;
+        let request = erasure_request_from_row(&row)?;
+
+        record_event(&mut tx, request.id, "requested", actor_id, reason).await?;
+        tx.commit().await?;
+
+        Ok(request)
+    }
+
+    async fn find_request(&self, id: Uuid) -> Result<Option<ErasureRequest>, AppError> {
+        let row = sqlx::query(&format!("SELECT {} FROM erasure_requests WHERE id = $1", ERASURE_REQUEST_COLUMNS))
+            .bind(id)
+            .fetch_optional(&self.pool)
+            .await?;
+
+        row.as_ref().map(erasure_request_from_row).transpose()
+    }
+
+    async fn find_pending_request(&self, user_id: Uuid) -> Result<Option<ErasureRequest>, AppError> {
+        let row = sqlx::query(&format!(
+            "SELECT {} FROM erasure_requests WHERE user_id = $1 AND status = 'pending'",
+            ERASURE_REQUEST_COLUMNS
+        ))
+        .bind(user_id)
+        .fetch_optional(&self.pool)
+        .await?;
+
+        row.as_ref().map(erasure_request_from_row).transpose()
+    }
+
+    async fn list_requests(&self, status: ErasureStatus) -> Result<Vec<ErasureRequest>, AppError> {
+        let rows = sqlx::query(&format!(
+            "SELECT {} FROM erasure_requests WHERE status = $1 ORDER BY requested_at",
+            ERASURE_REQUEST_COLUMNS
+        ))
+        .bind(status.as_str())
+        .fetch_all(&self.pool)
+        .await?;
+
+        rows.iter().map(erasure_request_from_row).collect()
+    }
+
+    async fn find_events(&self, request_id: Uuid) -> Result<Vec<ErasureRequestEvent>, AppError> {
+        let rows = sqlx::query(
+            r#"
+            SELECT action, actor_id, note, created_at
+            FROM erasure_request_events
+            WHERE request_id = $1
+            ORDER BY created_at
+            "#
+        )
+        .bind(request_id)
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(rows
+            .iter()
+            .map(|row| ErasureRequestEvent {
+                action: row.get("action"),
+                actor_id: row.get("actor_id"),
+                note: row.get("note"),
+                created_at: row.get("created_at"),
+            })
+            .collect())
+    }
+
+    async fn complete_request(
+        &self,
+        id: Uuid,
+        actor_id: Uuid,
+        note: Option<&str>,
+    ) -> Result<Option<ErasureRequest>, AppError> {
+        let mut tx = self.pool.begin().await?;
+
+        let Some(request) = close_request(&mut tx, id, ErasureStatus::Completed).await? else {
+            return Ok(None);
+        };
+        if is_sole_admin(&mut tx, request.user_id).await? {
+            return Err(AppError::Conflict("用戶是唯一的平台管理員，請先將管理員角色移交給其他用戶".to_string()));
+        }
+        anonymize_user(&mut tx, request.user_id).await?;
+        record_event(&mut tx, request.id, "completed", actor_id, note).await?;
+
+        tx.commit().await?;
+        Ok(Some(request))
+    }
+
+    async fn reject_request(&self, id: Uuid, actor_id: Uuid, note: &str) -> Result<Option<ErasureRequest>, AppError> {
+        let mut tx = self.pool.begin().await?;
+
+        let Some(request) = close_request(&mut tx, id, ErasureStatus::Rejected).await? else {
+            return Ok(None);
+        };
+        record_event(&mut tx, request.id, "rejected", actor_id, Some(note)).await?;
+
+        tx.commit().await?;
+        Ok(Some(request))
+    }
+}
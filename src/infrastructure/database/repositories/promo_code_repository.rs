@@ -0,0 +1,157 @@
+use async_trait::async_trait;
+use sqlx::postgres::PgRow;
+use sqlx::{PgConnection, PgPool, Row};
+use uuid::Uuid;
+
+use crate::domain::organization::model::Tenant;
+use crate::domain::promo_code::model::{CreatePromoCode, DiscountType, PromoCode};
+use crate::domain::promo_code::repository::PromoCodeRepository;
+use crate::infrastructure::database::repositories::{concert_in_tenant, is_unique_violation, tenant_filter};
+use crate::utils::error::AppError;
+use crate::utils::money::to_decimal;
+
+/// 優惠碼欄位
+const PROMO_CODE_COLUMNS: &str = r#"
+    p.id, p.concert_id, p.code, p.discount_type, p.amount::float8,
+    p.max_redemptions, p.redemptions, p.starts_at, p.ends_at
+"#;
+
+/// 優惠碼在有效期間內的條件，以資料庫時鐘判斷
+const PROMO_CODE_ACTIVE: &str = r#"
+    (p.starts_at IS NULL OR p.starts_at <= CURRENT_TIMESTAMP)
+    AND (p.ends_at IS NULL OR p.ends_at > CURRENT_TIMESTAMP)
+"#;
+
+/// PostgreSQL 優惠碼存儲庫實現
+pub struct PgPromoCodeRepository {
+    pool: PgPool,
+}
+
+impl PgPromoCodeRepository {
+    /// 創建新的 PostgreSQL 優惠碼存儲庫
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+}
+
+#[async_trait]
+impl PromoCodeRepository for PgPromoCodeRepository {
+    async fn find_by_concert_id(&self, concert_id: Uuid, tenant: Tenant) -> Result<Vec<PromoCode>, AppError> {
+        let rows = sqlx::query(&format!(
+            r#"
+            SELECT {}
+            FROM promo_codes p
+            JOIN concerts c ON p.concert_id = c.id
+            WHERE p.concert_id = $1 AND {}
+            ORDER BY p.created_at, p.code
+            "#,
+            PROMO_CODE_COLUMNS,
+            tenant_filter("c.organization_id", 2)
+        ))
+        .bind(concert_id)
+        .bind(tenant.organization_id())
+        .fetch_all(&self.pool)
+        .await?;
+
+        rows.iter().map(promo_code_from_row).collect()
+    }
+
+    async fn find_active(&self, concert_id: Uuid, code: &str) -> Result<Option<PromoCode>, AppError> {
+        let result = sqlx::query(&format!(
+            "SELECT {} FROM promo_codes p WHERE p.concert_id = $1 AND p.code = $2 AND {}",
+            PROMO_CODE_COLUMNS, PROMO_CODE_ACTIVE
+        ))
+        .bind(concert_id)
+        .bind(code)
+        .fetch_optional(&self.pool)
+        .await?;
+
+        result.as_ref().map(promo_code_from_row).transpose()
+    }
+
+    async fn create(&self, concert_id: Uuid, input: &CreatePromoCode, tenant: Tenant) -> Result<PromoCode, AppError> {
+        let mut conn = self.pool.acquire().await?;
+        if !concert_in_tenant(&mut conn, concert_id, tenant).await? {
+            return Err(AppError::NotFound(format!("找不到 ID 為 {} 的演唱會", concert_id)));
+        }
+
+        let row = sqlx::query(&format!(
+            r#"
+            INSERT INTO promo_codes AS p (concert_id, code, discount_type, amount, max_redemptions, starts_at, ends_at)
+            VALUES ($1, $2, $3, $4, $5, $6, $7)
+            RETURNING {}
+            "#,
+            PROMO_CODE_COLUMNS
+        ))
+        .bind(concert_id)
+        .bind(&input.code)
+        .bind(input.discount_type.as_str())
+        .bind(to_decimal(input.amount)?)
+        .bind(input.max_redemptions)
+        .bind(input.starts_at)
+        .bind(input.ends_at)
+        .fetch_one(&mut *conn)
+        .await
+        .map_err(|e| {
+            if is_unique_violation(&e) {
+                AppError::Conflict(format!("優惠碼 {} 已存在", input.code))
+            } else {
+                e.into()
+            }
+        })?;
+
+        promo_code_from_row(&row)
+    }
+}
+
+/// 在建立訂單的交易中使用一次優惠碼
+/// 已過期或已達使用次數上限時返回 false，並發下單時只有未超過上限的訂單會成功
+pub async fn redeem_promo_code(conn: &mut PgConnection, id: Uuid) -> Result<bool, AppError> {
+    let result = sqlx::query(&format!(
+        r#"
+        UPDATE promo_codes p
+        SET redemptions = redemptions + 1
+        WHERE p.id = $1 AND (p.max_redemptions IS NULL OR p.redemptions < p.max_redemptions) AND {}
+        "#,
+        PROMO_CODE_ACTIVE
+    ))
+    .bind(id)
+    .execute(&mut *conn)
+    .await?;
+
+    Ok(result.rows_affected() > 0)
+}
+
+/// 歸還訂單使用的優惠碼次數
+pub async fn release_promo_code(conn: &mut PgConnection, order_id: Uuid) -> Result<(), AppError> {
+    sqlx::query(
+        r#"
+        UPDATE promo_codes
+        SET redemptions = redemptions - 1
+        WHERE id = (SELECT promo_code_id FROM orders WHERE id = $1)
+        "#
+    )
+    .bind(order_id)
+    .execute(&mut *conn)
+    .await?;
+
+    Ok(())
+}
+
+/// 將資料庫記錄轉換為優惠碼模型
+fn promo_code_from_row(row: &PgRow) -> Result<PromoCode, AppError> {
+    let discount_type: String = row.get("discount_type");
+
+    Ok(PromoCode {
+        id: row.get("id"),
+        concert_id: row.get("concert_id"),
+        code: row.get("code"),
+        discount_type: DiscountType::parse(&discount_type)
+            .ok_or_else(|| AppError::Internal(format!("未知的折扣方式: {}", discount_type)))?,
+        amount: row.get("amount"),
+        max_redemptions: row.get("max_redemptions"),
+        redemptions: row.get("redemptions"),
+        starts_at: row.get("starts_at"),
+        ends_at: row.get("ends_at"),
+    })
+}
@@ -0,0 +1,232 @@
+use async_trait::async_trait;
+use sqlx::{PgPool, Row};
+
+use crate::domain::report::model::{
+    CancellationReport, Granularity, ReportQuery, SalesDataPoint, TicketSalesReport, TopBuyer,
+};
+use crate::domain::organization::model::Tenant;
+use crate::domain::report::repository::ReportRepository;
+use crate::infrastructure::database::repositories::tenant_filter;
+use crate::utils::error::AppError;
+use crate::utils::money::round_money;
+
+/// PostgreSQL 報表存儲庫實現
+pub struct PgReportRepository {
+    pool: PgPool,
+}
+
+impl PgReportRepository {
+    /// 創建新的 PostgreSQL 報表存儲庫
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+}
+
+/// 百分比，分母為零時回傳零
+fn percentage(part: i64, whole: i64) -> f64 {
+    if whole > 0 {
+        round_money(part as f64 * 100.0 / whole as f64)
+    } else {
+        0.0
+    }
+}
+
+#[async_trait]
+impl ReportRepository for PgReportRepository {
+    async fn refresh(&self) -> Result<(), AppError> {
+        // CONCURRENTLY 不會鎖住讀取，報表查詢在重新整理期間仍可使用舊資料
+        sqlx::query("REFRESH MATERIALIZED VIEW CONCURRENTLY report_sales_hourly")
+            .execute(&self.pool)
+            .await?;
+        sqlx::query("REFRESH MATERIALIZED VIEW CONCURRENTLY report_buyer_daily")
+            .execute(&self.pool)
+            .await?;
+
+        Ok(())
+    }
+
+    async fn sales_by_ticket(&self, query: &ReportQuery, tenant: Tenant) -> Result<Vec<TicketSalesReport>, AppError> {
+        // 沒有銷售的票種也列出，售出張數為零
+        let result = sqlx::query(&format!(
+            r#"
+            SELECT c.id as concert_id, c.title as concert_title,
+                   t.id as ticket_id, t.ticket_type, t.initial_stock,
+                   COALESCE(s.tickets, 0)::bigint as tickets_sold,
+                   COALESCE(s.revenue, 0)::float8 as revenue,
+                   COALESCE(cp.quantity, 0)::bigint as comps_issued
+            FROM tickets t
+            JOIN concerts c ON t.concert_id = c.id
+            LEFT JOIN (
+                SELECT ticket_id, SUM(tickets) as tickets, SUM(revenue) as revenue
+                FROM report_sales_hourly
+                WHERE status = 'paid'
+                  AND ($1::date IS NULL OR bucket >= $1::date)
+                  AND ($2::date IS NULL OR bucket < $2::date + 1)
+                GROUP BY ticket_id
+            ) s ON s.ticket_id = t.id
+            LEFT JOIN (
+                -- 招待票不經過訂單，直接從 comps 彙總
+                SELECT ticket_id, SUM(quantity) as quantity
+                FROM comps
+                WHERE ($1::date IS NULL OR issued_at >= $1::date)
+                  AND ($2::date IS NULL OR issued_at < $2::date + 1)
+                GROUP BY ticket_id
+            ) cp ON cp.ticket_id = t.id
+            WHERE ($3::uuid IS NULL OR c.id = $3) AND {}
+            ORDER BY c.date, c.title, t.ticket_type
+            "#,
+            tenant_filter("c.organization_id", 4)
+        ))
+        .bind(query.from)
+        .bind(query.to)
+        .bind(query.concert_id)
+        .bind(tenant.organization_id())
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(result
+            .iter()
+            .map(|row| {
+                let initial_stock: i32 = row.get("initial_stock");
+                let tickets_sold: i64 = row.get("tickets_sold");
+                TicketSalesReport {
+                    concert_id: row.get("concert_id"),
+                    concert_title: row.get("concert_title"),
+                    ticket_id: row.get("ticket_id"),
+                    ticket_type: row.get("ticket_type"),
+                    initial_stock,
+                    tickets_sold,
+                    revenue: row.get("revenue"),
+                    sell_through: percentage(tickets_sold, initial_stock as i64),
+                    comps_issued: row.get("comps_issued"),
+                }
+            })
+            .collect())
+    }
+
+    async fn time_series(
+        &self,
+        query: &ReportQuery,
+        granularity: Granularity,
+        tenant: Tenant,
+    ) -> Result<Vec<SalesDataPoint>, AppError> {
+        let result = sqlx::query(&format!(
+            r#"
+            SELECT date_trunc($1, s.bucket) as bucket,
+                   SUM(s.orders)::bigint as orders,
+                   SUM(s.tickets)::bigint as tickets_sold,
+                   SUM(s.revenue)::float8 as revenue
+            FROM report_sales_hourly s
+            JOIN concerts c ON s.concert_id = c.id
+            WHERE s.status = 'paid'
+              AND ($2::date IS NULL OR s.bucket >= $2::date)
+              AND ($3::date IS NULL OR s.bucket < $3::date + 1)
+              AND ($4::uuid IS NULL OR s.concert_id = $4)
+              AND {}
+            GROUP BY 1
+            ORDER BY 1
+            "#,
+            tenant_filter("c.organization_id", 5)
+        ))
+        .bind(granularity.as_str())
+        .bind(query.from)
+        .bind(query.to)
+        .bind(query.concert_id)
+        .bind(tenant.organization_id())
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(result
+            .iter()
+            .map(|row| SalesDataPoint {
+                bucket: row.get("bucket"),
+                orders: row.get("orders"),
+                tickets_sold: row.get("tickets_sold"),
+                revenue: row.get("revenue"),
+            })
+            .collect())
+    }
+
+    async fn top_buyers(&self, query: &ReportQuery, limit: i64, tenant: Tenant) -> Result<Vec<TopBuyer>, AppError> {
+        let result = sqlx::query(&format!(
+            r#"
+            SELECT b.user_id, u.email,
+                   SUM(b.orders)::bigint as orders,
+                   SUM(b.tickets)::bigint as tickets,
+                   SUM(b.spent)::float8 as total_spent
+            FROM report_buyer_daily b
+            JOIN users u ON b.user_id = u.id
+            JOIN concerts c ON b.concert_id = c.id
+            WHERE ($1::date IS NULL OR b.day >= $1)
+              AND ($2::date IS NULL OR b.day <= $2)
+              AND ($3::uuid IS NULL OR b.concert_id = $3)
+              AND {}
+            GROUP BY b.user_id, u.email
+            ORDER BY total_spent DESC, tickets DESC
+            LIMIT $4
+            "#,
+            tenant_filter("c.organization_id", 5)
+        ))
+        .bind(query.from)
+        .bind(query.to)
+        .bind(query.concert_id)
+        .bind(limit)
+        .bind(tenant.organization_id())
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(result
+            .iter()
+            .map(|row| TopBuyer {
+                user_id: row.get("user_id"),
+                email: row.get("email"),
+                orders: row.get("orders"),
+                tickets: row.get("tickets"),
+                total_spent: row.get("total_spent"),
+            })
+            .collect())
+    }
+
+    async fn cancellations(&self, query: &ReportQuery, tenant: Tenant) -> Result<Vec<CancellationReport>, AppError> {
+        let result = sqlx::query(&format!(
+            r#"
+            SELECT c.id as concert_id, c.title as concert_title,
+                   COALESCE(SUM(s.orders), 0)::bigint as total_orders,
+                   COALESCE(SUM(s.orders) FILTER (WHERE s.status = 'paid'), 0)::bigint as paid_orders,
+                   COALESCE(SUM(s.orders) FILTER (WHERE s.status = 'refunded'), 0)::bigint as refunded_orders,
+                   COALESCE(SUM(s.orders) FILTER (WHERE s.status = 'failed'), 0)::bigint as failed_orders
+            FROM concerts c
+            LEFT JOIN report_sales_hourly s ON s.concert_id = c.id
+                 AND ($1::date IS NULL OR s.bucket >= $1::date)
+                 AND ($2::date IS NULL OR s.bucket < $2::date + 1)
+            WHERE ($3::uuid IS NULL OR c.id = $3) AND {}
+            GROUP BY c.id, c.title, c.date
+            ORDER BY c.date, c.title
+            "#,
+            tenant_filter("c.organization_id", 4)
+        ))
+        .bind(query.from)
+        .bind(query.to)
+        .bind(query.concert_id)
+        .bind(tenant.organization_id())
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(result
+            .iter()
+            .map(|row| {
+                let paid_orders: i64 = row.get("paid_orders");
+                let refunded_orders: i64 = row.get("refunded_orders");
+                CancellationReport {
+                    concert_id: row.get("concert_id"),
+                    concert_title: row.get("concert_title"),
+                    total_orders: row.get("total_orders"),
+                    paid_orders,
+                    refunded_orders,
+                    failed_orders: row.get("failed_orders"),
+                    cancellation_rate: percentage(refunded_orders, paid_orders + refunded_orders),
+                }
+            })
+            .collect())
+    }
+}
@@ -0,0 +1,440 @@
+use async_trait::async_trait;
+use sqlx::postgres::PgRow;
+use sqlx::{PgPool, Row};
+use uuid::Uuid;
+
+use crate::domain::organization::model::Tenant;
+use crate::domain::seating::allocator::AllocationSeat;
+use crate::domain::seating::model::{
+    ConfigureSeating, ImportSeatMap, PriceZone, Seat, SeatAvailability, SeatMap, SeatMapSummary, SeatRow,
+    SeatSection, SeatStatus,
+};
+use crate::domain::seating::repository::SeatingRepository;
+use crate::infrastructure::database::repositories::{
+    concert_in_tenant, is_unique_violation, tenant_filter, tickets_in_tenant,
+};
+use crate::utils::error::AppError;
+
+/// PostgreSQL 座位存儲庫實現
+pub struct PgSeatingRepository {
+    pool: PgPool,
+}
+
+impl PgSeatingRepository {
+    /// 創建新的 PostgreSQL 座位存儲庫
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+}
+
+#[async_trait]
+impl SeatingRepository for PgSeatingRepository {
+    async fn create_seat_map(&self, input: &ImportSeatMap, tenant: Tenant) -> Result<Uuid, AppError> {
+        // 整張座位圖在同一個交易中寫入
+        let mut tx = self.pool.begin().await?;
+
+        let seat_map_id: Uuid = sqlx::query(
+            "INSERT INTO seat_maps (name, venue, organization_id) VALUES ($1, $2, $3) RETURNING id"
+        )
+        .bind(&input.name)
+        .bind(&input.venue)
+        .bind(tenant.organization_id())
+        .fetch_one(&mut *tx)
+        .await?
+        .get("id");
+
+        for (section_position, section) in input.sections.iter().enumerate() {
+            let section_id: Uuid = sqlx::query(
+                "INSERT INTO seat_sections (seat_map_id, name, position) VALUES ($1, $2, $3) RETURNING id"
+            )
+            .bind(seat_map_id)
+            .bind(&section.name)
+            .bind(section_position as i32)
+            .fetch_one(&mut *tx)
+            .await?
+            .get("id");
+
+            for (row_position, row) in section.rows.iter().enumerate() {
+                let row_id: Uuid = sqlx::query(
+                    "INSERT INTO seat_rows (section_id, label, position) VALUES ($1, $2, $3) RETURNING id"
+                )
+                .bind(section_id)
+                .bind(&row.label)
+                .bind(row_position as i32)
+                .fetch_one(&mut *tx)
+                .await?
+                .get("id");
+
+                for (seat_position, seat) in row.seats.iter().enumerate() {
+                    sqlx::query(
+                        "INSERT INTO seats (row_id, number, position, x, y) VALUES ($1, $2, $3, $4, $5)"
+                    )
+                    .bind(row_id)
+                    .bind(&seat.number)
+                    .bind(seat_position as i32)
+                    .bind(seat.x)
+                    .bind(seat.y)
+                    .execute(&mut *tx)
+                    .await?;
+                }
+            }
+        }
+
+        tx.commit().await?;
+        Ok(seat_map_id)
+    }
+
+    async fn find_seat_map(&self, id: Uuid, tenant: Tenant) -> Result<Option<SeatMap>, AppError> {
+        let seat_map = sqlx::query(&format!(
+            "SELECT m.id, m.name, m.venue FROM seat_maps m WHERE m.id = $1 AND {}",
+            tenant_filter("m.organization_id", 2)
+        ))
+        .bind(id)
+        .bind(tenant.organization_id())
+        .fetch_optional(&self.pool)
+        .await?;
+
+        let seat_map = match seat_map {
+            Some(row) => row,
+            None => return Ok(None),
+        };
+
+        // 依區、排、座位的順序讀出，再組成巢狀結構
+        let rows = sqlx::query(
+            r#"
+            SELECT sec.id as section_id, sec.name as section_name,
+                   r.id as row_id, r.label as row_label,
+                   s.id as seat_id, s.number, s.x, s.y
+            FROM seat_sections sec
+            JOIN seat_rows r ON r.section_id = sec.id
+            JOIN seats s ON s.row_id = r.id
+            WHERE sec.seat_map_id = $1
+            ORDER BY sec.position, r.position, s.position
+            "#
+        )
+        .bind(id)
+        .fetch_all(&self.pool)
+        .await?;
+
+        let mut sections: Vec<SeatSection> = Vec::new();
+        for row in &rows {
+            let section_id: Uuid = row.get("section_id");
+            if sections.last().map(|section| section.id) != Some(section_id) {
+                sections.push(SeatSection {
+                    id: section_id,
+                    name: row.get("section_name"),
+                    rows: Vec::new(),
+                });
+            }
+            let section = sections.last_mut().expect("剛加入的座位區");
+
+            let row_id: Uuid = row.get("row_id");
+            if section.rows.last().map(|r| r.id) != Some(row_id) {
+                section.rows.push(SeatRow {
+                    id: row_id,
+                    label: row.get("row_label"),
+                    seats: Vec::new(),
+                });
+            }
+            let seat_row = section.rows.last_mut().expect("剛加入的座位排");
+
+            seat_row.seats.push(Seat {
+                id: row.get("seat_id"),
+                number: row.get("number"),
+                x: row.get("x"),
+                y: row.get("y"),
+            });
+        }
+
+        Ok(Some(SeatMap {
+            id: seat_map.get("id"),
+            name: seat_map.get("name"),
+            venue: seat_map.get("venue"),
+            sections,
+        }))
+    }
+
+    async fn find_all_seat_maps(&self, tenant: Tenant) -> Result<Vec<SeatMapSummary>, AppError> {
+        let result = sqlx::query(&format!(
+            r#"
+            SELECT m.id, m.name, m.venue, COUNT(s.id)::bigint as seat_count
+            FROM seat_maps m
+            LEFT JOIN seat_sections sec ON sec.seat_map_id = m.id
+            LEFT JOIN seat_rows r ON r.section_id = sec.id
+            LEFT JOIN seats s ON s.row_id = r.id
+            WHERE {}
+            GROUP BY m.id, m.name, m.venue
+            ORDER BY m.name
+            "#,
+            tenant_filter("m.organization_id", 1)
+        ))
+        .bind(tenant.organization_id())
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(result
+            .iter()
+            .map(|row| SeatMapSummary {
+                id: row.get("id"),
+                name: row.get("name"),
+                venue: row.get("venue"),
+                seat_count: row.get("seat_count"),
+            })
+            .collect())
+    }
+
+    async fn configure_concert(&self, concert_id: Uuid, input: &ConfigureSeating, tenant: Tenant) -> Result<(), AppError> {
+        let mut tx = self.pool.begin().await?;
+
+        // 演唱會、座位圖與價區票種都必須屬於租戶範圍
+        let ticket_ids: Vec<Uuid> = input.zones.iter().map(|zone| zone.ticket_id).collect();
+        if !concert_in_tenant(&mut tx, concert_id, tenant).await?
+            || !tickets_in_tenant(&mut tx, &ticket_ids, tenant).await?
+        {
+            return Err(AppError::NotFound(format!("找不到 ID 為 {} 的演唱會", concert_id)));
+        }
+        let seat_map = sqlx::query(&format!(
+            "SELECT 1 FROM seat_maps m WHERE m.id = $1 AND {}",
+            tenant_filter("m.organization_id", 2)
+        ))
+        .bind(input.seat_map_id)
+        .bind(tenant.organization_id())
+        .fetch_optional(&mut *tx)
+        .await?;
+        if seat_map.is_none() {
+            return Err(AppError::NotFound(format!("找不到 ID 為 {} 的座位圖", input.seat_map_id)));
+        }
+
+        // 已有座位被保留或售出時不可重新設定
+        let taken: i64 = sqlx::query(
+            "SELECT COUNT(*)::bigint as taken FROM concert_seats WHERE concert_id = $1 AND status <> 'available'"
+        )
+        .bind(concert_id)
+        .fetch_one(&mut *tx)
+        .await?
+        .get("taken");
+        if taken > 0 {
+            return Err(AppError::Conflict("已有座位被保留或售出，無法重新設定座位".to_string()));
+        }
+
+        sqlx::query("DELETE FROM concert_seats WHERE concert_id = $1")
+            .bind(concert_id)
+            .execute(&mut *tx)
+            .await?;
+        sqlx::query("DELETE FROM price_zones WHERE concert_id = $1")
+            .bind(concert_id)
+            .execute(&mut *tx)
+            .await?;
+        sqlx::query("UPDATE concerts SET seat_map_id = $2 WHERE id = $1")
+            .bind(concert_id)
+            .bind(input.seat_map_id)
+            .execute(&mut *tx)
+            .await?;
+
+        for zone in &input.zones {
+            let zone_id: Uuid = sqlx::query(
+                "INSERT INTO price_zones (concert_id, name, ticket_id) VALUES ($1, $2, $3) RETURNING id"
+            )
+            .bind(concert_id)
+            .bind(&zone.name)
+            .bind(zone.ticket_id)
+            .fetch_one(&mut *tx)
+            .await
+            .map_err(|e| {
+                if is_unique_violation(&e) {
+                    AppError::Conflict(format!("價區 {} 重複", zone.name))
+                } else {
+                    AppError::Database(e)
+                }
+            })?
+            .get("id");
+
+            for zone_section in &zone.sections {
+                let result = sqlx::query(
+                    r#"
+                    INSERT INTO concert_seats (concert_id, seat_id, price_zone_id)
+                    SELECT $1, s.id, $2
+                    FROM seats s
+                    JOIN seat_rows r ON s.row_id = r.id
+                    JOIN seat_sections sec ON r.section_id = sec.id
+                    WHERE sec.seat_map_id = $3
+                      AND sec.name = $4
+                      AND ($5::text[] IS NULL OR r.label = ANY($5))
+                    "#
+                )
+                .bind(concert_id)
+                .bind(zone_id)
+                .bind(input.seat_map_id)
+                .bind(&zone_section.section)
+                .bind(&zone_section.rows)
+                .execute(&mut *tx)
+                .await
+                .map_err(|e| {
+                    if is_unique_violation(&e) {
+                        AppError::Conflict(format!("座位區 {} 的座位被指定到多個價區", zone_section.section))
+                    } else {
+                        AppError::Database(e)
+                    }
+                })?;
+
+                if result.rows_affected() == 0 {
+                    return Err(AppError::BadRequest(format!(
+                        "價區 {} 的座位區 {} 沒有符合的座位",
+                        zone.name, zone_section.section
+                    )));
+                }
+            }
+        }
+
+        // 劃位票種的庫存即為價區的座位數
+        sqlx::query(
+            r#"
+            UPDATE tickets t
+            SET stock = c.seat_count, initial_stock = c.seat_count, allocation_adjustment = 0
+            FROM (
+                SELECT z.ticket_id, COUNT(*)::int as seat_count
+                FROM concert_seats cs
+                JOIN price_zones z ON cs.price_zone_id = z.id
+                WHERE cs.concert_id = $1
+                GROUP BY z.ticket_id
+            ) c
+            WHERE t.id = c.ticket_id
+            "#
+        )
+        .bind(concert_id)
+        .execute(&mut *tx)
+        .await?;
+
+        tx.commit().await?;
+        Ok(())
+    }
+
+    async fn find_concert_seat_map_id(&self, concert_id: Uuid, tenant: Tenant) -> Result<Option<Uuid>, AppError> {
+        let result = sqlx::query(&format!(
+            "SELECT c.seat_map_id FROM concerts c WHERE c.id = $1 AND {}",
+            tenant_filter("c.organization_id", 2)
+        ))
+        .bind(concert_id)
+        .bind(tenant.organization_id())
+        .fetch_optional(&self.pool)
+        .await?;
+
+        Ok(result.and_then(|row| row.get("seat_map_id")))
+    }
+
+    async fn find_zones(&self, concert_id: Uuid, tenant: Tenant) -> Result<Vec<PriceZone>, AppError> {
+        let result = sqlx::query(&format!(
+            r#"
+            SELECT z.id, z.name, z.ticket_id, t.ticket_type, t.price::float8,
+                   COUNT(cs.seat_id)::bigint as seat_count,
+                   COUNT(cs.seat_id) FILTER (WHERE cs.status = 'available')::bigint as available_count
+            FROM price_zones z
+            JOIN concerts c ON z.concert_id = c.id
+            JOIN tickets t ON z.ticket_id = t.id
+            LEFT JOIN concert_seats cs ON cs.price_zone_id = z.id
+            WHERE z.concert_id = $1 AND {}
+            GROUP BY z.id, z.name, z.ticket_id, t.ticket_type, t.price
+            ORDER BY t.price DESC, z.name
+            "#,
+            tenant_filter("c.organization_id", 2)
+        ))
+        .bind(concert_id)
+        .bind(tenant.organization_id())
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(result
+            .iter()
+            .map(|row| PriceZone {
+                id: row.get("id"),
+                name: row.get("name"),
+                ticket_id: row.get("ticket_id"),
+                ticket_type: row.get("ticket_type"),
+                price: row.get("price"),
+                seat_count: row.get("seat_count"),
+                available_count: row.get("available_count"),
+            })
+            .collect())
+    }
+
+    async fn find_availability(&self, concert_id: Uuid, tenant: Tenant) -> Result<Vec<SeatAvailability>, AppError> {
+        let result = sqlx::query(&format!(
+            r#"
+            SELECT s.id as seat_id, sec.name as section, r.label as row, s.number,
+                   s.x, s.y, cs.price_zone_id, cs.status
+            FROM concert_seats cs
+            JOIN concerts c ON cs.concert_id = c.id
+            JOIN seats s ON cs.seat_id = s.id
+            JOIN seat_rows r ON s.row_id = r.id
+            JOIN seat_sections sec ON r.section_id = sec.id
+            WHERE cs.concert_id = $1 AND {}
+            ORDER BY sec.position, r.position, s.position
+            "#,
+            tenant_filter("c.organization_id", 2)
+        ))
+        .bind(concert_id)
+        .bind(tenant.organization_id())
+        .fetch_all(&self.pool)
+        .await?;
+
+        result.iter().map(availability_from_row).collect()
+    }
+
+    async fn is_seated_ticket(&self, ticket_id: Uuid) -> Result<bool, AppError> {
+        let result = sqlx::query(
+            "SELECT EXISTS (SELECT 1 FROM price_zones WHERE ticket_id = $1) as seated"
+        )
+        .bind(ticket_id)
+        .fetch_one(&self.pool)
+        .await?;
+
+        Ok(result.get("seated"))
+    }
+
+    async fn find_allocation_seats(&self, ticket_id: Uuid, price_zone_id: Option<Uuid>) -> Result<Vec<AllocationSeat>, AppError> {
+        let result = sqlx::query(
+            r#"
+            SELECT s.id as seat_id, s.row_id, s.position, s.x, s.y,
+                   cs.status = 'available' as available
+            FROM concert_seats cs
+            JOIN price_zones z ON cs.price_zone_id = z.id
+            JOIN seats s ON cs.seat_id = s.id
+            WHERE z.ticket_id = $1
+              AND ($2::uuid IS NULL OR z.id = $2)
+            "#
+        )
+        .bind(ticket_id)
+        .bind(price_zone_id)
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(result
+            .iter()
+            .map(|row| AllocationSeat {
+                seat_id: row.get("seat_id"),
+                row_id: row.get("row_id"),
+                position: row.get("position"),
+                x: row.get("x"),
+                y: row.get("y"),
+                available: row.get("available"),
+            })
+            .collect())
+    }
+}
+
+/// 將資料庫記錄轉換為座位可售狀態
+fn availability_from_row(row: &PgRow) -> Result<SeatAvailability, AppError> {
+    let status: String = row.get("status");
+
+    Ok(SeatAvailability {
+        seat_id: row.get("seat_id"),
+        section: row.get("section"),
+        row: row.get("row"),
+        number: row.get("number"),
+        x: row.get("x"),
+        y: row.get("y"),
+        price_zone_id: row.get("price_zone_id"),
+        status: SeatStatus::parse(&status)
+            .ok_or_else(|| AppError::Internal(format!("未知的座位狀態: {}", status)))?,
+    })
+}
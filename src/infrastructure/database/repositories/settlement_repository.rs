@@ -0,0 +1,235 @@
+use async_trait::async_trait;
+use sqlx::postgres::PgRow;
+use sqlx::{PgPool, Row};
+use uuid::Uuid;
+
+use crate::domain::ledger::model::Journal;
+use crate::domain::order::model::{OrderStatus, PriceBreakdown};
+use crate::domain::settlement::model::{Settlement, SettlementLine, SettlementOrder, SettlementStatus};
+use crate::domain::settlement::repository::SettlementRepository;
+use crate::infrastructure::database::repositories::ledger_repository::insert_journal;
+use crate::utils::error::AppError;
+use crate::utils::money::to_decimal;
+
+/// PostgreSQL 結算存儲庫實現
+pub struct PgSettlementRepository {
+    pool: PgPool,
+}
+
+impl PgSettlementRepository {
+    /// 創建新的 PostgreSQL 結算存儲庫
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+}
+
+#[async_trait]
+impl SettlementRepository for PgSettlementRepository {
+    async fn find_orders_by_concert_id(&self, concert_id: Uuid) -> Result<Vec<SettlementOrder>, AppError> {
+        // 組合商品訂單依組合內容的營收比例分配到各場演唱會，明細以「組合名稱 / 票種」與單獨售出的票種分開列示
+        let result = sqlx::query(
+            r#"
+            SELECT COALESCE(s.bundle_name || ' / ' || s.ticket_type, s.ticket_type) AS ticket_type,
+                   s.quantity, o.status,
+                   (o.unit_price * s.share)::float8 as price,
+                   (o.subtotal * s.share)::float8 as subtotal,
+                   (o.addon_total * s.share)::float8 as addon_total,
+                   (o.fee_total * s.share)::float8 as fee_total,
+                   (o.discount_total * s.share)::float8 as discount_total,
+                   o.tax_rate::float8, o.tax_inclusive,
+                   (o.tax_total * s.share)::float8 as tax_total,
+                   (o.total * s.share)::float8 as total
+            FROM orders o
+            JOIN order_concert_shares s ON s.order_id = o.id
+            WHERE s.concert_id = $1
+            ORDER BY 1, o.created_at
+            "#
+        )
+        .bind(concert_id)
+        .fetch_all(&self.pool)
+        .await?;
+
+        result
+            .iter()
+            .map(|row| {
+                let status: String = row.get("status");
+                Ok(SettlementOrder {
+                    ticket_type: row.get("ticket_type"),
+                    quantity: row.get("quantity"),
+                    status: OrderStatus::parse(&status)
+                        .ok_or_else(|| AppError::Internal(format!("未知的訂單狀態: {}", status)))?,
+                    breakdown: PriceBreakdown {
+                        unit_price: row.get("price"),
+                        subtotal: row.get("subtotal"),
+                        addon_total: row.get("addon_total"),
+                        fee_total: row.get("fee_total"),
+                        discount_total: row.get("discount_total"),
+                        tax_rate: row.get("tax_rate"),
+                        tax_inclusive: row.get("tax_inclusive"),
+                        tax_total: row.get("tax_total"),
+                        total: row.get("total"),
+                    },
+                })
+            })
+            .collect()
+    }
+
+    async fn find_by_concert_id(&self, concert_id: Uuid) -> Result<Option<Settlement>, AppError> {
+        let result = sqlx::query(
+            r#"
+            SELECT s.id, s.concert_id, c.title as concert_title,
+                   s.organization_id, g.name as organization_name, s.status,
+                   s.commission_rate::float8, s.gross_sales::float8, s.discounts::float8,
+                   s.refunds::float8, s.tax_withheld::float8, s.net_sales::float8,
+                   s.platform_commission::float8, s.net_payable::float8,
+                   s.locked_at, s.paid_at
+            FROM settlements s
+            JOIN concerts c ON s.concert_id = c.id
+            JOIN organizations g ON s.organization_id = g.id
+            WHERE s.concert_id = $1
+            "#
+        )
+        .bind(concert_id)
+        .fetch_optional(&self.pool)
+        .await?;
+
+        let row = match result {
+            Some(row) => row,
+            None => return Ok(None),
+        };
+
+        let settlement_id: Uuid = row.get("id");
+        let lines = sqlx::query(
+            r#"
+            SELECT ticket_type, tickets_sold, tickets_refunded,
+                   gross_sales::float8, discounts::float8, refunds::float8,
+                   tax_withheld::float8, net_sales::float8
+            FROM settlement_lines
+            WHERE settlement_id = $1
+            ORDER BY ticket_type
+            "#
+        )
+        .bind(settlement_id)
+        .fetch_all(&self.pool)
+        .await?;
+
+        let status: String = row.get("status");
+        Ok(Some(Settlement {
+            concert_id: row.get("concert_id"),
+            concert_title: row.get("concert_title"),
+            organization_id: row.get("organization_id"),
+            organization_name: row.get("organization_name"),
+            status: SettlementStatus::parse(&status)
+                .ok_or_else(|| AppError::Internal(format!("未知的結算狀態: {}", status)))?,
+            commission_rate: row.get("commission_rate"),
+            gross_sales: row.get("gross_sales"),
+            discounts: row.get("discounts"),
+            refunds: row.get("refunds"),
+            tax_withheld: row.get("tax_withheld"),
+            net_sales: row.get("net_sales"),
+            platform_commission: row.get("platform_commission"),
+            net_payable: row.get("net_payable"),
+            lines: lines.iter().map(line_from_row).collect(),
+            locked_at: row.get("locked_at"),
+            paid_at: row.get("paid_at"),
+        }))
+    }
+
+    async fn lock(&self, settlement: &Settlement) -> Result<bool, AppError> {
+        // 結算單與明細在同一個交易中寫入
+        let mut tx = self.pool.begin().await?;
+
+        let result = sqlx::query(
+            r#"
+            INSERT INTO settlements (concert_id, organization_id, status, commission_rate,
+                                     gross_sales, discounts, refunds, tax_withheld,
+                                     net_sales, platform_commission, net_payable)
+            VALUES ($1, $2, 'locked', $3, $4, $5, $6, $7, $8, $9, $10)
+            ON CONFLICT (concert_id) DO NOTHING
+            RETURNING id
+            "#
+        )
+        .bind(settlement.concert_id)
+        .bind(settlement.organization_id)
+        .bind(to_decimal(settlement.commission_rate)?)
+        .bind(to_decimal(settlement.gross_sales)?)
+        .bind(to_decimal(settlement.discounts)?)
+        .bind(to_decimal(settlement.refunds)?)
+        .bind(to_decimal(settlement.tax_withheld)?)
+        .bind(to_decimal(settlement.net_sales)?)
+        .bind(to_decimal(settlement.platform_commission)?)
+        .bind(to_decimal(settlement.net_payable)?)
+        .fetch_optional(&mut *tx)
+        .await?;
+
+        let settlement_id: Uuid = match result {
+            Some(row) => row.get("id"),
+            None => return Ok(false),
+        };
+
+        for line in &settlement.lines {
+            sqlx::query(
+                r#"
+                INSERT INTO settlement_lines (settlement_id, ticket_type, tickets_sold, tickets_refunded,
+                                              gross_sales, discounts, refunds, tax_withheld, net_sales)
+                VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9)
+                "#
+            )
+            .bind(settlement_id)
+            .bind(&line.ticket_type)
+            .bind(line.tickets_sold)
+            .bind(line.tickets_refunded)
+            .bind(to_decimal(line.gross_sales)?)
+            .bind(to_decimal(line.discounts)?)
+            .bind(to_decimal(line.refunds)?)
+            .bind(to_decimal(line.tax_withheld)?)
+            .bind(to_decimal(line.net_sales)?)
+            .execute(&mut *tx)
+            .await?;
+        }
+
+        tx.commit().await?;
+        Ok(true)
+    }
+
+    async fn mark_paid(&self, concert_id: Uuid, journals: &[Journal]) -> Result<bool, AppError> {
+        let mut tx = self.pool.begin().await?;
+
+        // 只有已鎖定的結算單可以撥款，重複呼叫不會重複寫入分錄
+        let result = sqlx::query(
+            r#"
+            UPDATE settlements
+            SET status = 'paid', paid_at = CURRENT_TIMESTAMP
+            WHERE concert_id = $1 AND status = 'locked'
+            "#
+        )
+        .bind(concert_id)
+        .execute(&mut *tx)
+        .await?;
+
+        if result.rows_affected() != 1 {
+            return Ok(false);
+        }
+
+        for journal in journals {
+            insert_journal(&mut tx, journal).await?;
+        }
+
+        tx.commit().await?;
+        Ok(true)
+    }
+}
+
+/// 將資料庫記錄轉換為結算明細
+fn line_from_row(row: &PgRow) -> SettlementLine {
+    SettlementLine {
+        ticket_type: row.get("ticket_type"),
+        tickets_sold: row.get("tickets_sold"),
+        tickets_refunded: row.get("tickets_refunded"),
+        gross_sales: row.get("gross_sales"),
+        discounts: row.get("discounts"),
+        refunds: row.get("refunds"),
+        tax_withheld: row.get("tax_withheld"),
+        net_sales: row.get("net_sales"),
+    }
+}
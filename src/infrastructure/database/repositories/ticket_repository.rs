@@ -1,12 +1,16 @@
 use async_trait::async_trait;
-use sqlx::{PgPool, Row};
+use sqlx::{PgConnection, PgPool, Row};
 use std::str::FromStr;
 use sqlx::types::BigDecimal;
 use uuid::Uuid;
 
-use crate::domain::ticket::model::{CreateTicket, Ticket};
+use crate::domain::organization::model::Tenant;
+use crate::domain::ticket::model::{CreateTicket, DemandRule, PriceTier, SetTicketPricing, Ticket};
+use crate::domain::ticket::pricing::PricingSchedule;
 use crate::domain::ticket::repository::TicketRepository;
+use crate::infrastructure::database::repositories::{tenant_filter, tickets_in_tenant};
 use crate::utils::error::AppError;
+use crate::utils::money::to_decimal;
 
 /// PostgreSQL 票券存儲庫實現
 pub struct PgTicketRepository {
@@ -22,16 +26,21 @@ impl PgTicketRepository {
 
 #[async_trait]
 impl TicketRepository for PgTicketRepository {
-    async fn find_by_id(&self, id: Uuid) -> Result<Option<Ticket>, AppError> {
+    async fn find_by_id(&self, id: Uuid, tenant: Tenant) -> Result<Option<Ticket>, AppError> {
         // 使用原生 SQL 查詢，避免 sqlx::query_as! 宏的類型轉換問題
-        let result = sqlx::query(
+        let result = sqlx::query(&format!(
             r#"
-            SELECT id, concert_id, ticket_type, price::float8, stock 
-            FROM tickets 
-            WHERE id = $1
-            "#
-        )
+            SELECT t.id, t.concert_id, t.ticket_type, t.price::float8,
+                   LEAST(t.stock, COALESCE(p.remaining, t.stock)) AS stock, t.pool_id
+            FROM tickets t
+            JOIN concerts c ON t.concert_id = c.id
+            LEFT JOIN inventory_pools p ON p.id = t.pool_id
+            WHERE t.id = $1 AND {}
+            "#,
+            tenant_filter("c.organization_id", 2)
+        ))
         .bind(id)
+        .bind(tenant.organization_id())
         .fetch_optional(&self.pool)
         .await?;
 
@@ -41,7 +50,9 @@ impl TicketRepository for PgTicketRepository {
                 concert_id: row.get("concert_id"),
                 ticket_type: row.get("ticket_type"),
                 price: row.get("price"),
+                current_price: row.get("price"),
                 stock: row.get("stock"),
+                pool_id: row.get("pool_id"),
             })),
             None => Ok(None),
         }
@@ -51,9 +62,11 @@ impl TicketRepository for PgTicketRepository {
         // 使用原生 SQL 查詢
         let result = sqlx::query(
             r#"
-            SELECT id, concert_id, ticket_type, price::float8, stock 
-            FROM tickets 
-            WHERE concert_id = $1
+            SELECT t.id, t.concert_id, t.ticket_type, t.price::float8,
+                   LEAST(t.stock, COALESCE(p.remaining, t.stock)) AS stock, t.pool_id
+            FROM tickets t
+            LEFT JOIN inventory_pools p ON p.id = t.pool_id
+            WHERE t.concert_id = $1
             "#
         )
         .bind(concert_id)
@@ -67,57 +80,229 @@ impl TicketRepository for PgTicketRepository {
                 concert_id: row.get("concert_id"),
                 ticket_type: row.get("ticket_type"),
                 price: row.get("price"),
+                current_price: row.get("price"),
                 stock: row.get("stock"),
+                pool_id: row.get("pool_id"),
             })
             .collect();
 
         Ok(tickets)
     }
 
-    async fn create(&self, input: &CreateTicket) -> Result<Ticket, AppError> {
+    async fn create(&self, input: &CreateTicket, tenant: Tenant) -> Result<Option<Ticket>, AppError> {
         // 將 f64 轉換為 BigDecimal
         let price_decimal = BigDecimal::from_str(&input.price.to_string())
             .unwrap_or_else(|_| BigDecimal::from_str("0").unwrap());
 
         // 使用原生 SQL 查詢
-        let result = sqlx::query(
+        // 演唱會不在租戶範圍內時不會插入任何資料列
+        let result = sqlx::query(&format!(
             r#"
-            INSERT INTO tickets (concert_id, ticket_type, price, stock)
-            VALUES ($1, $2, $3, $4)
-            RETURNING id, concert_id, ticket_type, price::float8, stock
-            "#
-        )
+            INSERT INTO tickets (concert_id, ticket_type, price, stock, initial_stock)
+            SELECT c.id, $2, $3, $4, $4
+            FROM concerts c
+            WHERE c.id = $1 AND {}
+            RETURNING id, concert_id, ticket_type, price::float8, stock, pool_id
+            "#,
+            tenant_filter("c.organization_id", 5)
+        ))
         .bind(input.concert_id)
         .bind(&input.ticket_type)
         .bind(price_decimal)
         .bind(input.stock)
-        .fetch_one(&self.pool)
+        .bind(tenant.organization_id())
+        .fetch_optional(&self.pool)
         .await?;
 
-        let ticket = Ticket {
+        Ok(result.map(|result| Ticket {
             id: result.get("id"),
             concert_id: result.get("concert_id"),
             ticket_type: result.get("ticket_type"),
             price: result.get("price"),
+            current_price: result.get("price"),
             stock: result.get("stock"),
-        };
+            pool_id: result.get("pool_id"),
+        }))
+    }
+
+    async fn find_pricing(&self, id: Uuid, tenant: Tenant) -> Result<Option<PricingSchedule>, AppError> {
+        let mut conn = self.pool.acquire().await?;
+        load_pricing(&mut conn, id, tenant, false).await
+    }
+
+    async fn set_pricing(&self, id: Uuid, input: &SetTicketPricing, tenant: Tenant) -> Result<bool, AppError> {
+        let mut tx = self.pool.begin().await?;
+
+        if !tickets_in_tenant(&mut tx, &[id], tenant).await? {
+            return Ok(false);
+        }
+
+        sqlx::query("DELETE FROM price_tiers WHERE ticket_id = $1")
+            .bind(id)
+            .execute(&mut *tx)
+            .await?;
+
+        for (position, tier) in input.tiers.iter().enumerate() {
+            sqlx::query(
+                r#"
+                INSERT INTO price_tiers (ticket_id, position, name, price, starts_at, ends_at, max_sold)
+                VALUES ($1, $2, $3, $4, $5, $6, $7)
+                "#
+            )
+            .bind(id)
+            .bind(position as i32)
+            .bind(&tier.name)
+            .bind(to_decimal(tier.price)?)
+            .bind(tier.starts_at)
+            .bind(tier.ends_at)
+            .bind(tier.max_sold)
+            .execute(&mut *tx)
+            .await?;
+        }
 
-        Ok(ticket)
+        match &input.demand {
+            Some(demand) => {
+                sqlx::query(
+                    r#"
+                    INSERT INTO ticket_demand_pricing (ticket_id, start_sell_through, max_increase)
+                    VALUES ($1, $2, $3)
+                    ON CONFLICT (ticket_id) DO UPDATE
+                    SET start_sell_through = EXCLUDED.start_sell_through, max_increase = EXCLUDED.max_increase
+                    "#
+                )
+                .bind(id)
+                .bind(to_decimal(demand.start_sell_through)?)
+                .bind(to_decimal(demand.max_increase)?)
+                .execute(&mut *tx)
+                .await?;
+            }
+            None => {
+                sqlx::query("DELETE FROM ticket_demand_pricing WHERE ticket_id = $1")
+                    .bind(id)
+                    .execute(&mut *tx)
+                    .await?;
+            }
+        }
+
+        tx.commit().await?;
+        Ok(true)
     }
+}
+
+/// 讀取票種定價設定
+/// lock 為 true 時鎖定票種列直到交易結束，讓計價使用的售出數量與同一交易中的庫存扣減一致
+async fn load_pricing(
+    conn: &mut PgConnection,
+    id: Uuid,
+    tenant: Tenant,
+    lock: bool,
+) -> Result<Option<PricingSchedule>, AppError> {
+    let ticket = sqlx::query(&format!(
+        r#"
+        SELECT t.price::float8, t.initial_stock + t.allocation_adjustment AS capacity,
+               t.initial_stock + t.allocation_adjustment - t.stock AS sold,
+               d.start_sell_through::float8, d.max_increase::float8
+        FROM tickets t
+        JOIN concerts c ON t.concert_id = c.id
+        LEFT JOIN ticket_demand_pricing d ON d.ticket_id = t.id
+        WHERE t.id = $1 AND {}
+        {}
+        "#,
+        tenant_filter("c.organization_id", 2),
+        if lock { "FOR UPDATE OF t" } else { "" }
+    ))
+    .bind(id)
+    .bind(tenant.organization_id())
+    .fetch_optional(&mut *conn)
+    .await?;
+
+    let Some(ticket) = ticket else {
+        return Ok(None);
+    };
+
+    let tiers = sqlx::query(
+        r#"
+        SELECT name, price::float8, starts_at, ends_at, max_sold
+        FROM price_tiers
+        WHERE ticket_id = $1
+        ORDER BY position
+        "#
+    )
+    .bind(id)
+    .fetch_all(&mut *conn)
+    .await?
+    .iter()
+    .map(|row| PriceTier {
+        name: row.get("name"),
+        price: row.get("price"),
+        starts_at: row.get("starts_at"),
+        ends_at: row.get("ends_at"),
+        max_sold: row.get("max_sold"),
+    })
+    .collect();
+
+    let demand = ticket
+        .get::<Option<f64>, _>("start_sell_through")
+        .map(|start_sell_through| DemandRule {
+            start_sell_through,
+            max_increase: ticket.get("max_increase"),
+        });
+
+    Ok(Some(PricingSchedule {
+        base_price: ticket.get("price"),
+        tiers,
+        demand,
+        sold: ticket.get("sold"),
+        capacity: ticket.get("capacity"),
+    }))
+}
 
-    async fn update_stock(&self, id: Uuid, quantity: i32) -> Result<(), AppError> {
-        sqlx::query!(
+/// 在交易中鎖定票種並讀取定價設定，供建立訂單時與庫存扣減一併計價
+pub async fn lock_pricing(conn: &mut PgConnection, id: Uuid) -> Result<Option<PricingSchedule>, AppError> {
+    load_pricing(conn, id, Tenant::Platform, true).await
+}
+
+/// 在交易中更新票種庫存
+/// 正數為扣減、負數為歸還；票種加入庫存池時同時更新池的剩餘數量。
+/// 任一數量不足時返回 false，呼叫端應放棄（回滾）交易
+pub async fn update_ticket_stock(conn: &mut PgConnection, id: Uuid, quantity: i32) -> Result<bool, AppError> {
+    // 條件式扣減：庫存不足時不會更新任何資料列，避免並發購買造成超賣
+    let ticket = sqlx::query(
+        r#"
+        UPDATE tickets
+        SET stock = stock - $2
+        WHERE id = $1 AND stock >= $2
+        RETURNING pool_id
+        "#
+    )
+    .bind(id)
+    .bind(quantity)
+    .fetch_optional(&mut *conn)
+    .await?;
+
+    let pool_id: Option<Uuid> = match ticket {
+        Some(row) => row.get("pool_id"),
+        None => return Ok(false),
+    };
+
+    // 共用庫存池在同一交易中扣減
+    if let Some(pool_id) = pool_id {
+        let result = sqlx::query(
             r#"
-            UPDATE tickets
-            SET stock = stock - $1
-            WHERE id = $2
-            "#,
-            quantity,
-            id
+            UPDATE inventory_pools
+            SET remaining = remaining - $2
+            WHERE id = $1 AND remaining >= $2
+            "#
         )
-        .execute(&self.pool)
+        .bind(pool_id)
+        .bind(quantity)
+        .execute(&mut *conn)
         .await?;
 
-        Ok(())
+        if result.rows_affected() == 0 {
+            return Ok(false);
+        }
     }
+
+    Ok(true)
 }
@@ -0,0 +1,225 @@
+use async_trait::async_trait;
+use chrono::Duration;
+use sqlx::{PgConnection, PgPool, Row};
+use uuid::Uuid;
+
+use crate::domain::auth::model::{NewRefreshToken, RefreshToken, UserTokenPurpose};
+use crate::domain::auth::repository::TokenRepository;
+use crate::utils::error::AppError;
+
+/// PostgreSQL 令牌存儲庫實現
+pub struct PgTokenRepository {
+    pool: PgPool,
+}
+
+impl PgTokenRepository {
+    /// 創建新的 PostgreSQL 令牌存儲庫
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+}
+
+#[async_trait]
+impl TokenRepository for PgTokenRepository {
+    async fn create_refresh_token(&self, token: &NewRefreshToken) -> Result<(), AppError> {
+        let mut tx = self.pool.begin().await?;
+
+        // 順便清除此用戶已過期的刷新令牌
+        sqlx::query("DELETE FROM refresh_tokens WHERE user_id = $1 AND expires_at < CURRENT_TIMESTAMP")
+            .bind(token.user_id)
+            .execute(&mut *tx)
+            .await?;
+
+        sqlx::query(
+            r#"
+            INSERT INTO refresh_tokens (user_id, family_id, token_hash, access_jti, access_expires_at, expires_at, two_factor)
+            VALUES ($1, $2, $3, $4,
+                    CURRENT_TIMESTAMP + $5 * INTERVAL '1 second',
+                    CURRENT_TIMESTAMP + $6 * INTERVAL '1 second',
+                    $7)
+            "#
+        )
+        .bind(token.user_id)
+        .bind(token.family_id)
+        .bind(&token.token_hash)
+        .bind(token.access_jti)
+        .bind(token.access_ttl.num_seconds())
+        .bind(token.ttl.num_seconds())
+        .bind(token.two_factor)
+        .execute(&mut *tx)
+        .await?;
+
+        tx.commit().await?;
+        Ok(())
+    }
+
+    async fn find_refresh_token(&self, token_hash: &str) -> Result<Option<RefreshToken>, AppError> {
+        let result = sqlx::query(
+            r#"
+            SELECT id, user_id, family_id, expires_at <= CURRENT_TIMESTAMP AS expired, used_at, revoked_at, two_factor
+            FROM refresh_tokens
+            WHERE token_hash = $1
+            "#
+        )
+        .bind(token_hash)
+        .fetch_optional(&self.pool)
+        .await?;
+
+        Ok(result.map(|row| RefreshToken {
+            id: row.get("id"),
+            user_id: row.get("user_id"),
+            family_id: row.get("family_id"),
+            expired: row.get("expired"),
+            used_at: row.get("used_at"),
+            revoked_at: row.get("revoked_at"),
+            two_factor: row.get("two_factor"),
+        }))
+    }
+
+    async fn consume_refresh_token(&self, id: Uuid) -> Result<bool, AppError> {
+        // 條件式更新：同一個令牌同時被換發時只有一方成功
+        let result = sqlx::query(
+            r#"
+            UPDATE refresh_tokens
+            SET used_at = CURRENT_TIMESTAMP
+            WHERE id = $1 AND used_at IS NULL AND revoked_at IS NULL
+            "#
+        )
+        .bind(id)
+        .execute(&self.pool)
+        .await?;
+
+        Ok(result.rows_affected() > 0)
+    }
+
+    async fn revoke_family(&self, family_id: Uuid) -> Result<(), AppError> {
+        let mut tx = self.pool.begin().await?;
+        revoke_where(&mut tx, "family_id = $1", family_id).await?;
+        tx.commit().await?;
+        Ok(())
+    }
+
+    async fn revoke_user(&self, user_id: Uuid) -> Result<(), AppError> {
+        let mut tx = self.pool.begin().await?;
+        revoke_where(&mut tx, "user_id = $1", user_id).await?;
+        tx.commit().await?;
+        Ok(())
+    }
+
+    async fn is_revoked(&self, jti: Uuid) -> Result<bool, AppError> {
+        let result = sqlx::query("SELECT 1 FROM revoked_tokens WHERE jti = $1")
+            .bind(jti)
+            .fetch_optional(&self.pool)
+            .await?;
+
+        Ok(result.is_some())
+    }
+
+    async fn create_user_token(
+        &self,
+        user_id: Uuid,
+        purpose: UserTokenPurpose,
+        token_hash: &str,
+        ttl: Duration,
+    ) -> Result<(), AppError> {
+        let mut tx = self.pool.begin().await?;
+
+        // 只有最新寄出的令牌有效
+        sqlx::query(
+            r#"
+            UPDATE user_tokens
+            SET used_at = CURRENT_TIMESTAMP
+            WHERE user_id = $1 AND purpose = $2 AND used_at IS NULL
+            "#
+        )
+        .bind(user_id)
+        .bind(purpose.as_str())
+        .execute(&mut *tx)
+        .await?;
+
+        sqlx::query(
+            r#"
+            INSERT INTO user_tokens (user_id, purpose, token_hash, expires_at)
+            VALUES ($1, $2, $3, CURRENT_TIMESTAMP + $4 * INTERVAL '1 second')
+            "#
+        )
+        .bind(user_id)
+        .bind(purpose.as_str())
+        .bind(token_hash)
+        .bind(ttl.num_seconds())
+        .execute(&mut *tx)
+        .await?;
+
+        tx.commit().await?;
+        Ok(())
+    }
+
+    async fn find_user_token(&self, purpose: UserTokenPurpose, token_hash: &str) -> Result<Option<Uuid>, AppError> {
+        let result = sqlx::query(
+            r#"
+            SELECT user_id
+            FROM user_tokens
+            WHERE token_hash = $1 AND purpose = $2
+              AND used_at IS NULL AND expires_at > CURRENT_TIMESTAMP
+            "#
+        )
+        .bind(token_hash)
+        .bind(purpose.as_str())
+        .fetch_optional(&self.pool)
+        .await?;
+
+        Ok(result.map(|row| row.get("user_id")))
+    }
+
+    async fn consume_user_token(&self, purpose: UserTokenPurpose, token_hash: &str) -> Result<Option<Uuid>, AppError> {
+        // 條件式更新：同一個令牌同時被使用時只有一方成功
+        let result = sqlx::query(
+            r#"
+            UPDATE user_tokens
+            SET used_at = CURRENT_TIMESTAMP
+            WHERE token_hash = $1 AND purpose = $2
+              AND used_at IS NULL AND expires_at > CURRENT_TIMESTAMP
+            RETURNING user_id
+            "#
+        )
+        .bind(token_hash)
+        .bind(purpose.as_str())
+        .fetch_optional(&self.pool)
+        .await?;
+
+        Ok(result.map(|row| row.get("user_id")))
+    }
+}
+
+/// 撤銷符合條件的刷新令牌，並將一起簽發、尚未過期的存取令牌記入撤銷清單
+/// `condition` 以 $1 綁定 `id`
+async fn revoke_where(conn: &mut PgConnection, condition: &str, id: Uuid) -> Result<(), AppError> {
+    sqlx::query(&format!(
+        r#"
+        INSERT INTO revoked_tokens (jti, expires_at)
+        SELECT access_jti, access_expires_at
+        FROM refresh_tokens
+        WHERE {} AND access_expires_at > CURRENT_TIMESTAMP
+        ON CONFLICT DO NOTHING
+        "#,
+        condition
+    ))
+    .bind(id)
+    .execute(&mut *conn)
+    .await?;
+
+    sqlx::query(&format!(
+        "UPDATE refresh_tokens SET revoked_at = CURRENT_TIMESTAMP WHERE {} AND revoked_at IS NULL",
+        condition
+    ))
+    .bind(id)
+    .execute(&mut *conn)
+    .await?;
+
+    // 過期的存取令牌已無法使用，不需要留在撤銷清單
+    sqlx::query("DELETE FROM revoked_tokens WHERE expires_at < CURRENT_TIMESTAMP")
+        .execute(&mut *conn)
+        .await?;
+
+    Ok(())
+}
@@ -0,0 +1,160 @@
+use async_trait::async_trait;
+use sqlx::{PgConnection, PgPool, Row};
+use uuid::Uuid;
+
+use crate::domain::auth::model::UserTotp;
+use crate::domain::auth::repository::TwoFactorRepository;
+use crate::utils::error::AppError;
+
+/// PostgreSQL 兩步驟驗證存儲庫實現
+pub struct PgTwoFactorRepository {
+    pool: PgPool,
+}
+
+impl PgTwoFactorRepository {
+    /// 創建新的 PostgreSQL 兩步驟驗證存儲庫
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+}
+
+#[async_trait]
+impl TwoFactorRepository for PgTwoFactorRepository {
+    async fn find(&self, user_id: Uuid) -> Result<Option<UserTotp>, AppError> {
+        let result = sqlx::query(
+            r#"
+            SELECT secret, enabled_at
+            FROM user_totp
+            WHERE user_id = $1
+            "#
+        )
+        .bind(user_id)
+        .fetch_optional(&self.pool)
+        .await?;
+
+        Ok(result.map(|row| UserTotp {
+            secret: row.get("secret"),
+            enabled_at: row.get("enabled_at"),
+        }))
+    }
+
+    async fn save_pending(&self, user_id: Uuid, secret: &str) -> Result<bool, AppError> {
+        let result = sqlx::query(
+            r#"
+            INSERT INTO user_totp (user_id, secret)
+            VALUES ($1, $2)
+            ON CONFLICT (user_id) DO UPDATE
+            SET secret = EXCLUDED.secret, created_at = CURRENT_TIMESTAMP
+            WHERE user_totp.enabled_at IS NULL
+            "#
+        )
+        .bind(user_id)
+        .bind(secret)
+        .execute(&self.pool)
+        .await?;
+
+        Ok(result.rows_affected() > 0)
+    }
+
+    async fn enable(&self, user_id: Uuid, step: i64, recovery_code_hashes: &[String]) -> Result<bool, AppError> {
+        let mut tx = self.pool.begin().await?;
+
+        let result = sqlx::query(
+            r#"
+            UPDATE user_totp
+            SET enabled_at = CURRENT_TIMESTAMP, last_used_step = $2
+            WHERE user_id = $1 AND enabled_at IS NULL
+            "#
+        )
+        .bind(user_id)
+        .bind(step)
+        .execute(&mut *tx)
+        .await?;
+        if result.rows_affected() == 0 {
+            return Ok(false);
+        }
+
+        replace_codes(&mut tx, user_id, recovery_code_hashes).await?;
+
+        tx.commit().await?;
+        Ok(true)
+    }
+
+    async fn use_step(&self, user_id: Uuid, step: i64) -> Result<bool, AppError> {
+        // 條件式更新：同一組驗證碼同時提交時只有一方成功
+        let result = sqlx::query(
+            r#"
+            UPDATE user_totp
+            SET last_used_step = $2
+            WHERE user_id = $1 AND enabled_at IS NOT NULL
+              AND (last_used_step IS NULL OR last_used_step < $2)
+            "#
+        )
+        .bind(user_id)
+        .bind(step)
+        .execute(&self.pool)
+        .await?;
+
+        Ok(result.rows_affected() > 0)
+    }
+
+    async fn use_recovery_code(&self, user_id: Uuid, code_hash: &str) -> Result<bool, AppError> {
+        let result = sqlx::query(
+            r#"
+            UPDATE totp_recovery_codes
+            SET used_at = CURRENT_TIMESTAMP
+            WHERE user_id = $1 AND code_hash = $2 AND used_at IS NULL
+            "#
+        )
+        .bind(user_id)
+        .bind(code_hash)
+        .execute(&self.pool)
+        .await?;
+
+        Ok(result.rows_affected() > 0)
+    }
+
+    async fn replace_recovery_codes(&self, user_id: Uuid, code_hashes: &[String]) -> Result<(), AppError> {
+        let mut tx = self.pool.begin().await?;
+        replace_codes(&mut tx, user_id, code_hashes).await?;
+        tx.commit().await?;
+        Ok(())
+    }
+
+    async fn disable(&self, user_id: Uuid) -> Result<(), AppError> {
+        let mut tx = self.pool.begin().await?;
+
+        sqlx::query("DELETE FROM totp_recovery_codes WHERE user_id = $1")
+            .bind(user_id)
+            .execute(&mut *tx)
+            .await?;
+        sqlx::query("DELETE FROM user_totp WHERE user_id = $1")
+            .bind(user_id)
+            .execute(&mut *tx)
+            .await?;
+
+        tx.commit().await?;
+        Ok(())
+    }
+}
+
+/// 刪除用戶所有救援碼並寫入新的救援碼雜湊
+async fn replace_codes(conn: &mut PgConnection, user_id: Uuid, code_hashes: &[String]) -> Result<(), AppError> {
+    sqlx::query("DELETE FROM totp_recovery_codes WHERE user_id = $1")
+        .bind(user_id)
+        .execute(&mut *conn)
+        .await?;
+
+    sqlx::query(
+        r#"
+        INSERT INTO totp_recovery_codes (user_id, code_hash)
+        SELECT $1, code_hash FROM UNNEST($2::text[]) AS code_hash
+        "#
+    )
+    .bind(user_id)
+    .bind(code_hashes)
+    .execute(&mut *conn)
+    .await?;
+
+    Ok(())
+}
@@ -2,8 +2,9 @@ use async_trait::async_trait;
 use sqlx::PgPool;
 use uuid::Uuid;
 
-use crate::domain::auth::model::{RegisterInput, User};
+use crate::domain::auth::model::{ProfileDetails, RegisterInput, Role, RoleAssignment, User, UserProfile, UserRoles};
 use crate::domain::auth::repository::UserRepository;
+use crate::infrastructure::database::repositories::{is_foreign_key_violation, is_unique_violation};
 use crate::utils::error::AppError;
 
 /// PostgreSQL 用戶存儲庫實現
@@ -18,15 +19,20 @@ impl PgUserRepository {
     }
 }
 
+/// 解析資料庫中的角色字串
+fn parse_role(value: &str) -> Result<Role, AppError> {
+    Role::parse(value).ok_or_else(|| AppError::Internal(format!("未知的角色: {}", value)))
+}
+
 #[async_trait]
 impl UserRepository for PgUserRepository {
     async fn find_by_id(&self, id: Uuid) -> Result<Option<User>, AppError> {
-        // 使用 query! 而不是 query_as! 來手動處理 Option<bool>
         let record = sqlx::query!(
             r#"
-            SELECT id, email, password_hash, is_admin
-            FROM users
-            WHERE id = $1
+            SELECT u.id, u.email, u.password_hash, u.organization_id, u.email_verified_at,
+                   ARRAY(SELECT role FROM user_roles WHERE user_id = u.id ORDER BY role) AS "roles!"
+            FROM users u
+            WHERE u.id = $1
             "#,
             id
         )
@@ -34,21 +40,27 @@ impl UserRepository for PgUserRepository {
         .await?;
 
         // 手動轉換為 User 模型
-        Ok(record.map(|r| User {
-            id: r.id,
-            email: r.email,
-            password_hash: r.password_hash,
-            is_admin: r.is_admin.unwrap_or(false),
-        }))
+        record
+            .map(|r| {
+                Ok(User {
+                    id: r.id,
+                    email: r.email,
+                    password_hash: r.password_hash,
+                    organization_id: r.organization_id,
+                    roles: r.roles.iter().map(|role| parse_role(role)).collect::<Result<_, _>>()?,
+                    email_verified_at: r.email_verified_at,
+                })
+            })
+            .transpose()
     }
 
     async fn find_by_email(&self, email: &str) -> Result<Option<User>, AppError> {
-        // 使用 query! 而不是 query_as! 來手動處理 Option<bool>
         let record = sqlx::query!(
             r#"
-            SELECT id, email, password_hash, is_admin
-            FROM users
-            WHERE email = $1
+            SELECT u.id, u.email, u.password_hash, u.organization_id, u.email_verified_at,
+                   ARRAY(SELECT role FROM user_roles WHERE user_id = u.id ORDER BY role) AS "roles!"
+            FROM users u
+            WHERE u.email = $1
             "#,
             email
         )
@@ -56,28 +68,55 @@ impl UserRepository for PgUserRepository {
         .await?;
 
         // 手動轉換為 User 模型
-        Ok(record.map(|r| User {
-            id: r.id,
-            email: r.email,
-            password_hash: r.password_hash,
-            is_admin: r.is_admin.unwrap_or(false),
-        }))
+        record
+            .map(|r| {
+                Ok(User {
+                    id: r.id,
+                    email: r.email,
+                    password_hash: r.password_hash,
+                    organization_id: r.organization_id,
+                    roles: r.roles.iter().map(|role| parse_role(role)).collect::<Result<_, _>>()?,
+                    email_verified_at: r.email_verified_at,
+                })
+            })
+            .transpose()
     }
 
-    async fn create(&self, input: &RegisterInput, password_hash: &str) -> Result<(), AppError> {
-        // 使用 query! 而不是 query_as! 來手動處理 Option<bool>
-        sqlx::query!(
+    async fn create(&self, input: &RegisterInput, password_hash: &str) -> Result<User, AppError> {
+        let mut tx = self.pool.begin().await?;
+
+        let r = sqlx::query!(
             r#"
-            INSERT INTO users (email, password_hash, is_admin)
-            VALUES ($1, $2, false)
+            INSERT INTO users (email, password_hash)
+            VALUES ($1, $2)
+            RETURNING id, email, password_hash
             "#,
             input.email,
             password_hash
         )
-        .execute(&self.pool)
+        .fetch_one(&mut *tx)
         .await?;
 
-        Ok(())
+        // 新註冊的使用者都是顧客
+        sqlx::query!(
+            r#"
+            INSERT INTO user_roles (user_id, role) VALUES ($1, 'customer')
+            "#,
+            r.id
+        )
+        .execute(&mut *tx)
+        .await?;
+
+        tx.commit().await?;
+
+        Ok(User {
+            id: r.id,
+            email: r.email,
+            password_hash: r.password_hash,
+            organization_id: None,
+            roles: vec![Role::Customer],
+            email_verified_at: None,
+        })
     }
 
     async fn email_exists(&self, email: &str) -> Result<bool, AppError> {
@@ -94,4 +133,244 @@ impl UserRepository for PgUserRepository {
 
         Ok(exists)
     }
+
+    async fn find_roles(&self, user_id: Uuid) -> Result<Option<UserRoles>, AppError> {
+        let user = sqlx::query!(
+            r#"
+            SELECT id, email, organization_id FROM users WHERE id = $1
+            "#,
+            user_id
+        )
+        .fetch_optional(&self.pool)
+        .await?;
+
+        let user = match user {
+            Some(user) => user,
+            None => return Ok(None),
+        };
+
+        let records = sqlx::query!(
+            r#"
+            SELECT role, granted_by, granted_at
+            FROM user_roles
+            WHERE user_id = $1
+            ORDER BY granted_at, role
+            "#,
+            user_id
+        )
+        .fetch_all(&self.pool)
+        .await?;
+
+        let roles = records
+            .iter()
+            .map(|r| {
+                Ok(RoleAssignment {
+                    role: parse_role(&r.role)?,
+                    granted_by: r.granted_by,
+                    granted_at: r.granted_at,
+                })
+            })
+            .collect::<Result<_, AppError>>()?;
+
+        Ok(Some(UserRoles {
+            user_id: user.id,
+            email: user.email,
+            organization_id: user.organization_id,
+            roles,
+        }))
+    }
+
+    async fn grant_role(&self, user_id: Uuid, role: Role, granted_by: Uuid) -> Result<bool, AppError> {
+        let result = sqlx::query!(
+            r#"
+            INSERT INTO user_roles (user_id, role, granted_by)
+            VALUES ($1, $2, $3)
+            ON CONFLICT DO NOTHING
+            "#,
+            user_id,
+            role.as_str(),
+            granted_by
+        )
+        .execute(&self.pool)
+        .await?;
+
+        Ok(result.rows_affected() > 0)
+    }
+
+    async fn revoke_role(&self, user_id: Uuid, role: Role) -> Result<bool, AppError> {
+        let result = sqlx::query!(
+            r#"
+            DELETE FROM user_roles WHERE user_id = $1 AND role = $2
+            "#,
+            user_id,
+            role.as_str()
+        )
+        .execute(&self.pool)
+        .await?;
+
+        Ok(result.rows_affected() > 0)
+    }
+
+    async fn set_organization(&self, user_id: Uuid, organization_id: Option<Uuid>) -> Result<bool, AppError> {
+        let result = sqlx::query!(
+            r#"
+            UPDATE users SET organization_id = $2 WHERE id = $1
+            "#,
+            user_id,
+            organization_id
+        )
+        .execute(&self.pool)
+        .await
+        .map_err(|e| {
+            if is_foreign_key_violation(&e) {
+                AppError::NotFound(format!("找不到 ID 為 {} 的主辦單位", organization_id.unwrap_or_default()))
+            } else {
+                e.into()
+            }
+        })?;
+
+        Ok(result.rows_affected() > 0)
+    }
+
+    async fn find_members(&self, organization_id: Uuid) -> Result<Vec<UserRoles>, AppError> {
+        let ids = sqlx::query!(
+            r#"
+            SELECT id FROM users WHERE organization_id = $1 ORDER BY email
+            "#,
+            organization_id
+        )
+        .fetch_all(&self.pool)
+        .await?;
+
+        let mut members = Vec::with_capacity(ids.len());
+        for r in ids {
+            if let Some(member) = self.find_roles(r.id).await? {
+                members.push(member);
+            }
+        }
+        Ok(members)
+    }
+    async fn set_email_verified(&self, user_id: Uuid) -> Result<(), AppError> {
+        sqlx::query!(
+            r#"
+            UPDATE users SET email_verified_at = COALESCE(email_verified_at, CURRENT_TIMESTAMP)
+            WHERE id = $1
+            "#,
+            user_id
+        )
+        .execute(&self.pool)
+        .await?;
+
+        Ok(())
+    }
+
+    async fn update_password(&self, user_id: Uuid, password_hash: &str) -> Result<(), AppError> {
+        sqlx::query!(
+            r#"
+            UPDATE users SET password_hash = $2 WHERE id = $1
+            "#,
+            user_id,
+            password_hash
+        )
+        .execute(&self.pool)
+        .await?;
+
+        Ok(())
+    }
+
+    async fn find_profile(&self, user_id: Uuid) -> Result<Option<UserProfile>, AppError> {
+        let record = sqlx::query!(
+            r#"
+            SELECT u.id, u.email, u.email_verified_at, u.pending_email, u.password_hash IS NOT NULL AS "has_password!",
+                   u.organization_id, u.display_name, u.phone, u.locale, u.date_of_birth,
+                   u.marketing_consent, u.marketing_consent_at, u.created_at,
+                   ARRAY(SELECT role FROM user_roles WHERE user_id = u.id ORDER BY role) AS "roles!"
+            FROM users u
+            WHERE u.id = $1
+            "#,
+            user_id
+        )
+        .fetch_optional(&self.pool)
+        .await?;
+
+        record
+            .map(|r| {
+                Ok(UserProfile {
+                    id: r.id,
+                    email: r.email,
+                    email_verified: r.email_verified_at.is_some(),
+                    pending_email: r.pending_email,
+                    has_password: r.has_password,
+                    roles: r.roles.iter().map(|role| parse_role(role)).collect::<Result<_, _>>()?,
+                    organization_id: r.organization_id,
+                    display_name: r.display_name,
+                    phone: r.phone,
+                    locale: r.locale,
+                    date_of_birth: r.date_of_birth,
+                    marketing_consent: r.marketing_consent,
+                    marketing_consent_at: r.marketing_consent_at,
+                    created_at: r.created_at,
+                })
+            })
+            .transpose()
+    }
+
+    async fn update_profile(&self, user_id: Uuid, details: &ProfileDetails) -> Result<(), AppError> {
+        sqlx::query!(
+            r#"
+            UPDATE users
+            SET display_name = $2, phone = $3, locale = $4, date_of_birth = $5,
+                marketing_consent_at = CASE WHEN marketing_consent = $6 THEN marketing_consent_at ELSE CURRENT_TIMESTAMP END,
+                marketing_consent = $6
+            WHERE id = $1
+            "#,
+            user_id,
+            details.display_name,
+            details.phone,
+            details.locale,
+            details.date_of_birth,
+            details.marketing_consent
+        )
+        .execute(&self.pool)
+        .await?;
+
+        Ok(())
+    }
+
+    async fn set_pending_email(&self, user_id: Uuid, email: &str) -> Result<(), AppError> {
+        sqlx::query!(
+            r#"
+            UPDATE users SET pending_email = $2 WHERE id = $1
+            "#,
+            user_id,
+            email
+        )
+        .execute(&self.pool)
+        .await?;
+
+        Ok(())
+    }
+
+    async fn confirm_email_change(&self, user_id: Uuid) -> Result<Option<String>, AppError> {
+        let record = sqlx::query!(
+            r#"
+            UPDATE users
+            SET email = pending_email, pending_email = NULL, email_verified_at = CURRENT_TIMESTAMP
+            WHERE id = $1 AND pending_email IS NOT NULL
+            RETURNING email
+            "#,
+            user_id
+        )
+        .fetch_optional(&self.pool)
+        .await
+        .map_err(|e| {
+            if is_unique_violation(&e) {
+                AppError::Conflict("電子郵件已存在".to_string())
+            } else {
+                e.into()
+            }
+        })?;
+
+        Ok(record.map(|r| r.email))
+    }
 }
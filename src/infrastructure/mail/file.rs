@@ -0,0 +1,38 @@
+use std::path::PathBuf;
+
+use async_trait::async_trait;
+use uuid::Uuid;
+
+use crate::infrastructure::mail::{Email, Mailer};
+use crate::utils::error::AppError;
+
+/// 將郵件寫入目錄的寄送實作
+/// 每封郵件存成一個 .eml 檔，不連線外部服務，用於開發與測試環境
+pub struct FileMailer {
+    dir: PathBuf,
+    from: String,
+}
+
+impl FileMailer {
+    /// 創建新的檔案寄送實作
+    pub fn new(dir: impl Into<PathBuf>, from: String) -> Self {
+        Self { dir: dir.into(), from }
+    }
+}
+
+#[async_trait]
+impl Mailer for FileMailer {
+    async fn send(&self, email: &Email) -> Result<(), AppError> {
+        tokio::fs::create_dir_all(&self.dir).await
+            .map_err(|e| AppError::Internal(format!("無法建立郵件目錄: {}", e)))?;
+
+        // 檔名以時間開頭，方便依寄送順序查看
+        let file_name = format!("{}-{}.eml", chrono::Local::now().format("%Y%m%d%H%M%S"), Uuid::new_v4());
+        let path = self.dir.join(file_name);
+        tokio::fs::write(&path, email.to_message(&self.from)).await
+            .map_err(|e| AppError::Internal(format!("無法寫入郵件檔案: {}", e)))?;
+
+        tracing::info!("郵件已寫入 {}", path.display());
+        Ok(())
+    }
+}
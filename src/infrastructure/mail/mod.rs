@@ -0,0 +1,54 @@
+//! 電子郵件寄送
+//!
+//! 以 `Mailer` 抽象寄信方式，認證服務只依賴這個介面。
+//! `FileMailer` 將郵件寫成 .eml 檔，適合開發與測試環境；
+//! `SmtpMailer` 透過 SMTP 轉寄伺服器寄出。
+
+pub mod file;
+pub mod smtp;
+
+use async_trait::async_trait;
+use base64::engine::general_purpose::STANDARD;
+use base64::Engine;
+
+use crate::utils::error::AppError;
+
+/// 待寄送的郵件，內文為純文字
+#[derive(Debug, Clone)]
+pub struct Email {
+    pub to: String,
+    pub subject: String,
+    pub body: String,
+}
+
+impl Email {
+    /// 組成 RFC 5322 格式的郵件內容，換行一律為 CRLF
+    pub fn to_message(&self, from: &str) -> String {
+        let body = self.body.lines().collect::<Vec<_>>().join("\r\n");
+        format!(
+            "From: {}\r\nTo: {}\r\nSubject: {}\r\nDate: {}\r\nMIME-Version: 1.0\r\n\
+             Content-Type: text/plain; charset=UTF-8\r\nContent-Transfer-Encoding: 8bit\r\n\r\n{}\r\n",
+            from,
+            self.to,
+            encode_header(&self.subject),
+            chrono::Local::now().to_rfc2822(),
+            body,
+        )
+    }
+}
+
+/// 以 RFC 2047 編碼非 ASCII 的標頭值
+fn encode_header(value: &str) -> String {
+    if value.is_ascii() {
+        value.to_string()
+    } else {
+        format!("=?UTF-8?B?{}?=", STANDARD.encode(value))
+    }
+}
+
+/// 郵件寄送介面
+#[async_trait]
+pub trait Mailer: Send + Sync {
+    /// 寄送郵件
+    async fn send(&self, email: &Email) -> Result<(), AppError>;
+}
@@ -0,0 +1,79 @@
+use async_trait::async_trait;
+use tokio::io::{AsyncBufReadExt, AsyncWriteExt, BufReader};
+use tokio::net::TcpStream;
+
+use crate::infrastructure::mail::{Email, Mailer};
+use crate::utils::error::AppError;
+
+/// 透過 SMTP 寄送郵件
+/// 只實作未加密、不需認證的基本指令，預期連線到同一網段的郵件轉寄伺服器（例如 Postfix），
+/// 由轉寄伺服器負責 TLS 與對外投遞
+pub struct SmtpMailer {
+    host: String,
+    port: u16,
+    from: String,
+}
+
+impl SmtpMailer {
+    /// 創建新的 SMTP 寄送實作
+    pub fn new(host: String, port: u16, from: String) -> Self {
+        Self { host, port, from }
+    }
+}
+
+#[async_trait]
+impl Mailer for SmtpMailer {
+    async fn send(&self, email: &Email) -> Result<(), AppError> {
+        let stream = TcpStream::connect((self.host.as_str(), self.port)).await
+            .map_err(|e| AppError::Internal(format!("無法連線到 SMTP 伺服器: {}", e)))?;
+        let mut session = SmtpSession { stream: BufReader::new(stream) };
+
+        session.expect(220).await?;
+        session.command("EHLO ticket-service", 250).await?;
+        session.command(&format!("MAIL FROM:<{}>", self.from), 250).await?;
+        session.command(&format!("RCPT TO:<{}>", email.to), 250).await?;
+        session.command("DATA", 354).await?;
+
+        // 行首的句點需重複一次，避免被當成 DATA 結束
+        let message = email.to_message(&self.from).replace("\r\n.", "\r\n..");
+        session.command(&format!("{}.", message), 250).await?;
+        session.command("QUIT", 221).await?;
+
+        Ok(())
+    }
+}
+
+/// 一次 SMTP 連線
+struct SmtpSession {
+    stream: BufReader<TcpStream>,
+}
+
+impl SmtpSession {
+    /// 送出指令並檢查回應碼
+    async fn command(&mut self, line: &str, code: u16) -> Result<(), AppError> {
+        self.stream.get_mut().write_all(format!("{}\r\n", line).as_bytes()).await
+            .map_err(|e| AppError::Internal(format!("SMTP 寫入失敗: {}", e)))?;
+        self.expect(code).await
+    }
+
+    /// 讀取回應（可能跨多行）並檢查回應碼
+    async fn expect(&mut self, code: u16) -> Result<(), AppError> {
+        loop {
+            let mut line = String::new();
+            let read = self.stream.read_line(&mut line).await
+                .map_err(|e| AppError::Internal(format!("SMTP 讀取失敗: {}", e)))?;
+            if read == 0 {
+                return Err(AppError::Internal("SMTP 伺服器中斷連線".to_string()));
+            }
+
+            // 多行回應以 "250-" 接續，最後一行為 "250 "
+            if line.as_bytes().get(3) == Some(&b'-') {
+                continue;
+            }
+            return match line.get(..3).and_then(|value| value.parse::<u16>().ok()) {
+                Some(actual) if actual == code => Ok(()),
+                _ => Err(AppError::Internal(format!("SMTP 伺服器回應錯誤: {}", line.trim_end()))),
+            };
+        }
+    }
+}
@@ -0,0 +1,128 @@
+use std::collections::HashMap;
+use std::sync::Mutex;
+
+use async_trait::async_trait;
+use chrono::{Duration, Utc};
+
+use crate::domain::auth::model::LoginAttempt;
+use crate::domain::auth::repository::LoginAttemptRepository;
+use crate::utils::error::AppError;
+
+/// 記憶體登入失敗記錄存儲庫實現
+/// 重新啟動後記錄即清空，只適合單一實例部署；時間一律以 UTC 計算
+#[derive(Default)]
+pub struct InMemoryLoginAttemptRepository {
+    attempts: Mutex<HashMap<String, LoginAttempt>>,
+}
+
+impl InMemoryLoginAttemptRepository {
+    /// 創建新的記憶體登入失敗記錄存儲庫
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    fn attempts(&self) -> std::sync::MutexGuard<'_, HashMap<String, LoginAttempt>> {
+        // 持有鎖的期間不會 panic，鎖中毒時沿用內部資料即可
+        self.attempts.lock().unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+}
+
+#[async_trait]
+impl LoginAttemptRepository for InMemoryLoginAttemptRepository {
+    async fn is_locked(&self, key: &str) -> Result<bool, AppError> {
+        let now = Utc::now().naive_utc();
+        Ok(self.attempts().get(key)
+            .and_then(|attempt| attempt.locked_until)
+            .is_some_and(|until| until > now))
+    }
+
+    async fn record_failure(&self, key: &str, window: Duration) -> Result<i32, AppError> {
+        let now = Utc::now().naive_utc();
+        let window_start = now - window;
+        let mut attempts = self.attempts();
+
+        // 順便清除已過計數期間的記錄，避免記憶體無限成長
+        attempts.retain(|_, attempt| {
+            attempt.last_failure_at >= window_start || attempt.locked_until.is_some_and(|until| until >= window_start)
+        });
+
+        let attempt = attempts.entry(key.to_string()).or_insert(LoginAttempt {
+            failures: 0,
+            last_failure_at: now,
+            locked_until: None,
+        });
+        attempt.failures += 1;
+        attempt.last_failure_at = now;
+
+        Ok(attempt.failures)
+    }
+
+    async fn lock(&self, key: &str, lockout: Duration) -> Result<(), AppError> {
+        if let Some(attempt) = self.attempts().get_mut(key) {
+            attempt.locked_until = Some(Utc::now().naive_utc() + lockout);
+        }
+        Ok(())
+    }
+
+    async fn clear(&self, key: &str) -> Result<(), AppError> {
+        self.attempts().remove(key);
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn failures_accumulate_within_window() {
+        let repository = InMemoryLoginAttemptRepository::new();
+
+        assert_eq!(repository.record_failure("account:a@x.com", Duration::minutes(15)).await.unwrap(), 1);
+        assert_eq!(repository.record_failure("account:a@x.com", Duration::minutes(15)).await.unwrap(), 2);
+        assert_eq!(repository.record_failure("account:b@x.com", Duration::minutes(15)).await.unwrap(), 1);
+    }
+
+    #[tokio::test]
+    async fn failures_reset_after_window() {
+        let repository = InMemoryLoginAttemptRepository::new();
+        repository.record_failure("account:a@x.com", Duration::minutes(15)).await.unwrap();
+        repository.record_failure("account:a@x.com", Duration::minutes(15)).await.unwrap();
+
+        // 最後一次失敗已超過計數期間
+        repository.attempts().get_mut("account:a@x.com").unwrap().last_failure_at -= Duration::minutes(16);
+
+        assert_eq!(repository.record_failure("account:a@x.com", Duration::minutes(15)).await.unwrap(), 1);
+    }
+
+    #[tokio::test]
+    async fn window_is_counted_from_lockout_expiry() {
+        let repository = InMemoryLoginAttemptRepository::new();
+        repository.record_failure("account:a@x.com", Duration::minutes(15)).await.unwrap();
+        repository.lock("account:a@x.com", Duration::minutes(30)).await.unwrap();
+        assert!(repository.is_locked("account:a@x.com").await.unwrap());
+
+        // 最後一次失敗已超過計數期間，但鎖定剛到期，失敗次數繼續累計
+        {
+            let mut attempts = repository.attempts();
+            let attempt = attempts.get_mut("account:a@x.com").unwrap();
+            attempt.last_failure_at -= Duration::minutes(31);
+            attempt.locked_until = attempt.locked_until.map(|until| until - Duration::minutes(31));
+        }
+        assert!(!repository.is_locked("account:a@x.com").await.unwrap());
+
+        assert_eq!(repository.record_failure("account:a@x.com", Duration::minutes(15)).await.unwrap(), 2);
+    }
+
+    #[tokio::test]
+    async fn clear_resets_failures() {
+        let repository = InMemoryLoginAttemptRepository::new();
+        repository.record_failure("account:a@x.com", Duration::minutes(15)).await.unwrap();
+        repository.lock("account:a@x.com", Duration::minutes(1)).await.unwrap();
+
+        repository.clear("account:a@x.com").await.unwrap();
+
+        assert!(!repository.is_locked("account:a@x.com").await.unwrap());
+        assert_eq!(repository.record_failure("account:a@x.com", Duration::minutes(15)).await.unwrap(), 1);
+    }
+}
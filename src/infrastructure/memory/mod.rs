@@ -0,0 +1,6 @@
+//! 記憶體存儲
+//!
+//! 不需要持久化、只在單一實例內共用的資料，例如登入失敗次數。
+//! 多個實例同時運行時請改用 PostgreSQL 實作。
+
+pub mod login_attempt_repository;
@@ -1,3 +1,7 @@
 pub mod database;
+pub mod payment;
 pub mod security;
 pub mod http;
+pub mod mail;
+pub mod memory;
+pub mod oidc;
@@ -0,0 +1,267 @@
+//! OpenID Connect 用戶端
+//!
+//! 以授權碼流程搭配 PKCE（S256）向身分提供者取得 ID Token，
+//! 並以提供者 JWKS 公開的金鑰驗證簽章、發行者、受眾、有效期與 nonce。
+//! 提供者端點透過 discovery 文件取得，JWKS 遇到未知的 kid 時重新下載以支援金鑰輪替。
+
+use std::time::{Duration, Instant};
+
+use base64::engine::general_purpose::URL_SAFE_NO_PAD;
+use base64::Engine;
+use jsonwebtoken::jwk::JwkSet;
+use jsonwebtoken::{decode, decode_header, Algorithm, DecodingKey, Validation};
+use reqwest::Url;
+use serde::Deserialize;
+use sha2::{Digest, Sha256};
+use tokio::sync::RwLock;
+
+use crate::config::OidcProviderConfig;
+use crate::domain::auth::model::{ExternalIdentity, OidcLoginState};
+use crate::utils::error::AppError;
+
+/// 呼叫身分提供者的逾時時間
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// 未知 kid 觸發重新下載 JWKS 的最短間隔，避免偽造的令牌讓服務不斷向提供者請求
+const JWKS_REFRESH_INTERVAL: Duration = Duration::from_secs(60);
+
+/// ID Token 可接受的簽章演算法，只允許非對稱演算法
+const ALLOWED_ALGORITHMS: [Algorithm; 9] = [
+    Algorithm::RS256,
+    Algorithm::RS384,
+    Algorithm::RS512,
+    Algorithm::PS256,
+    Algorithm::PS384,
+    Algorithm::PS512,
+    Algorithm::ES256,
+    Algorithm::ES384,
+    Algorithm::EdDSA,
+];
+
+/// discovery 文件中用到的欄位
+#[derive(Debug, Clone, Deserialize)]
+struct ProviderMetadata {
+    issuer: String,
+    authorization_endpoint: String,
+    token_endpoint: String,
+    jwks_uri: String,
+}
+
+/// 令牌端點的回應
+#[derive(Debug, Deserialize)]
+struct TokenResponse {
+    id_token: Option<String>,
+}
+
+/// ID Token 中用到的聲明，iss、aud、exp 由 `Validation` 檢查
+#[derive(Debug, Deserialize)]
+struct IdTokenClaims {
+    sub: String,
+    nonce: Option<String>,
+    email: Option<String>,
+    /// 多數提供者為布林值，部分提供者（如 Apple）為字串 "true"
+    #[serde(default)]
+    email_verified: serde_json::Value,
+}
+
+/// 快取的 JWKS 與下載時間
+struct CachedJwks {
+    keys: JwkSet,
+    fetched_at: Instant,
+}
+
+/// 單一身分提供者的 OpenID Connect 用戶端
+pub struct OidcClient {
+    config: OidcProviderConfig,
+    http: reqwest::Client,
+    metadata: RwLock<Option<ProviderMetadata>>,
+    jwks: RwLock<Option<CachedJwks>>,
+}
+
+impl OidcClient {
+    /// 創建新的 OpenID Connect 用戶端，第一次使用時才下載 discovery 文件
+    pub fn new(config: OidcProviderConfig) -> Self {
+        let http = reqwest::Client::builder()
+            .timeout(REQUEST_TIMEOUT)
+            .build()
+            .expect("建立 HTTP 用戶端失敗");
+        Self {
+            config,
+            http,
+            metadata: RwLock::new(None),
+            jwks: RwLock::new(None),
+        }
+    }
+
+    /// 組成授權請求網址
+    /// 以 `login_state` 的驗證碼計算 PKCE challenge，nonce 會寫入 ID Token 供回呼時比對
+    pub async fn authorization_url(
+        &self,
+        redirect_uri: &str,
+        state: &str,
+        login_state: &OidcLoginState,
+        login_hint: Option<&str>,
+    ) -> Result<String, AppError> {
+        let metadata = self.metadata().await?;
+        let code_challenge = pkce_challenge(&login_state.code_verifier);
+
+        let mut params = vec![
+            ("response_type", "code"),
+            ("client_id", self.config.client_id.as_str()),
+            ("redirect_uri", redirect_uri),
+            ("scope", self.config.scopes.as_str()),
+            ("state", state),
+            ("nonce", login_state.nonce.as_str()),
+            ("code_challenge", code_challenge.as_str()),
+            ("code_challenge_method", "S256"),
+        ];
+        if let Some(login_hint) = login_hint {
+            params.push(("login_hint", login_hint));
+        }
+
+        let url = Url::parse_with_params(&metadata.authorization_endpoint, &params)
+            .map_err(|e| AppError::Internal(format!("{} 的授權端點無效: {}", self.config.name, e)))?;
+        Ok(url.to_string())
+    }
+
+    /// 以授權碼換取 ID Token，驗證後返回外部身分
+    pub async fn exchange_code(
+        &self,
+        code: &str,
+        redirect_uri: &str,
+        login_state: &OidcLoginState,
+    ) -> Result<ExternalIdentity, AppError> {
+        let metadata = self.metadata().await?;
+
+        let mut form = vec![
+            ("grant_type", "authorization_code"),
+            ("code", code),
+            ("redirect_uri", redirect_uri),
+            ("client_id", self.config.client_id.as_str()),
+            ("code_verifier", login_state.code_verifier.as_str()),
+        ];
+        if let Some(client_secret) = &self.config.client_secret {
+            form.push(("client_secret", client_secret.as_str()));
+        }
+
+        let response = self
+            .http
+            .post(&metadata.token_endpoint)
+            .form(&form)
+            .send()
+            .await
+            .map_err(|e| self.unavailable(e))?;
+        if !response.status().is_success() {
+            tracing::warn!("{} 拒絕授權碼: {}", self.config.name, response.status());
+            return Err(AppError::Unauthorized("授權碼無效或已過期".to_string()));
+        }
+
+        let token: TokenResponse = response.json().await.map_err(|e| self.unavailable(e))?;
+        let id_token = token
+            .id_token
+            .ok_or_else(|| AppError::Unauthorized(format!("{} 未回傳 ID Token", self.config.name)))?;
+
+        self.validate_id_token(&id_token, &login_state.nonce).await
+    }
+
+    /// 驗證 ID Token 的簽章與聲明
+    async fn validate_id_token(&self, id_token: &str, nonce: &str) -> Result<ExternalIdentity, AppError> {
+        let invalid = || AppError::Unauthorized("無效的 ID Token".to_string());
+
+        let header = decode_header(id_token).map_err(|_| invalid())?;
+        if !ALLOWED_ALGORITHMS.contains(&header.alg) {
+            return Err(invalid());
+        }
+        let kid = header.kid.ok_or_else(invalid)?;
+        let key = self.decoding_key(&kid).await?.ok_or_else(invalid)?;
+
+        let mut validation = Validation::new(header.alg);
+        validation.set_issuer(&[&self.config.issuer]);
+        validation.set_audience(&[&self.config.client_id]);
+        validation.set_required_spec_claims(&["exp", "iss", "aud", "sub"]);
+
+        let claims = decode::<IdTokenClaims>(id_token, &key, &validation)
+            .map_err(|e| {
+                tracing::warn!("{} 的 ID Token 驗證失敗: {}", self.config.name, e);
+                invalid()
+            })?
+            .claims;
+
+        if claims.nonce.as_deref() != Some(nonce) {
+            return Err(invalid());
+        }
+
+        let email_verified = claims.email_verified == serde_json::Value::Bool(true)
+            || claims.email_verified.as_str() == Some("true");
+        Ok(ExternalIdentity {
+            provider: self.config.name.clone(),
+            subject: claims.sub,
+            email: claims.email,
+            email_verified,
+        })
+    }
+
+    /// 取得 discovery 文件，成功後快取
+    async fn metadata(&self) -> Result<ProviderMetadata, AppError> {
+        if let Some(metadata) = self.metadata.read().await.as_ref() {
+            return Ok(metadata.clone());
+        }
+
+        let url = format!("{}/.well-known/openid-configuration", self.config.issuer.trim_end_matches('/'));
+        let metadata: ProviderMetadata = self.get_json(&url).await?;
+        // 發行者必須與設定完全相同，否則 ID Token 的 iss 檢查會失去意義
+        if metadata.issuer != self.config.issuer {
+            return Err(AppError::Internal(format!(
+                "{} 的 discovery 文件發行者 {} 與設定不符",
+                self.config.name, metadata.issuer
+            )));
+        }
+
+        *self.metadata.write().await = Some(metadata.clone());
+        Ok(metadata)
+    }
+
+    /// 依 kid 取得驗證金鑰，快取中沒有時重新下載 JWKS
+    async fn decoding_key(&self, kid: &str) -> Result<Option<DecodingKey>, AppError> {
+        if let Some(cached) = self.jwks.read().await.as_ref() {
+            if let Some(jwk) = cached.keys.find(kid) {
+                return Ok(DecodingKey::from_jwk(jwk).ok());
+            }
+            if cached.fetched_at.elapsed() < JWKS_REFRESH_INTERVAL {
+                return Ok(None);
+            }
+        }
+
+        let metadata = self.metadata().await?;
+        let keys: JwkSet = self.get_json(&metadata.jwks_uri).await?;
+        let key = keys.find(kid).and_then(|jwk| DecodingKey::from_jwk(jwk).ok());
+        *self.jwks.write().await = Some(CachedJwks {
+            keys,
+            fetched_at: Instant::now(),
+        });
+        Ok(key)
+    }
+
+    /// 下載 JSON 文件
+    async fn get_json<T: serde::de::DeserializeOwned>(&self, url: &str) -> Result<T, AppError> {
+        self.http
+            .get(url)
+            .send()
+            .await
+            .and_then(|response| response.error_for_status())
+            .map_err(|e| self.unavailable(e))?
+            .json()
+            .await
+            .map_err(|e| self.unavailable(e))
+    }
+
+    /// 無法連線身分提供者或回應格式錯誤
+    fn unavailable(&self, error: reqwest::Error) -> AppError {
+        AppError::Internal(format!("無法連線身分提供者 {}: {}", self.config.name, error))
+    }
+}
+
+/// 計算 PKCE S256 challenge：驗證碼 SHA-256 雜湊的 Base64url 編碼
+fn pkce_challenge(code_verifier: &str) -> String {
+    URL_SAFE_NO_PAD.encode(Sha256::digest(code_verifier.as_bytes()))
+}
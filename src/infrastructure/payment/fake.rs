@@ -0,0 +1,221 @@
+use std::str::FromStr;
+
+use async_trait::async_trait;
+use hmac::{Hmac, Mac};
+use serde::Deserialize;
+use sha2::Sha256;
+use uuid::Uuid;
+
+use crate::domain::payment::model::PaymentStatus;
+use crate::infrastructure::payment::{
+    PaymentGateway, PaymentIntent, RefundResult, WebhookEvent, WebhookEventType,
+};
+use crate::utils::error::AppError;
+
+type HmacSha256 = Hmac<Sha256>;
+
+/// 模擬金流的行為模式
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FakePaymentMode {
+    /// 請款立即成功
+    Succeed,
+    /// 請款被拒絕
+    Decline,
+    /// 請款後保持待確認，需透過 Webhook 完成
+    Delayed,
+}
+
+impl FromStr for FakePaymentMode {
+    type Err = String;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        match value {
+            "succeed" | "success" => Ok(FakePaymentMode::Succeed),
+            "decline" => Ok(FakePaymentMode::Decline),
+            "delayed" => Ok(FakePaymentMode::Delayed),
+            other => Err(format!("未知的模擬金流模式: {}", other)),
+        }
+    }
+}
+
+/// 模擬金流的 Webhook 內容
+#[derive(Debug, Deserialize)]
+struct FakeWebhookPayload {
+    id: String,
+    #[serde(rename = "type")]
+    event_type: String,
+    intent_id: String,
+}
+
+/// 本地模擬金流閘道
+/// 不連線任何外部服務，適合開發與測試環境使用
+///
+/// Webhook 簽章為請求內容的 HMAC-SHA256（十六進位），放在 `X-Payment-Signature` 標頭，
+/// 內容格式為 `{"id": "...", "type": "payment.succeeded" | "payment.failed", "intent_id": "..."}`。
+/// 延遲確認模式下可用 `openssl dgst -sha256 -hmac <密鑰>` 簽章後手動送出 Webhook 完成付款。
+pub struct FakePaymentGateway {
+    mode: FakePaymentMode,
+    webhook_secret: String,
+}
+
+impl FakePaymentGateway {
+    /// 創建新的模擬金流閘道
+    pub fn new(mode: FakePaymentMode, webhook_secret: String) -> Self {
+        Self {
+            mode,
+            webhook_secret,
+        }
+    }
+}
+
+#[async_trait]
+impl PaymentGateway for FakePaymentGateway {
+    fn provider(&self) -> &'static str {
+        "fake"
+    }
+
+    async fn create_intent(&self, order_id: Uuid, amount: f64, currency: &str) -> Result<PaymentIntent, AppError> {
+        let intent_id = format!("fake_pi_{}", Uuid::new_v4().simple());
+
+        Ok(PaymentIntent {
+            client_secret: format!("{}_secret_{}", intent_id, order_id.simple()),
+            intent_id,
+            amount,
+            currency: currency.to_string(),
+        })
+    }
+
+    async fn capture(&self, _intent_id: &str) -> Result<PaymentStatus, AppError> {
+        Ok(match self.mode {
+            FakePaymentMode::Succeed => PaymentStatus::Succeeded,
+            FakePaymentMode::Decline => PaymentStatus::Failed,
+            FakePaymentMode::Delayed => PaymentStatus::Pending,
+        })
+    }
+
+    async fn refund(&self, _intent_id: &str, amount: f64) -> Result<RefundResult, AppError> {
+        Ok(RefundResult {
+            refund_id: format!("fake_re_{}", Uuid::new_v4().simple()),
+            amount,
+        })
+    }
+
+    fn verify_webhook(&self, payload: &[u8], signature: &str) -> Result<WebhookEvent, AppError> {
+        let signature = hex::decode(signature.trim())
+            .map_err(|_| AppError::Unauthorized("無效的 Webhook 簽章".to_string()))?;
+
+        let mut mac = HmacSha256::new_from_slice(self.webhook_secret.as_bytes())
+            .expect("HMAC 可接受任意長度的密鑰");
+        mac.update(payload);
+        // verify_slice 以常數時間比較，避免時序攻擊
+        mac.verify_slice(&signature)
+            .map_err(|_| AppError::Unauthorized("無效的 Webhook 簽章".to_string()))?;
+
+        let payload: FakeWebhookPayload = serde_json::from_slice(payload)
+            .map_err(|e| AppError::BadRequest(format!("無效的 Webhook 內容: {}", e)))?;
+
+        let event_type = match payload.event_type.as_str() {
+            "payment.succeeded" => WebhookEventType::PaymentSucceeded,
+            "payment.failed" => WebhookEventType::PaymentFailed,
+            _ => WebhookEventType::Unknown,
+        };
+
+        Ok(WebhookEvent {
+            event_id: payload.id,
+            event_type,
+            intent_id: payload.intent_id,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SECRET: &str = "test_webhook_secret";
+
+    fn gateway(mode: FakePaymentMode) -> FakePaymentGateway {
+        FakePaymentGateway::new(mode, SECRET.to_string())
+    }
+
+    /// 依模擬金流格式產生 Webhook 內容
+    fn payload(event_type: &str) -> Vec<u8> {
+        format!(r#"{{"id": "evt_1", "type": "{}", "intent_id": "fake_pi_1"}}"#, event_type).into_bytes()
+    }
+
+    /// 以指定密鑰簽章，結果為十六進位字串
+    fn sign(secret: &str, payload: &[u8]) -> String {
+        let mut mac = HmacSha256::new_from_slice(secret.as_bytes()).unwrap();
+        mac.update(payload);
+        hex::encode(mac.finalize().into_bytes())
+    }
+
+    #[tokio::test]
+    async fn succeed_mode_captures_immediately() {
+        let gateway = gateway(FakePaymentMode::Succeed);
+        let intent = gateway.create_intent(Uuid::new_v4(), 100.0, "TWD").await.unwrap();
+
+        assert_eq!(gateway.capture(&intent.intent_id).await.unwrap(), PaymentStatus::Succeeded);
+    }
+
+    #[tokio::test]
+    async fn decline_mode_fails_capture() {
+        let gateway = gateway(FakePaymentMode::Decline);
+        let intent = gateway.create_intent(Uuid::new_v4(), 100.0, "TWD").await.unwrap();
+
+        assert_eq!(gateway.capture(&intent.intent_id).await.unwrap(), PaymentStatus::Failed);
+    }
+
+    #[tokio::test]
+    async fn delayed_mode_stays_pending_until_webhook() {
+        let gateway = gateway(FakePaymentMode::Delayed);
+        let intent = gateway.create_intent(Uuid::new_v4(), 100.0, "TWD").await.unwrap();
+
+        assert_eq!(gateway.capture(&intent.intent_id).await.unwrap(), PaymentStatus::Pending);
+
+        let payload = payload("payment.succeeded");
+        let event = gateway.verify_webhook(&payload, &sign(SECRET, &payload)).unwrap();
+        assert_eq!(event.event_type, WebhookEventType::PaymentSucceeded);
+        assert_eq!(event.event_id, "evt_1");
+        assert_eq!(event.intent_id, "fake_pi_1");
+    }
+
+    #[test]
+    fn failed_webhook_is_parsed() {
+        let payload = payload("payment.failed");
+        let event = gateway(FakePaymentMode::Delayed).verify_webhook(&payload, &sign(SECRET, &payload)).unwrap();
+
+        assert_eq!(event.event_type, WebhookEventType::PaymentFailed);
+    }
+
+    #[test]
+    fn unrecognized_webhook_type_is_unknown() {
+        let payload = payload("payment.refunded");
+        let event = gateway(FakePaymentMode::Delayed).verify_webhook(&payload, &sign(SECRET, &payload)).unwrap();
+
+        assert_eq!(event.event_type, WebhookEventType::Unknown);
+    }
+
+    #[test]
+    fn rejects_signature_from_another_secret() {
+        let payload = payload("payment.succeeded");
+        let result = gateway(FakePaymentMode::Delayed).verify_webhook(&payload, &sign("forged_secret", &payload));
+
+        assert!(matches!(result, Err(AppError::Unauthorized(_))));
+    }
+
+    #[test]
+    fn rejects_tampered_payload() {
+        let signature = sign(SECRET, &payload("payment.failed"));
+        let result = gateway(FakePaymentMode::Delayed).verify_webhook(&payload("payment.succeeded"), &signature);
+
+        assert!(matches!(result, Err(AppError::Unauthorized(_))));
+    }
+
+    #[test]
+    fn rejects_malformed_signature() {
+        let result = gateway(FakePaymentMode::Delayed).verify_webhook(&payload("payment.succeeded"), "not-hex");
+
+        assert!(matches!(result, Err(AppError::Unauthorized(_))));
+    }
+}
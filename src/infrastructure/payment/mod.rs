@@ -0,0 +1,71 @@
+//! 金流閘道
+//!
+//! 以 `PaymentGateway` 抽象不同的金流供應商，訂單服務只依賴這個介面。
+//! 目前內建 `FakePaymentGateway`，可在離線環境模擬成功、拒絕與延遲確認。
+
+pub mod fake;
+
+use async_trait::async_trait;
+use uuid::Uuid;
+
+use crate::domain::payment::model::PaymentStatus;
+use crate::utils::error::AppError;
+
+/// 金流供應商建立的付款意圖
+#[derive(Debug, Clone)]
+pub struct PaymentIntent {
+    /// 供應商端的付款意圖 ID
+    pub intent_id: String,
+    /// 提供給前端完成付款的密鑰
+    pub client_secret: String,
+    pub amount: f64,
+    pub currency: String,
+}
+
+/// 退款結果
+#[derive(Debug, Clone)]
+pub struct RefundResult {
+    /// 供應商端的退款 ID
+    pub refund_id: String,
+    pub amount: f64,
+}
+
+/// Webhook 事件類型
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum WebhookEventType {
+    /// 付款成功
+    PaymentSucceeded,
+    /// 付款失敗
+    PaymentFailed,
+    /// 其他不需要處理的事件
+    Unknown,
+}
+
+/// 驗證簽章後的 Webhook 事件
+#[derive(Debug, Clone)]
+pub struct WebhookEvent {
+    /// 供應商端的事件 ID，用於確保冪等處理
+    pub event_id: String,
+    pub event_type: WebhookEventType,
+    pub intent_id: String,
+}
+
+/// 金流閘道介面
+#[async_trait]
+pub trait PaymentGateway: Send + Sync {
+    /// 供應商名稱，會記錄在付款記錄上
+    fn provider(&self) -> &'static str;
+
+    /// 為訂單建立付款意圖
+    async fn create_intent(&self, order_id: Uuid, amount: f64, currency: &str) -> Result<PaymentIntent, AppError>;
+
+    /// 請款
+    /// 返回 `Pending` 表示需等待供應商透過 Webhook 確認
+    async fn capture(&self, intent_id: &str) -> Result<PaymentStatus, AppError>;
+
+    /// 退款
+    async fn refund(&self, intent_id: &str, amount: f64) -> Result<RefundResult, AppError>;
+
+    /// 驗證 Webhook 簽章並解析事件
+    fn verify_webhook(&self, payload: &[u8], signature: &str) -> Result<WebhookEvent, AppError>;
+}
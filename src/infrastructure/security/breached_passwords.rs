@@ -0,0 +1,128 @@
+//! 外洩密碼清單
+//!
+//! 以 k-anonymity 的方式查詢：只以密碼 SHA-1 雜湊的前 5 個十六進位字元向清單取得同一範圍的雜湊後綴，
+//! 再於本地比對完整雜湊，與 Have I Been Pwned 的 range API 相同。
+//! 目前的 `FileBreachedPasswords` 從本地檔案載入，日後改接線上服務時查詢端不需要修改。
+
+use std::collections::{HashMap, HashSet};
+use std::fs;
+
+use async_trait::async_trait;
+use sha1::{Digest, Sha1};
+
+use crate::utils::error::AppError;
+
+/// 查詢範圍的雜湊前綴長度（十六進位字元）
+const PREFIX_LEN: usize = 5;
+
+/// 外洩密碼清單介面
+#[async_trait]
+pub trait BreachedPasswordSource: Send + Sync {
+    /// 返回 SHA-1 雜湊以 `prefix`（5 個大寫十六進位字元）開頭的所有雜湊後綴
+    async fn range(&self, prefix: &str) -> Result<Vec<String>, AppError>;
+}
+
+/// 密碼是否出現在外洩密碼清單中，清單只會收到雜湊前綴
+pub async fn is_breached(source: &dyn BreachedPasswordSource, password: &str) -> Result<bool, AppError> {
+    let hash = hex::encode_upper(Sha1::digest(password.as_bytes()));
+    let (prefix, suffix) = hash.split_at(PREFIX_LEN);
+    Ok(source.range(prefix).await?.iter().any(|candidate| candidate == suffix))
+}
+
+/// 從本地檔案載入的外洩密碼清單
+/// 每行一筆，可為 Have I Been Pwned 格式的 `SHA1雜湊[:次數]`，或未雜湊的密碼（例如常見密碼清單），
+/// 剛好是 40 個十六進位字元的行視為雜湊。載入後依雜湊前綴分組保存在記憶體中
+pub struct FileBreachedPasswords {
+    ranges: HashMap<String, HashSet<String>>,
+}
+
+impl FileBreachedPasswords {
+    /// 讀取清單檔案
+    pub fn load(path: &str) -> Result<Self, AppError> {
+        let content = fs::read_to_string(path)
+            .map_err(|e| AppError::Internal(format!("無法讀取外洩密碼清單 {}: {}", path, e)))?;
+
+        let mut ranges: HashMap<String, HashSet<String>> = HashMap::new();
+        for line in content.lines().map(|line| line.trim_end_matches('\r')).filter(|line| !line.is_empty()) {
+            let hash = match line.split_once(':') {
+                Some((hash, _)) if is_sha1_hex(hash) => hash.to_uppercase(),
+                _ if is_sha1_hex(line) => line.to_uppercase(),
+                _ => hex::encode_upper(Sha1::digest(line.as_bytes())),
+            };
+            let (prefix, suffix) = hash.split_at(PREFIX_LEN);
+            ranges.entry(prefix.to_string()).or_default().insert(suffix.to_string());
+        }
+
+        tracing::info!("已載入外洩密碼清單 {}，共 {} 個雜湊範圍", path, ranges.len());
+        Ok(Self { ranges })
+    }
+}
+
+#[async_trait]
+impl BreachedPasswordSource for FileBreachedPasswords {
+    async fn range(&self, prefix: &str) -> Result<Vec<String>, AppError> {
+        Ok(self.ranges
+            .get(prefix)
+            .map(|suffixes| suffixes.iter().cloned().collect())
+            .unwrap_or_default())
+    }
+}
+
+/// 是否為 SHA-1 雜湊的十六進位字串
+fn is_sha1_hex(value: &str) -> bool {
+    value.len() == 40 && value.chars().all(|c| c.is_ascii_hexdigit())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn load(name: &str, content: &str) -> FileBreachedPasswords {
+        let path = std::env::temp_dir().join(format!("breached-{}-{}.txt", std::process::id(), name));
+        fs::write(&path, content).unwrap();
+        let source = FileBreachedPasswords::load(path.to_str().unwrap()).unwrap();
+        fs::remove_file(&path).unwrap();
+        source
+    }
+
+    #[tokio::test]
+    async fn matches_bare_hash_lines() {
+        // SHA1("password")，小寫也接受
+        let source = load("bare", "5baa61e4c9b93f3f0682250b6cf8331b7ee68fd8\r\n");
+
+        assert!(is_breached(&source, "password").await.unwrap());
+        assert!(!is_breached(&source, "Password").await.unwrap());
+    }
+
+    #[tokio::test]
+    async fn matches_hash_with_count_lines() {
+        // SHA1("hunter2")
+        let source = load("count", "F3BBBD66A63D4BF1747940578EC3D0103530E21D:17043\n");
+
+        assert!(is_breached(&source, "hunter2").await.unwrap());
+        assert!(!is_breached(&source, "hunter3").await.unwrap());
+    }
+
+    #[tokio::test]
+    async fn matches_plaintext_lines() {
+        let source = load("plain", "letmein\n\nadmin:admin\n");
+
+        assert!(is_breached(&source, "letmein").await.unwrap());
+        // 冒號前不是雜湊的行整行視為密碼
+        assert!(is_breached(&source, "admin:admin").await.unwrap());
+        assert!(!is_breached(&source, "admin").await.unwrap());
+    }
+
+    #[tokio::test]
+    async fn range_only_returns_suffixes_for_prefix() {
+        let source = load("range", "password\nletmein\n");
+
+        assert_eq!(source.range("5BAA6").await.unwrap(), vec!["1E4C9B93F3F0682250B6CF8331B7EE68FD8".to_string()]);
+        assert!(source.range("00000").await.unwrap().is_empty());
+    }
+
+    #[test]
+    fn missing_file_is_an_error() {
+        assert!(FileBreachedPasswords::load("/nonexistent/breached.txt").is_err());
+    }
+}
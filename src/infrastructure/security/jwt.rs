@@ -0,0 +1,119 @@
+use std::collections::HashMap;
+use std::fs;
+
+use base64::engine::general_purpose::URL_SAFE_NO_PAD;
+use base64::Engine;
+use jsonwebtoken::{decode, decode_header, encode, Algorithm, DecodingKey, EncodingKey, Header, Validation};
+use ring::signature::{Ed25519KeyPair, KeyPair};
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use sha2::{Digest, Sha256};
+
+use crate::domain::auth::model::{Jwk, JwkSet};
+use crate::utils::error::AppError;
+
+/// Ed25519 公鑰的 SubjectPublicKeyInfo DER 前綴，後接 32 位元組公鑰
+const ED25519_SPKI_PREFIX: [u8; 12] = [0x30, 0x2a, 0x30, 0x05, 0x06, 0x03, 0x2b, 0x65, 0x70, 0x03, 0x21, 0x00];
+
+/// JWT 簽章金鑰組
+/// 以 Ed25519 私鑰（EdDSA）簽發令牌，驗證時依 `kid` 標頭選用公鑰；
+/// 輪替期間舊公鑰仍保留在驗證金鑰中，直到以舊私鑰簽發的令牌全部過期
+pub struct JwtKeys {
+    signing_kid: String,
+    signing_key: EncodingKey,
+    verification_keys: HashMap<String, DecodingKey>,
+    jwks: JwkSet,
+}
+
+impl JwtKeys {
+    /// 從 PEM 檔案載入金鑰
+    /// `signing_key_file` 為 PKCS#8 私鑰，其公鑰自動加入驗證金鑰；
+    /// `verification_key_files` 為其他仍有效的公鑰（SubjectPublicKeyInfo）
+    pub fn load(signing_key_file: &str, verification_key_files: &[String]) -> Result<Self, AppError> {
+        let private_pem = read_pem(signing_key_file, "PRIVATE KEY")?;
+        let key_pair = Ed25519KeyPair::from_pkcs8_maybe_unchecked(private_pem.contents())
+            .map_err(|e| AppError::Internal(format!("無效的 Ed25519 私鑰 {}: {}", signing_key_file, e)))?;
+        let signing_key = EncodingKey::from_ed_der(private_pem.contents());
+
+        let mut public_keys = vec![key_pair.public_key().as_ref().to_vec()];
+        for file in verification_key_files {
+            let public_pem = read_pem(file, "PUBLIC KEY")?;
+            let der = public_pem.contents();
+            if der.len() != ED25519_SPKI_PREFIX.len() + 32 || !der.starts_with(&ED25519_SPKI_PREFIX) {
+                return Err(AppError::Internal(format!("{} 不是 Ed25519 公鑰", file)));
+            }
+            public_keys.push(der[ED25519_SPKI_PREFIX.len()..].to_vec());
+        }
+
+        let mut verification_keys = HashMap::new();
+        let mut jwks = JwkSet { keys: Vec::new() };
+        for public_key in &public_keys {
+            let x = URL_SAFE_NO_PAD.encode(public_key);
+            let kid = thumbprint(&x);
+            if verification_keys.contains_key(&kid) {
+                continue;
+            }
+            verification_keys.insert(kid.clone(), DecodingKey::from_ed_der(public_key));
+            jwks.keys.push(Jwk {
+                kty: "OKP".to_string(),
+                crv: "Ed25519".to_string(),
+                alg: "EdDSA".to_string(),
+                key_use: "sig".to_string(),
+                kid,
+                x,
+            });
+        }
+
+        Ok(Self {
+            signing_kid: jwks.keys[0].kid.clone(),
+            signing_key,
+            verification_keys,
+            jwks,
+        })
+    }
+
+    /// 以目前的簽章金鑰簽發令牌，標頭帶有 `kid`
+    pub fn sign<T: Serialize>(&self, claims: &T) -> Result<String, AppError> {
+        let mut header = Header::new(Algorithm::EdDSA);
+        header.kid = Some(self.signing_kid.clone());
+
+        encode(&header, claims, &self.signing_key)
+            .map_err(|_| AppError::Internal("無法生成令牌".to_string()))
+    }
+
+    /// 依 `kid` 選用公鑰驗證令牌並解析聲明
+    pub fn verify<T: DeserializeOwned>(&self, token: &str) -> Result<T, AppError> {
+        let invalid = || AppError::Unauthorized("無效的令牌".to_string());
+
+        let kid = decode_header(token).map_err(|_| invalid())?.kid.ok_or_else(invalid)?;
+        let key = self.verification_keys.get(&kid).ok_or_else(invalid)?;
+
+        decode::<T>(token, key, &Validation::new(Algorithm::EdDSA))
+            .map(|data| data.claims)
+            .map_err(|_| invalid())
+    }
+
+    /// 公開的驗證金鑰（JWKS），供其他服務驗證令牌
+    pub fn jwks(&self) -> &JwkSet {
+        &self.jwks
+    }
+}
+
+/// 讀取 PEM 檔案並檢查標籤
+fn read_pem(file: &str, tag: &str) -> Result<pem::Pem, AppError> {
+    let content = fs::read(file)
+        .map_err(|e| AppError::Internal(format!("無法讀取金鑰檔案 {}: {}", file, e)))?;
+    let parsed = pem::parse(content)
+        .map_err(|e| AppError::Internal(format!("無效的 PEM 檔案 {}: {}", file, e)))?;
+    if parsed.tag() != tag {
+        return Err(AppError::Internal(format!("{} 應為 {}，實際為 {}", file, tag, parsed.tag())));
+    }
+    Ok(parsed)
+}
+
+/// RFC 7638 JWK 指紋，作為金鑰的 `kid`
+/// 同一把公鑰永遠得到相同的 `kid`，各服務不需要另外協調金鑰編號
+fn thumbprint(x: &str) -> String {
+    let canonical = format!(r#"{{"crv":"Ed25519","kty":"OKP","x":"{}"}}"#, x);
+    URL_SAFE_NO_PAD.encode(Sha256::digest(canonical.as_bytes()))
+}
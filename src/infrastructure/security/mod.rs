@@ -1 +1,5 @@
+pub mod breached_passwords;
+pub mod jwt;
 pub mod password;
+pub mod token;
+pub mod totp;
@@ -1,27 +1,115 @@
 use argon2::{
     password_hash::{rand_core::OsRng, PasswordHash, PasswordHasher, PasswordVerifier, SaltString},
-    Argon2,
+    Algorithm, Argon2, Params, Version,
 };
 
 use crate::utils::error::AppError;
 
-/// 雜湊密碼
-pub fn hash_password(password: &str) -> Result<String, AppError> {
-    let salt = SaltString::generate(&mut OsRng);
-    let argon2 = Argon2::default();
-    
-    Ok(argon2
-        .hash_password(password.as_bytes(), &salt)
-        .map_err(|_| AppError::Internal("密碼雜湊失敗".to_string()))?
-        .to_string())
+/// Argon2id 密碼雜湊
+/// 記憶體用量、迭代次數與平行度由設定決定；驗證時使用雜湊字串本身記錄的參數，
+/// 因此調高參數後舊雜湊仍可驗證，再由 `needs_rehash` 判斷是否需要以新參數重新雜湊
+pub struct PasswordHashing {
+    argon2: Argon2<'static>,
 }
 
-/// 驗證密碼
-pub fn verify_password(password: &str, hash: &str) -> Result<bool, AppError> {
-    let parsed_hash = PasswordHash::new(hash)
-        .map_err(|_| AppError::Internal("無效的密碼雜湊".to_string()))?;
-    
-    Ok(Argon2::default()
-        .verify_password(password.as_bytes(), &parsed_hash)
-        .is_ok())
+impl PasswordHashing {
+    /// 以指定參數建立密碼雜湊器
+    ///
+    /// # 參數
+    /// * `memory_kib` - 記憶體用量（KiB）
+    /// * `iterations` - 迭代次數
+    /// * `parallelism` - 平行度
+    pub fn new(memory_kib: u32, iterations: u32, parallelism: u32) -> Result<Self, AppError> {
+        let params = Params::new(memory_kib, iterations, parallelism, None)
+            .map_err(|e| AppError::Internal(format!("無效的 Argon2 參數: {}", e)))?;
+        Ok(Self {
+            argon2: Argon2::new(Algorithm::Argon2id, Version::V0x13, params),
+        })
+    }
+
+    /// 雜湊密碼
+    pub fn hash(&self, password: &str) -> Result<String, AppError> {
+        let salt = SaltString::generate(&mut OsRng);
+
+        Ok(self.argon2
+            .hash_password(password.as_bytes(), &salt)
+            .map_err(|_| AppError::Internal("密碼雜湊失敗".to_string()))?
+            .to_string())
+    }
+
+    /// 驗證密碼
+    pub fn verify(&self, password: &str, hash: &str) -> Result<bool, AppError> {
+        let parsed_hash = PasswordHash::new(hash)
+            .map_err(|_| AppError::Internal("無效的密碼雜湊".to_string()))?;
+
+        Ok(self.argon2
+            .verify_password(password.as_bytes(), &parsed_hash)
+            .is_ok())
+    }
+
+    /// 雜湊是否弱於目前的設定
+    /// 演算法或版本不同，或任一成本參數低於設定時需要重新雜湊；參數高於設定的雜湊保持不變
+    pub fn needs_rehash(&self, hash: &str) -> bool {
+        let Ok(parsed_hash) = PasswordHash::new(hash) else {
+            return true;
+        };
+        if parsed_hash.algorithm != Algorithm::Argon2id.ident()
+            || parsed_hash.version != Some(Version::V0x13.into())
+        {
+            return true;
+        }
+        let Ok(params) = Params::try_from(&parsed_hash) else {
+            return true;
+        };
+
+        let current = self.argon2.params();
+        params.m_cost() < current.m_cost()
+            || params.t_cost() < current.t_cost()
+            || params.p_cost() < current.p_cost()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // 測試使用最低成本參數以縮短執行時間
+    fn hashing(memory_kib: u32, iterations: u32, parallelism: u32) -> PasswordHashing {
+        PasswordHashing::new(memory_kib, iterations, parallelism).unwrap()
+    }
+
+    #[test]
+    fn hash_with_current_parameters_does_not_need_rehash() {
+        let current = hashing(64, 2, 2);
+        let hash = current.hash("correct horse").unwrap();
+
+        assert!(current.verify("correct horse", &hash).unwrap());
+        assert!(!current.verify("wrong horse", &hash).unwrap());
+        assert!(!current.needs_rehash(&hash));
+    }
+
+    #[test]
+    fn hash_with_weaker_parameters_needs_rehash() {
+        let current = hashing(64, 2, 2);
+
+        assert!(current.needs_rehash(&hashing(32, 2, 2).hash("pw").unwrap()));
+        assert!(current.needs_rehash(&hashing(64, 1, 2).hash("pw").unwrap()));
+        assert!(current.needs_rehash(&hashing(64, 2, 1).hash("pw").unwrap()));
+        // 參數高於設定的雜湊保持不變
+        assert!(!current.needs_rehash(&hashing(128, 3, 2).hash("pw").unwrap()));
+    }
+
+    #[test]
+    fn hash_with_different_algorithm_needs_rehash() {
+        let current = hashing(64, 2, 2);
+        let argon2i = Argon2::new(Algorithm::Argon2i, Version::V0x13, Params::new(64, 2, 2, None).unwrap())
+            .hash_password(b"pw", &SaltString::generate(&mut OsRng))
+            .unwrap()
+            .to_string();
+
+        // 舊演算法的雜湊仍可驗證，登入成功後再重新雜湊
+        assert!(current.verify("pw", &argon2i).unwrap());
+        assert!(current.needs_rehash(&argon2i));
+        assert!(current.needs_rehash("not a phc string"));
+    }
 }
@@ -0,0 +1,31 @@
+use argon2::password_hash::rand_core::{OsRng, RngCore};
+use sha2::{Digest, Sha256};
+
+/// 產生不透明令牌：32 位元組隨機值的十六進位字串
+pub fn generate_token() -> String {
+    let mut bytes = [0u8; 32];
+    OsRng.fill_bytes(&mut bytes);
+    hex::encode(bytes)
+}
+
+/// 計算令牌的 SHA-256 雜湊
+/// 令牌本身已有足夠的隨機性，不需要加鹽；資料庫只保存雜湊，外洩時無法還原令牌
+pub fn hash_token(token: &str) -> String {
+    hex::encode(Sha256::digest(token.as_bytes()))
+}
+
+/// 產生一次性的救援碼，格式為 xxxxx-xxxxx（小寫 Base32）
+pub fn generate_recovery_code() -> String {
+    let mut bytes = [0u8; 7];
+    OsRng.fill_bytes(&mut bytes);
+    let encoded = data_encoding::BASE32_NOPAD.encode(&bytes).to_lowercase();
+    format!("{}-{}", &encoded[..5], &encoded[5..10])
+}
+
+/// 正規化使用者輸入的救援碼：忽略大小寫、空白與連字號
+pub fn normalize_recovery_code(code: &str) -> String {
+    code.chars()
+        .filter(|c| !c.is_whitespace() && *c != '-')
+        .flat_map(char::to_lowercase)
+        .collect()
+}
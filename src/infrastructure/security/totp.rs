@@ -0,0 +1,156 @@
+//! RFC 6238 TOTP（以時間為基礎的一次性密碼）
+//!
+//! 使用 HMAC-SHA1、6 位數、30 秒一個時間步，與常見的驗證器 App 相容。
+
+use argon2::password_hash::rand_core::{OsRng, RngCore};
+use data_encoding::BASE32_NOPAD;
+use hmac::{Hmac, Mac};
+use qrcode::render::svg;
+use qrcode::QrCode;
+use sha1::Sha1;
+
+use crate::utils::error::AppError;
+
+type HmacSha1 = Hmac<Sha1>;
+
+/// 每個時間步的秒數
+const STEP_SECS: i64 = 30;
+
+/// 驗證碼位數
+const DIGITS: u32 = 6;
+
+/// 允許前後各一個時間步的時鐘誤差
+const ALLOWED_SKEW: i64 = 1;
+
+/// 產生 160 位元的隨機密鑰，以 Base32 編碼
+pub fn generate_secret() -> String {
+    let mut bytes = [0u8; 20];
+    OsRng.fill_bytes(&mut bytes);
+    BASE32_NOPAD.encode(&bytes)
+}
+
+/// 產生驗證器 App 使用的 otpauth:// 設定網址
+pub fn provisioning_uri(secret: &str, issuer: &str, account: &str) -> String {
+    format!(
+        "otpauth://totp/{}:{}?secret={}&issuer={}&algorithm=SHA1&digits={}&period={}",
+        percent_encode(issuer),
+        percent_encode(account),
+        secret,
+        percent_encode(issuer),
+        DIGITS,
+        STEP_SECS,
+    )
+}
+
+/// 將設定網址轉成 QR Code（SVG）
+pub fn qr_svg(uri: &str) -> Result<String, AppError> {
+    let code = QrCode::new(uri.as_bytes())
+        .map_err(|e| AppError::Internal(format!("無法產生 QR Code: {}", e)))?;
+    Ok(code.render::<svg::Color>().min_dimensions(200, 200).build())
+}
+
+/// 驗證驗證碼，成功時返回符合的時間步
+/// 呼叫端需記錄時間步，拒絕重複使用同一個或更早的時間步
+pub fn verify(secret: &str, code: &str, unix_time: i64) -> Option<i64> {
+    let code = code.trim();
+    if code.len() != DIGITS as usize || !code.bytes().all(|b| b.is_ascii_digit()) {
+        return None;
+    }
+    let key = BASE32_NOPAD.decode(secret.as_bytes()).ok()?;
+
+    let current = unix_time / STEP_SECS;
+    (current - ALLOWED_SKEW..=current + ALLOWED_SKEW)
+        .find(|&step| constant_time_eq(hotp(&key, step as u64).as_bytes(), code.as_bytes()))
+}
+
+/// RFC 4226 HOTP
+fn hotp(key: &[u8], counter: u64) -> String {
+    let mut mac = HmacSha1::new_from_slice(key).expect("HMAC 可接受任意長度的金鑰");
+    mac.update(&counter.to_be_bytes());
+    let digest = mac.finalize().into_bytes();
+
+    // 動態截斷：以最後一個位元組的低 4 位元決定取值位置
+    let offset = (digest[digest.len() - 1] & 0x0f) as usize;
+    let value = u32::from_be_bytes([digest[offset], digest[offset + 1], digest[offset + 2], digest[offset + 3]])
+        & 0x7fff_ffff;
+    format!("{:0width$}", value % 10_u32.pow(DIGITS), width = DIGITS as usize)
+}
+
+/// 固定時間比較，避免以回應時間推測驗證碼
+fn constant_time_eq(a: &[u8], b: &[u8]) -> bool {
+    a.len() == b.len() && a.iter().zip(b).fold(0u8, |acc, (x, y)| acc | (x ^ y)) == 0
+}
+
+/// 設定網址中的標籤與參數需百分比編碼
+fn percent_encode(value: &str) -> String {
+    value
+        .bytes()
+        .map(|b| match b {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'.' | b'_' | b'~' => (b as char).to_string(),
+            _ => format!("%{:02X}", b),
+        })
+        .collect()
+}
+
+/// 產生指定時間的驗證碼，供測試模擬驗證器 App
+#[cfg(test)]
+pub fn code_at(secret: &str, unix_time: i64) -> String {
+    let key = BASE32_NOPAD.decode(secret.as_bytes()).expect("無效的 Base32 密鑰");
+    hotp(&key, (unix_time / STEP_SECS) as u64)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// RFC 6238 附錄 B 的 SHA-1 測試密鑰
+    const RFC_KEY: &[u8] = b"12345678901234567890";
+
+    #[test]
+    fn matches_rfc6238_sha1_vectors() {
+        // 附錄 B 為 8 位數，6 位數驗證碼即取其後 6 位
+        let vectors = [
+            (59, "94287082"),
+            (1111111109, "07081804"),
+            (1111111111, "14050471"),
+            (1234567890, "89005924"),
+            (2000000000, "69279037"),
+            (20000000000, "65353130"),
+        ];
+        for (time, expected) in vectors {
+            assert_eq!(hotp(RFC_KEY, (time / STEP_SECS) as u64), expected[2..], "T = {}", time);
+        }
+    }
+
+    #[test]
+    fn verify_accepts_one_step_of_skew() {
+        let secret = BASE32_NOPAD.encode(RFC_KEY);
+        let now = 1111111111;
+        let step = now / STEP_SECS;
+
+        for offset in -ALLOWED_SKEW..=ALLOWED_SKEW {
+            let code = code_at(&secret, now + offset * STEP_SECS);
+            assert_eq!(verify(&secret, &code, now), Some(step + offset));
+        }
+    }
+
+    #[test]
+    fn verify_rejects_codes_outside_skew_window() {
+        let secret = BASE32_NOPAD.encode(RFC_KEY);
+        let now = 1111111111;
+
+        assert_eq!(verify(&secret, &code_at(&secret, now - 2 * STEP_SECS), now), None);
+        assert_eq!(verify(&secret, &code_at(&secret, now + 2 * STEP_SECS), now), None);
+    }
+
+    #[test]
+    fn verify_rejects_malformed_codes() {
+        let secret = BASE32_NOPAD.encode(RFC_KEY);
+        let code = code_at(&secret, 59);
+
+        assert_eq!(verify(&secret, &format!(" {} ", code), 59), Some(1));
+        assert_eq!(verify(&secret, &code[..5], 59), None);
+        assert_eq!(verify(&secret, "12345a", 59), None);
+        assert_eq!(verify("not base32!", &code, 59), None);
+    }
+}
@@ -1,9 +1,11 @@
 // 引入標準庫中的網絡地址和線程安全引用計數類型
 use std::net::SocketAddr;
 use std::sync::Arc;  // Arc 是 Atomic Reference Counting 的縮寫，用於在多線程環境中安全地共享數據
+use std::time::Duration;
 
-// 引入 Axum 框架的 HTTP 方法類型
+// 引入 Axum 框架的 HTTP 方法類型與請求擴展
 use axum::http::Method;
+use axum::Extension;
 // 引入跨域資源共享(CORS)相關功能，允許不同網站的前端訪問我們的 API
 use tower_http::cors::{Any, CorsLayer};
 // 引入請求體大小限制功能，防止過大的請求導致服務器負擔過重
@@ -19,13 +21,43 @@ use utoipa_swagger_ui::SwaggerUi;
 // API 文檔定義
 use crate::api::docs::ApiDoc;
 // 路由創建函數
-use crate::api::routes::create_router;
+use crate::api::routes::{create_router, AppState};
+// 來源 IP 判斷設定
+use crate::api::middleware::client_ip::TrustedProxyHops;
+// 入場憑證服務，處理入口驗票
+use crate::application::addon::service::AddonService;
+use crate::application::admission::service::AdmissionService;
+// API 金鑰服務，處理合作夥伴金鑰的管理與驗證
+use crate::application::api_key::service::ApiKeyService;
 // 認證服務，處理用戶登錄、註冊等功能
+use crate::application::auth::oidc::OidcService;
 use crate::application::auth::service::AuthService;
+use crate::application::auth::password_policy::PasswordPolicy;
+use crate::application::auth::throttle::LoginThrottle;
+use crate::application::auth::two_factor::TwoFactorAuth;
+// 組合商品服務，處理組合商品目錄
+use crate::application::box_office::service::BoxOfficeService;
+use crate::application::bundle::service::BundleService;
+use crate::application::comp::service::CompService;
 // 演唱會服務，處理演唱會相關邏輯
 use crate::application::concert::service::ConcertService;
+// 庫存服務，處理共用庫存池與票種配額
+use crate::application::inventory::service::InventoryService;
+// 分錄服務，處理對帳與分錄查詢
+use crate::application::ledger::service::LedgerService;
 // 訂單服務，處理訂單相關邏輯
 use crate::application::order::service::OrderService;
+// 個人資料服務，處理個人資料匯出與帳號刪除
+use crate::application::privacy::service::PrivacyService;
+// 報表服務，處理銷售分析報表
+use crate::application::promo_code::service::PromoCodeService;
+use crate::application::report::service::ReportService;
+// 主辦單位服務，處理主辦單位與抽成比例
+use crate::application::organization::service::OrganizationService;
+// 座位服務，處理座位圖、價區與座位可售狀態
+use crate::application::seating::service::SeatingService;
+// 結算服務，處理主辦單位結算與撥款
+use crate::application::settlement::service::SettlementService;
 // 票券服務，處理票券相關邏輯
 use crate::application::ticket::service::TicketService;
 // 應用程序配置，從環境變量中讀取配置信息
@@ -33,10 +65,43 @@ use crate::config::AppConfig;
 // 數據庫連接池初始化函數
 use crate::infrastructure::database::connection::init_pool;
 // 各種資料庫存儲庫的實現
+use crate::infrastructure::database::repositories::addon_repository::PgAddonRepository;
+use crate::infrastructure::database::repositories::admission_repository::PgAdmissionRepository;
+use crate::infrastructure::database::repositories::api_key_repository::PgApiKeyRepository;
+use crate::infrastructure::database::repositories::box_office_repository::PgBoxOfficeRepository;
+use crate::infrastructure::database::repositories::bundle_repository::PgBundleRepository;
+use crate::infrastructure::database::repositories::comp_repository::PgCompRepository;
 use crate::infrastructure::database::repositories::concert_repository::PgConcertRepository;
+use crate::infrastructure::database::repositories::identity_repository::PgIdentityRepository;
+use crate::infrastructure::database::repositories::inventory_repository::PgInventoryRepository;
+use crate::infrastructure::database::repositories::ledger_repository::PgLedgerRepository;
+use crate::infrastructure::database::repositories::login_attempt_repository::PgLoginAttemptRepository;
+use crate::infrastructure::database::repositories::login_event_repository::PgLoginEventRepository;
 use crate::infrastructure::database::repositories::order_repository::PgOrderRepository;
+use crate::infrastructure::database::repositories::organization_repository::PgOrganizationRepository;
+use crate::infrastructure::database::repositories::payment_repository::PgPaymentRepository;
+use crate::infrastructure::database::repositories::privacy_repository::PgPrivacyRepository;
+use crate::infrastructure::database::repositories::promo_code_repository::PgPromoCodeRepository;
+use crate::infrastructure::database::repositories::report_repository::PgReportRepository;
+use crate::infrastructure::database::repositories::seating_repository::PgSeatingRepository;
+use crate::infrastructure::database::repositories::settlement_repository::PgSettlementRepository;
 use crate::infrastructure::database::repositories::ticket_repository::PgTicketRepository;
+use crate::infrastructure::database::repositories::token_repository::PgTokenRepository;
+use crate::infrastructure::database::repositories::two_factor_repository::PgTwoFactorRepository;
 use crate::infrastructure::database::repositories::user_repository::PgUserRepository;
+use crate::infrastructure::security::breached_passwords::{BreachedPasswordSource, FileBreachedPasswords};
+use crate::infrastructure::security::jwt::JwtKeys;
+use crate::infrastructure::security::password::PasswordHashing;
+// 登入失敗記錄的記憶體實作，單一實例部署時可取代資料庫
+use crate::domain::auth::repository::LoginAttemptRepository;
+use crate::infrastructure::memory::login_attempt_repository::InMemoryLoginAttemptRepository;
+// 郵件寄送
+use crate::infrastructure::mail::file::FileMailer;
+use crate::infrastructure::mail::smtp::SmtpMailer;
+use crate::infrastructure::mail::Mailer;
+// 金流閘道
+use crate::infrastructure::payment::fake::{FakePaymentGateway, FakePaymentMode};
+use crate::infrastructure::payment::PaymentGateway;
 
 // 聲明我們的模塊結構
 // API 層：處理 HTTP 請求和響應
@@ -74,35 +139,214 @@ async fn main() {
     // Arc 使這些存儲庫可以在多個服務之間安全共享
     // pool.clone() 是複製的是資料庫連接池的智慧指針（增加引用計數），而非實際建立新連線，避免重複建立連線造成的資源浪費
     let user_repository = Arc::new(PgUserRepository::new(pool.clone()));
+    let token_repository = Arc::new(PgTokenRepository::new(pool.clone()));
+    let two_factor_repository = Arc::new(PgTwoFactorRepository::new(pool.clone()));
+    let identity_repository = Arc::new(PgIdentityRepository::new(pool.clone()));
+    let login_event_repository = Arc::new(PgLoginEventRepository::new(pool.clone()));
+    // 登入失敗記錄依 LOGIN_ATTEMPT_STORE 存放在資料庫或記憶體
+    let login_attempt_repository: Arc<dyn LoginAttemptRepository> = match config.login_attempt_store.as_str() {
+        "postgres" => Arc::new(PgLoginAttemptRepository::new(pool.clone())),
+        "memory" => Arc::new(InMemoryLoginAttemptRepository::new()),
+        other => panic!("不支援的登入失敗記錄存放位置: {}", other),
+    };
     let concert_repository = Arc::new(PgConcertRepository::new(pool.clone()));
     let ticket_repository = Arc::new(PgTicketRepository::new(pool.clone()));
     let order_repository = Arc::new(PgOrderRepository::new(pool.clone()));
+    let payment_repository = Arc::new(PgPaymentRepository::new(pool.clone()));
+    let ledger_repository = Arc::new(PgLedgerRepository::new(pool.clone()));
+    let organization_repository = Arc::new(PgOrganizationRepository::new(pool.clone()));
+    let settlement_repository = Arc::new(PgSettlementRepository::new(pool.clone()));
+    let report_repository = Arc::new(PgReportRepository::new(pool.clone()));
+    let seating_repository = Arc::new(PgSeatingRepository::new(pool.clone()));
+    let inventory_repository = Arc::new(PgInventoryRepository::new(pool.clone()));
+    let bundle_repository = Arc::new(PgBundleRepository::new(pool.clone()));
+    let admission_repository = Arc::new(PgAdmissionRepository::new(pool.clone()));
+    let addon_repository = Arc::new(PgAddonRepository::new(pool.clone()));
+    let comp_repository = Arc::new(PgCompRepository::new(pool.clone()));
+    let box_office_repository = Arc::new(PgBoxOfficeRepository::new(pool.clone()));
+    let api_key_repository = Arc::new(PgApiKeyRepository::new(pool.clone()));
+    let privacy_repository = Arc::new(PgPrivacyRepository::new(pool.clone()));
+    let promo_code_repository = Arc::new(PgPromoCodeRepository::new(pool.clone()));
+    
+    // 初始化金流閘道
+    // 依照 PAYMENT_PROVIDER 選擇供應商，目前只有本地模擬金流
+    let payment_gateway: Arc<dyn PaymentGateway> = match config.payment_provider.as_str() {
+        "fake" => {
+            let mode: FakePaymentMode = config.fake_payment_mode.parse().expect("FAKE_PAYMENT_MODE 無效");
+            Arc::new(FakePaymentGateway::new(mode, config.payment_webhook_secret.clone()))
+        }
+        other => panic!("不支援的金流供應商: {}", other),
+    };
+
+    // 初始化郵件寄送
+    // 依照 MAILER 選擇寫入本地目錄或透過 SMTP 寄出
+    let mailer: Arc<dyn Mailer> = match config.mailer.as_str() {
+        "file" => Arc::new(FileMailer::new(config.mail_file_dir.clone(), config.mail_from.clone())),
+        "smtp" => Arc::new(SmtpMailer::new(config.smtp_host.clone(), config.smtp_port, config.mail_from.clone())),
+        other => panic!("不支援的郵件寄送方式: {}", other),
+    };
     
     // 初始化各種服務
     // 服務實現業務邏輯，使用存儲庫來訪問數據
-    let auth_service = Arc::new(AuthService::new(user_repository, config.jwt_secret.clone()));
+    // 載入 JWT 簽章私鑰與輪替期間仍有效的驗證公鑰
+    let jwt_keys = JwtKeys::load(&config.jwt_signing_key_file, &config.jwt_verification_key_files)
+        .expect("無法載入 JWT 金鑰");
+    // 載入密碼雜湊參數與外洩密碼清單
+    let password_hashing = PasswordHashing::new(
+        config.argon2_memory_kib,
+        config.argon2_iterations,
+        config.argon2_parallelism,
+    )
+    .expect("無效的 Argon2 參數");
+    let breached_passwords = config.breached_passwords_file.as_deref().map(|file| {
+        Arc::new(FileBreachedPasswords::load(file).expect("無法載入外洩密碼清單")) as Arc<dyn BreachedPasswordSource>
+    });
+    let password_policy = Arc::new(PasswordPolicy::new(
+        password_hashing,
+        config.password_min_length,
+        breached_passwords,
+    ));
+    let login_throttle = LoginThrottle::new(
+        login_attempt_repository,
+        config.lockout_policy(config.login_max_failures_per_account),
+        config.lockout_policy(config.login_max_failures_per_ip),
+    );
+    let two_factor = TwoFactorAuth::new(
+        two_factor_repository,
+        config.two_factor_required_roles.clone(),
+        config.totp_issuer.clone(),
+    );
+    let auth_service = Arc::new(AuthService::new(
+        user_repository.clone(),
+        token_repository,
+        login_event_repository.clone(),
+        jwt_keys,
+        password_policy.clone(),
+        login_throttle,
+        two_factor,
+        mailer,
+        config.public_base_url.clone(),
+        config.access_token_ttl_secs,
+        config.refresh_token_ttl_days,
+    ));
+    let oidc_service = Arc::new(OidcService::new(
+        identity_repository.clone(),
+        user_repository.clone(),
+        auth_service.clone(),
+        config.oidc_providers.clone(),
+        config.public_base_url.clone(),
+    ));
     let concert_service = Arc::new(ConcertService::new(concert_repository.clone()));
     let ticket_service = Arc::new(TicketService::new(ticket_repository.clone(), concert_repository.clone()));
-    let order_service = Arc::new(OrderService::new(order_repository, ticket_repository));
+    let order_service = Arc::new(OrderService::new(
+        order_repository,
+        ticket_repository.clone(),
+        concert_repository.clone(),
+        payment_repository,
+        seating_repository.clone(),
+        bundle_repository.clone(),
+        addon_repository.clone(),
+        promo_code_repository.clone(),
+        payment_gateway,
+        config.currency.clone(),
+        config.require_verified_email,
+    ));
+    let box_office_service = Arc::new(BoxOfficeService::new(
+        box_office_repository,
+        order_service.clone(),
+        user_repository.clone(),
+        password_policy,
+    ));
+    let ledger_service = Arc::new(LedgerService::new(ledger_repository));
+    let organization_service = Arc::new(OrganizationService::new(
+        organization_repository.clone(),
+        concert_repository.clone(),
+        user_repository.clone(),
+    ));
+    let settlement_service = Arc::new(SettlementService::new(
+        settlement_repository,
+        organization_repository,
+        concert_repository.clone(),
+    ));
+    let report_service = Arc::new(ReportService::new(report_repository));
+    let inventory_service = Arc::new(InventoryService::new(
+        inventory_repository,
+        ticket_repository.clone(),
+        concert_repository.clone(),
+        seating_repository.clone(),
+    ));
+    let bundle_service = Arc::new(BundleService::new(
+        bundle_repository,
+        ticket_repository.clone(),
+        seating_repository.clone(),
+    ));
+    let admission_service = Arc::new(AdmissionService::new(admission_repository));
+    let addon_service = Arc::new(AddonService::new(
+        addon_repository,
+        concert_repository.clone(),
+        ticket_repository.clone(),
+    ));
+    let comp_service = Arc::new(CompService::new(
+        comp_repository,
+        concert_repository.clone(),
+        ticket_repository.clone(),
+        seating_repository.clone(),
+    ));
+    let api_key_service = Arc::new(ApiKeyService::new(api_key_repository));
+    let promo_code_service = Arc::new(PromoCodeService::new(promo_code_repository));
+    let seating_service = Arc::new(SeatingService::new(seating_repository, concert_repository, ticket_repository));
+    let privacy_service = Arc::new(PrivacyService::new(
+        privacy_repository,
+        user_repository,
+        identity_repository,
+        login_event_repository,
+        order_service.clone(),
+        auth_service.clone(),
+    ));
+    
+    // 啟動報表背景任務
+    // 報表的彙總資料來自物化視圖，定期重新整理以避免拖慢購票流程
+    report_service.spawn_refresh_task(Duration::from_secs(config.report_refresh_interval_secs));
+
+    // 啟動逾時訂單背景任務
+    // 延遲確認的付款若一直沒有收到 Webhook，逾時後釋放訂單佔用的庫存
+    order_service.spawn_expiry_task(chrono::Duration::seconds(config.pending_order_timeout_secs));
     
     // 創建 API 路由
     // 路由定義了 HTTP 請求如何映射到處理函數
-    let app = create_router(
+    let app = create_router(AppState {
         auth_service,
+        oidc_service,
         concert_service,
         ticket_service,
         order_service,
-    )
+        ledger_service,
+        organization_service,
+        settlement_service,
+        report_service,
+        seating_service,
+        inventory_service,
+        bundle_service,
+        admission_service,
+        addon_service,
+        promo_code_service,
+        comp_service,
+        box_office_service,
+        api_key_service,
+        privacy_service,
+    })
     // 添加 Swagger UI
     // 這提供了一個網頁界面，可以查看和測試 API
     .merge(SwaggerUi::new("/docs").url("/api-doc/openapi.json", ApiDoc::openapi()))
+    // 告訴來源 IP 提取器信任的反向代理層數，未信任 X-Forwarded-For 標頭時為 0
+    .layer(Extension(TrustedProxyHops(if config.trust_forwarded_for { config.trusted_proxy_hops } else { 0 })))
     
     // 添加中間件
     // 中間件是在請求處理前後執行的功能
     .layer(
         // CORS 中間件允許不同網站的前端訪問我們的 API
         CorsLayer::new()
-            .allow_methods([Method::GET, Method::POST, Method::PUT, Method::DELETE])
+            .allow_methods([Method::GET, Method::POST, Method::PUT, Method::PATCH, Method::DELETE])
             .allow_origin(Any)  // 允許任何來源的請求
             .allow_headers(Any),  // 允許任何 HTTP 頭部
     )
@@ -122,5 +366,6 @@ async fn main() {
     let listener = tokio::net::TcpListener::bind(addr).await.unwrap();
     // 啟動 Axum 服務器，處理傳入的 HTTP 請求
     // unwrap() 在出錯時會導致程序崩潰，這裡用於簡化錯誤處理
-    axum::serve(listener, app).await.unwrap();
+    // 保留連線資訊，讓登入防暴力破解可以取得來源 IP
+    axum::serve(listener, app.into_make_service_with_connect_info::<SocketAddr>()).await.unwrap();
 }
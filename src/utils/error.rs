@@ -24,6 +24,9 @@ pub enum AppError {
     #[error("資源衝突: {0}")]
     Conflict(String),
 
+    #[error("請求過於頻繁: {0}")]
+    TooManyRequests(String),
+
     #[error("資料庫錯誤: {0}")]
     Database(#[from] sqlx::Error),
 
@@ -39,6 +42,7 @@ impl IntoResponse for AppError {
             AppError::NotFound(message) => (StatusCode::NOT_FOUND, message),
             AppError::BadRequest(message) => (StatusCode::BAD_REQUEST, message),
             AppError::Conflict(message) => (StatusCode::CONFLICT, message),
+            AppError::TooManyRequests(message) => (StatusCode::TOO_MANY_REQUESTS, message),
             AppError::Database(err) => (
                 StatusCode::INTERNAL_SERVER_ERROR,
                 format!("資料庫錯誤: {}", err),
@@ -0,0 +1,66 @@
+use std::io::{Cursor, Write};
+
+use axum::{
+    http::header,
+    response::{IntoResponse, Response},
+};
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+use crate::utils::error::AppError;
+
+/// 報表輸出格式
+#[derive(Debug, Clone, Copy, Default, Deserialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum ExportFormat {
+    #[default]
+    Json,
+    Csv,
+}
+
+/// 將扁平的資料列序列化為 CSV，欄位名稱取自結構體欄位
+pub fn to_csv<T: Serialize>(rows: &[T]) -> Result<String, AppError> {
+    let mut writer = csv::Writer::from_writer(Vec::new());
+    for row in rows {
+        writer.serialize(row).map_err(|e| AppError::Internal(format!("產生 CSV 失敗: {}", e)))?;
+    }
+
+    let bytes = writer.into_inner().map_err(|e| AppError::Internal(format!("產生 CSV 失敗: {}", e)))?;
+    String::from_utf8(bytes).map_err(|e| AppError::Internal(format!("產生 CSV 失敗: {}", e)))
+}
+
+/// 以附件方式回傳 CSV
+pub fn csv_response(filename: &str, body: String) -> Response {
+    (
+        [
+            (header::CONTENT_TYPE, "text/csv; charset=utf-8".to_string()),
+            (header::CONTENT_DISPOSITION, format!("attachment; filename=\"{}\"", filename)),
+        ],
+        body,
+    ).into_response()
+}
+
+/// 將多個檔案壓縮為 ZIP，檔案以 (檔名, 內容) 表示
+pub fn to_zip(files: &[(&str, Vec<u8>)]) -> Result<Vec<u8>, AppError> {
+    let zip_error = |e: &dyn std::fmt::Display| AppError::Internal(format!("產生 ZIP 失敗: {}", e));
+    let mut writer = zip::ZipWriter::new(Cursor::new(Vec::new()));
+    let options = zip::write::SimpleFileOptions::default().compression_method(zip::CompressionMethod::Deflated);
+
+    for (name, content) in files {
+        writer.start_file(*name, options).map_err(|e| zip_error(&e))?;
+        writer.write_all(content).map_err(|e| zip_error(&e))?;
+    }
+
+    Ok(writer.finish().map_err(|e| zip_error(&e))?.into_inner())
+}
+
+/// 以附件方式回傳 ZIP
+pub fn zip_response(filename: &str, body: Vec<u8>) -> Response {
+    (
+        [
+            (header::CONTENT_TYPE, "application/zip".to_string()),
+            (header::CONTENT_DISPOSITION, format!("attachment; filename=\"{}\"", filename)),
+        ],
+        body,
+    ).into_response()
+}
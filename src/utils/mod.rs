@@ -1 +1,3 @@
 pub mod error;
+pub mod export;
+pub mod money;
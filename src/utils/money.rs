@@ -0,0 +1,12 @@
+/// 金額四捨五入到小數點後兩位
+pub fn round_money(amount: f64) -> f64 {
+    (amount * 100.0).round() / 100.0
+}
+
+/// 將 f64 金額轉換為資料庫使用的 BigDecimal
+pub fn to_decimal(amount: f64) -> Result<sqlx::types::BigDecimal, crate::utils::error::AppError> {
+    use std::str::FromStr;
+
+    sqlx::types::BigDecimal::from_str(&amount.to_string())
+        .map_err(|_| crate::utils::error::AppError::Internal(format!("無效的金額: {}", amount)))
+}